@@ -0,0 +1,119 @@
+//! Rebuild metrics and reports from a Goose request log, or compare two load tests.
+//!
+//! See [`goose::replay`] for details.
+
+use gumdrop::Options;
+
+use goose::replay::{GooseComparison, GooseReport};
+use goose::GooseError;
+
+/// Options available when rebuilding metrics from a request log.
+#[derive(Options, Debug)]
+#[options(
+    help = r#"Rebuilds metrics and reports from a request log written by a Goose load test with
+--request-log, in the json, csv, raw or pretty format. Alternatively compares two load tests,
+each a request log or a metrics file written with --baseline-file."#
+)]
+struct GooseReportOptions {
+    /// Displays this help
+    #[options(short = "h")]
+    help: bool,
+    /// Sets request log file name
+    #[options(short = "R", meta = "NAME")]
+    request_log: String,
+    /// Create a report file (.html, .md or .json), repeatable
+    #[options(no_short, meta = "NAME")]
+    report_file: Vec<String>,
+    /// Only includes requests made SECONDS or more after the load test started
+    #[options(no_short, meta = "SECONDS")]
+    from: Option<usize>,
+    /// Only includes requests made less than SECONDS after the load test started
+    #[options(no_short, meta = "SECONDS")]
+    to: Option<usize>,
+    /// Only includes requests with this name, repeatable
+    #[options(no_short, meta = "NAME")]
+    name: Vec<String>,
+    /// Compares a baseline and then a current load test, set twice
+    #[options(no_short, meta = "NAME")]
+    compare: Vec<String>,
+    /// Sets baseline regression tolerance (default: 10)
+    #[options(no_short, meta = "PERCENT")]
+    baseline_tolerance: Option<usize>,
+    /// Doesn't display metrics
+    #[options(no_short)]
+    no_print_metrics: bool,
+}
+impl GooseReportOptions {
+    /// Apply the configured filters to a request log.
+    fn filter(&self, mut goose_report: GooseReport) -> GooseReport {
+        if let Some(from) = self.from {
+            goose_report = goose_report.set_from(from);
+        }
+        if let Some(to) = self.to {
+            goose_report = goose_report.set_to(to);
+        }
+        for name in &self.name {
+            goose_report = goose_report.set_name(name);
+        }
+        goose_report
+    }
+}
+
+#[tokio::main]
+async fn main() -> Result<(), GooseError> {
+    let options = GooseReportOptions::parse_args_default_or_exit();
+
+    // Compare two load tests.
+    if !options.compare.is_empty() {
+        if options.compare.len() != 2 || options.report_file.len() != 1 {
+            return Err(GooseError::InvalidOption {
+                option: "--compare".to_string(),
+                value: options.compare.join(", "),
+                detail: "Set --compare twice, and --report-file once.".to_string(),
+            });
+        }
+        let mut goose_comparison = GooseComparison::load_with(
+            &options.compare[0],
+            &options.compare[1],
+            &options.report_file[0],
+            |goose_report| options.filter(goose_report),
+        )
+        .await?;
+        if let Some(tolerance) = options.baseline_tolerance {
+            goose_comparison = goose_comparison.set_tolerance(tolerance);
+        }
+        for metrics_file in goose_comparison.metrics_files() {
+            eprintln!(
+                "warning: {} is a metrics file without per second data, graphs are left out of the comparison",
+                metrics_file
+            );
+        }
+
+        let metrics = goose_comparison.write().await?;
+        if !options.no_print_metrics {
+            println!("{}", metrics);
+        }
+        println!("report file written to: {}", options.report_file[0]);
+
+        return Ok(());
+    }
+
+    if options.request_log.is_empty() {
+        return Err(GooseError::InvalidOption {
+            option: "--request-log".to_string(),
+            value: "".to_string(),
+            detail: "A request log is required.".to_string(),
+        });
+    }
+
+    let goose_report = options.filter(GooseReport::from_request_log(&options.request_log).await?);
+    let metrics = goose_report.write(&options.report_file).await?;
+    if !options.no_print_metrics {
+        println!("{}", metrics);
+    }
+    for report_file in &options.report_file {
+        println!("report file written to: {}", report_file);
+    }
+
+    Ok(())
+}
@@ -11,9 +11,12 @@ use simplelog::*;
 use std::path::PathBuf;
 use std::str::FromStr;
 
-use crate::logger::GooseLogFormat;
-use crate::metrics::GooseCoordinatedOmissionMitigation;
-use crate::test_plan::TestPlan;
+use crate::logger::{self, GooseLogFormat};
+use crate::metrics::{
+    GooseAbortRules, GooseApdex, GooseCoordinatedOmissionMitigation, GooseMetricsSink,
+    GoosePercentiles, GooseThresholds,
+};
+use crate::test_plan::{BreakpointStep, TestPlan};
 use crate::util;
 use crate::{GooseAttack, GooseError};
 
@@ -100,16 +103,61 @@ pub struct GooseConfiguration {
     /// Doesn't display an error summary
     #[options(no_short)]
     pub no_error_summary: bool,
-    /// Create an html-formatted report
+    /// Sets histogram precision from 1 to 5 (default: 3)
+    #[options(no_short, meta = "DIGITS")]
+    pub significant_digits: Option<usize>,
+    /// Sets response time percentiles ("50,90,99,99.9")
+    #[options(no_short, meta = "\"PERCENTILES\"")]
+    pub percentiles: Option<GoosePercentiles>,
+    /// Sets Apdex target response times ("500;GET /:100")
+    #[options(no_short, meta = "\"TARGETS\"")]
+    pub apdex: Option<GooseApdex>,
+    /// Collects and displays metrics for each test plan step
+    #[options(no_short)]
+    pub phase_metrics: bool,
+    /// Fails load test unless thresholds are met ("p95<500")
+    #[options(no_short, meta = "\"THRESHOLDS\"")]
+    pub thresholds: Option<GooseThresholds>,
+    /// Aborts load test if thresholds are breached ("error-rate<50,30s")
+    #[options(no_short, meta = "\"RULES\"")]
+    pub abort_on_fail: Option<GooseAbortRules>,
+    /// Pushes metrics to StatsD or InfluxDB ("statsd://HOST:PORT")
+    #[options(no_short, meta = "URL")]
+    pub metrics_sink: Option<GooseMetricsSink>,
+    /// How often to push metrics to the sink (default: 10)
+    #[options(no_short, meta = "TIME")]
+    pub metrics_sink_interval: Option<usize>,
+    /// Exports metrics to an OpenTelemetry collector ("http://HOST:4318")
+    #[options(no_short, meta = "URL")]
+    pub otlp_endpoint: String,
+    /// Also exports a span per request and transaction
+    #[options(no_short)]
+    pub otlp_traces: bool,
+    /// Create a report file (.html, .md or .json), repeatable
     #[options(no_short, meta = "NAME")]
-    pub report_file: String,
+    pub report_file: Vec<String>,
     /// Disable granular graphs in report file
     #[options(no_short)]
     pub no_granular_report: bool,
+    /// Creates a JUnit XML file for CI systems
+    #[options(no_short, meta = "NAME")]
+    pub junit_file: String,
+    /// Writes Locust-compatible CSV files named PREFIX_*.csv
+    #[options(no_short, meta = "PREFIX")]
+    pub csv: String,
+    /// Saves metrics to a baseline file
+    #[options(no_short, meta = "NAME")]
+    pub baseline_file: String,
+    /// Compares metrics to a baseline file
+    #[options(no_short, meta = "NAME")]
+    pub baseline: String,
+    /// Sets baseline regression tolerance (default: 10)
+    #[options(no_short, meta = "PERCENT")]
+    pub baseline_tolerance: Option<usize>,
     /// Sets request log file name
     #[options(short = "R", meta = "NAME")]
     pub request_log: String,
-    /// Sets request log format (csv, json, raw, pretty)
+    /// Sets request log format (csv, json, raw, pretty, jtl)
     #[options(no_short, meta = "FORMAT")]
     pub request_format: Option<GooseLogFormat>,
     /// Include the request body in the request log
@@ -150,6 +198,24 @@ pub struct GooseConfiguration {
     /// Defines a more complex test plan ("10,60s;0,30s")
     #[options(no_short, meta = "\"TESTPLAN\"")]
     pub(crate) test_plan: Option<TestPlan>,
+    /// Defines a test plan of iterations started per second ("10,60s;0,30s")
+    #[options(no_short, meta = "\"TESTPLAN\"")]
+    pub(crate) arrival_rate: Option<TestPlan>,
+    /// Starts requests per second instead of iterations
+    #[options(no_short)]
+    pub arrival_requests: bool,
+    /// Steps load up until a limit is hit ("10,30s")
+    #[options(no_short, meta = "\"STEP\"")]
+    pub(crate) breakpoint: Option<BreakpointStep>,
+    /// Stops a breakpoint search above this error rate
+    #[options(no_short, meta = "PERCENT")]
+    pub breakpoint_error_rate: Option<usize>,
+    /// Stops a breakpoint search above this 95th percentile
+    #[options(no_short, meta = "MS")]
+    pub breakpoint_response_time: Option<usize>,
+    /// Steps iterations per second instead of users
+    #[options(no_short)]
+    pub breakpoint_arrival_rate: bool,
     /// Sets how many times to run scenarios then exit
     #[options(no_short)]
     pub iterations: usize,
@@ -177,12 +243,21 @@ pub struct GooseConfiguration {
     /// Sets WebSocket Controller TCP port (default: 5117)
     #[options(no_short, meta = "PORT")]
     pub websocket_port: u16,
+    /// Sets Prometheus endpoint host (default: 0.0.0.0)
+    #[options(no_short, meta = "HOST")]
+    pub prometheus_host: String,
+    /// Serves Prometheus metrics on TCP port
+    #[options(no_short, meta = "PORT")]
+    pub prometheus_port: u16,
     /// Doesn't automatically start load test
     #[options(no_short)]
     pub no_autostart: bool,
     /// Doesn't set the gzip Accept-Encoding header
     #[options(no_short)]
     pub no_gzip: bool,
+    /// Reads response bodies when requests are made, to count bytes and download times
+    #[options(no_short)]
+    pub buffer_responses: bool,
     /// Sets per-request timeout, in seconds (default: 60)
     #[options(no_short, meta = "VALUE")]
     pub timeout: Option<String>,
@@ -252,6 +327,18 @@ pub(crate) struct GooseDefaults {
     pub run_time: Option<usize>,
     /// An optional default test plan.
     pub test_plan: Option<TestPlan>,
+    /// An optional default arrival rate test plan.
+    pub arrival_rate: Option<TestPlan>,
+    /// An optional default for starting requests per second instead of scenario iterations.
+    pub arrival_requests: Option<bool>,
+    /// An optional default breakpoint search step.
+    pub breakpoint: Option<BreakpointStep>,
+    /// An optional default maximum error rate for a breakpoint search.
+    pub breakpoint_error_rate: Option<usize>,
+    /// An optional default maximum 95th percentile response time for a breakpoint search.
+    pub breakpoint_response_time: Option<usize>,
+    /// An optional default for stepping iterations per second in a breakpoint search.
+    pub breakpoint_arrival_rate: Option<bool>,
     /// An optional default test plan.
     pub iterations: Option<usize>,
     /// Optional default scenarios.
@@ -278,10 +365,40 @@ pub(crate) struct GooseDefaults {
     pub no_print_metrics: Option<bool>,
     /// An optional default for not displaying an error summary.
     pub no_error_summary: Option<bool>,
-    /// An optional default for the html-formatted report file name.
-    pub report_file: Option<String>,
+    /// An optional default for how many significant digits response times are recorded with.
+    pub significant_digits: Option<usize>,
+    /// An optional default for the response time percentiles displayed.
+    pub percentiles: Option<GoosePercentiles>,
+    /// An optional default for Apdex target response times.
+    pub apdex: Option<GooseApdex>,
+    /// An optional default for displaying metrics for each test plan step.
+    pub phase_metrics: Option<bool>,
+    /// An optional default for pass/fail thresholds.
+    pub thresholds: Option<GooseThresholds>,
+    /// An optional default for abort-on-fail rules.
+    pub abort_on_fail: Option<GooseAbortRules>,
+    /// An optional default for where metrics are pushed while the load test runs.
+    pub metrics_sink: Option<GooseMetricsSink>,
+    /// An optional default for how often metrics are pushed to the metrics sink.
+    pub metrics_sink_interval: Option<usize>,
+    /// An optional default for the OpenTelemetry collector metrics are exported to.
+    pub otlp_endpoint: Option<String>,
+    /// An optional default for exporting a span per request and transaction.
+    pub otlp_traces: Option<bool>,
+    /// An optional default for the report file names.
+    pub report_file: Option<Vec<String>>,
     /// An optional default for the flag that disables granular data in HTML report graphs.
     pub no_granular_report: Option<bool>,
+    /// An optional default for the JUnit XML file name.
+    pub junit_file: Option<String>,
+    /// An optional default for the prefix of Locust-compatible CSV files.
+    pub csv: Option<String>,
+    /// An optional default for the baseline file name metrics are saved to.
+    pub baseline_file: Option<String>,
+    /// An optional default for the baseline file name metrics are compared to.
+    pub baseline: Option<String>,
+    /// An optional default for the baseline regression tolerance.
+    pub baseline_tolerance: Option<usize>,
     /// An optional default for the requests log file name.
     pub request_log: Option<String>,
     /// An optional default for the requests log file format.
@@ -314,6 +431,8 @@ pub(crate) struct GooseDefaults {
     pub no_autostart: Option<bool>,
     /// An optional default for not setting the gzip Accept-Encoding header.
     pub no_gzip: Option<bool>,
+    /// An optional default for reading response bodies when requests are made.
+    pub buffer_responses: Option<bool>,
     /// An optional default number of seconds to timeout requests.
     pub timeout: Option<String>,
     /// An optional default for coordinated omission mitigation.
@@ -332,6 +451,10 @@ pub(crate) struct GooseDefaults {
     pub websocket_host: Option<String>,
     /// An optional default for port WebSocket Controller listens on.
     pub websocket_port: Option<u16>,
+    /// An optional default for host Prometheus endpoint listens on.
+    pub prometheus_host: Option<String>,
+    /// An optional default for port Prometheus endpoint listens on.
+    pub prometheus_port: Option<u16>,
 }
 
 /// Defines all [`GooseConfiguration`] options that can be programmatically configured with
@@ -352,6 +475,18 @@ pub enum GooseDefault {
     RunTime,
     /// An optional default test plan.
     TestPlan,
+    /// An optional default arrival rate test plan.
+    ArrivalRate,
+    /// An optional default for starting requests per second instead of scenario iterations.
+    ArrivalRequests,
+    /// An optional default breakpoint search step.
+    Breakpoint,
+    /// An optional default maximum error rate for a breakpoint search.
+    BreakpointErrorRate,
+    /// An optional default maximum 95th percentile response time for a breakpoint search.
+    BreakpointResponseTime,
+    /// An optional default for stepping iterations per second in a breakpoint search.
+    BreakpointArrivalRate,
     /// An optional default number of iterations to run scenarios then exit.
     Iterations,
     /// Optional default list of scenarios to run.
@@ -378,10 +513,40 @@ pub enum GooseDefault {
     NoPrintMetrics,
     /// An optional default for not displaying an error summary.
     NoErrorSummary,
-    /// An optional default for the report file name.
+    /// An optional default for how many significant digits response times are recorded with.
+    SignificantDigits,
+    /// An optional default for the response time percentiles displayed.
+    Percentiles,
+    /// An optional default for Apdex target response times.
+    Apdex,
+    /// An optional default for displaying metrics for each test plan step.
+    PhaseMetrics,
+    /// An optional default for pass/fail thresholds.
+    Thresholds,
+    /// An optional default for abort-on-fail rules.
+    AbortOnFail,
+    /// An optional default for where metrics are pushed while the load test runs.
+    MetricsSink,
+    /// An optional default for how often metrics are pushed to the metrics sink.
+    MetricsSinkInterval,
+    /// An optional default for the OpenTelemetry collector metrics are exported to.
+    OtlpEndpoint,
+    /// An optional default for exporting a span per request and transaction.
+    OtlpTraces,
+    /// An optional default for the report file name, can be set multiple times.
     ReportFile,
     /// An optional default for the flag that disables granular data in HTML report graphs.
     NoGranularData,
+    /// An optional default for the JUnit XML file name.
+    JunitFile,
+    /// An optional default for the prefix of Locust-compatible CSV files.
+    Csv,
+    /// An optional default for the baseline file name metrics are saved to.
+    BaselineFile,
+    /// An optional default for the baseline file name metrics are compared to.
+    Baseline,
+    /// An optional default for the baseline regression tolerance.
+    BaselineTolerance,
     /// An optional default for the request log file name.
     RequestLog,
     /// An optional default for the request log file format.
@@ -418,6 +583,8 @@ pub enum GooseDefault {
     Timeout,
     /// An optional default for not setting the gzip Accept-Encoding header.
     NoGzip,
+    /// An optional default for reading response bodies when requests are made.
+    BufferResponses,
     /// An optional default to not track status code metrics.
     NoStatusCodes,
     /// An optional default maximum requests per second.
@@ -432,6 +599,10 @@ pub enum GooseDefault {
     WebSocketHost,
     /// An optional default for port WebSocket Controller listens on.
     WebSocketPort,
+    /// An optional default for host Prometheus endpoint listens on.
+    PrometheusHost,
+    /// An optional default for port Prometheus endpoint listens on.
+    PrometheusPort,
 }
 
 /// Most run-time options can be programmatically configured with custom defaults.
@@ -476,9 +647,22 @@ pub enum GooseDefault {
 ///  - [`GooseDefault::Scenarios`]
 ///  - [`GooseDefault::TelnetHost`]
 ///  - [`GooseDefault::TestPlan`]
+///  - [`GooseDefault::ArrivalRate`]
+///  - [`GooseDefault::Breakpoint`]
+///  - [`GooseDefault::Thresholds`]
+///  - [`GooseDefault::AbortOnFail`]
+///  - [`GooseDefault::Percentiles`]
+///  - [`GooseDefault::Apdex`]
+///  - [`GooseDefault::MetricsSink`]
+///  - [`GooseDefault::BaselineFile`]
+///  - [`GooseDefault::Baseline`]
 ///  - [`GooseDefault::Timeout`]
 ///  - [`GooseDefault::TransactionLog`]
 ///  - [`GooseDefault::WebSocketHost`]
+///  - [`GooseDefault::PrometheusHost`]
+///  - [`GooseDefault::OtlpEndpoint`]
+///  - [`GooseDefault::JunitFile`]
+///  - [`GooseDefault::Csv`]
 ///
 /// The following run-time options can be configured with a custom default using a
 /// [`usize`] integer:
@@ -493,6 +677,12 @@ pub enum GooseDefault {
 ///  - [`GooseDefault::ThrottleRequests`]
 ///  - [`GooseDefault::TelnetPort`]
 ///  - [`GooseDefault::WebSocketPort`]
+///  - [`GooseDefault::PrometheusPort`]
+///  - [`GooseDefault::MetricsSinkInterval`]
+///  - [`GooseDefault::BreakpointErrorRate`]
+///  - [`GooseDefault::BreakpointResponseTime`]
+///  - [`GooseDefault::BaselineTolerance`]
+///  - [`GooseDefault::SignificantDigits`]
 ///
 /// The following run-time flags can be configured with a custom default using a
 /// [`bool`] (and otherwise default to [`false`]).
@@ -508,9 +698,14 @@ pub enum GooseDefault {
 ///  - [`GooseDefault::NoWebSocket`]
 ///  - [`GooseDefault::NoAutoStart`]
 ///  - [`GooseDefault::NoGzip`]
+///  - [`GooseDefault::BufferResponses`]
 ///  - [`GooseDefault::NoStatusCodes`]
 ///  - [`GooseDefault::StickyFollow`]
 ///  - [`GooseDefault::NoGranularData`]
+///  - [`GooseDefault::ArrivalRequests`]
+///  - [`GooseDefault::BreakpointArrivalRate`]
+///  - [`GooseDefault::PhaseMetrics`]
+///  - [`GooseDefault::OtlpTraces`]
 ///
 /// The following run-time flags can be configured with a custom default using a
 /// [`GooseLogFormat`].
@@ -562,7 +757,11 @@ impl GooseDefaultType<&str> for GooseAttack {
                     Some(value.to_string())
                 }
             }
-            GooseDefault::ReportFile => self.defaults.report_file = Some(value.to_string()),
+            GooseDefault::ReportFile => self
+                .defaults
+                .report_file
+                .get_or_insert_with(Vec::new)
+                .push(value.to_string()),
             GooseDefault::RequestLog => self.defaults.request_log = Some(value.to_string()),
             GooseDefault::ScenarioLog => self.defaults.scenario_log = Some(value.to_string()),
             GooseDefault::Scenarios => {
@@ -572,9 +771,34 @@ impl GooseDefaultType<&str> for GooseAttack {
             GooseDefault::TestPlan => {
                 self.defaults.test_plan = Some(value.parse::<TestPlan>().unwrap())
             }
+            GooseDefault::ArrivalRate => {
+                self.defaults.arrival_rate = Some(value.parse::<TestPlan>()?)
+            }
+            GooseDefault::Breakpoint => {
+                self.defaults.breakpoint = Some(value.parse::<BreakpointStep>()?)
+            }
+            GooseDefault::Thresholds => {
+                self.defaults.thresholds = Some(value.parse::<GooseThresholds>()?)
+            }
+            GooseDefault::AbortOnFail => {
+                self.defaults.abort_on_fail = Some(value.parse::<GooseAbortRules>()?)
+            }
+            GooseDefault::Percentiles => {
+                self.defaults.percentiles = Some(value.parse::<GoosePercentiles>()?)
+            }
+            GooseDefault::Apdex => self.defaults.apdex = Some(value.parse::<GooseApdex>()?),
+            GooseDefault::MetricsSink => {
+                self.defaults.metrics_sink = Some(value.parse::<GooseMetricsSink>()?)
+            }
+            GooseDefault::BaselineFile => self.defaults.baseline_file = Some(value.to_string()),
+            GooseDefault::Baseline => self.defaults.baseline = Some(value.to_string()),
             GooseDefault::Timeout => self.defaults.timeout = Some(value.to_string()),
             GooseDefault::TransactionLog => self.defaults.transaction_log = Some(value.to_string()),
             GooseDefault::WebSocketHost => self.defaults.websocket_host = Some(value.to_string()),
+            GooseDefault::PrometheusHost => self.defaults.prometheus_host = Some(value.to_string()),
+            GooseDefault::OtlpEndpoint => self.defaults.otlp_endpoint = Some(value.to_string()),
+            GooseDefault::JunitFile => self.defaults.junit_file = Some(value.to_string()),
+            GooseDefault::Csv => self.defaults.csv = Some(value.to_string()),
             // Otherwise display a helpful and explicit error.
             GooseDefault::Users
             | GooseDefault::StartupTime
@@ -585,7 +809,13 @@ impl GooseDefaultType<&str> for GooseAttack {
             | GooseDefault::Verbose
             | GooseDefault::ThrottleRequests
             | GooseDefault::TelnetPort
-            | GooseDefault::WebSocketPort => {
+            | GooseDefault::WebSocketPort
+            | GooseDefault::PrometheusPort
+            | GooseDefault::MetricsSinkInterval
+            | GooseDefault::BreakpointErrorRate
+            | GooseDefault::BreakpointResponseTime
+            | GooseDefault::BaselineTolerance
+            | GooseDefault::SignificantDigits => {
                 return Err(GooseError::InvalidOption {
                     option: format!("GooseDefault::{:?}", key),
                     value: value.to_string(),
@@ -608,9 +838,14 @@ impl GooseDefaultType<&str> for GooseAttack {
             | GooseDefault::NoWebSocket
             | GooseDefault::NoAutoStart
             | GooseDefault::NoGzip
+            | GooseDefault::BufferResponses
             | GooseDefault::NoStatusCodes
             | GooseDefault::StickyFollow
-            | GooseDefault::NoGranularData => {
+            | GooseDefault::NoGranularData
+            | GooseDefault::ArrivalRequests
+            | GooseDefault::BreakpointArrivalRate
+            | GooseDefault::PhaseMetrics
+            | GooseDefault::OtlpTraces => {
                 return Err(GooseError::InvalidOption {
                     option: format!("GooseDefault::{:?}", key),
                     value: value.to_string(),
@@ -663,6 +898,14 @@ impl GooseDefaultType<usize> for GooseAttack {
             GooseDefault::ThrottleRequests => self.defaults.throttle_requests = Some(value),
             GooseDefault::TelnetPort => self.defaults.telnet_port = Some(value as u16),
             GooseDefault::WebSocketPort => self.defaults.websocket_port = Some(value as u16),
+            GooseDefault::PrometheusPort => self.defaults.prometheus_port = Some(value as u16),
+            GooseDefault::MetricsSinkInterval => self.defaults.metrics_sink_interval = Some(value),
+            GooseDefault::BreakpointErrorRate => self.defaults.breakpoint_error_rate = Some(value),
+            GooseDefault::BreakpointResponseTime => {
+                self.defaults.breakpoint_response_time = Some(value)
+            }
+            GooseDefault::BaselineTolerance => self.defaults.baseline_tolerance = Some(value),
+            GooseDefault::SignificantDigits => self.defaults.significant_digits = Some(value),
             // Otherwise display a helpful and explicit error.
             GooseDefault::DebugLog
             | GooseDefault::ErrorLog
@@ -675,9 +918,22 @@ impl GooseDefaultType<usize> for GooseAttack {
             | GooseDefault::Scenarios
             | GooseDefault::TelnetHost
             | GooseDefault::TestPlan
+            | GooseDefault::ArrivalRate
+            | GooseDefault::Breakpoint
+            | GooseDefault::Thresholds
+            | GooseDefault::AbortOnFail
+            | GooseDefault::Percentiles
+            | GooseDefault::Apdex
+            | GooseDefault::MetricsSink
+            | GooseDefault::BaselineFile
+            | GooseDefault::Baseline
             | GooseDefault::Timeout
             | GooseDefault::TransactionLog
-            | GooseDefault::WebSocketHost => {
+            | GooseDefault::WebSocketHost
+            | GooseDefault::PrometheusHost
+            | GooseDefault::OtlpEndpoint
+            | GooseDefault::JunitFile
+            | GooseDefault::Csv => {
                 return Err(GooseError::InvalidOption {
                     option: format!("GooseDefault::{:?}", key),
                     value: format!("{}", value),
@@ -699,9 +955,14 @@ impl GooseDefaultType<usize> for GooseAttack {
             | GooseDefault::NoWebSocket
             | GooseDefault::NoAutoStart
             | GooseDefault::NoGzip
+            | GooseDefault::BufferResponses
             | GooseDefault::NoStatusCodes
             | GooseDefault::StickyFollow
-            | GooseDefault::NoGranularData => {
+            | GooseDefault::NoGranularData
+            | GooseDefault::ArrivalRequests
+            | GooseDefault::BreakpointArrivalRate
+            | GooseDefault::PhaseMetrics
+            | GooseDefault::OtlpTraces => {
                 return Err(GooseError::InvalidOption {
                     option: format!("GooseDefault::{:?}", key),
                     value: format!("{}", value),
@@ -757,9 +1018,16 @@ impl GooseDefaultType<bool> for GooseAttack {
             GooseDefault::NoWebSocket => self.defaults.no_websocket = Some(value),
             GooseDefault::NoAutoStart => self.defaults.no_autostart = Some(value),
             GooseDefault::NoGzip => self.defaults.no_gzip = Some(value),
+            GooseDefault::BufferResponses => self.defaults.buffer_responses = Some(value),
             GooseDefault::NoStatusCodes => self.defaults.no_status_codes = Some(value),
             GooseDefault::StickyFollow => self.defaults.sticky_follow = Some(value),
             GooseDefault::NoGranularData => self.defaults.no_granular_report = Some(value),
+            GooseDefault::ArrivalRequests => self.defaults.arrival_requests = Some(value),
+            GooseDefault::BreakpointArrivalRate => {
+                self.defaults.breakpoint_arrival_rate = Some(value)
+            }
+            GooseDefault::PhaseMetrics => self.defaults.phase_metrics = Some(value),
+            GooseDefault::OtlpTraces => self.defaults.otlp_traces = Some(value),
             // Otherwise display a helpful and explicit error.
             GooseDefault::DebugLog
             | GooseDefault::ErrorLog
@@ -772,9 +1040,22 @@ impl GooseDefaultType<bool> for GooseAttack {
             | GooseDefault::Scenarios
             | GooseDefault::TelnetHost
             | GooseDefault::TestPlan
+            | GooseDefault::ArrivalRate
+            | GooseDefault::Breakpoint
+            | GooseDefault::Thresholds
+            | GooseDefault::AbortOnFail
+            | GooseDefault::Percentiles
+            | GooseDefault::Apdex
+            | GooseDefault::MetricsSink
+            | GooseDefault::BaselineFile
+            | GooseDefault::Baseline
             | GooseDefault::Timeout
             | GooseDefault::TransactionLog
-            | GooseDefault::WebSocketHost => {
+            | GooseDefault::WebSocketHost
+            | GooseDefault::PrometheusHost
+            | GooseDefault::OtlpEndpoint
+            | GooseDefault::JunitFile
+            | GooseDefault::Csv => {
                 return Err(GooseError::InvalidOption {
                     option: format!("GooseDefault::{:?}", key),
                     value: format!("{}", value),
@@ -794,7 +1075,13 @@ impl GooseDefaultType<bool> for GooseAttack {
             | GooseDefault::Verbose
             | GooseDefault::ThrottleRequests
             | GooseDefault::TelnetPort
-            | GooseDefault::WebSocketPort => {
+            | GooseDefault::WebSocketPort
+            | GooseDefault::PrometheusPort
+            | GooseDefault::MetricsSinkInterval
+            | GooseDefault::BreakpointErrorRate
+            | GooseDefault::BreakpointResponseTime
+            | GooseDefault::BaselineTolerance
+            | GooseDefault::SignificantDigits => {
                 return Err(GooseError::InvalidOption {
                     option: format!("GooseDefault::{:?}", key),
                     value: format!("{}", value),
@@ -854,9 +1141,14 @@ impl GooseDefaultType<GooseCoordinatedOmissionMitigation> for GooseAttack {
             | GooseDefault::NoWebSocket
             | GooseDefault::NoAutoStart
             | GooseDefault::NoGzip
+            | GooseDefault::BufferResponses
             | GooseDefault::NoStatusCodes
             | GooseDefault::StickyFollow
-            | GooseDefault::NoGranularData => {
+            | GooseDefault::NoGranularData
+            | GooseDefault::ArrivalRequests
+            | GooseDefault::BreakpointArrivalRate
+            | GooseDefault::PhaseMetrics
+            | GooseDefault::OtlpTraces => {
                 return Err(GooseError::InvalidOption {
                     option: format!("GooseDefault::{:?}", key),
                     value: format!("{:?}", value),
@@ -878,9 +1170,22 @@ impl GooseDefaultType<GooseCoordinatedOmissionMitigation> for GooseAttack {
             | GooseDefault::Scenarios
             | GooseDefault::TelnetHost
             | GooseDefault::TestPlan
+            | GooseDefault::ArrivalRate
+            | GooseDefault::Breakpoint
+            | GooseDefault::Thresholds
+            | GooseDefault::AbortOnFail
+            | GooseDefault::Percentiles
+            | GooseDefault::Apdex
+            | GooseDefault::MetricsSink
+            | GooseDefault::BaselineFile
+            | GooseDefault::Baseline
             | GooseDefault::Timeout
             | GooseDefault::TransactionLog
-            | GooseDefault::WebSocketHost => {
+            | GooseDefault::WebSocketHost
+            | GooseDefault::PrometheusHost
+            | GooseDefault::OtlpEndpoint
+            | GooseDefault::JunitFile
+            | GooseDefault::Csv => {
                 return Err(GooseError::InvalidOption {
                     option: format!("GooseDefault::{:?}", key),
                     value: format!("{:?}", value),
@@ -900,7 +1205,13 @@ impl GooseDefaultType<GooseCoordinatedOmissionMitigation> for GooseAttack {
             | GooseDefault::Verbose
             | GooseDefault::ThrottleRequests
             | GooseDefault::TelnetPort
-            | GooseDefault::WebSocketPort => {
+            | GooseDefault::WebSocketPort
+            | GooseDefault::PrometheusPort
+            | GooseDefault::MetricsSinkInterval
+            | GooseDefault::BreakpointErrorRate
+            | GooseDefault::BreakpointResponseTime
+            | GooseDefault::BaselineTolerance
+            | GooseDefault::SignificantDigits => {
                 return Err(GooseError::InvalidOption {
                     option: format!("GooseDefault::{:?}", key),
                     value: format!("{:?}", value),
@@ -936,6 +1247,15 @@ impl GooseDefaultType<GooseLogFormat> for GooseAttack {
         value: GooseLogFormat,
     ) -> Result<Box<Self>, GooseError> {
         match key {
+            // The jtl format is only supported by the request log.
+            GooseDefault::DebugFormat
+            | GooseDefault::ErrorFormat
+            | GooseDefault::TransactionFormat
+            | GooseDefault::ScenarioFormat
+                if value == GooseLogFormat::Jtl =>
+            {
+                return Err(logger::jtl_unsupported(&format!("GooseDefault::{:?}", key)));
+            }
             GooseDefault::RequestFormat => self.defaults.request_format = Some(value),
             GooseDefault::DebugFormat => self.defaults.debug_format = Some(value),
             GooseDefault::ErrorFormat => self.defaults.error_format = Some(value),
@@ -954,9 +1274,14 @@ impl GooseDefaultType<GooseLogFormat> for GooseAttack {
             | GooseDefault::NoWebSocket
             | GooseDefault::NoAutoStart
             | GooseDefault::NoGzip
+            | GooseDefault::BufferResponses
             | GooseDefault::NoStatusCodes
             | GooseDefault::StickyFollow
-            | GooseDefault::NoGranularData => {
+            | GooseDefault::NoGranularData
+            | GooseDefault::ArrivalRequests
+            | GooseDefault::BreakpointArrivalRate
+            | GooseDefault::PhaseMetrics
+            | GooseDefault::OtlpTraces => {
                 return Err(GooseError::InvalidOption {
                     option: format!("GooseDefault::{:?}", key),
                     value: format!("{:?}", value),
@@ -978,9 +1303,22 @@ impl GooseDefaultType<GooseLogFormat> for GooseAttack {
             | GooseDefault::Scenarios
             | GooseDefault::TelnetHost
             | GooseDefault::TestPlan
+            | GooseDefault::ArrivalRate
+            | GooseDefault::Breakpoint
+            | GooseDefault::Thresholds
+            | GooseDefault::AbortOnFail
+            | GooseDefault::Percentiles
+            | GooseDefault::Apdex
+            | GooseDefault::MetricsSink
+            | GooseDefault::BaselineFile
+            | GooseDefault::Baseline
             | GooseDefault::Timeout
             | GooseDefault::TransactionLog
-            | GooseDefault::WebSocketHost => {
+            | GooseDefault::WebSocketHost
+            | GooseDefault::PrometheusHost
+            | GooseDefault::OtlpEndpoint
+            | GooseDefault::JunitFile
+            | GooseDefault::Csv => {
                 return Err(GooseError::InvalidOption {
                     option: format!("GooseDefault::{:?}", key),
                     value: format!("{:?}", value),
@@ -1000,7 +1338,13 @@ impl GooseDefaultType<GooseLogFormat> for GooseAttack {
             | GooseDefault::Verbose
             | GooseDefault::ThrottleRequests
             | GooseDefault::TelnetPort
-            | GooseDefault::WebSocketPort => {
+            | GooseDefault::WebSocketPort
+            | GooseDefault::PrometheusPort
+            | GooseDefault::MetricsSinkInterval
+            | GooseDefault::BreakpointErrorRate
+            | GooseDefault::BreakpointResponseTime
+            | GooseDefault::BaselineTolerance
+            | GooseDefault::SignificantDigits => {
                 return Err(GooseError::InvalidOption {
                     option: format!("GooseDefault::{:?}", key),
                     value: format!("{:?}", value),
@@ -1131,6 +1475,114 @@ impl GooseConfigure<TestPlan> for GooseConfiguration {
         None
     }
 }
+impl GooseConfigure<BreakpointStep> for GooseConfiguration {
+    /// Use [`GooseValue`] to set a breakpoint search step value.
+    fn get_value(&self, values: Vec<GooseValue<BreakpointStep>>) -> Option<BreakpointStep> {
+        for value in values {
+            if let Some(v) = value.value {
+                if value.filter {
+                    continue;
+                } else {
+                    if !value.message.is_empty() {
+                        info!("{} = {:?}", value.message, v)
+                    }
+                    return Some(v);
+                }
+            }
+        }
+        None
+    }
+}
+impl GooseConfigure<GooseThresholds> for GooseConfiguration {
+    /// Use [`GooseValue`] to set a [`GooseThresholds`] value.
+    fn get_value(&self, values: Vec<GooseValue<GooseThresholds>>) -> Option<GooseThresholds> {
+        for value in values {
+            if let Some(v) = value.value {
+                if value.filter {
+                    continue;
+                } else {
+                    if !value.message.is_empty() {
+                        info!("{} = {:?}", value.message, v)
+                    }
+                    return Some(v);
+                }
+            }
+        }
+        None
+    }
+}
+impl GooseConfigure<GooseApdex> for GooseConfiguration {
+    /// Use [`GooseValue`] to set a [`GooseApdex`] value.
+    fn get_value(&self, values: Vec<GooseValue<GooseApdex>>) -> Option<GooseApdex> {
+        for value in values {
+            if let Some(v) = value.value {
+                if value.filter {
+                    continue;
+                } else {
+                    if !value.message.is_empty() {
+                        info!("{} = {}", value.message, v)
+                    }
+                    return Some(v);
+                }
+            }
+        }
+        None
+    }
+}
+impl GooseConfigure<GooseMetricsSink> for GooseConfiguration {
+    /// Use [`GooseValue`] to set a [`GooseMetricsSink`] value.
+    fn get_value(&self, values: Vec<GooseValue<GooseMetricsSink>>) -> Option<GooseMetricsSink> {
+        for value in values {
+            if let Some(v) = value.value {
+                if value.filter {
+                    continue;
+                } else {
+                    if !value.message.is_empty() {
+                        info!("{} = {}", value.message, v)
+                    }
+                    return Some(v);
+                }
+            }
+        }
+        None
+    }
+}
+impl GooseConfigure<GoosePercentiles> for GooseConfiguration {
+    /// Use [`GooseValue`] to set a [`GoosePercentiles`] value.
+    fn get_value(&self, values: Vec<GooseValue<GoosePercentiles>>) -> Option<GoosePercentiles> {
+        for value in values {
+            if let Some(v) = value.value {
+                if value.filter {
+                    continue;
+                } else {
+                    if !value.message.is_empty() {
+                        info!("{} = {}", value.message, v)
+                    }
+                    return Some(v);
+                }
+            }
+        }
+        None
+    }
+}
+impl GooseConfigure<GooseAbortRules> for GooseConfiguration {
+    /// Use [`GooseValue`] to set a [`GooseAbortRules`] value.
+    fn get_value(&self, values: Vec<GooseValue<GooseAbortRules>>) -> Option<GooseAbortRules> {
+        for value in values {
+            if let Some(v) = value.value {
+                if value.filter {
+                    continue;
+                } else {
+                    if !value.message.is_empty() {
+                        info!("{} = {:?}", value.message, v)
+                    }
+                    return Some(v);
+                }
+            }
+        }
+        None
+    }
+}
 impl GooseConfigure<String> for GooseConfiguration {
     /// Use [`GooseValue`] to set a [`String`] value.
     fn get_value(&self, values: Vec<GooseValue<String>>) -> Option<String> {
@@ -1149,6 +1601,24 @@ impl GooseConfigure<String> for GooseConfiguration {
         None
     }
 }
+impl GooseConfigure<Vec<String>> for GooseConfiguration {
+    /// Use [`GooseValue`] to set a [`Vec<String>`] value.
+    fn get_value(&self, values: Vec<GooseValue<Vec<String>>>) -> Option<Vec<String>> {
+        for value in values {
+            if let Some(v) = value.value {
+                if value.filter {
+                    continue;
+                } else {
+                    if !value.message.is_empty() {
+                        info!("{} = {:?}", value.message, v)
+                    }
+                    return Some(v);
+                }
+            }
+        }
+        None
+    }
+}
 impl GooseConfigure<bool> for GooseConfiguration {
     /// Use [`GooseValue`] to set a [`bool`] value.
     fn get_value(&self, values: Vec<GooseValue<bool>>) -> Option<bool> {
@@ -1322,6 +1792,108 @@ impl GooseConfiguration {
             },
         ]);
 
+        // Configure `arrival_rate` before `users`, as with an arrival rate users are the maximum
+        // number of users that can be launched.
+        self.arrival_rate = self.get_value(vec![
+            // Use --arrival-rate if set.
+            GooseValue {
+                value: self.arrival_rate.clone(),
+                filter: self.arrival_rate.is_none(),
+                message: "arrival_rate",
+            },
+            // Otherwise use GooseDefault if set.
+            GooseValue {
+                value: defaults.arrival_rate.clone(),
+                filter: defaults.arrival_rate.is_none(),
+                message: "arrival_rate",
+            },
+        ]);
+
+        // Configure `breakpoint` before `users`, as when stepping users a breakpoint search
+        // only stops at a user limit if one is configured.
+        self.breakpoint = self.get_value(vec![
+            // Use --breakpoint if set.
+            GooseValue {
+                value: self.breakpoint,
+                filter: self.breakpoint.is_none(),
+                message: "breakpoint",
+            },
+            // Otherwise use GooseDefault if set.
+            GooseValue {
+                value: defaults.breakpoint,
+                filter: defaults.breakpoint.is_none(),
+                message: "breakpoint",
+            },
+        ]);
+
+        // Configure `breakpoint_error_rate`.
+        self.breakpoint_error_rate = self.get_value(vec![
+            // Use --breakpoint-error-rate if set.
+            GooseValue {
+                value: self.breakpoint_error_rate,
+                filter: self.breakpoint_error_rate.is_none(),
+                message: "breakpoint_error_rate",
+            },
+            // Otherwise use GooseDefault if set.
+            GooseValue {
+                value: defaults.breakpoint_error_rate,
+                filter: defaults.breakpoint_error_rate.is_none(),
+                message: "breakpoint_error_rate",
+            },
+        ]);
+
+        // Configure `breakpoint_response_time`.
+        self.breakpoint_response_time = self.get_value(vec![
+            // Use --breakpoint-response-time if set.
+            GooseValue {
+                value: self.breakpoint_response_time,
+                filter: self.breakpoint_response_time.is_none(),
+                message: "breakpoint_response_time",
+            },
+            // Otherwise use GooseDefault if set.
+            GooseValue {
+                value: defaults.breakpoint_response_time,
+                filter: defaults.breakpoint_response_time.is_none(),
+                message: "breakpoint_response_time",
+            },
+        ]);
+
+        // Configure `arrival_requests`.
+        self.arrival_requests = self
+            .get_value(vec![
+                // Use --arrival-requests if set.
+                GooseValue {
+                    value: Some(self.arrival_requests),
+                    filter: !self.arrival_requests,
+                    message: "arrival_requests",
+                },
+                // Otherwise use GooseDefault if set.
+                GooseValue {
+                    value: defaults.arrival_requests,
+                    filter: defaults.arrival_requests.is_none(),
+                    message: "arrival_requests",
+                },
+            ])
+            .unwrap_or(false);
+
+        // Configure `breakpoint_arrival_rate`.
+        self.breakpoint_arrival_rate = self
+            .get_value(vec![
+                // Use --breakpoint-arrival-rate if set.
+                GooseValue {
+                    value: Some(self.breakpoint_arrival_rate),
+                    filter: !self.breakpoint_arrival_rate,
+                    message: "breakpoint_arrival_rate",
+                },
+                // Otherwise use GooseDefault if set.
+                GooseValue {
+                    value: defaults.breakpoint_arrival_rate,
+                    filter: defaults.breakpoint_arrival_rate.is_none(),
+                    message: "breakpoint_arrival_rate",
+                },
+            ])
+            .unwrap_or(false);
+
         // Configure `users`.
         self.users = self.get_value(vec![
             // Use --users if set.
@@ -1339,7 +1911,8 @@ impl GooseConfiguration {
             // Otherwise use detected number of CPUs if not on Worker.
             GooseValue {
                 value: Some(num_cpus::get()),
-                filter: self.test_plan.is_some(),
+                filter: self.test_plan.is_some()
+                    || (self.breakpoint.is_some() && !self.breakpoint_arrival_rate),
                 message: "users defaulted to number of CPUs",
             },
         ]);
@@ -1540,78 +2113,339 @@ impl GooseConfiguration {
             ])
             .unwrap_or(false);
 
-        // Configure `report_file`.
-        self.report_file = match self.get_value(vec![
-            // Use --report-file if set.
+        // Configure `thresholds`.
+        self.thresholds = self.get_value(vec![
+            // Use --thresholds if set.
             GooseValue {
-                value: Some(self.report_file.to_string()),
-                filter: self.report_file.is_empty(),
-                message: "report_file",
+                value: self.thresholds.clone(),
+                filter: self.thresholds.is_none(),
+                message: "thresholds",
             },
             // Otherwise use GooseDefault if set.
             GooseValue {
-                value: defaults.report_file.clone(),
-                filter: defaults.report_file.is_none(),
-                message: "report_file",
+                value: defaults.thresholds.clone(),
+                filter: defaults.thresholds.is_none(),
+                message: "thresholds",
             },
-        ]) {
-            Some(v) => v,
-            None => "".to_string(),
-        };
+        ]);
 
-        // Configure `no_granular_report`.
-        self.no_debug_body = self
+        // Configure `abort_on_fail`.
+        self.abort_on_fail = self.get_value(vec![
+            // Use --abort-on-fail if set.
+            GooseValue {
+                value: self.abort_on_fail.clone(),
+                filter: self.abort_on_fail.is_none(),
+                message: "abort_on_fail",
+            },
+            // Otherwise use GooseDefault if set.
+            GooseValue {
+                value: defaults.abort_on_fail.clone(),
+                filter: defaults.abort_on_fail.is_none(),
+                message: "abort_on_fail",
+            },
+        ]);
+
+        // Configure `metrics_sink`.
+        self.metrics_sink = self.get_value(vec![
+            // Use --metrics-sink if set.
+            GooseValue {
+                value: self.metrics_sink.clone(),
+                filter: self.metrics_sink.is_none(),
+                message: "metrics_sink",
+            },
+            // Otherwise use GooseDefault if set.
+            GooseValue {
+                value: defaults.metrics_sink.clone(),
+                filter: defaults.metrics_sink.is_none(),
+                message: "metrics_sink",
+            },
+        ]);
+
+        // Configure `metrics_sink_interval`.
+        self.metrics_sink_interval = self.get_value(vec![
+            // Use --metrics-sink-interval if set.
+            GooseValue {
+                value: self.metrics_sink_interval,
+                filter: self.metrics_sink_interval.is_none(),
+                message: "metrics_sink_interval",
+            },
+            // Otherwise use GooseDefault if set.
+            GooseValue {
+                value: defaults.metrics_sink_interval,
+                filter: defaults.metrics_sink_interval.is_none(),
+                message: "metrics_sink_interval",
+            },
+        ]);
+
+        // Configure `otlp_endpoint`.
+        self.otlp_endpoint = self
             .get_value(vec![
-                // Use --no-granular-report if set.
+                // Use --otlp-endpoint if set.
                 GooseValue {
-                    value: Some(self.no_granular_report),
-                    filter: !self.no_granular_report,
-                    message: "no_granular_report",
+                    value: Some(self.otlp_endpoint.to_string()),
+                    filter: self.otlp_endpoint.is_empty(),
+                    message: "otlp_endpoint",
                 },
                 // Otherwise use GooseDefault if set.
                 GooseValue {
-                    value: defaults.no_debug_body,
-                    filter: defaults.no_debug_body.is_none(),
-                    message: "no_granular_report",
+                    value: defaults.otlp_endpoint.clone(),
+                    filter: defaults.otlp_endpoint.is_none(),
+                    message: "otlp_endpoint",
                 },
             ])
-            .unwrap_or(false);
+            .unwrap_or_default();
 
-        // Configure `iterations`.
-        self.iterations = self
+        // Configure `otlp_traces`.
+        self.otlp_traces = self
             .get_value(vec![
-                // Use --iterations if set.
+                // Use --otlp-traces if set.
                 GooseValue {
-                    value: Some(self.iterations),
-                    filter: false,
-                    message: "iterations",
+                    value: Some(self.otlp_traces),
+                    filter: !self.otlp_traces,
+                    message: "otlp_traces",
                 },
-                // Use GooseDefault if not already set and not Worker.
+                // Otherwise use GooseDefault if set.
                 GooseValue {
-                    value: defaults.iterations,
-                    filter: defaults.iterations.is_none(),
-                    message: "iterations",
+                    value: defaults.otlp_traces,
+                    filter: defaults.otlp_traces.is_none(),
+                    message: "otlp_traces",
                 },
             ])
-            .unwrap_or(0);
+            .unwrap_or(false);
 
-        // Configure `scenarios`.
-        self.scenarios = self
+        // Configure `percentiles`.
+        self.percentiles = self.get_value(vec![
+            // Use --percentiles if set.
+            GooseValue {
+                value: self.percentiles.clone(),
+                filter: self.percentiles.is_none(),
+                message: "percentiles",
+            },
+            // Otherwise use GooseDefault if set.
+            GooseValue {
+                value: defaults.percentiles.clone(),
+                filter: defaults.percentiles.is_none(),
+                message: "percentiles",
+            },
+        ]);
+
+        // Configure `apdex`.
+        self.apdex = self.get_value(vec![
+            // Use --apdex if set.
+            GooseValue {
+                value: self.apdex.clone(),
+                filter: self.apdex.is_none(),
+                message: "apdex",
+            },
+            // Otherwise use GooseDefault if set.
+            GooseValue {
+                value: defaults.apdex.clone(),
+                filter: defaults.apdex.is_none(),
+                message: "apdex",
+            },
+        ]);
+
+        // Configure `report_file`.
+        self.report_file = self
             .get_value(vec![
-                // Use --scenarios if set.
+                // Use --report-file if set.
                 GooseValue {
-                    value: Some(self.scenarios.clone()),
-                    filter: self.scenarios.active.is_empty(),
-                    message: "scenarios",
+                    value: Some(self.report_file.clone()),
+                    filter: self.report_file.is_empty(),
+                    message: "report_file",
                 },
-                // Use GooseDefault if not already set and not Worker.
+                // Otherwise use GooseDefault if set.
                 GooseValue {
-                    value: defaults.scenarios.clone(),
-                    filter: defaults.scenarios.is_none(),
-                    message: "scenarios",
+                    value: defaults.report_file.clone(),
+                    filter: defaults.report_file.is_none(),
+                    message: "report_file",
                 },
             ])
-            .unwrap_or_else(|| Scenarios { active: Vec::new() });
+            .unwrap_or_default();
+
+        // Configure `no_granular_report`.
+        self.no_debug_body = self
+            .get_value(vec![
+                // Use --no-granular-report if set.
+                GooseValue {
+                    value: Some(self.no_granular_report),
+                    filter: !self.no_granular_report,
+                    message: "no_granular_report",
+                },
+                // Otherwise use GooseDefault if set.
+                GooseValue {
+                    value: defaults.no_debug_body,
+                    filter: defaults.no_debug_body.is_none(),
+                    message: "no_granular_report",
+                },
+            ])
+            .unwrap_or(false);
+
+        // Configure `junit_file`.
+        self.junit_file = self
+            .get_value(vec![
+                // Use --junit-file if set.
+                GooseValue {
+                    value: Some(self.junit_file.to_string()),
+                    filter: self.junit_file.is_empty(),
+                    message: "junit_file",
+                },
+                // Otherwise use GooseDefault if set.
+                GooseValue {
+                    value: defaults.junit_file.clone(),
+                    filter: defaults.junit_file.is_none(),
+                    message: "junit_file",
+                },
+            ])
+            .unwrap_or_default();
+
+        // Configure `csv`.
+        self.csv = self
+            .get_value(vec![
+                // Use --csv if set.
+                GooseValue {
+                    value: Some(self.csv.to_string()),
+                    filter: self.csv.is_empty(),
+                    message: "csv",
+                },
+                // Otherwise use GooseDefault if set.
+                GooseValue {
+                    value: defaults.csv.clone(),
+                    filter: defaults.csv.is_none(),
+                    message: "csv",
+                },
+            ])
+            .unwrap_or_default();
+
+        // Configure `baseline_file`.
+        self.baseline_file = match self.get_value(vec![
+            // Use --baseline-file if set.
+            GooseValue {
+                value: Some(self.baseline_file.to_string()),
+                filter: self.baseline_file.is_empty(),
+                message: "baseline_file",
+            },
+            // Otherwise use GooseDefault if set.
+            GooseValue {
+                value: defaults.baseline_file.clone(),
+                filter: defaults.baseline_file.is_none(),
+                message: "baseline_file",
+            },
+        ]) {
+            Some(v) => v,
+            None => "".to_string(),
+        };
+
+        // Configure `baseline`.
+        self.baseline = match self.get_value(vec![
+            // Use --baseline if set.
+            GooseValue {
+                value: Some(self.baseline.to_string()),
+                filter: self.baseline.is_empty(),
+                message: "baseline",
+            },
+            // Otherwise use GooseDefault if set.
+            GooseValue {
+                value: defaults.baseline.clone(),
+                filter: defaults.baseline.is_none(),
+                message: "baseline",
+            },
+        ]) {
+            Some(v) => v,
+            None => "".to_string(),
+        };
+
+        // Configure `baseline_tolerance`.
+        self.baseline_tolerance = self.get_value(vec![
+            // Use --baseline-tolerance if set.
+            GooseValue {
+                value: self.baseline_tolerance,
+                filter: self.baseline_tolerance.is_none(),
+                message: "baseline_tolerance",
+            },
+            // Otherwise use GooseDefault if set.
+            GooseValue {
+                value: defaults.baseline_tolerance,
+                filter: defaults.baseline_tolerance.is_none(),
+                message: "baseline_tolerance",
+            },
+            // Otherwise default to 10% when comparing to a baseline.
+            GooseValue {
+                value: Some(10),
+                filter: self.baseline.is_empty(),
+                message: "baseline_tolerance",
+            },
+        ]);
+
+        // Configure `significant_digits`.
+        self.significant_digits = self.get_value(vec![
+            // Use --significant-digits if set.
+            GooseValue {
+                value: self.significant_digits,
+                filter: self.significant_digits.is_none(),
+                message: "significant_digits",
+            },
+            // Otherwise use GooseDefault if set.
+            GooseValue {
+                value: defaults.significant_digits,
+                filter: defaults.significant_digits.is_none(),
+                message: "significant_digits",
+            },
+        ]);
+
+        // Configure `phase_metrics`.
+        self.phase_metrics = self
+            .get_value(vec![
+                // Use --phase-metrics if set.
+                GooseValue {
+                    value: Some(self.phase_metrics),
+                    filter: !self.phase_metrics,
+                    message: "phase_metrics",
+                },
+                // Otherwise use GooseDefault if set.
+                GooseValue {
+                    value: defaults.phase_metrics,
+                    filter: defaults.phase_metrics.is_none(),
+                    message: "phase_metrics",
+                },
+            ])
+            .unwrap_or(false);
+
+        // Configure `iterations`.
+        self.iterations = self
+            .get_value(vec![
+                // Use --iterations if set.
+                GooseValue {
+                    value: Some(self.iterations),
+                    filter: false,
+                    message: "iterations",
+                },
+                // Use GooseDefault if not already set and not Worker.
+                GooseValue {
+                    value: defaults.iterations,
+                    filter: defaults.iterations.is_none(),
+                    message: "iterations",
+                },
+            ])
+            .unwrap_or(0);
+
+        // Configure `scenarios`.
+        self.scenarios = self
+            .get_value(vec![
+                // Use --scenarios if set.
+                GooseValue {
+                    value: Some(self.scenarios.clone()),
+                    filter: self.scenarios.active.is_empty(),
+                    message: "scenarios",
+                },
+                // Use GooseDefault if not already set and not Worker.
+                GooseValue {
+                    value: defaults.scenarios.clone(),
+                    filter: defaults.scenarios.is_none(),
+                    message: "scenarios",
+                },
+            ])
+            .unwrap_or_else(|| Scenarios { active: Vec::new() });
 
         // Configure `no_debug_body`.
         self.no_debug_body = self
@@ -1721,6 +2555,24 @@ impl GooseConfiguration {
             ])
             .unwrap_or(false);
 
+        // Configure `buffer_responses`.
+        self.buffer_responses = self
+            .get_value(vec![
+                // Use --buffer-responses if set.
+                GooseValue {
+                    value: Some(self.buffer_responses),
+                    filter: !self.buffer_responses,
+                    message: "buffer_responses",
+                },
+                // Use GooseDefault if not already set and not Worker.
+                GooseValue {
+                    value: defaults.buffer_responses,
+                    filter: defaults.buffer_responses.is_none(),
+                    message: "buffer_responses",
+                },
+            ])
+            .unwrap_or(false);
+
         self.co_mitigation = self.get_value(vec![
             // Use --co-mitigation if set.
             GooseValue {
@@ -1900,6 +2752,292 @@ impl GooseConfiguration {
             }
         }
 
+        // Validate `arrival_rate`.
+        if self.arrival_rate.is_some() {
+            // The --test-plan option isn't compatible with --arrival-rate.
+            if self.test_plan.is_some() {
+                return Err(GooseError::InvalidOption {
+                    option: "`configuration.test_plan`".to_string(),
+                    value: format!("{:?}", self.test_plan),
+                    detail: "`configuration.test_plan` can not be set with `configuration.arrival_rate`.".to_string(),
+                });
+            }
+            // The --startup-time option isn't compatible with --arrival-rate.
+            if self.startup_time != "0" {
+                return Err(GooseError::InvalidOption {
+                    option: "`configuration.startup_time`".to_string(),
+                    value: self.startup_time.to_string(),
+                    detail: "`configuration.startup_time` can not be set with `configuration.arrival_rate`.".to_string(),
+                });
+            }
+            // The --hatch-rate option isn't compatible with --arrival-rate.
+            if let Some(hatch_rate) = self.hatch_rate.as_ref() {
+                return Err(GooseError::InvalidOption {
+                    option: "`configuration.hatch_rate`".to_string(),
+                    value: hatch_rate.to_string(),
+                    detail: "`configuration.hatch_rate` can not be set with `configuration.arrival_rate`.".to_string(),
+                });
+            }
+            // The --run-time option isn't compatible with --arrival-rate.
+            if self.run_time != "0" {
+                return Err(GooseError::InvalidOption {
+                    option: "`configuration.run_time`".to_string(),
+                    value: self.run_time.to_string(),
+                    detail:
+                        "`configuration.run_time` can not be set with `configuration.arrival_rate`."
+                            .to_string(),
+                });
+            }
+            // The --iterations option isn't compatible with --arrival-rate.
+            if self.iterations > 0 {
+                return Err(GooseError::InvalidOption {
+                    option: "`configuration.iterations`".to_string(),
+                    value: self.iterations.to_string(),
+                    detail: "`configuration.iterations` can not be set with `configuration.arrival_rate`.".to_string(),
+                });
+            }
+            // The --no-reset-metrics option isn't compatible with --arrival-rate.
+            if self.no_reset_metrics {
+                return Err(GooseError::InvalidOption {
+                    option: "`configuration.no_reset_metrics".to_string(),
+                    value: self.no_reset_metrics.to_string(),
+                    detail: "`configuration.no_reset_metrics` can not be set with `configuration.arrival_rate` (metrics are not reset)."
+                        .to_string(),
+                });
+            }
+        }
+
+        // Validate `breakpoint`.
+        if let Some(breakpoint) = self.breakpoint.as_ref() {
+            // A breakpoint search must step up the load, and measure each step.
+            if breakpoint.step == 0 || breakpoint.duration == 0 {
+                return Err(GooseError::InvalidOption {
+                    option: "`configuration.breakpoint`".to_string(),
+                    value: format!("{:?}", breakpoint),
+                    detail: "`configuration.breakpoint` must be a single step of more than 0 users and more than 0 seconds, for example \"10,30s\".".to_string(),
+                });
+            }
+            // The --test-plan option isn't compatible with --breakpoint.
+            if self.test_plan.is_some() {
+                return Err(GooseError::InvalidOption {
+                    option: "`configuration.test_plan`".to_string(),
+                    value: format!("{:?}", self.test_plan),
+                    detail:
+                        "`configuration.test_plan` can not be set with `configuration.breakpoint`."
+                            .to_string(),
+                });
+            }
+            // The --arrival-rate option isn't compatible with --breakpoint.
+            if self.arrival_rate.is_some() {
+                return Err(GooseError::InvalidOption {
+                    option: "`configuration.arrival_rate`".to_string(),
+                    value: format!("{:?}", self.arrival_rate),
+                    detail: "`configuration.arrival_rate` can not be set with `configuration.breakpoint`, use `configuration.breakpoint_arrival_rate` instead.".to_string(),
+                });
+            }
+            // The --startup-time option isn't compatible with --breakpoint.
+            if self.startup_time != "0" {
+                return Err(GooseError::InvalidOption {
+                    option: "`configuration.startup_time`".to_string(),
+                    value: self.startup_time.to_string(),
+                    detail: "`configuration.startup_time` can not be set with `configuration.breakpoint`.".to_string(),
+                });
+            }
+            // The --hatch-rate option isn't compatible with --breakpoint.
+            if let Some(hatch_rate) = self.hatch_rate.as_ref() {
+                return Err(GooseError::InvalidOption {
+                    option: "`configuration.hatch_rate`".to_string(),
+                    value: hatch_rate.to_string(),
+                    detail:
+                        "`configuration.hatch_rate` can not be set with `configuration.breakpoint`."
+                            .to_string(),
+                });
+            }
+            // The --run-time option isn't compatible with --breakpoint.
+            if self.run_time != "0" {
+                return Err(GooseError::InvalidOption {
+                    option: "`configuration.run_time`".to_string(),
+                    value: self.run_time.to_string(),
+                    detail:
+                        "`configuration.run_time` can not be set with `configuration.breakpoint`."
+                            .to_string(),
+                });
+            }
+            // The --iterations option isn't compatible with --breakpoint.
+            if self.iterations > 0 {
+                return Err(GooseError::InvalidOption {
+                    option: "`configuration.iterations`".to_string(),
+                    value: self.iterations.to_string(),
+                    detail:
+                        "`configuration.iterations` can not be set with `configuration.breakpoint`."
+                            .to_string(),
+                });
+            }
+            // A breakpoint search measures each step with metrics.
+            if self.no_metrics {
+                return Err(GooseError::InvalidOption {
+                    option: "`configuration.no_metrics`".to_string(),
+                    value: self.no_metrics.to_string(),
+                    detail:
+                        "`configuration.no_metrics` can not be set with `configuration.breakpoint`."
+                            .to_string(),
+                });
+            }
+            // The error rate is a percentage.
+            if let Some(error_rate) = self.breakpoint_error_rate {
+                if error_rate > 100 {
+                    return Err(GooseError::InvalidOption {
+                        option: "`configuration.breakpoint_error_rate`".to_string(),
+                        value: error_rate.to_string(),
+                        detail: "`configuration.breakpoint_error_rate` must be a percentage from 0 to 100.".to_string(),
+                    });
+                }
+            }
+        } else if let Some(error_rate) = self.breakpoint_error_rate {
+            // The --breakpoint-error-rate option requires --breakpoint.
+            return Err(GooseError::InvalidOption {
+                option: "`configuration.breakpoint_error_rate`".to_string(),
+                value: error_rate.to_string(),
+                detail: "`configuration.breakpoint_error_rate` can not be set without `configuration.breakpoint`.".to_string(),
+            });
+        } else if let Some(response_time) = self.breakpoint_response_time {
+            // The --breakpoint-response-time option requires --breakpoint.
+            return Err(GooseError::InvalidOption {
+                option: "`configuration.breakpoint_response_time`".to_string(),
+                value: response_time.to_string(),
+                detail: "`configuration.breakpoint_response_time` can not be set without `configuration.breakpoint`.".to_string(),
+            });
+        } else if self.breakpoint_arrival_rate {
+            // The --breakpoint-arrival-rate option requires --breakpoint.
+            return Err(GooseError::InvalidOption {
+                option: "`configuration.breakpoint_arrival_rate`".to_string(),
+                value: self.breakpoint_arrival_rate.to_string(),
+                detail: "`configuration.breakpoint_arrival_rate` can not be set without `configuration.breakpoint`.".to_string(),
+            });
+        }
+
+        // The --arrival-requests option requires --arrival-rate or --breakpoint-arrival-rate.
+        if self.arrival_requests && self.arrival_rate.is_none() && !self.breakpoint_arrival_rate {
+            return Err(GooseError::InvalidOption {
+                option: "`configuration.arrival_requests`".to_string(),
+                value: self.arrival_requests.to_string(),
+                detail: "`configuration.arrival_requests` can not be set without `configuration.arrival_rate` or `configuration.breakpoint_arrival_rate`.".to_string(),
+            });
+        }
+
+        // Validate `thresholds`.
+        if self.thresholds.is_some() {
+            // Thresholds are evaluated against metrics.
+            if self.no_metrics {
+                return Err(GooseError::InvalidOption {
+                    option: "`configuration.no_metrics`".to_string(),
+                    value: self.no_metrics.to_string(),
+                    detail:
+                        "`configuration.no_metrics` can not be set with `configuration.thresholds`."
+                            .to_string(),
+                });
+            }
+        }
+
+        // Validate `apdex`.
+        if self.apdex.is_some() {
+            // Apdex scores are calculated from metrics.
+            if self.no_metrics {
+                return Err(GooseError::InvalidOption {
+                    option: "`configuration.no_metrics`".to_string(),
+                    value: self.no_metrics.to_string(),
+                    detail: "`configuration.no_metrics` can not be set with `configuration.apdex`."
+                        .to_string(),
+                });
+            }
+        }
+
+        // Validate `abort_on_fail`.
+        if self.abort_on_fail.is_some() {
+            // Abort-on-fail rules are evaluated against metrics.
+            if self.no_metrics {
+                return Err(GooseError::InvalidOption {
+                    option: "`configuration.no_metrics`".to_string(),
+                    value: self.no_metrics.to_string(),
+                    detail: "`configuration.no_metrics` can not be set with `configuration.abort_on_fail`."
+                        .to_string(),
+                });
+            }
+        }
+
+        // Validate `metrics_sink`.
+        if self.metrics_sink.is_some() {
+            // Only collected metrics can be pushed to the sink.
+            if self.no_metrics {
+                return Err(GooseError::InvalidOption {
+                    option: "`configuration.no_metrics`".to_string(),
+                    value: self.no_metrics.to_string(),
+                    detail: "`configuration.no_metrics` can not be set with `configuration.metrics_sink`."
+                        .to_string(),
+                });
+            }
+        }
+
+        // Validate `metrics_sink_interval`.
+        if let Some(metrics_sink_interval) = self.metrics_sink_interval {
+            if metrics_sink_interval == 0 {
+                return Err(GooseError::InvalidOption {
+                    option: "`configuration.metrics_sink_interval`".to_string(),
+                    value: metrics_sink_interval.to_string(),
+                    detail: "`configuration.metrics_sink_interval` must be at least 1 second."
+                        .to_string(),
+                });
+            }
+        }
+
+        // Validate `otlp_endpoint`.
+        if !self.otlp_endpoint.is_empty() {
+            // Only collected metrics can be exported.
+            if self.no_metrics {
+                return Err(GooseError::InvalidOption {
+                    option: "`configuration.no_metrics`".to_string(),
+                    value: self.no_metrics.to_string(),
+                    detail: "`configuration.no_metrics` can not be set with `configuration.otlp_endpoint`."
+                        .to_string(),
+                });
+            }
+            // Metrics are posted to the endpoint over HTTP.
+            let scheme = url::Url::parse(&self.otlp_endpoint).map(|url| url.scheme().to_string());
+            if !matches!(scheme.as_deref(), Ok("http") | Ok("https")) {
+                return Err(GooseError::InvalidOption {
+                    option: "`configuration.otlp_endpoint`".to_string(),
+                    value: self.otlp_endpoint.to_string(),
+                    detail: "`configuration.otlp_endpoint` must be an http:// or https:// URL."
+                        .to_string(),
+                });
+            }
+        }
+
+        // Validate `otlp_traces`.
+        if self.otlp_traces && self.otlp_endpoint.is_empty() {
+            return Err(GooseError::InvalidOption {
+                option: "`configuration.otlp_traces`".to_string(),
+                value: self.otlp_traces.to_string(),
+                detail: "`configuration.otlp_traces` requires `configuration.otlp_endpoint`."
+                    .to_string(),
+            });
+        }
+
+        // Validate log formats, the jtl format is only supported by the request log.
+        for (option, format) in [
+            ("`configuration.debug_format`", &self.debug_format),
+            ("`configuration.error_format`", &self.error_format),
+            (
+                "`configuration.transaction_format`",
+                &self.transaction_format,
+            ),
+            ("`configuration.scenario_format`", &self.scenario_format),
+        ] {
+            if format == &Some(GooseLogFormat::Jtl) {
+                return Err(logger::jtl_unsupported(option));
+            }
+        }
+
         // Validate `iterations`.
         if self.iterations > 0 {
             // The --run-time option isn't compatible with --iterations.
@@ -1973,11 +3111,55 @@ impl GooseConfiguration {
             } else if !self.report_file.is_empty() {
                 return Err(GooseError::InvalidOption {
                     option: "`configuration.report_file`".to_string(),
-                    value: self.report_file.to_string(),
+                    value: self.report_file.join(", "),
                     detail:
                         "`configuration.report_file` can not be set with `configuration.no_metrics`."
                             .to_string(),
                 });
+            // JUnit file can't be written if metrics are disabled.
+            } else if !self.junit_file.is_empty() {
+                return Err(GooseError::InvalidOption {
+                    option: "`configuration.junit_file`".to_string(),
+                    value: self.junit_file.to_string(),
+                    detail:
+                        "`configuration.junit_file` can not be set with `configuration.no_metrics`."
+                            .to_string(),
+                });
+            // CSV files can't be written if metrics are disabled.
+            } else if !self.csv.is_empty() {
+                return Err(GooseError::InvalidOption {
+                    option: "`configuration.csv`".to_string(),
+                    value: self.csv.to_string(),
+                    detail: "`configuration.csv` can not be set with `configuration.no_metrics`."
+                        .to_string(),
+                });
+            // Baseline file can't be written if metrics are disabled.
+            } else if !self.baseline_file.is_empty() {
+                return Err(GooseError::InvalidOption {
+                    option: "`configuration.baseline_file`".to_string(),
+                    value: self.baseline_file.to_string(),
+                    detail:
+                        "`configuration.baseline_file` can not be set with `configuration.no_metrics`."
+                            .to_string(),
+                });
+            // Metrics can't be compared to a baseline if metrics are disabled.
+            } else if !self.baseline.is_empty() {
+                return Err(GooseError::InvalidOption {
+                    option: "`configuration.baseline`".to_string(),
+                    value: self.baseline.to_string(),
+                    detail:
+                        "`configuration.baseline` can not be set with `configuration.no_metrics`."
+                            .to_string(),
+                });
+            // Per phase metrics can't be displayed if metrics are disabled.
+            } else if self.phase_metrics {
+                return Err(GooseError::InvalidOption {
+                    option: "`configuration.phase_metrics`".to_string(),
+                    value: self.phase_metrics.to_string(),
+                    detail:
+                        "`configuration.phase_metrics` can not be set with `configuration.no_metrics`."
+                            .to_string(),
+                });
             // Coordinated Omission Mitigation can't be enabled if metrics are disabled.
             } else if self.co_mitigation.as_ref().unwrap()
                 != &GooseCoordinatedOmissionMitigation::Disabled
@@ -2001,6 +3183,28 @@ impl GooseConfiguration {
             });
         }
 
+        if self.baseline.is_empty() {
+            if let Some(baseline_tolerance) = self.baseline_tolerance {
+                return Err(GooseError::InvalidOption {
+                    option: "`configuration.baseline_tolerance`".to_string(),
+                    value: baseline_tolerance.to_string(),
+                    detail: "`configuration.baseline_tolerance` can not be set without `configuration.baseline`."
+                        .to_string(),
+                });
+            }
+        }
+
+        // Histograms preserve from 1 to 5 significant digits.
+        if let Some(significant_digits) = self.significant_digits {
+            if !(1..=5).contains(&significant_digits) {
+                return Err(GooseError::InvalidOption {
+                    option: "`configuration.significant_digits`".to_string(),
+                    value: significant_digits.to_string(),
+                    detail: "`configuration.significant_digits` must be from 1 to 5.".to_string(),
+                });
+            }
+        }
+
         // Can't disable autostart if there's no Controller enabled.
         if self.no_autostart && self.no_telnet && self.no_websocket {
             return Err(GooseError::InvalidOption {
@@ -2124,6 +3328,8 @@ mod test {
         let quiet: usize = 0;
         let verbose: usize = 0;
         let report_file = "custom-goose-report.html".to_string();
+        let baseline_file = "custom-goose-baseline.json".to_string();
+        let baseline = "previous-goose-baseline.json".to_string();
         let request_log = "custom-goose-request.log".to_string();
         let transaction_log = "custom-goose-transaction.log".to_string();
         let scenario_log = "custom-goose-scenario.log".to_string();
@@ -2173,6 +3379,8 @@ mod test {
             .unwrap()
             .set_default(GooseDefault::NoGzip, true)
             .unwrap()
+            .set_default(GooseDefault::BufferResponses, true)
+            .unwrap()
             .set_default(GooseDefault::ReportFile, report_file.as_str())
             .unwrap()
             .set_default(GooseDefault::RequestLog, request_log.as_str())
@@ -2209,6 +3417,52 @@ mod test {
             .set_default(GooseDefault::ThrottleRequests, throttle_requests)
             .unwrap()
             .set_default(GooseDefault::StickyFollow, true)
+            .unwrap()
+            .set_default(GooseDefault::ArrivalRate, "10,30s;10,1m;0,0")
+            .unwrap()
+            .set_default(GooseDefault::ArrivalRequests, true)
+            .unwrap()
+            .set_default(GooseDefault::Breakpoint, "10,30s")
+            .unwrap()
+            .set_default(GooseDefault::BreakpointErrorRate, 5)
+            .unwrap()
+            .set_default(GooseDefault::BreakpointResponseTime, 500)
+            .unwrap()
+            .set_default(GooseDefault::BreakpointArrivalRate, true)
+            .unwrap()
+            .set_default(GooseDefault::PhaseMetrics, true)
+            .unwrap()
+            .set_default(GooseDefault::Thresholds, "p95<500;GET /:error-rate<1")
+            .unwrap()
+            .set_default(GooseDefault::AbortOnFail, "error-rate<50,30s")
+            .unwrap()
+            .set_default(GooseDefault::BaselineFile, baseline_file.as_str())
+            .unwrap()
+            .set_default(GooseDefault::Baseline, baseline.as_str())
+            .unwrap()
+            .set_default(GooseDefault::BaselineTolerance, 5)
+            .unwrap()
+            .set_default(GooseDefault::SignificantDigits, 4)
+            .unwrap()
+            .set_default(GooseDefault::Percentiles, "50,90,99.9")
+            .unwrap()
+            .set_default(GooseDefault::Apdex, "500;GET /:100")
+            .unwrap()
+            .set_default(GooseDefault::PrometheusHost, "127.0.0.1")
+            .unwrap()
+            .set_default(GooseDefault::PrometheusPort, 9464)
+            .unwrap()
+            .set_default(GooseDefault::MetricsSink, "statsd://127.0.0.1")
+            .unwrap()
+            .set_default(GooseDefault::MetricsSinkInterval, 5)
+            .unwrap()
+            .set_default(GooseDefault::OtlpEndpoint, "http://127.0.0.1:4318")
+            .unwrap()
+            .set_default(GooseDefault::OtlpTraces, true)
+            .unwrap()
+            .set_default(GooseDefault::JunitFile, "junit.xml")
+            .unwrap()
+            .set_default(GooseDefault::Csv, "goose")
             .unwrap();
 
         assert!(goose_attack.defaults.host == Some(host));
@@ -2233,7 +3487,8 @@ mod test {
         assert!(goose_attack.defaults.no_autostart == Some(true));
         assert!(goose_attack.defaults.timeout == Some(timeout));
         assert!(goose_attack.defaults.no_gzip == Some(true));
-        assert!(goose_attack.defaults.report_file == Some(report_file));
+        assert!(goose_attack.defaults.buffer_responses == Some(true));
+        assert!(goose_attack.defaults.report_file == Some(vec![report_file]));
         assert!(goose_attack.defaults.request_log == Some(request_log));
         assert!(goose_attack.defaults.request_format == Some(GooseLogFormat::Raw));
         assert!(goose_attack.defaults.error_log == Some(error_log));
@@ -2247,5 +3502,39 @@ mod test {
         );
         assert!(goose_attack.defaults.throttle_requests == Some(throttle_requests));
         assert!(goose_attack.defaults.sticky_follow == Some(true));
+        assert!(
+            goose_attack.defaults.arrival_rate.unwrap().steps
+                == vec![(10, 30_000), (10, 60_000), (0, 0)]
+        );
+        assert!(
+            goose_attack.defaults.breakpoint
+                == Some(BreakpointStep {
+                    step: 10,
+                    duration: 30_000
+                })
+        );
+        assert!(goose_attack.defaults.arrival_requests == Some(true));
+        assert!(goose_attack.defaults.breakpoint_error_rate == Some(5));
+        assert!(goose_attack.defaults.breakpoint_response_time == Some(500));
+        assert!(goose_attack.defaults.breakpoint_arrival_rate == Some(true));
+        assert!(goose_attack.defaults.phase_metrics == Some(true));
+        assert!(goose_attack.defaults.thresholds.unwrap().thresholds.len() == 2);
+        assert!(goose_attack.defaults.abort_on_fail.unwrap().rules[0].duration == 30);
+        assert!(goose_attack.defaults.baseline_file == Some(baseline_file));
+        assert!(goose_attack.defaults.baseline == Some(baseline));
+        assert!(goose_attack.defaults.baseline_tolerance == Some(5));
+        assert!(goose_attack.defaults.significant_digits == Some(4));
+        assert!(goose_attack.defaults.percentiles.unwrap().percentiles == vec![50.0, 90.0, 99.9]);
+        assert!(goose_attack.defaults.apdex.unwrap().targets.len() == 2);
+        assert!(goose_attack.defaults.prometheus_host == Some("127.0.0.1".to_string()));
+        assert!(goose_attack.defaults.prometheus_port == Some(9464));
+        assert!(
+            goose_attack.defaults.metrics_sink.unwrap().to_string() == "statsd://127.0.0.1:8125"
+        );
+        assert!(goose_attack.defaults.metrics_sink_interval == Some(5));
+        assert!(goose_attack.defaults.otlp_endpoint == Some("http://127.0.0.1:4318".to_string()));
+        assert!(goose_attack.defaults.otlp_traces == Some(true));
+        assert!(goose_attack.defaults.junit_file == Some("junit.xml".to_string()));
+        assert!(goose_attack.defaults.csv == Some("goose".to_string()));
     }
 }
@@ -5,7 +5,8 @@
 
 use crate::config::GooseConfiguration;
 use crate::metrics::GooseMetrics;
-use crate::test_plan::{TestPlan, TestPlanHistory, TestPlanStepAction};
+use crate::prometheus;
+use crate::test_plan::{TestPlan, TestPlanHistory, TestPlanRamp, TestPlanStepAction};
 use crate::util;
 use crate::{AttackPhase, GooseAttack, GooseAttackRunState, GooseError};
 
@@ -192,6 +193,18 @@ pub enum ControllerCommand {
     ///
     /// This command can be run at any time.
     MetricsJson,
+    /// Display the current [`GooseMetric`](../metrics/struct.GooseMetrics.html)s in the
+    /// Prometheus text exposition format, the same as served by the `--prometheus-port`
+    /// endpoint.
+    ///
+    /// # Example
+    /// Returns the current Goose metrics in Prometheus format.
+    /// ```notest
+    /// metrics-prometheus
+    /// ```
+    ///
+    /// This command can be run at any time.
+    MetricsPrometheus,
 }
 
 /// Defines details around identifying and processing ControllerCommands.
@@ -300,17 +313,31 @@ impl ControllerCommand {
                     }
                 }),
             },
-            ControllerCommand::MetricsJson => {
+            ControllerCommand::MetricsJson => ControllerCommandDetails {
+                help: ControllerHelp {
+                    name: "metrics-json",
+                    description: "display metrics for current load test in json format\n",
+                },
+                regex: r"(?i)^(metricsjson|metrics-json|statsjson|stats-json)$",
+                process_response: Box::new(|response| {
+                    if let ControllerResponseMessage::Metrics(metrics) = response {
+                        Ok(serde_json::to_string(&metrics).expect("unexpected serde failure"))
+                    } else {
+                        Err("error loading metrics".to_string())
+                    }
+                }),
+            },
+            ControllerCommand::MetricsPrometheus => {
                 ControllerCommandDetails {
                     help: ControllerHelp {
-                        name: "metrics-json",
+                        name: "metrics-prometheus",
                         // No new-line as this is the last line of the help screen.
-                        description: "display metrics for current load test in json format",
+                        description: "display metrics for current load test in prometheus format",
                     },
-                    regex: r"(?i)^(metricsjson|metrics-json|statsjson|stats-json)$",
+                    regex: r"(?i)^(metricsprometheus|metrics-prometheus)$",
                     process_response: Box::new(|response| {
-                        if let ControllerResponseMessage::Metrics(metrics) = response {
-                            Ok(serde_json::to_string(&metrics).expect("unexpected serde failure"))
+                        if let ControllerResponseMessage::Prometheus(metrics) = response {
+                            Ok(metrics)
                         } else {
                             Err("error loading metrics".to_string())
                         }
@@ -513,6 +540,18 @@ impl GooseAttack {
                                 ControllerResponseMessage::Metrics(Box::new(self.metrics.clone())),
                             );
                         }
+                        // Send back the running metrics in the Prometheus text format.
+                        ControllerCommand::MetricsPrometheus => {
+                            let metrics = prometheus::format_metrics(
+                                &self.metrics,
+                                &self.attack_phase,
+                                goose_attack_run_state.running_users(),
+                            );
+                            self.reply_to_controller(
+                                message,
+                                ControllerResponseMessage::Prometheus(metrics),
+                            );
+                        }
                         // Start the load test, and acknowledge command.
                         ControllerCommand::Start => {
                             // We can only start an idle load test.
@@ -533,6 +572,7 @@ impl GooseAttack {
                                     self.metrics.history.push(TestPlanHistory::step(
                                         TestPlanStepAction::Increasing,
                                         0,
+                                        self.test_plan.ramp(0),
                                     ));
                                 } else {
                                     // Do not move to Starting phase if unable to prepare load test.
@@ -694,6 +734,10 @@ impl GooseAttack {
                                             // Configure the new user count.
                                             (new_users, total_time),
                                         ];
+                                        // Adjust users linearly at the configured hatch rate.
+                                        self.test_plan.ramps = Vec::new();
+                                        // Manually setting users ends a breakpoint search.
+                                        self.test_plan.breakpoint = None;
 
                                         // Reset the current step to what was happening when reconfiguration happened.
                                         self.test_plan.current = 0;
@@ -862,6 +906,11 @@ impl GooseAttack {
                                                     0,
                                                     (goose_attack_run_state.active_users, elapsed),
                                                 );
+                                                if !self.test_plan.ramps.is_empty() {
+                                                    self.test_plan
+                                                        .ramps
+                                                        .insert(0, TestPlanRamp::Linear);
+                                                }
 
                                                 // Finally, advance to the next step to adjust user count.
                                                 self.advance_test_plan(goose_attack_run_state);
@@ -1096,6 +1145,8 @@ pub(crate) enum ControllerResponseMessage {
     Config(Box<GooseConfiguration>),
     /// A response containing current load test metrics.
     Metrics(Box<GooseMetrics>),
+    /// A response containing current load test metrics in the Prometheus text format.
+    Prometheus(String),
 }
 
 /// The request that's passed from the controller to the parent thread.
@@ -288,12 +288,18 @@
 //! limitations under the License.
 
 use downcast_rs::{impl_downcast, Downcast};
+use flate2::read::GzDecoder;
 use http::method::Method;
+use hyper::client::connect::{dns::Name, HttpInfo};
 use regex::Regex;
-use reqwest::{header, Client, ClientBuilder, RequestBuilder, Response};
+use reqwest::dns::{Addrs, Resolve, Resolving};
+use reqwest::{header, Client, ClientBuilder, RequestBuilder, Response, ResponseBuilderExt};
 use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
 use std::hash::{Hash, Hasher};
-use std::sync::Arc;
+use std::io::Read;
+use std::net::SocketAddr;
+use std::sync::{Arc, Mutex};
 use std::time::Duration;
 use std::{fmt, str};
 use std::{future::Future, pin::Pin, time::Instant};
@@ -301,8 +307,10 @@ use url::Url;
 
 use crate::logger::GooseLog;
 use crate::metrics::{
-    GooseCoordinatedOmissionMitigation, GooseMetric, GooseRawRequest, GooseRequestMetric,
+    GooseCoordinatedOmissionMitigation, GooseCustomMetric, GooseCustomMetricValue, GooseMetric,
+    GooseRawRequest, GooseRequestMetric,
 };
+use crate::test_plan::{TestPlan, TestPlanRamp};
 use crate::{GooseConfiguration, GooseError, WeightedTransactions};
 
 /// By default Goose sets the following User-Agent header when making requests.
@@ -311,6 +319,9 @@ static APP_USER_AGENT: &str = concat!(env!("CARGO_PKG_NAME"), "/", env!("CARGO_P
 /// By default Goose times out requests after 60,000 milliseconds.
 static GOOSE_REQUEST_TIMEOUT: u64 = 60_000;
 
+/// By default Goose closes connections that were idle in the pool for 90,000 milliseconds.
+static GOOSE_POOL_IDLE_TIMEOUT: u64 = 90_000;
+
 /// `transaction!(foo)` expands to `Transaction::new(foo)`, but also does some boxing to work around a limitation in the compiler.
 #[macro_export]
 macro_rules! transaction {
@@ -346,7 +357,8 @@ pub enum TransactionError {
         raw_request: GooseRequestMetric,
     },
     /// The request was canceled. This happens when the throttle is enabled and the load
-    /// test finishes.
+    /// test finishes, or when an arrival rate test plan starting requests per second shuts
+    /// down an idle user or finishes.
     RequestCanceled {
         /// Wraps a [`flume::SendError`](https://docs.rs/flume/*/flume/struct.SendError.html),
         /// a [`GooseRequestMetric`](./struct.GooseRequestMetric.html) has not yet been constructed.
@@ -378,7 +390,7 @@ impl TransactionError {
             TransactionError::Url(_) => "url::ParseError",
             TransactionError::RequestFailed { .. } => "request failed",
             TransactionError::RequestCanceled { .. } => {
-                "request canceled because throttled load test ended"
+                "request canceled because throttled or arrival rate load test ended"
             }
             TransactionError::MetricsFailed { .. } => "failed to send metrics to parent thread",
             TransactionError::LoggerFailed { .. } => "failed to send log message to logger thread",
@@ -496,6 +508,9 @@ pub struct Scenario {
     pub weighted_on_stop_transactions: WeightedTransactions,
     /// An optional default host to run this `Scenario` against.
     pub host: Option<String>,
+    /// An optional test plan controlling how many users run this `Scenario`, independent of
+    /// the global test plan.
+    pub(crate) test_plan: Option<TestPlan>,
 }
 impl Scenario {
     /// Creates a new [`Scenario`](./struct.Scenario.html). Once created, a
@@ -522,6 +537,7 @@ impl Scenario {
             weighted_on_start_transactions: Vec::new(),
             weighted_on_stop_transactions: Vec::new(),
             host: None,
+            test_plan: None,
         }
     }
 
@@ -590,6 +606,71 @@ impl Scenario {
         Ok(self)
     }
 
+    /// Gives a scenario its own test plan, controlling how many users run this scenario
+    /// independently of the global test plan. The test plan uses the same format as the
+    /// `--test-plan` run-time option, for example `"2000,10m;2000,1h;0,5m"` starts 2,000 users
+    /// over 10 minutes, runs them for an hour, then stops them over 5 minutes.
+    ///
+    /// A scenario with its own test plan is not assigned users by
+    /// [`set_weight`](./struct.Scenario.html#method.set_weight): its users are launched in
+    /// addition to the users launched by the global test plan, and its metrics are combined
+    /// with the metrics of all other scenarios. The test plan starts when the load test starts,
+    /// and all of its users are stopped when the global test plan finishes.
+    ///
+    /// # Example
+    /// ```rust
+    /// use goose::prelude::*;
+    ///
+    /// #[tokio::main]
+    /// async fn main() -> Result<(), GooseError> {
+    ///     let mut example_transactions = scenario!("ExampleTransactions").set_test_plan("2000,10m;2000,1h;0,5m")?;
+    ///
+    ///     Ok(())
+    /// }
+    /// ```
+    pub fn set_test_plan(mut self, test_plan: &str) -> Result<Self, GooseError> {
+        trace!("{} set_test_plan: {}", self.name, test_plan);
+        self.test_plan = Some(test_plan.parse()?);
+
+        Ok(self)
+    }
+
+    /// Runs a fixed number of users for this scenario, independently of the global test plan.
+    /// All users are launched when the load test starts and run until the global test plan
+    /// finishes.
+    ///
+    /// As with [`set_test_plan`](./struct.Scenario.html#method.set_test_plan), a scenario with
+    /// a fixed number of users is not assigned users by
+    /// [`set_weight`](./struct.Scenario.html#method.set_weight).
+    ///
+    /// # Example
+    /// ```rust
+    /// use goose::prelude::*;
+    ///
+    /// #[tokio::main]
+    /// async fn main() -> Result<(), GooseError> {
+    ///     let mut example_transactions = scenario!("ExampleTransactions").set_users(5)?;
+    ///
+    ///     Ok(())
+    /// }
+    /// ```
+    pub fn set_users(mut self, users: usize) -> Result<Self, GooseError> {
+        trace!("{} set_users: {}", self.name, users);
+        if users == 0 {
+            return Err(GooseError::InvalidOption {
+                option: "Scenario::set_users".to_string(),
+                value: users.to_string(),
+                detail: "Users must be set to at least 1.".to_string(),
+            });
+        }
+        let mut test_plan = TestPlan::new();
+        test_plan.steps.push((users, 0));
+        test_plan.ramps.push(TestPlanRamp::Spike);
+        self.test_plan = Some(test_plan);
+
+        Ok(self)
+    }
+
     /// Set a default host for the scenario. If no `--host` flag is set when running the load test, this
     /// host will be pre-pended on all requests. For example, this can configure your load test to run
     /// against your local development environment by default, and the `--host` option could be used to
@@ -818,7 +899,8 @@ impl<T: Send + Sync + 'static> GooseUserData for T {}
 /// An individual user state, repeatedly running all [`Transaction`](./struct.Transaction.html)s
 /// in a specific [`Scenario`](./struct.Scenario.html).
 pub struct GooseUser {
-    /// The Instant when this `GooseUser` client started.
+    /// The Instant when the load test started, request, transaction and scenario times are
+    /// relative to this.
     pub started: Instant,
     /// How many iterations of the scenario this GooseUser has run.
     pub(crate) iterations: usize,
@@ -843,6 +925,20 @@ pub struct GooseUser {
     pub metrics_channel: Option<flume::Sender<GooseMetric>>,
     /// Channel for notifying the parent when thread shuts down.
     pub shutdown_channel: Option<flume::Sender<usize>>,
+    /// Channel for receiving permission from the parent to start the next scenario iteration
+    /// (or request with `--arrival-requests`), only used when running an arrival rate test
+    /// plan.
+    pub(crate) arrival_channel: Option<flume::Receiver<bool>>,
+    /// Channel for receiving [`GooseUserCommand::Exit`] from the parent while waiting for
+    /// permission to make a request, only used when running an arrival rate test plan with
+    /// `--arrival-requests`.
+    pub(crate) command_channel: Option<flume::Receiver<GooseUserCommand>>,
+    /// Set when a user is launched to make its first request without waiting, only used when
+    /// running an arrival rate test plan with `--arrival-requests`.
+    pub(crate) arrival_granted: bool,
+    /// Set when the parent shut down this user while it was waiting to make a request, only
+    /// used when running an arrival rate test plan with `--arrival-requests`.
+    pub(crate) arrival_exited: bool,
     /// An index into the internal [`GooseAttack`](../struct.GooseAttack.html)`.weighted_users`
     /// vector, indicating which weighted `GooseUser` is running.
     pub weighted_users_index: usize,
@@ -853,6 +949,8 @@ pub struct GooseUser {
     request_cadence: GooseRequestCadence,
     /// Tracks how much time is spent sleeping during a loop through all transactions.
     pub(crate) slept: u64,
+    /// Tracks the connections opened by the client, and how long it took to open them.
+    connections: GooseConnections,
     /// Current transaction name.
     pub(crate) transaction_name: Option<String>,
     /// Optional per-user session data of a generic type implementing the
@@ -879,12 +977,25 @@ impl GooseUser {
             GOOSE_REQUEST_TIMEOUT
         };
 
+        let connections = GooseConnections::default();
+        // Request gzip-encoded responses unless `--no-gzip` flag is enabled. Reqwest decompresses
+        // responses as they are read, unless `--buffer-responses` is enabled in which case
+        // Goose decompresses them after counting the compressed bytes.
+        let mut headers = header::HeaderMap::new();
+        if configuration.buffer_responses && !configuration.no_gzip {
+            headers.insert(
+                header::ACCEPT_ENCODING,
+                header::HeaderValue::from_static("gzip"),
+            );
+        }
         let client = Client::builder()
             .user_agent(APP_USER_AGENT)
             .cookie_store(true)
             .timeout(Duration::from_millis(timeout))
-            // Enable gzip unless `--no-gzip` flag is enabled.
-            .gzip(!configuration.no_gzip)
+            .pool_idle_timeout(Duration::from_millis(GOOSE_POOL_IDLE_TIMEOUT))
+            .default_headers(headers)
+            .gzip(!configuration.no_gzip && !configuration.buffer_responses)
+            .dns_resolver(Arc::new(connections.clone()))
             .build()?;
 
         Ok(GooseUser {
@@ -899,11 +1010,16 @@ impl GooseUser {
             is_throttled: true,
             metrics_channel: None,
             shutdown_channel: None,
+            arrival_channel: None,
+            command_channel: None,
+            arrival_granted: false,
+            arrival_exited: false,
             // A value of max_value() indicates this user isn't fully initialized yet.
             weighted_users_index: usize::max_value(),
             load_test_hash,
             request_cadence: GooseRequestCadence::new(),
             slept: 0,
+            connections,
             transaction_name: None,
             session_data: None,
         })
@@ -1497,6 +1613,12 @@ impl GooseUser {
             self.get_request_builder(&request.method, request.path)?
         };
 
+        // If starting requests per second, wait until the parent schedules this request.
+        // Will result in TransactionError::RequestCanceled if this user is shut down.
+        if self.is_throttled {
+            self.wait_for_arrival().await?;
+        }
+
         // Determine the name for this request.
         let request_name = self.get_request_name(&request);
 
@@ -1565,10 +1687,42 @@ impl GooseUser {
             self.weighted_users_index,
         );
 
+        // Count the bytes in the request body, unless it's streamed.
+        request_metric.request_bytes = built_request
+            .body()
+            .and_then(|b| b.as_bytes())
+            .map_or(0, |b| b.len() as u64);
+
         // Make the actual request.
         let response = self.client.execute(built_request).await;
         request_metric.set_response_time(started.elapsed().as_millis());
 
+        // Count how many bytes were received. If enabled, read the response body first to
+        // count all responses and time how long the download took.
+        let response = match response {
+            Ok(r) if self.config.buffer_responses => {
+                self.connections.record(&mut request_metric, &r);
+                let downloading = Instant::now();
+                buffer_response(r)
+                    .await
+                    .map(|(r, response_bytes, compressed_bytes)| {
+                        request_metric.response_bytes = response_bytes as u64;
+                        request_metric.compressed_bytes = compressed_bytes as u64;
+                        request_metric.download_time = downloading.elapsed().as_millis() as u64;
+                        r
+                    })
+            }
+            Ok(r) => {
+                self.connections.record(&mut request_metric, &r);
+                // Without reading the body only its Content-Length is known, which Reqwest
+                // removes from responses it decompresses.
+                request_metric.response_bytes = r.content_length().unwrap_or(0);
+                request_metric.compressed_bytes = request_metric.response_bytes;
+                Ok(r)
+            }
+            Err(e) => Err(e),
+        };
+
         // Determine if the request suceeded or failed.
         match &response {
             Ok(r) => {
@@ -1814,6 +1968,38 @@ impl GooseUser {
         Ok(())
     }
 
+    /// When running an arrival rate test plan with `--arrival-requests`, waits until the parent
+    /// schedules the next request. Fails if the parent shut down this user while it was idle,
+    /// or if the load test has ended.
+    async fn wait_for_arrival(&mut self) -> Result<(), Box<TransactionError>> {
+        let arrival_channel = match self.arrival_channel.clone() {
+            Some(arrival_channel) if self.config.arrival_requests => arrival_channel,
+            _ => return Ok(()),
+        };
+
+        // A newly launched user makes its first request immediately.
+        if self.arrival_granted {
+            self.arrival_granted = false;
+            return Ok(());
+        }
+
+        if !self.arrival_exited {
+            let arrived = match self.command_channel.as_ref() {
+                Some(command_channel) => {
+                    crate::user::received_arrival(&arrival_channel, command_channel).await
+                }
+                None => matches!(arrival_channel.recv_async().await, Ok(true)),
+            };
+            if arrived {
+                return Ok(());
+            }
+        }
+
+        // Don't make any more requests, the user exits after this transaction.
+        self.arrival_exited = true;
+        Err(Box::new(flume::SendError(false).into()))
+    }
+
     /// If `request_name` is set, unwrap and use this. Otherwise, if the Transaction has a name
     /// set use it. Otherwise use the path.
     fn get_request_name<'a>(&'a self, request: &'a GooseRequest) -> &'a str {
@@ -1954,6 +2140,108 @@ impl GooseUser {
         }))
     }
 
+    /// Add an amount to a custom counter.
+    ///
+    /// Custom counters track how often something happens that isn't a request, for example
+    /// how many items were added to a shopping cart. The total of each counter is displayed
+    /// in the metrics and html report, with a graph of the amount added each second. Each
+    /// custom metric name can only be used for one kind of custom metric.
+    ///
+    /// # Example
+    /// ```rust
+    /// use goose::prelude::*;
+    ///
+    /// let mut transaction = transaction!(add_to_cart);
+    ///
+    /// async fn add_to_cart(user: &mut GooseUser) -> TransactionResult {
+    ///     let _goose = user.post("cart", "item=1&quantity=2").await?;
+    ///     user.record_counter("cart_items", 2)?;
+    ///
+    ///     Ok(())
+    /// }
+    /// ```
+    pub fn record_counter(&self, name: &str, amount: u64) -> TransactionResult {
+        self.send_custom_metric_to_parent(name, GooseCustomMetricValue::Counter(amount))
+    }
+
+    /// Set the current value of a custom gauge.
+    ///
+    /// Custom gauges track a value observed by the load test, for example how many results
+    /// a search returned. The average, smallest and largest value of each gauge are displayed
+    /// in the metrics and html report, with a graph of the average value each second.
+    ///
+    /// # Example
+    /// ```rust
+    /// use goose::prelude::*;
+    ///
+    /// let mut transaction = transaction!(search);
+    ///
+    /// async fn search(user: &mut GooseUser) -> TransactionResult {
+    ///     let goose = user.get("search?q=goose").await?;
+    ///     if let Ok(response) = goose.response {
+    ///         if let Ok(html) = response.text().await {
+    ///             let results = html.matches("class=\"result\"").count();
+    ///             user.record_gauge("search_results_count", results as f64)?;
+    ///         }
+    ///     }
+    ///
+    ///     Ok(())
+    /// }
+    /// ```
+    pub fn record_gauge(&self, name: &str, value: f64) -> TransactionResult {
+        self.send_custom_metric_to_parent(name, GooseCustomMetricValue::Gauge(value))
+    }
+
+    /// Record how long something took in a custom timing.
+    ///
+    /// Custom timings track the time spent doing something other than making a request
+    /// with [`GooseUser::request`], for example calling a non-HTTP service. The number of
+    /// times recorded, and the average, shortest and longest time of each timing are
+    /// displayed in the metrics and html report, with a graph of the average time each second.
+    ///
+    /// # Example
+    /// ```rust
+    /// use goose::prelude::*;
+    /// use std::time::Instant;
+    ///
+    /// let mut transaction = transaction!(render);
+    ///
+    /// async fn render(user: &mut GooseUser) -> TransactionResult {
+    ///     let started = Instant::now();
+    ///     // Do something that isn't an HTTP request.
+    ///     user.record_timing("render", started.elapsed())?;
+    ///
+    ///     Ok(())
+    /// }
+    /// ```
+    pub fn record_timing(&self, name: &str, duration: Duration) -> TransactionResult {
+        self.send_custom_metric_to_parent(
+            name,
+            GooseCustomMetricValue::Timing(duration.as_millis() as u64),
+        )
+    }
+
+    fn send_custom_metric_to_parent(
+        &self,
+        name: &str,
+        value: GooseCustomMetricValue,
+    ) -> TransactionResult {
+        // Parent is not defined when running
+        // [`test_start`](../struct.GooseAttack.html#method.test_start),
+        // [`test_stop`](../struct.GooseAttack.html#method.test_stop), and during testing.
+        if let Some(metrics_channel) = self.metrics_channel.as_ref() {
+            if let Err(e) = metrics_channel.send(GooseMetric::Custom(GooseCustomMetric {
+                elapsed: self.started.elapsed().as_millis() as u64,
+                name: name.to_string(),
+                value,
+            })) {
+                return Err(Box::new(e.into()));
+            }
+        }
+
+        Ok(())
+    }
+
     /// Write to [`debug_file`](../struct.GooseConfiguration.html#structfield.debug_file)
     /// if enabled.
     ///
@@ -2066,10 +2354,18 @@ impl GooseUser {
     ///    requesting web pages (ie `goose/0.15.2`);
     ///  - [stores cookies](https://docs.rs/reqwest/*/reqwest/struct.ClientBuilder.html#method.cookie_store),
     ///    generally necessary if you aim to simulate logged in users;
-    ///  - enables
-    ///    [`gzip`](https://docs.rs/reqwest/*/reqwest/struct.ClientBuilder.html#method.gzip) compression;
+    ///  - supports [`gzip`](https://docs.rs/reqwest/*/reqwest/struct.ClientBuilder.html#method.gzip)
+    ///    compression (with `--buffer-responses` Goose requests and decompresses gzip-encoded
+    ///    responses itself so the compressed size can also be counted, custom clients that
+    ///    enable `gzip` only count the decompressed size);
     ///  - sets a 60 second [`timeout`](https://docs.rs/reqwest/*/reqwest/struct.ClientBuilder.html#method.timeout) all
-    ///    on all requests.
+    ///    on all requests;
+    ///  - closes connections idle for 90 seconds with a
+    ///    [`pool_idle_timeout`](https://docs.rs/reqwest/*/reqwest/struct.ClientBuilder.html#method.pool_idle_timeout),
+    ///    used to tell when a connection was reused;
+    ///  - times how long it takes to resolve host names with a custom
+    ///    [`dns_resolver`](https://docs.rs/reqwest/*/reqwest/struct.ClientBuilder.html#method.dns_resolver).
+    ///    Custom clients use their own resolver, so the DNS time of their requests is not recorded.
     ///
     /// # Default configuration:
     ///
@@ -2082,8 +2378,9 @@ impl GooseUser {
     /// let builder = Client::builder()
     ///   .user_agent(APP_USER_AGENT)
     ///   .cookie_store(true)
-    ///   .gzip(true)
-    ///   .timeout(Duration::from_secs(60));
+    ///   .timeout(Duration::from_secs(60))
+    ///   .pool_idle_timeout(Duration::from_secs(90))
+    ///   .gzip(true);
     /// ```
     ///
     /// Alternatively, you can use this function to manually build a
@@ -2208,6 +2505,8 @@ impl GooseUser {
         builder: ClientBuilder,
     ) -> Result<(), TransactionError> {
         self.client = builder.build()?;
+        // The new client opens new connections.
+        self.connections.clear();
 
         Ok(())
     }
@@ -2602,6 +2901,137 @@ impl<'a> GooseRequestBuilder<'a> {
     }
 }
 
+/// Tracks the connections opened by a [`GooseUser`]'s client.
+///
+/// Also resolves host names for the client, recording how long each resolution took so it
+/// can be included in the metrics of the request that opened the connection.
+#[derive(Clone, Default)]
+struct GooseConnections {
+    /// How many milliseconds it took to resolve each host name not yet claimed by a request.
+    resolved: Arc<Mutex<HashMap<String, u64>>>,
+    /// The local address of each connection in the client's connection pool, and when it
+    /// last received a response.
+    local_addrs: Arc<Mutex<HashMap<SocketAddr, Instant>>>,
+}
+impl GooseConnections {
+    /// Record whether a response was received on a new or a reused connection, and for
+    /// new connections how long it took to resolve the host name.
+    fn record(&self, request_metric: &mut GooseRequestMetric, response: &Response) {
+        let local_addr = match response.extensions().get::<HttpInfo>() {
+            Some(info) => info.local_addr(),
+            None => return,
+        };
+        // Each connection has its own local address, so seeing the same address again means
+        // the request was made on a pooled connection. Connections idle for longer than the
+        // pool idle timeout were closed, and their address may be reused by a new connection.
+        if let Ok(mut local_addrs) = self.local_addrs.lock() {
+            local_addrs.retain(|_, received| {
+                received.elapsed() < Duration::from_millis(GOOSE_POOL_IDLE_TIMEOUT)
+            });
+            request_metric.connection_reused =
+                local_addrs.insert(local_addr, Instant::now()).is_some();
+        }
+        let resolved = match (response.url().host_str(), self.resolved.lock()) {
+            (Some(host), Ok(mut resolved)) => resolved.remove(host),
+            _ => None,
+        };
+        // A new connection may have been opened in the background while the request waited
+        // for a pooled connection, so only count the resolution if the connection was used.
+        if let Some(dns_time) = resolved.filter(|_| !request_metric.connection_reused) {
+            request_metric.dns_time = dns_time;
+        }
+    }
+
+    /// Forget all connections, used when the client is replaced.
+    fn clear(&self) {
+        if let Ok(mut local_addrs) = self.local_addrs.lock() {
+            local_addrs.clear();
+        }
+    }
+}
+impl Resolve for GooseConnections {
+    fn resolve(&self, name: Name) -> Resolving {
+        let resolved = self.resolved.clone();
+        let host = name.as_str().to_string();
+        Box::pin(async move {
+            let started = Instant::now();
+            // The port is replaced with the port of the request when connecting.
+            let addrs: Vec<SocketAddr> =
+                tokio::net::lookup_host((host.as_str(), 0)).await?.collect();
+            if let Ok(mut resolved) = resolved.lock() {
+                resolved.insert(host, started.elapsed().as_millis() as u64);
+            }
+            Ok(Box::new(addrs.into_iter()) as Addrs)
+        })
+    }
+}
+
+/// Read the entire body of a response when `--buffer-responses` is enabled, returning a copy
+/// of the response that the load test can still read along with how many bytes the body
+/// contained after and before it was decompressed.
+///
+/// Gzip-encoded bodies are decompressed here instead of by Reqwest, so the compressed size
+/// can also be counted. If the client decompresses responses itself, for example because
+/// it was built with `ClientBuilder::gzip(true)`, both sizes are the decompressed size.
+async fn buffer_response(response: Response) -> Result<(Response, usize, usize), reqwest::Error> {
+    let mut builder = http::Response::builder()
+        .status(response.status())
+        .version(response.version())
+        .url(response.url().clone());
+    if let Some(headers) = builder.headers_mut() {
+        *headers = response.headers().clone();
+    }
+    // Preserve the connection information, used by `Response::remote_addr`.
+    if let Some(info) = response.extensions().get::<HttpInfo>() {
+        builder = builder.extension(info.clone());
+    }
+    let mut body = response.bytes().await?;
+    let compressed_bytes = body.len();
+    if let Some(headers) = builder
+        .headers_mut()
+        .filter(|headers| gzip_encoded(headers))
+    {
+        // Decompress on a blocking thread, so large bodies don't stall other users.
+        let compressed = body.clone();
+        match tokio::task::spawn_blocking(move || gunzip(&compressed)).await {
+            Ok(Some(decompressed)) => {
+                // The body is no longer encoded, as when Reqwest decompresses it.
+                headers.remove(header::CONTENT_ENCODING);
+                headers.remove(header::CONTENT_LENGTH);
+                body = decompressed.into();
+            }
+            Ok(None) => (),
+            Err(e) => warn!("failed to decompress gzip-encoded response: {}", e),
+        }
+    }
+    let bytes = body.len();
+    // The status, version and headers were all copied from a valid response.
+    let response = builder.body(body).expect("failed to rebuild response");
+    Ok((Response::from(response), bytes, compressed_bytes))
+}
+
+/// Determine if a response body is gzip-encoded.
+fn gzip_encoded(headers: &header::HeaderMap) -> bool {
+    headers
+        .get(header::CONTENT_ENCODING)
+        .and_then(|encoding| encoding.to_str().ok())
+        .is_some_and(|encoding| encoding.trim().eq_ignore_ascii_case("gzip"))
+}
+
+/// Decompress a gzip-encoded body. Returns `None` if the body is empty or can't be
+/// decompressed.
+fn gunzip(body: &[u8]) -> Option<Vec<u8>> {
+    if body.is_empty() {
+        return None;
+    }
+    let mut decompressed = Vec::new();
+    if let Err(e) = GzDecoder::new(body).read_to_end(&mut decompressed) {
+        warn!("failed to decompress gzip-encoded response: {}", e);
+        return None;
+    }
+    Some(decompressed)
+}
+
 /// Remove path from Reqwest error to avoid having a lot of distincts error
 /// when path parameters are used.
 fn clean_reqwest_error(e: &reqwest::Error, request_name: &str) -> String {
@@ -5,16 +5,31 @@
 //! this data is converted into [`Graph`] structures and HTML markup is generated
 //! based on them.
 
+use crate::metrics::{
+    response_time_percentile, GooseCustomMetricValue, GooseRequestMetricTimingData,
+};
 use crate::test_plan::{TestPlanHistory, TestPlanStepAction};
 use chrono::prelude::*;
 use itertools::Itertools;
 use serde::Serialize;
 use serde_json::json;
 use std::cmp::Ordering;
-use std::collections::HashMap;
+use std::collections::{BTreeMap, HashMap};
 use std::fmt::Write;
 use std::marker::PhantomData;
 
+/// Response time percentiles graphed over time in the HTML report, each with the HTML ID
+/// of its graph and the label of its y axis.
+const RESPONSE_TIME_GRAPH_PERCENTILES: [(f32, &str, &str); 3] = [
+    (0.5, "graph-p50-response-time", "p50 response time [ms]"),
+    (0.95, "graph-p95-response-time", "p95 response time [ms]"),
+    (0.99, "graph-p99-response-time", "p99 response time [ms]"),
+];
+
+/// How many seconds response times are collected for before the percentiles of a second are
+/// calculated, allowing for metrics that reach the parent out of order.
+const RESPONSE_TIME_PERCENTILE_OPEN_SECONDS: usize = 5;
+
 /// Used to collect graph data during a load test.
 pub(crate) struct GraphData {
     /// Counts requests per second for each request type.
@@ -23,6 +38,19 @@ pub(crate) struct GraphData {
     errors_per_second: HashMap<String, TimeSeries<u32, u32>>,
     /// Maintains average response time per second.
     average_response_time_per_second: HashMap<String, TimeSeries<MovingAverage, f32>>,
+    /// Counts bytes received per second for each request type.
+    response_bytes_per_second: HashMap<String, TimeSeries<usize, usize>>,
+    /// Maintains response time percentiles per second for each request type.
+    response_time_percentiles_per_second: HashMap<String, ResponseTimePercentiles>,
+    /// Maintains response time percentiles per second for all requests, which can't be
+    /// calculated by adding together the percentiles of each request type.
+    total_response_time_percentiles_per_second: ResponseTimePercentiles,
+    /// Totals the amounts added to each custom counter per second.
+    custom_counters_per_second: HashMap<String, TimeSeries<usize, usize>>,
+    /// Maintains the average value of each custom gauge per second.
+    custom_gauges_per_second: HashMap<String, TimeSeries<MovingAverage, f32>>,
+    /// Maintains the average time of each custom timing per second.
+    custom_timings_per_second: HashMap<String, TimeSeries<MovingAverage, f32>>,
     /// Number of transactions at the end of each second of the test.
     transactions_per_second: TimeSeries<usize, usize>,
     /// Number of scenarios at the end of each second of the test.
@@ -39,6 +67,12 @@ impl GraphData {
             requests_per_second: HashMap::new(),
             errors_per_second: HashMap::new(),
             average_response_time_per_second: HashMap::new(),
+            response_bytes_per_second: HashMap::new(),
+            response_time_percentiles_per_second: HashMap::new(),
+            total_response_time_percentiles_per_second: ResponseTimePercentiles::new(),
+            custom_counters_per_second: HashMap::new(),
+            custom_gauges_per_second: HashMap::new(),
+            custom_timings_per_second: HashMap::new(),
             transactions_per_second: TimeSeries::new(),
             scenarios_per_second: TimeSeries::new(),
             users_per_second: TimeSeries::new(),
@@ -98,6 +132,85 @@ impl GraphData {
         );
     }
 
+    /// Record bytes received per second metric.
+    pub(crate) fn record_response_bytes_per_second(
+        &mut self,
+        key: &str,
+        second: usize,
+        response_bytes: usize,
+    ) {
+        if !self.response_bytes_per_second.contains_key(key) {
+            self.response_bytes_per_second
+                .insert(key.to_string(), TimeSeries::new());
+        }
+        let data = self.response_bytes_per_second.get_mut(key).unwrap();
+        data.increase_value(second, response_bytes);
+
+        debug!(
+            "incremented second {} for bytes received per second counter: {}",
+            second,
+            data.get(second)
+        );
+    }
+
+    /// Record response time percentiles per second metric, in the second the response was
+    /// received.
+    pub(crate) fn record_response_time_percentiles_per_second(
+        &mut self,
+        key: &str,
+        second: usize,
+        response_time: u64,
+    ) {
+        if !self.response_time_percentiles_per_second.contains_key(key) {
+            self.response_time_percentiles_per_second
+                .insert(key.to_string(), ResponseTimePercentiles::new());
+        }
+        let data = self
+            .response_time_percentiles_per_second
+            .get_mut(key)
+            .unwrap();
+        data.record(second, response_time);
+        self.total_response_time_percentiles_per_second
+            .record(second, response_time);
+
+        debug!(
+            "updated second {} for response time percentiles per second: {} seconds closed",
+            second,
+            data.closed.len()
+        );
+    }
+
+    /// Record custom metric per second.
+    pub(crate) fn record_custom_metric_per_second(
+        &mut self,
+        name: &str,
+        second: usize,
+        value: &GooseCustomMetricValue,
+    ) {
+        match value {
+            GooseCustomMetricValue::Counter(amount) => self
+                .custom_counters_per_second
+                .entry(name.to_string())
+                .or_insert_with(TimeSeries::new)
+                .increase_value(second, *amount as usize),
+            GooseCustomMetricValue::Gauge(value) => self
+                .custom_gauges_per_second
+                .entry(name.to_string())
+                .or_insert_with(TimeSeries::new)
+                .increase_value(second, *value as f32),
+            GooseCustomMetricValue::Timing(time) => self
+                .custom_timings_per_second
+                .entry(name.to_string())
+                .or_insert_with(TimeSeries::new)
+                .increase_value(second, *time as f32),
+        }
+
+        debug!(
+            "updated second {} for custom metric {}: {:?}",
+            second, name, value
+        );
+    }
+
     /// Record transactions per second metric.
     pub(crate) fn record_transactions_per_second(&mut self, second: usize) {
         self.transactions_per_second.increase_value(second, 1);
@@ -158,6 +271,91 @@ impl GraphData {
         )
     }
 
+    /// Generate bytes received per second graph.
+    pub(crate) fn get_bandwidth_graph(&self, granular_data: bool) -> Graph<'_, usize, usize> {
+        self.create_graph_from_data(
+            "graph-bandwidth",
+            "Received [bytes]",
+            granular_data,
+            self.response_bytes_per_second.clone(),
+        )
+    }
+
+    /// Generate p50, p95 and p99 response time graphs.
+    pub(crate) fn get_response_time_percentile_graphs(
+        &self,
+        granular_data: bool,
+    ) -> Vec<Graph<'_, usize, usize>> {
+        // Split the percentiles of each request type into a time series for each graph.
+        let mut data: [HashMap<String, TimeSeries<usize, usize>>; 3] = Default::default();
+        for (key, percentiles) in &self.response_time_percentiles_per_second {
+            for (graph_data, time_series) in data.iter_mut().zip(percentiles.time_series()) {
+                graph_data.insert(key.clone(), time_series);
+            }
+        }
+
+        RESPONSE_TIME_GRAPH_PERCENTILES
+            .iter()
+            .zip(data)
+            .zip(
+                self.total_response_time_percentiles_per_second
+                    .time_series(),
+            )
+            .map(|(((_, html_id, y_axis_label), data), total)| {
+                self.create_graph_from_data(html_id, y_axis_label, granular_data, data)
+                    .with_total(total)
+            })
+            .collect()
+    }
+
+    /// Generate custom counters per second graph, if any custom counters were recorded.
+    pub(crate) fn get_custom_counters_graph(
+        &self,
+        granular_data: bool,
+    ) -> Option<Graph<'_, usize, usize>> {
+        if self.custom_counters_per_second.is_empty() {
+            return None;
+        }
+        Some(self.create_graph_from_data(
+            "graph-custom-counters",
+            "Counters #",
+            granular_data,
+            self.custom_counters_per_second.clone(),
+        ))
+    }
+
+    /// Generate average custom gauges graph, if any custom gauges were recorded.
+    pub(crate) fn get_custom_gauges_graph(
+        &self,
+        granular_data: bool,
+    ) -> Option<Graph<'_, MovingAverage, f32>> {
+        if self.custom_gauges_per_second.is_empty() {
+            return None;
+        }
+        Some(self.create_graph_from_data(
+            "graph-custom-gauges",
+            "Gauges",
+            granular_data,
+            self.custom_gauges_per_second.clone(),
+        ))
+    }
+
+    /// Generate average custom timings graph, if any custom timings were recorded.
+    pub(crate) fn get_custom_timings_graph(
+        &self,
+        granular_data: bool,
+    ) -> Option<Graph<'_, MovingAverage, f32>> {
+        if self.custom_timings_per_second.is_empty() {
+            return None;
+        }
+        Some(self.create_graph_from_data(
+            "graph-custom-timings",
+            "Timings [ms]",
+            granular_data,
+            self.custom_timings_per_second.clone(),
+        ))
+    }
+
     /// Generate active transactions graph.
     pub(crate) fn get_transactions_per_second_graph(
         &self,
@@ -194,6 +392,66 @@ impl GraphData {
         )
     }
 
+    /// Generate graphs overlaying the requests per second, average response times, errors
+    /// per second and active users of a baseline load test on those of this load test. Both
+    /// load tests are aligned by how long they had been running.
+    pub(crate) fn get_comparison_markup(
+        &self,
+        baseline: &GraphData,
+        test_started_time: DateTime<Utc>,
+    ) -> String {
+        // Each load test is a separate line, rather than part of a total.
+        fn overlay<T: Clone + TimeSeriesValue<T, U>, U>(
+            baseline: TimeSeries<T, U>,
+            current: TimeSeries<T, U>,
+        ) -> HashMap<String, TimeSeries<T, U>> {
+            let mut data = HashMap::new();
+            data.insert("Baseline".to_string(), baseline);
+            data.insert("Current".to_string(), current);
+            data
+        }
+
+        [
+            Graph::new_overlay(
+                "graph-compare-rps",
+                "Requests #",
+                overlay(
+                    total_time_series(&baseline.requests_per_second),
+                    total_time_series(&self.requests_per_second),
+                ),
+            )
+            .get_markup(&[], test_started_time),
+            Graph::new_overlay(
+                "graph-compare-avg-response-time",
+                "Response time [ms]",
+                overlay(
+                    total_time_series(&baseline.average_response_time_per_second),
+                    total_time_series(&self.average_response_time_per_second),
+                ),
+            )
+            .get_markup(&[], test_started_time),
+            Graph::new_overlay(
+                "graph-compare-eps",
+                "Errors #",
+                overlay(
+                    total_time_series(&baseline.errors_per_second),
+                    total_time_series(&self.errors_per_second),
+                ),
+            )
+            .get_markup(&[], test_started_time),
+            Graph::new_overlay(
+                "graph-compare-active-users",
+                "Active users #",
+                overlay(
+                    baseline.users_per_second.clone(),
+                    self.users_per_second.clone(),
+                ),
+            )
+            .get_markup(&[], test_started_time),
+        ]
+        .join("\n")
+    }
+
     /// Creates a Graph from granular data.
     fn create_graph_from_data<
         'a,
@@ -239,6 +497,11 @@ pub(crate) struct Graph<'a, T: Clone + TimeSeriesValue<T, U>, U: Serialize + Cop
     granular_data: bool,
     /// Graph data.
     data: HashMap<String, TimeSeries<T, U>>,
+    /// Indicates whether each data series is a separate load test being compared, rather
+    /// than part of a total.
+    overlay: bool,
+    /// Total of all data series, if it can't be calculated by adding them together.
+    total: Option<TimeSeries<T, U>>,
 }
 
 impl<'a, T: Clone + TimeSeriesValue<T, U>, U: Serialize + Copy + PartialEq + PartialOrd>
@@ -257,6 +520,30 @@ impl<'a, T: Clone + TimeSeriesValue<T, U>, U: Serialize + Copy + PartialEq + Par
             y_axis_label,
             granular_data,
             data,
+            overlay: false,
+            total: None,
+        }
+    }
+
+    /// Sets the total of all data series, for example of percentiles that can't be added
+    /// together.
+    fn with_total(self, total: TimeSeries<T, U>) -> Graph<'a, T, U> {
+        Graph {
+            total: Some(total),
+            ..self
+        }
+    }
+
+    /// Creates a new Graph object comparing load tests, with a line for each data series
+    /// in alphabetical order.
+    fn new_overlay(
+        html_id: &'a str,
+        y_axis_label: &'a str,
+        data: HashMap<String, TimeSeries<T, U>>,
+    ) -> Graph<'a, T, U> {
+        Graph {
+            overlay: true,
+            ..Graph::new(html_id, y_axis_label, true, data)
         }
     }
 
@@ -318,7 +605,9 @@ impl<'a, T: Clone + TimeSeriesValue<T, U>, U: Serialize + Copy + PartialEq + Par
                     );
                 }
                 // For decreasing show the new number of users from the current number of users.
-                TestPlanStepAction::Decreasing | TestPlanStepAction::Canceling => {
+                TestPlanStepAction::Decreasing
+                | TestPlanStepAction::Canceling
+                | TestPlanStepAction::Aborting => {
                     let _ = write!(
                         steps,
                         r#"[
@@ -357,10 +646,53 @@ impl<'a, T: Clone + TimeSeriesValue<T, U>, U: Serialize + Copy + PartialEq + Par
         }
 
         let mut total_values: TimeSeries<T, U> = TimeSeries::new();
-        let (legend, main_label, main_values, other_values) = if self.data.len() > 1 {
-            // If we are dealing with a metric with granular data we need to calculate totals.
-            for (_, single_data) in self.data.iter() {
-                total_values.add_time_series(single_data);
+        let (legend, main_label, main_values, other_values) = if self.overlay {
+            // When comparing load tests, the first data series is the main line and the
+            // others are overlaid on it.
+            let mut sorted_data = self.data.iter().sorted_by(|a, b| a.0.cmp(b.0));
+            let (main_label, main_values) = sorted_data.next().unwrap();
+            let mut other_values = String::new();
+            for (label, sub_data) in sorted_data {
+                let _ = write!(
+                    other_values,
+                    r#"{{
+                                name: '{label}',
+                                type: 'line',
+                                symbol: 'none',
+                                sampling: 'lttb',
+                                data: {values},
+                            }},
+                            "#,
+                    label = label,
+                    values = json!(self.add_timestamp_to_html_graph_data(
+                        &sub_data.get_graph_data(),
+                        test_started_time
+                    ))
+                );
+            }
+            (
+                format!(
+                    r#"legend: {{
+                            type: 'plain',
+                            width: '75%',
+                            data: {data},
+                        }},"#,
+                    data = json!(self.data.keys().sorted().collect::<Vec<_>>())
+                ),
+                main_label.as_str(),
+                main_values,
+                other_values,
+            )
+        } else if self.data.len() > 1 {
+            // If we are dealing with a metric with granular data we need to calculate totals,
+            // unless they were provided.
+            match self.total.as_ref() {
+                Some(total) => total_values = total.clone(),
+                None => {
+                    for (_, single_data) in self.data.iter() {
+                        total_values.add_time_series(single_data);
+                    }
+                }
             }
 
             // We will have multiple lines. We need to prepare the legend section on the graph
@@ -536,6 +868,17 @@ impl<'a, T: Clone + TimeSeriesValue<T, U>, U: Serialize + Copy + PartialEq + Par
     }
 }
 
+/// Adds up all time series, for example to get the total requests per second.
+fn total_time_series<T: Clone + TimeSeriesValue<T, U>, U>(
+    data: &HashMap<String, TimeSeries<T, U>>,
+) -> TimeSeries<T, U> {
+    let mut total = TimeSeries::new();
+    for time_series in data.values() {
+        total.add_time_series(time_series);
+    }
+    total
+}
+
 /// Data structure to represent time series data.
 #[derive(Debug, Clone)]
 struct TimeSeries<T: TimeSeriesValue<T, U>, U> {
@@ -597,6 +940,19 @@ impl<T: Clone + TimeSeriesValue<T, U>, U> TimeSeries<T, U> {
         }
     }
 
+    /// Creates a new TimeSeries object from a value for each second.
+    fn from_values(values: Vec<T>) -> TimeSeries<T, U> {
+        let mut total = T::initial_value();
+        for value in &values {
+            total.merge(value);
+        }
+        TimeSeries {
+            data: values,
+            phantom: PhantomData,
+            total,
+        }
+    }
+
     /// Increases the the value for a given second.
     fn increase_value(&mut self, second: usize, value: U) {
         self.expand(second, T::initial_value());
@@ -743,6 +1099,80 @@ impl TimeSeriesValue<MovingAverage, f32> for MovingAverage {
     }
 }
 
+/// Data structure to maintain response time percentiles per second.
+///
+/// It will maintain a histogram of the response times of the most recent seconds. Once no
+/// more response times are expected for a second, its p50, p95 and p99 response times are
+/// calculated and the histogram is dropped.
+#[derive(Debug, Clone)]
+pub(crate) struct ResponseTimePercentiles {
+    /// Response times of the seconds that are still being collected.
+    open: BTreeMap<usize, GooseRequestMetricTimingData>,
+    /// The p50, p95 and p99 response times of each second that was closed.
+    closed: Vec<[usize; 3]>,
+}
+
+impl ResponseTimePercentiles {
+    /// Create a new ResponseTimePercentiles object.
+    fn new() -> Self {
+        ResponseTimePercentiles {
+            open: BTreeMap::new(),
+            closed: Vec::new(),
+        }
+    }
+
+    /// Record a response time, then close any seconds that are too old to receive more.
+    fn record(&mut self, second: usize, response_time: u64) {
+        // A response time that arrives after its second was closed is added to the oldest
+        // second still open.
+        let second = second.max(self.closed.len());
+        self.open
+            .entry(second)
+            .or_default()
+            .record_time(response_time);
+
+        while let Some(oldest) = self.open.keys().next().copied() {
+            if oldest + RESPONSE_TIME_PERCENTILE_OPEN_SECONDS > second {
+                break;
+            }
+            let data = self.open.remove(&oldest).unwrap();
+            // Seconds without any responses are graphed as 0.
+            self.closed.resize(oldest, [0; 3]);
+            self.closed.push(Self::percentiles(&data));
+        }
+    }
+
+    /// Calculate the p50, p95 and p99 response times of a second.
+    fn percentiles(data: &GooseRequestMetricTimingData) -> [usize; 3] {
+        RESPONSE_TIME_GRAPH_PERCENTILES.map(|(percentile, _, _)| {
+            response_time_percentile(
+                &data.times,
+                data.counter,
+                data.minimum_time,
+                data.maximum_time,
+                percentile,
+            )
+        })
+    }
+
+    /// Build a time series for each of the p50, p95 and p99 response times, including the
+    /// seconds that are still open.
+    fn time_series(&self) -> [TimeSeries<usize, usize>; 3] {
+        let mut values = self.closed.clone();
+        for (second, data) in &self.open {
+            values.resize(*second, [0; 3]);
+            values.push(Self::percentiles(data));
+        }
+        let mut time_series: [Vec<usize>; 3] = Default::default();
+        for second in values {
+            for (time_series, value) in time_series.iter_mut().zip(second) {
+                time_series.push(value);
+            }
+        }
+        time_series.map(TimeSeries::from_values)
+    }
+}
+
 /// Data structure to maintain moving averages.
 ///
 /// It will maintain the current average and the number of data items that
@@ -807,6 +1237,7 @@ impl Eq for MovingAverage {}
 #[cfg(test)]
 mod test {
     use super::*;
+    use crate::test_plan::TestPlanRamp;
 
     #[test]
     fn test_graph_setters() {
@@ -1343,6 +1774,119 @@ mod test {
         );
     }
 
+    #[test]
+    fn test_record_response_bytes_per_second() {
+        // Should be initialized with empty bytes received per second vector.
+        let mut graph = GraphData::new();
+        assert_eq!(graph.response_bytes_per_second.len(), 0);
+
+        graph.record_response_bytes_per_second("GET /", 0, 1024);
+        graph.record_response_bytes_per_second("GET /", 0, 512);
+        graph.record_response_bytes_per_second("GET /", 2, 0);
+        graph.record_response_bytes_per_second("GET /about", 1, 2048);
+        let data = graph.response_bytes_per_second.get("GET /").unwrap();
+        assert_eq!(data.get_graph_data(), vec![1536, 0, 0]);
+        assert_eq!(data.total(), 1536);
+        let data = graph.response_bytes_per_second.get("GET /about").unwrap();
+        assert_eq!(data.get_graph_data(), vec![0, 2048]);
+
+        let bandwidth_graph = graph.get_bandwidth_graph(true);
+        assert_eq!(bandwidth_graph.html_id, "graph-bandwidth");
+        assert_eq!(bandwidth_graph.y_axis_label, "Received [bytes]");
+        assert_eq!(bandwidth_graph.data.len(), 2);
+    }
+
+    #[test]
+    fn test_record_custom_metric_per_second() {
+        // Should be initialized without custom metrics, and without custom metric graphs.
+        let mut graph = GraphData::new();
+        assert!(graph.get_custom_counters_graph(true).is_none());
+        assert!(graph.get_custom_gauges_graph(true).is_none());
+        assert!(graph.get_custom_timings_graph(true).is_none());
+
+        graph.record_custom_metric_per_second("cart_items", 0, &GooseCustomMetricValue::Counter(2));
+        graph.record_custom_metric_per_second("cart_items", 0, &GooseCustomMetricValue::Counter(3));
+        graph.record_custom_metric_per_second("cart_items", 1, &GooseCustomMetricValue::Counter(1));
+        let data = graph.custom_counters_per_second.get("cart_items").unwrap();
+        assert_eq!(data.get_graph_data(), vec![5, 1]);
+
+        graph.record_custom_metric_per_second("results", 1, &GooseCustomMetricValue::Gauge(4.0));
+        graph.record_custom_metric_per_second("results", 1, &GooseCustomMetricValue::Gauge(8.0));
+        let data = graph.custom_gauges_per_second.get("results").unwrap();
+        assert_eq!(data.get_graph_data(), vec![0., 6.]);
+
+        graph.record_custom_metric_per_second("render", 0, &GooseCustomMetricValue::Timing(10));
+        graph.record_custom_metric_per_second("render", 0, &GooseCustomMetricValue::Timing(20));
+        let data = graph.custom_timings_per_second.get("render").unwrap();
+        assert_eq!(data.get_graph_data(), vec![15.]);
+
+        let counters_graph = graph.get_custom_counters_graph(true).unwrap();
+        assert_eq!(counters_graph.html_id, "graph-custom-counters");
+        assert_eq!(counters_graph.data.len(), 1);
+        let gauges_graph = graph.get_custom_gauges_graph(true).unwrap();
+        assert_eq!(gauges_graph.html_id, "graph-custom-gauges");
+        let timings_graph = graph.get_custom_timings_graph(true).unwrap();
+        assert_eq!(timings_graph.html_id, "graph-custom-timings");
+        assert_eq!(timings_graph.y_axis_label, "Timings [ms]");
+    }
+
+    #[test]
+    fn test_record_response_time_percentiles_per_second() {
+        // Should be initialized with empty response time percentiles per second vector.
+        let mut graph = GraphData::new();
+        assert_eq!(graph.response_time_percentiles_per_second.len(), 0);
+
+        for response_time in 1..=100 {
+            graph.record_response_time_percentiles_per_second("GET /", 0, response_time);
+        }
+        graph.record_response_time_percentiles_per_second("GET /", 2, 7);
+        graph.record_response_time_percentiles_per_second("GET /about", 0, 1000);
+        let data = graph
+            .response_time_percentiles_per_second
+            .get("GET /")
+            .unwrap();
+        // Recent seconds are still open.
+        assert_eq!(data.open.len(), 2);
+        assert_eq!(data.closed.len(), 0);
+
+        // A graph is generated for each percentile.
+        let graphs = graph.get_response_time_percentile_graphs(true);
+        assert_eq!(graphs.len(), 3);
+        assert_eq!(graphs[0].html_id, "graph-p50-response-time");
+        assert_eq!(graphs[1].html_id, "graph-p95-response-time");
+        assert_eq!(graphs[2].y_axis_label, "p99 response time [ms]");
+
+        // Percentiles are calculated per second for each request.
+        let p95 = graphs[1].data.get("GET /").unwrap();
+        assert_eq!(p95.get_graph_data(), vec![95, 0, 7]);
+        let p95 = graphs[1].data.get("GET /about").unwrap();
+        assert_eq!(p95.get_graph_data(), vec![1000]);
+
+        // Percentiles of all requests are calculated from all response times, not by
+        // adding together the percentiles of each request.
+        let total = graphs[0].total.as_ref().unwrap();
+        assert_eq!(total.get_graph_data(), vec![51, 0, 7]);
+
+        // Once no more responses are expected for a second, only its percentiles are kept.
+        graph.record_response_time_percentiles_per_second("GET /", 7, 5);
+        let data = graph
+            .response_time_percentiles_per_second
+            .get("GET /")
+            .unwrap();
+        assert_eq!(data.closed, vec![[50, 95, 99], [0, 0, 0], [7, 7, 7]]);
+        assert_eq!(data.open.len(), 1);
+        // Late responses are added to the oldest open second.
+        graph.record_response_time_percentiles_per_second("GET /", 1, 9);
+        let data = graph
+            .response_time_percentiles_per_second
+            .get("GET /")
+            .unwrap();
+        assert_eq!(data.open.get(&3).unwrap().counter, 1);
+        let graphs = graph.get_response_time_percentile_graphs(true);
+        let p50 = graphs[0].data.get("GET /").unwrap();
+        assert_eq!(p50.get_graph_data(), vec![50, 0, 7, 9, 0, 0, 0, 5]);
+    }
+
     #[test]
     fn test_record_transactions_per_second() {
         // Should be initialized with empty transactions per second vector.
@@ -1825,21 +2369,29 @@ mod test {
                 action: TestPlanStepAction::Increasing,
                 timestamp: Utc.with_ymd_and_hms(2021, 11, 21, 21, 20, 32).unwrap(),
                 users: 123,
+                ramp: TestPlanRamp::Linear,
+                reason: None,
             },
             TestPlanHistory {
                 action: TestPlanStepAction::Decreasing,
                 timestamp: Utc.with_ymd_and_hms(2021, 11, 21, 21, 20, 33).unwrap(),
                 users: 123,
+                ramp: TestPlanRamp::Linear,
+                reason: None,
             },
             TestPlanHistory {
                 action: TestPlanStepAction::Canceling,
                 timestamp: Utc.with_ymd_and_hms(2021, 11, 21, 21, 20, 34).unwrap(),
                 users: 123,
+                ramp: TestPlanRamp::Linear,
+                reason: None,
             },
             TestPlanHistory {
                 action: TestPlanStepAction::Finished,
                 timestamp: Utc.with_ymd_and_hms(2021, 11, 21, 21, 20, 35).unwrap(),
                 users: 123,
+                ramp: TestPlanRamp::Linear,
+                reason: None,
             },
         ];
 
@@ -2206,4 +2758,50 @@ mod test {
             expected
         );
     }
+
+    #[test]
+    fn test_comparison_markup() {
+        let started = Utc.with_ymd_and_hms(2021, 11, 21, 21, 20, 32).unwrap();
+        let second = |second: i64| {
+            Local
+                .timestamp_opt(started.timestamp() + second, 0)
+                .unwrap()
+                .format("%Y-%m-%d %H:%M:%S")
+                .to_string()
+        };
+
+        let mut baseline = GraphData::new();
+        baseline.record_requests_per_second("GET /", 0);
+        baseline.record_requests_per_second("GET /about", 0);
+        baseline.record_users_per_second(2, 0);
+        let mut current = GraphData::new();
+        current.record_requests_per_second("GET /", 0);
+        current.record_requests_per_second("GET /", 1);
+        current.record_users_per_second(1, 1);
+
+        let markup = current.get_comparison_markup(&baseline, started);
+        for html_id in [
+            "graph-compare-rps",
+            "graph-compare-avg-response-time",
+            "graph-compare-eps",
+            "graph-compare-active-users",
+        ] {
+            assert!(markup.contains(&format!(r#"id="{}""#, html_id)));
+        }
+        assert!(markup.contains(r#"data: ["Baseline","Current"]"#));
+        // Load tests are overlaid, not added up.
+        assert!(!markup.contains("Total"));
+        // Each load test is a line adding up all of its requests.
+        assert!(markup.contains(&format!(r#"data: [["{}",2]]"#, second(0))));
+        assert!(markup.contains(&format!(
+            r#"data: [["{}",1],["{}",1]]"#,
+            second(0),
+            second(1)
+        )));
+        assert!(markup.contains(&format!(
+            r#"data: [["{}",0],["{}",1]]"#,
+            second(0),
+            second(1)
+        )));
+    }
 }
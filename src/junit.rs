@@ -0,0 +1,287 @@
+//! Optional JUnit XML results.
+//!
+//! When enabled with `--junit-file`, Goose writes the final metrics of a load test as a
+//! JUnit XML document, allowing CI systems such as GitLab and Jenkins to display load test
+//! regressions natively. Each request and each transaction is a test case that fails if any
+//! errors were recorded, or if a threshold configured for it was not met. Thresholds that
+//! apply to all requests are reported as test cases of their own.
+
+use std::fmt::Write;
+
+use crate::metrics::{GooseMetrics, GooseThresholdResult};
+
+/// A single JUnit test case.
+struct TestCase {
+    /// Groups related test cases, ie the scenario a transaction belongs to.
+    classname: String,
+    /// The name of the test case.
+    name: String,
+    /// The average time in seconds.
+    time: f32,
+    /// Why the test case failed, if it failed.
+    failures: Vec<String>,
+    /// Details of the failures, such as the errors returned.
+    details: Vec<String>,
+}
+
+/// Escape text so it can be used in XML attributes and content.
+fn escape(text: &str) -> String {
+    let mut escaped = String::with_capacity(text.len());
+    for c in text.chars() {
+        match c {
+            '&' => escaped.push_str("&amp;"),
+            '<' => escaped.push_str("&lt;"),
+            '>' => escaped.push_str("&gt;"),
+            '"' => escaped.push_str("&quot;"),
+            '\'' => escaped.push_str("&apos;"),
+            _ => escaped.push(c),
+        }
+    }
+    escaped
+}
+
+/// Convert a total time in milliseconds to an average time in seconds.
+fn average_seconds(total_time: usize, counter: usize) -> f32 {
+    if counter > 0 {
+        total_time as f32 / counter as f32 / 1_000.0
+    } else {
+        0.0
+    }
+}
+
+/// Describe why a threshold was not met.
+fn threshold_failure(result: &GooseThresholdResult) -> String {
+    match result.value {
+        Some(value) => format!(
+            "threshold {} not met, measured {:.2}",
+            result.threshold, value
+        ),
+        None => format!(
+            "threshold {} not met, no matching requests",
+            result.threshold
+        ),
+    }
+}
+
+/// Append a `<testsuite>` containing all provided test cases.
+fn write_suite(xml: &mut String, name: &str, test_cases: &[TestCase]) {
+    let failures = test_cases
+        .iter()
+        .filter(|test_case| !test_case.failures.is_empty())
+        .count();
+    let time: f32 = test_cases.iter().map(|test_case| test_case.time).sum();
+    let _ = writeln!(
+        xml,
+        r#"  <testsuite name="{}" tests="{}" failures="{}" errors="0" skipped="0" time="{:.3}">"#,
+        escape(name),
+        test_cases.len(),
+        failures,
+        time
+    );
+    for test_case in test_cases {
+        let _ = write!(
+            xml,
+            r#"    <testcase classname="{}" name="{}" time="{:.3}""#,
+            escape(&test_case.classname),
+            escape(&test_case.name),
+            test_case.time
+        );
+        if test_case.failures.is_empty() {
+            xml.push_str("/>\n");
+        } else {
+            let _ = writeln!(
+                xml,
+                ">\n      <failure message=\"{}\" type=\"failure\">{}</failure>\n    </testcase>",
+                escape(&test_case.failures.join("; ")),
+                escape(&test_case.details.join("\n"))
+            );
+        }
+    }
+    xml.push_str("  </testsuite>\n");
+}
+
+/// Build a JUnit XML document from the final metrics of a load test.
+pub(crate) fn build_junit(metrics: &GooseMetrics) -> String {
+    // Thresholds that apply to a specific request are reported as part of that request.
+    let mut reported_thresholds = vec![false; metrics.thresholds.len()];
+
+    let mut requests = Vec::new();
+    let mut request_keys: Vec<&String> = metrics.requests.keys().collect();
+    request_keys.sort();
+    for key in request_keys {
+        let aggregate = &metrics.requests[key];
+        let mut failures = Vec::new();
+        let mut details = Vec::new();
+        if aggregate.fail_count > 0 {
+            failures.push(format!(
+                "{} of {} requests failed",
+                aggregate.fail_count,
+                aggregate.success_count + aggregate.fail_count
+            ));
+            for error in metrics
+                .errors
+                .values()
+                .filter(|error| error.method == aggregate.method && error.name == aggregate.path)
+            {
+                details.push(format!("{} x {}", error.occurrences, error.error));
+            }
+        }
+        for (index, result) in metrics.thresholds.iter().enumerate() {
+            let applies = match result.threshold.request.as_ref() {
+                Some(request) => request == key || request == &aggregate.path,
+                None => false,
+            };
+            if applies {
+                reported_thresholds[index] = true;
+                if !result.passed {
+                    failures.push(threshold_failure(result));
+                }
+            }
+        }
+        requests.push(TestCase {
+            classname: "requests".to_string(),
+            name: key.to_string(),
+            time: average_seconds(aggregate.raw_data.total_time, aggregate.raw_data.counter),
+            failures,
+            details,
+        });
+    }
+
+    let mut transactions = Vec::new();
+    for aggregate in metrics.transactions.iter().flatten() {
+        let mut failures = Vec::new();
+        if aggregate.fail_count > 0 {
+            failures.push(format!(
+                "{} of {} transactions failed",
+                aggregate.fail_count,
+                aggregate.success_count + aggregate.fail_count
+            ));
+        }
+        let name = if aggregate.transaction_name.is_empty() {
+            format!("{}", aggregate.transaction_index + 1)
+        } else {
+            format!(
+                "{}: {}",
+                aggregate.transaction_index + 1,
+                aggregate.transaction_name
+            )
+        };
+        transactions.push(TestCase {
+            classname: aggregate.scenario_name.to_string(),
+            name,
+            time: average_seconds(aggregate.total_time, aggregate.counter),
+            failures,
+            details: Vec::new(),
+        });
+    }
+
+    // Thresholds that apply to all requests, or to requests that were never made.
+    let thresholds: Vec<TestCase> = metrics
+        .thresholds
+        .iter()
+        .zip(reported_thresholds)
+        .filter(|(_, reported)| !reported)
+        .map(|(result, _)| TestCase {
+            classname: "thresholds".to_string(),
+            name: result.threshold.to_string(),
+            time: 0.0,
+            failures: if result.passed {
+                Vec::new()
+            } else {
+                vec![threshold_failure(result)]
+            },
+            details: Vec::new(),
+        })
+        .collect();
+
+    let suites = [
+        ("requests", requests),
+        ("transactions", transactions),
+        ("thresholds", thresholds),
+    ];
+    let tests: usize = suites.iter().map(|(_, cases)| cases.len()).sum();
+    let failures: usize = suites
+        .iter()
+        .flat_map(|(_, cases)| cases)
+        .filter(|test_case| !test_case.failures.is_empty())
+        .count();
+
+    let mut xml = String::from("<?xml version=\"1.0\" encoding=\"UTF-8\"?>\n");
+    let _ = writeln!(
+        xml,
+        r#"<testsuites name="Goose" tests="{}" failures="{}" errors="0" time="{}">"#,
+        tests, failures, metrics.duration
+    );
+    for (name, test_cases) in &suites {
+        // Don't include empty suites, ie when no thresholds are configured.
+        if !test_cases.is_empty() {
+            write_suite(&mut xml, name, test_cases);
+        }
+    }
+    xml.push_str("</testsuites>\n");
+    xml
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::goose::GooseMethod;
+    use crate::metrics::{
+        GooseErrorMetricAggregate, GooseRequestMetricAggregate, GooseThresholds,
+        TransactionMetricAggregate,
+    };
+
+    #[test]
+    fn format_junit() {
+        let mut index = GooseRequestMetricAggregate::new("/", GooseMethod::Get, 0, 3);
+        for time in [10, 20] {
+            index.record_time(time, false);
+            index.success_count += 1;
+        }
+        let mut about = GooseRequestMetricAggregate::new("/about", GooseMethod::Get, 0, 3);
+        about.record_time(1_000, false);
+        about.fail_count += 1;
+        let mut error = GooseErrorMetricAggregate::new(
+            GooseMethod::Get,
+            "/about".to_string(),
+            "500 Internal Server Error: <html>".to_string(),
+        );
+        error.occurrences = 1;
+        let mut transaction = TransactionMetricAggregate::new(0, "Load & Test", 0, "", 3);
+        transaction.set_time(20, true);
+
+        let mut metrics = GooseMetrics {
+            duration: 2,
+            ..Default::default()
+        };
+        metrics.requests.insert("GET /".to_string(), index);
+        metrics.requests.insert("GET /about".to_string(), about);
+        metrics.errors.insert("GET /about 500".to_string(), error);
+        metrics.transactions.push(vec![transaction]);
+        let thresholds = "GET /:p95<10;p95<5000;GET /missing:max<100"
+            .parse::<GooseThresholds>()
+            .unwrap();
+        metrics.evaluate_thresholds(Some(&thresholds));
+
+        let xml = build_junit(&metrics);
+        assert!(xml.starts_with("<?xml"));
+        assert!(
+            xml.contains(r#"<testsuites name="Goose" tests="5" failures="3" errors="0" time="2">"#)
+        );
+        assert!(xml.contains(r#"<testsuite name="requests" tests="2" failures="2""#));
+        // Request thresholds are reported as part of the request.
+        assert!(xml.contains(r#"<testcase classname="requests" name="GET /" time="0.015">"#));
+        assert!(xml.contains("threshold GET /:p95&lt;10 not met, measured 20.00"));
+        // Errors are escaped.
+        assert!(xml.contains(r#"message="1 of 1 requests failed""#));
+        assert!(xml.contains("1 x 500 Internal Server Error: &lt;html&gt;</failure>"));
+        assert!(xml.contains(r#"<testcase classname="Load &amp; Test" name="1" time="0.020"/>"#));
+        // Global thresholds and thresholds for requests that weren't made are test cases.
+        assert!(xml.contains(r#"<testsuite name="thresholds" tests="2" failures="1""#));
+        assert!(
+            xml.contains(r#"<testcase classname="thresholds" name="p95&lt;5000" time="0.000"/>"#)
+        );
+        assert!(xml.contains("threshold GET /missing:max&lt;100 not met, no matching requests"));
+        assert!(xml.ends_with("</testsuites>\n"));
+    }
+}
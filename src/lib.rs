@@ -43,10 +43,16 @@ pub mod config;
 pub mod controller;
 pub mod goose;
 mod graph;
+mod junit;
+mod locust;
 pub mod logger;
 pub mod metrics;
+mod otlp;
 pub mod prelude;
+mod prometheus;
+pub mod replay;
 mod report;
+mod sink;
 mod test_plan;
 mod throttle;
 mod user;
@@ -67,9 +73,15 @@ use crate::config::{GooseConfiguration, GooseDefaults};
 use crate::controller::{ControllerProtocol, ControllerRequest};
 use crate::goose::{GooseUser, GooseUserCommand, Scenario, Transaction};
 use crate::graph::GraphData;
-use crate::logger::{GooseLoggerJoinHandle, GooseLoggerTx};
-use crate::metrics::{GooseMetric, GooseMetrics};
-use crate::test_plan::{TestPlan, TestPlanHistory, TestPlanStepAction};
+use crate::locust::LocustHistory;
+use crate::logger::{GooseLog, GooseLoggerJoinHandle, GooseLoggerTx};
+use crate::metrics::{
+    GooseAbortSnapshot, GooseArrivalRateMetrics, GooseBaseline, GooseBreakpointMetrics,
+    GooseBreakpointSnapshot, GooseMetric, GooseMetrics,
+};
+use crate::otlp::OtlpExporter;
+use crate::sink::MetricsSink;
+use crate::test_plan::{TestPlan, TestPlanHistory, TestPlanRamp, TestPlanStepAction};
 
 /// Constant defining Goose's default telnet Controller port.
 const DEFAULT_TELNET_PORT: &str = "5116";
@@ -153,6 +165,20 @@ pub enum GooseError {
         /// An optional explanation of the error.
         detail: String,
     },
+    /// The load test completed, but one or more thresholds were not met.
+    ThresholdsFailed {
+        /// The metrics of the completed load test, including the result of each threshold.
+        metrics: Box<GooseMetrics>,
+        /// An optional explanation of the error.
+        detail: String,
+    },
+    /// The load test was aborted by an abort-on-fail rule.
+    Aborted {
+        /// The abort-on-fail rule that was breached, also recorded in the history.
+        reason: String,
+        /// The metrics of the aborted load test.
+        metrics: Box<GooseMetrics>,
+    },
 }
 /// Implement a helper to provide a text description of all possible types of errors.
 impl GooseError {
@@ -168,6 +194,8 @@ impl GooseError {
             GooseError::InvalidWeight { .. } => "invalid weight specified",
             GooseError::InvalidControllerCommand { .. } => "invalid controller command",
             GooseError::NoScenarios { .. } => "no scenarios defined",
+            GooseError::ThresholdsFailed { .. } => "thresholds not met",
+            GooseError::Aborted { .. } => "load test aborted",
         }
     }
 }
@@ -187,6 +215,12 @@ impl fmt::Display for GooseError {
             GooseError::InvalidHost {
                 ref parse_error, ..
             } => write!(f, "GooseError: {} ({})", self.describe(), parse_error),
+            GooseError::ThresholdsFailed { ref detail, .. } => {
+                write!(f, "GooseError: {} ({})", self.describe(), detail)
+            }
+            GooseError::Aborted { ref reason, .. } => {
+                write!(f, "GooseError: {} ({})", self.describe(), reason)
+            }
             _ => write!(f, "GooseError: {}", self.describe()),
         }
     }
@@ -305,6 +339,9 @@ struct GooseAttackRunState {
     /// Optional unbounded sender from all [`GooseUser`](./goose/struct.GooseUser.html)s
     /// to logger thread, if enabled.
     all_threads_logger_tx: GooseLoggerTx,
+    /// How many [`GooseUser`](./goose/struct.GooseUser.html)s were running when the logger
+    /// thread was last told.
+    logged_users: usize,
     /// Optional receiver for all [`GooseUser`](./goose/struct.GooseUser.html)s from
     /// throttle thread, if enabled.
     throttle_threads_tx: Option<flume::Sender<bool>>,
@@ -336,6 +373,99 @@ struct GooseAttackRunState {
     shutdown_after_stop: bool,
     /// Whether or not the load test is currently canceling.
     canceling: bool,
+    /// Optional sender used to start scenario iterations (or requests) on idle
+    /// [`GooseUser`](./goose/struct.GooseUser.html)s, if running an arrival rate test plan.
+    arrival_tx: Option<flume::Sender<bool>>,
+    /// Optional receiver shared by all [`GooseUser`](./goose/struct.GooseUser.html)s to
+    /// wait for their next scenario iteration (or request), if running an arrival rate test plan.
+    arrival_rx: Option<flume::Receiver<bool>>,
+    /// A timestamp tracking when scenario iterations were last scheduled.
+    arrival_timer: std::time::Instant,
+    /// How many scenario iterations are due to be scheduled, including partial iterations
+    /// carried over until the next time iterations are scheduled.
+    arrivals_due: f32,
+    /// The highest arrival rate the running users were launched to sustain.
+    arrival_peak_rate: f32,
+    /// How many users were running at the highest arrival rate, used to shut down idle users
+    /// when the rate drops.
+    arrival_peak_users: usize,
+    /// Run state for each active [`Scenario`](./goose/struct.Scenario.html) with its own
+    /// test plan.
+    scenario_run_states: Vec<ScenarioRunState>,
+    /// A timestamp tracking when scenarios with their own test plan started.
+    scenario_timer: std::time::Instant,
+    /// Request metrics collected when the current breakpoint search step started, if running
+    /// a breakpoint search.
+    breakpoint_snapshot: Option<GooseBreakpointSnapshot>,
+    /// Request metrics used to check abort-on-fail rules, if configured.
+    abort_snapshot: Option<GooseAbortSnapshot>,
+    /// Optional sink metrics are periodically pushed to, if enabled.
+    metrics_sink: Option<MetricsSink>,
+    /// Optional OpenTelemetry exporter, if enabled.
+    otlp_exporter: Option<OtlpExporter>,
+    /// Optional Locust-compatible CSV history file, if enabled.
+    locust_history: Option<LocustHistory>,
+}
+impl GooseAttackRunState {
+    // Determine how many [`GooseUser`](./goose/struct.GooseUser.html)s are running scenarios
+    // with their own test plan.
+    fn scenario_users(&self) -> usize {
+        self.scenario_run_states
+            .iter()
+            .map(|scenario_run_state| scenario_run_state.user_channels.len())
+            .sum()
+    }
+
+    // Determine how many [`GooseUser`](./goose/struct.GooseUser.html)s are running in total.
+    fn running_users(&self) -> usize {
+        self.active_users + self.scenario_users()
+    }
+
+    // Tell the most recently launched [`GooseUser`](./goose/struct.GooseUser.html) to exit.
+    fn stop_user(&mut self) {
+        if let Some(send_to_user) = self.user_channels.pop() {
+            match send_to_user.send(GooseUserCommand::Exit) {
+                Ok(_) => {
+                    debug!("telling user {} to exit", self.completed_users);
+                }
+                Err(e) => {
+                    // Error is expected if this user already shut down.
+                    if !self.users_shutdown.contains(&self.completed_users) {
+                        info!(
+                            "failed to tell user {} to exit: {}",
+                            self.completed_users, e
+                        );
+                    }
+                }
+            }
+            self.completed_users += 1;
+            self.active_users -= 1;
+        }
+    }
+
+    // If the logger thread is enabled, tell it how many [`GooseUser`](./goose/struct.GooseUser.html)s
+    // are running when it changes, to include in JTL-formatted request logs.
+    fn log_running_users(&mut self) {
+        let running_users = self.running_users();
+        if running_users != self.logged_users {
+            if let Some(logger) = self.all_threads_logger_tx.as_ref() {
+                let _ = logger.send(Some(GooseLog::RunningUsers(running_users)));
+            }
+            self.logged_users = running_users;
+        }
+    }
+}
+
+#[derive(Debug)]
+/// Internal run state for a [`Scenario`](./goose/struct.Scenario.html) with its own test plan.
+struct ScenarioRunState {
+    /// An index into the [`GooseAttack`](./struct.GooseAttack.html)`.scenarios` vector.
+    scenarios_index: usize,
+    /// The test plan controlling how many users run this scenario.
+    test_plan: TestPlan,
+    /// All unbounded senders to allow communication with the
+    /// [`GooseUser`](./goose/struct.GooseUser.html) threads running this scenario.
+    user_channels: Vec<flume::Sender<GooseUserCommand>>,
 }
 
 /// Global internal state for the load test.
@@ -371,6 +501,8 @@ pub struct GooseAttack {
     metrics: GooseMetrics,
     /// All data for report graphs.
     graph_data: GraphData,
+    /// Optional metrics of a previous load test to compare to.
+    baseline: Option<GooseBaseline>,
 }
 
 /// Goose's internal global state.
@@ -401,6 +533,7 @@ impl GooseAttack {
             step_started: None,
             metrics: GooseMetrics::default(),
             graph_data: GraphData::new(),
+            baseline: None,
         })
     }
 
@@ -437,6 +570,7 @@ impl GooseAttack {
             step_started: None,
             metrics: GooseMetrics::default(),
             graph_data: GraphData::new(),
+            baseline: None,
         })
     }
 
@@ -637,6 +771,36 @@ impl GooseAttack {
         }
     }
 
+    /// Internal helper to determine if users are assigned to the scenario by its weight,
+    /// which is the case for all active scenarios that don't have their own test plan.
+    fn scenario_is_weighted(&self, scenario: &Scenario) -> bool {
+        scenario.test_plan.is_none() && self.scenario_is_active(scenario)
+    }
+
+    /// Internal helper to determine if any users are assigned to scenarios by weight.
+    fn has_weighted_scenarios(&self) -> bool {
+        self.scenarios
+            .iter()
+            .any(|scenario| self.scenario_is_weighted(scenario))
+    }
+
+    /// Internal helper to determine the total number of users required by the load test,
+    /// including users running scenarios with their own test plan.
+    fn total_users(&self) -> usize {
+        let weighted_users = if self.has_weighted_scenarios() {
+            self.test_plan.total_users()
+        } else {
+            0
+        };
+        self.scenarios
+            .iter()
+            .filter(|scenario| self.scenario_is_active(scenario))
+            .filter_map(|scenario| scenario.test_plan.as_ref())
+            .fold(weighted_users, |total_users, test_plan| {
+                total_users + test_plan.total_users()
+            })
+    }
+
     /// Use configured GooseScheduler to build out a properly weighted list of
     /// [`Scenario`](./goose/struct.Scenario.html)s to be assigned to
     /// [`GooseUser`](./goose/struct.GooseUser.html)s
@@ -646,7 +810,7 @@ impl GooseAttack {
         let mut u: usize = 0;
         let mut v: usize;
         for scenario in &self.scenarios {
-            if self.scenario_is_active(scenario) {
+            if self.scenario_is_weighted(scenario) {
                 if u == 0 {
                     u = scenario.weight;
                 } else {
@@ -664,7 +828,7 @@ impl GooseAttack {
         let mut available_scenarios = Vec::with_capacity(self.scenarios.len());
         let mut total_scenarios = 0;
         for (index, scenario) in self.scenarios.iter().enumerate() {
-            if self.scenario_is_active(scenario) {
+            if self.scenario_is_weighted(scenario) {
                 // divide by greatest common divisor so vector is as short as possible
                 let weight = scenario.weight / u;
                 trace!(
@@ -745,6 +909,12 @@ impl GooseAttack {
 
         let weighted_scenarios = self.allocate_scenarios();
 
+        // There are no users to allocate if all scenarios have their own test plan.
+        if weighted_scenarios.is_empty() || total_users == 0 {
+            debug!("no weighted_users to create");
+            return Ok(Vec::new());
+        }
+
         // Allocate a state for each user that will be launched.
         info!(
             "initializing {} user states...",
@@ -800,17 +970,6 @@ impl GooseAttack {
         self.attack_phase = phase;
     }
 
-    // If enabled, returns the path of the report_file, otherwise returns None.
-    fn get_report_file_path(&mut self) -> Option<String> {
-        // Return if enabled.
-        if !self.configuration.report_file.is_empty() {
-            Some(self.configuration.report_file.to_string())
-        // Otherwise there is no report file.
-        } else {
-            None
-        }
-    }
-
     // Display all scenarios (sorted by machine name).
     fn print_scenarios(&self) {
         let mut scenarios = BTreeMap::new();
@@ -948,8 +1107,47 @@ impl GooseAttack {
             );
             print!("{}", self.metrics);
 
-            // Write an html report, if enabled.
-            self.write_html_report().await?;
+            // Write reports, if enabled.
+            self.write_reports().await?;
+        }
+
+        // Write a baseline file, if enabled.
+        self.write_baseline_file().await?;
+
+        // Write a JUnit XML file, if enabled.
+        self.write_junit_file().await?;
+
+        // Write Locust-compatible CSV files, if enabled.
+        self.write_locust_csv().await?;
+
+        // Fail the load test if it was aborted by an abort-on-fail rule.
+        let aborted = self
+            .metrics
+            .history
+            .iter()
+            .find_map(|step| match step.action {
+                TestPlanStepAction::Aborting => step.reason.clone(),
+                _ => None,
+            });
+        if let Some(reason) = aborted {
+            return Err(GooseError::Aborted {
+                reason,
+                metrics: Box::new(self.metrics),
+            });
+        }
+
+        // Fail the load test if any thresholds were not met.
+        let failed_thresholds = self
+            .metrics
+            .failed_thresholds()
+            .iter()
+            .map(|result| result.threshold.to_string())
+            .collect::<Vec<String>>();
+        if !failed_thresholds.is_empty() {
+            return Err(GooseError::ThresholdsFailed {
+                detail: format!("failed thresholds: {}", failed_thresholds.join(", ")),
+                metrics: Box::new(self.metrics),
+            });
         }
 
         Ok(self.metrics)
@@ -1079,13 +1277,24 @@ impl GooseAttack {
         (Some(all_threads_throttle), Some(parent_to_throttle_tx))
     }
 
-    // Helper to optionally spawn a telnet and/or WebSocket Controller thread. The Controller
-    // threads share a control channel, allowing it to send requests to the parent process. When
-    // a response is required, the Controller will also send a one-shot channel allowing a direct
-    // reply.
+    // Helper to optionally spawn a telnet and/or WebSocket Controller thread, and a Prometheus
+    // endpoint thread. The threads share a control channel, allowing them to send requests to
+    // the parent process. When a response is required, the thread will also send a one-shot
+    // channel allowing a direct reply.
     async fn setup_controllers(&mut self) -> Option<flume::Receiver<ControllerRequest>> {
-        // If the telnet controller is disabled, return immediately.
-        if self.configuration.no_telnet && self.configuration.no_websocket {
+        // Configure prometheus_port, using default if run-time option is not set.
+        if self.configuration.prometheus_port == 0 {
+            if let Some(port) = self.defaults.prometheus_port {
+                self.configuration.prometheus_port = port;
+            }
+        }
+
+        // If the controllers are disabled and the Prometheus endpoint isn't enabled, return
+        // immediately.
+        if self.configuration.no_telnet
+            && self.configuration.no_websocket
+            && self.configuration.prometheus_port == 0
+        {
             return None;
         }
 
@@ -1152,22 +1361,33 @@ impl GooseAttack {
             // There is no need to rejoin this thread when the load test ends.
             let _ = Some(tokio::spawn(controller::controller_main(
                 self.configuration.clone(),
-                all_threads_controller_request_tx,
+                all_threads_controller_request_tx.clone(),
                 ControllerProtocol::WebSocket,
             )));
         }
 
-        // Return the parent end of the Controller channel.
-        Some(controller_request_rx)
-    }
+        // Configured Prometheus endpoint if enabled.
+        if self.configuration.prometheus_port != 0 {
+            // Configure prometheus_host, using default if run-time option is not set.
+            if self.configuration.prometheus_host.is_empty() {
+                self.configuration.prometheus_host =
+                    if let Some(host) = self.defaults.prometheus_host.clone() {
+                        host
+                    } else {
+                        "0.0.0.0".to_string()
+                    }
+            }
 
-    // Prepare an asynchronous file writer for `report_file` (if enabled).
-    async fn prepare_report_file(&mut self) -> Result<Option<File>, GooseError> {
-        if let Some(report_file_path) = self.get_report_file_path() {
-            Ok(Some(File::create(&report_file_path).await?))
-        } else {
-            Ok(None)
+            // Spawn the Prometheus thread to serve live metrics. There is no need to rejoin
+            // this thread when the load test ends.
+            let _ = Some(tokio::spawn(prometheus::prometheus_main(
+                self.configuration.clone(),
+                all_threads_controller_request_tx,
+            )));
         }
+
+        // Return the parent end of the Controller channel.
+        Some(controller_request_rx)
     }
 
     // Invoke `test_start` transactions if existing.
@@ -1252,6 +1472,7 @@ impl GooseAttack {
             shutdown_rx,
             logger_handle: None,
             all_threads_logger_tx: None,
+            logged_users: 0,
             throttle_threads_tx: None,
             parent_to_throttle_tx: None,
             controller_channel_rx,
@@ -1265,6 +1486,19 @@ impl GooseAttack {
             all_users_spawned: false,
             shutdown_after_stop: !self.configuration.no_autostart,
             canceling: false,
+            arrival_tx: None,
+            arrival_rx: None,
+            arrival_timer: std_now,
+            arrivals_due: 0.0,
+            arrival_peak_rate: 0.0,
+            arrival_peak_users: 0,
+            scenario_run_states: Vec::new(),
+            scenario_timer: std_now,
+            breakpoint_snapshot: None,
+            abort_snapshot: None,
+            metrics_sink: None,
+            otlp_exporter: None,
+            locust_history: None,
         };
 
         // Catch ctrl-c to allow clean shutdown to display metrics.
@@ -1304,21 +1538,166 @@ impl GooseAttack {
         }
     }
 
+    // Launch the next weighted [`GooseUser`](./goose/struct.GooseUser.html) thread in the
+    // active [`GooseAttack`](./struct.GooseAttack.html).
+    fn launch_user(&mut self, goose_attack_run_state: &mut GooseAttackRunState) {
+        let mut thread_user = self
+            .weighted_users
+            .pop()
+            .expect("insufficent weighted_users");
+
+        // Copy the GooseUser-arrival receiver channel, if running an arrival rate test plan. The
+        // user was launched to immediately start an iteration (or request).
+        thread_user.arrival_channel = goose_attack_run_state.arrival_rx.clone();
+        thread_user.arrival_granted = thread_user.arrival_channel.is_some();
+
+        let parent_sender = self.spawn_user(thread_user, goose_attack_run_state);
+        goose_attack_run_state.user_channels.push(parent_sender);
+        goose_attack_run_state.active_users += 1;
+        self.update_maximum_users(goose_attack_run_state);
+    }
+
+    // Spawn a [`GooseUser`](./goose/struct.GooseUser.html) thread, returning the channel used
+    // to control it.
+    fn spawn_user(
+        &mut self,
+        mut thread_user: GooseUser,
+        goose_attack_run_state: &mut GooseAttackRunState,
+    ) -> flume::Sender<GooseUserCommand> {
+        // Remember which task group this user is using.
+        thread_user.weighted_users_index = self.metrics.total_users;
+
+        // Create a per-thread channel allowing parent thread to control child threads.
+        let (parent_sender, thread_receiver): (
+            flume::Sender<GooseUserCommand>,
+            flume::Receiver<GooseUserCommand>,
+        ) = flume::unbounded();
+
+        // Users waiting for permission to make a request also watch for GooseUserCommand::Exit,
+        // only used when running an arrival rate test plan with `--arrival-requests`.
+        if thread_user.arrival_channel.is_some() && self.configuration.arrival_requests {
+            thread_user.command_channel = Some(thread_receiver.clone());
+        }
+
+        // Clone the logger_tx if enabled, otherwise is None.
+        thread_user.logger = goose_attack_run_state.all_threads_logger_tx.clone();
+
+        // Request, transaction and scenario times are relative to when the load test started.
+        if let Some(started) = self.started {
+            thread_user.started = started;
+        }
+
+        // Copy the GooseUser-throttle receiver channel, used by all threads.
+        thread_user.throttle = if self.configuration.throttle_requests > 0 {
+            Some(goose_attack_run_state.throttle_threads_tx.clone().unwrap())
+        } else {
+            None
+        };
+
+        // Copy the GooseUser-metrics sender channel, used by all threads.
+        thread_user.metrics_channel = Some(goose_attack_run_state.all_threads_metrics_tx.clone());
+
+        // Copy the GooseUser-shutdown sender channel, used by all threads.
+        thread_user.shutdown_channel = Some(goose_attack_run_state.all_threads_shutdown_tx.clone());
+
+        // Copy the appropriate task_set into the thread.
+        let thread_scenario = self.scenarios[thread_user.scenarios_index].clone();
+
+        // Start at 1 as this is human visible.
+        let thread_number = self.metrics.total_users + 1;
+
+        // Launch a new user.
+        let user = tokio::spawn(user::user_main(
+            thread_number,
+            thread_scenario,
+            thread_user,
+            thread_receiver,
+        ));
+
+        goose_attack_run_state.users.push(user);
+        self.metrics.total_users += 1;
+
+        parent_sender
+    }
+
+    // Record the maximum number of [`GooseUser`](./goose/struct.GooseUser.html) threads running
+    // at the same time.
+    fn update_maximum_users(&mut self, goose_attack_run_state: &GooseAttackRunState) {
+        let running_users = goose_attack_run_state.running_users();
+        if running_users > self.metrics.maximum_users {
+            self.metrics.maximum_users = running_users;
+        }
+    }
+
+    // Launch or stop [`GooseUser`](./goose/struct.GooseUser.html) threads running scenarios with
+    // their own test plan, following each scenario's test plan from when the load test started.
+    fn adjust_scenario_users(
+        &mut self,
+        goose_attack_run_state: &mut GooseAttackRunState,
+    ) -> Result<(), GooseError> {
+        let elapsed = goose_attack_run_state.scenario_timer.elapsed().as_millis() as usize;
+        for index in 0..goose_attack_run_state.scenario_run_states.len() {
+            let scenario_run_state = &goose_attack_run_state.scenario_run_states[index];
+            let scenarios_index = scenario_run_state.scenarios_index;
+            let users = scenario_run_state.test_plan.users_at(elapsed);
+            let running_users = scenario_run_state.user_channels.len();
+
+            // Launch users until the scenario's test plan is satisfied.
+            for _ in running_users..users {
+                let base_url = goose::get_base_url(
+                    self.get_configuration_host(),
+                    self.scenarios[scenarios_index].host.clone(),
+                    self.defaults.host.clone(),
+                )?;
+                let thread_user = GooseUser::new(
+                    scenarios_index,
+                    base_url,
+                    &self.configuration,
+                    self.metrics.hash,
+                )?;
+                let parent_sender = self.spawn_user(thread_user, goose_attack_run_state);
+                goose_attack_run_state.scenario_run_states[index]
+                    .user_channels
+                    .push(parent_sender);
+                self.update_maximum_users(goose_attack_run_state);
+            }
+
+            // Stop users until the scenario's test plan is satisfied.
+            for _ in users..running_users {
+                if let Some(send_to_user) = goose_attack_run_state.scenario_run_states[index]
+                    .user_channels
+                    .pop()
+                {
+                    // Error is expected if this user already shut down.
+                    let _ = send_to_user.send(GooseUserCommand::Exit);
+                    goose_attack_run_state.completed_users += 1;
+                }
+            }
+        }
+
+        Ok(())
+    }
+
     // Increase the number of active [`GooseUser`](./goose/struct.GooseUser.html) threads in the
     // active [`GooseAttack`](./struct.GooseAttack.html).
     async fn increase_attack(
         &mut self,
         goose_attack_run_state: &mut GooseAttackRunState,
     ) -> Result<(), GooseError> {
-        // Determine if enough users have been launched.
-        let all_users_launched =
-            goose_attack_run_state.active_users >= self.test_plan.steps[self.test_plan.current].0;
+        // Determine if enough users have been launched, or if there are no more users to launch
+        // because all scenarios have their own test plan.
+        let all_users_launched = goose_attack_run_state.active_users
+            >= self.test_plan.steps[self.test_plan.current].0
+            || self.weighted_users.is_empty();
 
         if all_users_launched {
             // All users were increased, delay until test_plan step time has elapsed.
             self.end_of_step_delay().await;
 
             if self.step_elapsed() as usize >= self.test_plan.steps[self.test_plan.current].1 {
+                // If running a breakpoint search, determine the next step.
+                self.probe_breakpoint(goose_attack_run_state).await?;
+
                 // Automatically reset metrics if appropriate.
                 self.reset_metrics(goose_attack_run_state).await?;
 
@@ -1341,12 +1720,8 @@ impl GooseAttack {
             // in the current step.
             assert!(self.test_plan.steps[self.test_plan.current].0 > previous_users);
 
-            // Divide the number of new users to launch by the time configured to launch them.
-            let increase_rate: f32 = (self.test_plan.steps[self.test_plan.current].0 - previous_users)
-                as f32
-                / self.test_plan.steps[self.test_plan.current].1 as f32
-                // Convert from milliseconds to seconds.
-                * 1_000.0;
+            // Determine how many users this step launches in total.
+            let increase_users = self.test_plan.steps[self.test_plan.current].0 - previous_users;
 
             // Determine if it's time to spawn a GooseUser.
             if goose_attack_run_state.adjust_user_in_ms == 0
@@ -1355,65 +1730,21 @@ impl GooseAttack {
                     goose_attack_run_state.adjust_user_in_ms,
                 )
             {
-                let mut thread_user = self
-                    .weighted_users
-                    .pop()
-                    .expect("insufficent weighted_users");
+                // Launch a new user.
+                self.launch_user(goose_attack_run_state);
+
                 // Reset the spawn timer.
                 goose_attack_run_state.adjust_user_timer = std::time::Instant::now();
 
-                // To determine how long before we spawn the next GooseUser, start with 1,000.0
-                // milliseconds and divide by the increase_rate.
-                goose_attack_run_state.adjust_user_in_ms = (1_000.0 / increase_rate) as usize;
-
-                // Remember which task group this user is using.
-                thread_user.weighted_users_index = self.metrics.total_users;
-
-                // Create a per-thread channel allowing parent thread to control child threads.
-                let (parent_sender, thread_receiver): (
-                    flume::Sender<GooseUserCommand>,
-                    flume::Receiver<GooseUserCommand>,
-                ) = flume::unbounded();
-                goose_attack_run_state.user_channels.push(parent_sender);
-
-                // Clone the logger_tx if enabled, otherwise is None.
-                thread_user.logger = goose_attack_run_state.all_threads_logger_tx.clone();
-
-                // Copy the GooseUser-throttle receiver channel, used by all threads.
-                thread_user.throttle = if self.configuration.throttle_requests > 0 {
-                    Some(goose_attack_run_state.throttle_threads_tx.clone().unwrap())
-                } else {
-                    None
-                };
-
-                // Copy the GooseUser-metrics sender channel, used by all threads.
-                thread_user.metrics_channel =
-                    Some(goose_attack_run_state.all_threads_metrics_tx.clone());
-
-                // Copy the GooseUser-shutdown sender channel, used by all threads.
-                thread_user.shutdown_channel =
-                    Some(goose_attack_run_state.all_threads_shutdown_tx.clone());
-
-                // Copy the appropriate task_set into the thread.
-                let thread_scenario = self.scenarios[thread_user.scenarios_index].clone();
-
-                // Start at 1 as this is human visible.
-                let thread_number = self.metrics.total_users + 1;
-
-                // Launch a new user.
-                let user = tokio::spawn(user::user_main(
-                    thread_number,
-                    thread_scenario,
-                    thread_user,
-                    thread_receiver,
-                ));
-
-                goose_attack_run_state.users.push(user);
-                goose_attack_run_state.active_users += 1;
-                self.metrics.total_users += 1;
-                if goose_attack_run_state.active_users > self.metrics.maximum_users {
-                    self.metrics.maximum_users = goose_attack_run_state.active_users;
-                }
+                // Determine how long before we spawn the next GooseUser, following the ramp
+                // shape configured for this step.
+                let launched_users = goose_attack_run_state
+                    .active_users
+                    .saturating_sub(previous_users);
+                goose_attack_run_state.adjust_user_in_ms = self
+                    .test_plan
+                    .next_adjustment_in_ms(launched_users, increase_users)
+                    .saturating_sub(self.step_elapsed() as usize);
 
                 if let Some(running_metrics) = self.configuration.running_metrics {
                     if util::ms_timer_expired(
@@ -1455,6 +1786,9 @@ impl GooseAttack {
                 self.test_plan.steps[self.test_plan.current].1,
             )
         {
+            // If running a breakpoint search, determine the next step.
+            self.probe_breakpoint(goose_attack_run_state).await?;
+
             self.advance_test_plan(goose_attack_run_state);
         } else {
             // Subtract the time spent doing other things, running the main parent loop twice
@@ -1469,6 +1803,71 @@ impl GooseAttack {
         Ok(())
     }
 
+    // Launch and stop [`GooseUser`](./goose/struct.GooseUser.html) threads to follow a test plan
+    // step with an oscillating ramp, such as a sine wave.
+    async fn oscillate_attack(
+        &mut self,
+        goose_attack_run_state: &mut GooseAttackRunState,
+    ) -> Result<(), GooseError> {
+        let (users, duration) = self.test_plan.steps[self.test_plan.current];
+        let elapsed = self.step_elapsed() as usize;
+
+        // When the step's time has elapsed, adjust to the number of users configured for the
+        // step and move to the next step as usual.
+        if elapsed >= duration {
+            self.adjust_users(goose_attack_run_state, users)?;
+            return match self.attack_phase {
+                AttackPhase::Increase => self.increase_attack(goose_attack_run_state).await,
+                AttackPhase::Decrease => self.decrease_attack(goose_attack_run_state).await,
+                _ => self.maintain_attack(goose_attack_run_state).await,
+            };
+        }
+
+        let users = self.test_plan.current_users_at(elapsed);
+        self.adjust_users(goose_attack_run_state, users)?;
+
+        // Wake up often enough to follow the wave, and at least twice a second to handle messages.
+        let sleep_ms = match self.test_plan.ramp(self.test_plan.current) {
+            TestPlanRamp::Sine { amplitude, period } => {
+                (period / (4 * amplitude.max(1))).clamp(10, 500)
+            }
+            _ => 500,
+        };
+        goose_attack_run_state.drift_timer = util::sleep_minus_drift(
+            Duration::from_millis(sleep_ms as u64),
+            goose_attack_run_state.drift_timer,
+        )
+        .await;
+
+        Ok(())
+    }
+
+    // Launch or stop [`GooseUser`](./goose/struct.GooseUser.html) threads until the requested
+    // number of users are running.
+    fn adjust_users(
+        &mut self,
+        goose_attack_run_state: &mut GooseAttackRunState,
+        users: usize,
+    ) -> Result<(), GooseError> {
+        while goose_attack_run_state.active_users < users {
+            // Stopped users can't be launched again, so allocate more users when needed.
+            if self.weighted_users.is_empty() {
+                self.weighted_users =
+                    self.weight_scenario_users(users - goose_attack_run_state.active_users)?;
+                if self.weighted_users.is_empty() {
+                    break;
+                }
+            }
+            self.launch_user(goose_attack_run_state);
+        }
+        while goose_attack_run_state.active_users > users
+            && !goose_attack_run_state.user_channels.is_empty()
+        {
+            goose_attack_run_state.stop_user();
+        }
+        Ok(())
+    }
+
     // Decrease the number of active [`GooseUser`](./goose/struct.GooseUser.html) threads in the
     // active [`GooseAttack`](./struct.GooseAttack.html).
     async fn decrease_attack(
@@ -1486,6 +1885,14 @@ impl GooseAttack {
             // Subtract 1 from len() as it starts at 1 while current starts at 0.
             && self.test_plan.current >= self.test_plan.steps.len() - 1
         {
+            // Tell all users running scenarios with their own test plan to exit.
+            for scenario_run_state in &mut goose_attack_run_state.scenario_run_states {
+                for send_to_user in std::mem::take(&mut scenario_run_state.user_channels) {
+                    let _ = send_to_user.send(GooseUserCommand::Exit);
+                    goose_attack_run_state.completed_users += 1;
+                }
+            }
+
             // If throttle is enabled, tell throttle thread the load test is over.
             if let Some(throttle_tx) = goose_attack_run_state.parent_to_throttle_tx.clone() {
                 let _ = throttle_tx.send(false);
@@ -1520,21 +1927,51 @@ impl GooseAttack {
                 let _received_message = self.receive_metrics(goose_attack_run_state, true).await?;
             }
 
+            // Abort-on-fail rules are no longer checked once the load test is stopped.
+            goose_attack_run_state.abort_snapshot = None;
             // Stop any running GooseUser threads.
             self.stop_attack().await?;
             // Collect all metrics sent by GooseUser threads.
             self.sync_metrics(goose_attack_run_state, true).await?;
+            // If enabled, push the final metrics to the metrics sink and wait for it to exit.
+            if let Some(metrics_sink) = goose_attack_run_state.metrics_sink.take() {
+                metrics_sink
+                    .shutdown(&self.metrics, goose_attack_run_state.running_users())
+                    .await;
+            }
+            // If enabled, export the final metrics and spans and wait for the exporter to exit.
+            if let Some(otlp_exporter) = goose_attack_run_state.otlp_exporter.take() {
+                otlp_exporter
+                    .shutdown(&self.metrics, goose_attack_run_state.running_users())
+                    .await;
+            }
+            // If enabled, append the final metrics to the Locust-compatible CSV history.
+            if let Some(locust_history) = goose_attack_run_state.locust_history.take() {
+                locust_history
+                    .shutdown(&self.metrics, goose_attack_run_state.running_users())
+                    .await;
+            }
+            // Evaluate any configured thresholds against the final metrics.
+            self.metrics
+                .evaluate_thresholds(self.configuration.thresholds.as_ref());
+            // Compare the final metrics to a previous load test, if enabled.
+            self.metrics.compare_baseline(
+                self.baseline.as_ref(),
+                self.configuration.baseline_tolerance.unwrap_or(10),
+            );
             // Record last users for users per second graph in HTML report.
             if let Some(started) = self.started {
                 self.graph_data.record_users_per_second(
-                    goose_attack_run_state.active_users,
+                    goose_attack_run_state.running_users(),
                     started.elapsed().as_secs() as usize,
                 );
             };
             // The load test is fully stopped at this point.
-            self.metrics
-                .history
-                .push(TestPlanHistory::step(TestPlanStepAction::Finished, 0));
+            self.metrics.history.push(TestPlanHistory::step(
+                TestPlanStepAction::Finished,
+                0,
+                TestPlanRamp::Linear,
+            ));
             // Shutdown Goose or go into an idle waiting state.
             if goose_attack_run_state.shutdown_after_stop {
                 self.set_attack_phase(goose_attack_run_state, AttackPhase::Shutdown);
@@ -1543,8 +1980,14 @@ impl GooseAttack {
                 if !self.configuration.no_metrics {
                     println!("{}", self.metrics);
                 }
-                // Write an html report, if enabled.
-                self.write_html_report().await?;
+                // Write reports, if enabled.
+                self.write_reports().await?;
+                // Write a baseline file, if enabled.
+                self.write_baseline_file().await?;
+                // Write a JUnit XML file, if enabled.
+                self.write_junit_file().await?;
+                // Write Locust-compatible CSV files, if enabled.
+                self.write_locust_csv().await?;
                 // Return to an Idle state.
                 self.set_attack_phase(goose_attack_run_state, AttackPhase::Idle);
             }
@@ -1568,12 +2011,9 @@ impl GooseAttack {
             // Retreive the number of users configured in the previous step.
             let previous_users = self.test_plan.steps[self.test_plan.current - 1].0;
 
-            // Divide the number of users to decrease by the time configured to decrease them.
-            let decrease_rate: f32 = (previous_users - self.test_plan.steps[self.test_plan.current].0)
-                as f32
-                / self.test_plan.steps[self.test_plan.current].1 as f32
-                // Convert from milliseconds to seconds.
-                * 1_000.0;
+            // Determine how many users this step stops in total.
+            let decrease_users =
+                previous_users.saturating_sub(self.test_plan.steps[self.test_plan.current].0);
 
             // Determine if it's time to decrease a GooseUser.
             if goose_attack_run_state.adjust_user_in_ms == 0
@@ -1585,34 +2025,16 @@ impl GooseAttack {
                 // Reset the adjust timer.
                 goose_attack_run_state.adjust_user_timer = std::time::Instant::now();
 
-                // To determine how long before we decrease the next GooseUser, start with 1,000.0
-                // milliseconds and divide by the decrease_rate.
-                goose_attack_run_state.adjust_user_in_ms = (1_000.0 / decrease_rate) as usize;
-
-                if let Some(send_to_user) = goose_attack_run_state.user_channels.pop() {
-                    match send_to_user.send(GooseUserCommand::Exit) {
-                        Ok(_) => {
-                            debug!(
-                                "telling user {} to exit",
-                                goose_attack_run_state.completed_users
-                            );
-                        }
-                        Err(e) => {
-                            // Error is expected if this user already shut down.
-                            if !goose_attack_run_state
-                                .users_shutdown
-                                .contains(&goose_attack_run_state.completed_users)
-                            {
-                                info!(
-                                    "failed to tell user {} to exit: {}",
-                                    goose_attack_run_state.completed_users, e
-                                );
-                            }
-                        }
-                    }
-                    goose_attack_run_state.completed_users += 1;
-                    goose_attack_run_state.active_users -= 1;
-                }
+                goose_attack_run_state.stop_user();
+
+                // Determine how long before we decrease the next GooseUser, following the ramp
+                // shape configured for this step.
+                let stopped_users =
+                    previous_users.saturating_sub(goose_attack_run_state.active_users);
+                goose_attack_run_state.adjust_user_in_ms = self
+                    .test_plan
+                    .next_adjustment_in_ms(stopped_users, decrease_users)
+                    .saturating_sub(self.step_elapsed() as usize);
             } else {
                 // Wake up twice a second to handle messages and allow for a quick shutdown if the
                 // load test is canceled during decrease.
@@ -1631,6 +2053,122 @@ impl GooseAttack {
         Ok(())
     }
 
+    // Start scenario iterations (or requests) at the rate defined by an arrival rate test plan,
+    // launching additional [`GooseUser`](./goose/struct.GooseUser.html) threads when all running
+    // users are busy, and shutting down idle users when the rate drops.
+    async fn arrival_rate_attack(
+        &mut self,
+        goose_attack_run_state: &mut GooseAttackRunState,
+    ) -> Result<(), GooseError> {
+        let step_elapsed = self.step_elapsed() as usize;
+
+        // Determine if it's time to move to the next test plan step.
+        if self.test_plan.current < self.test_plan.steps.len()
+            && step_elapsed >= self.test_plan.steps[self.test_plan.current].1
+        {
+            // If running a breakpoint search, determine the next step.
+            self.probe_breakpoint(goose_attack_run_state).await?;
+
+            // Subtract 1 from len() as it starts at 1 while current starts at 0.
+            if self.test_plan.current == self.test_plan.steps.len() - 1
+                && self.test_plan.steps[self.test_plan.current].0 == 0
+            {
+                // No more iterations will be started, tell all users to exit.
+                for send_to_user in std::mem::take(&mut goose_attack_run_state.user_channels) {
+                    let _ = send_to_user.send(GooseUserCommand::Exit);
+                    goose_attack_run_state.completed_users += 1;
+                }
+                goose_attack_run_state.active_users = 0;
+                goose_attack_run_state.arrival_tx = None;
+                goose_attack_run_state.arrival_rx = None;
+
+                // Shut down through the decrease phase, as with any other test plan.
+                self.set_attack_phase(goose_attack_run_state, AttackPhase::Decrease);
+                return self.decrease_attack(goose_attack_run_state).await;
+            }
+
+            // Moving to the next step, don't carry over partial iterations.
+            goose_attack_run_state.arrivals_due = 0.0;
+
+            // Advance to the next TestPlan step.
+            self.advance_test_plan(goose_attack_run_state);
+            return Ok(());
+        }
+
+        // Determine how many scenario iterations are due since they were last scheduled.
+        let arrivals_per_second = self.test_plan.arrivals_per_second(step_elapsed);
+        goose_attack_run_state.arrivals_due +=
+            arrivals_per_second * goose_attack_run_state.arrival_timer.elapsed().as_secs_f32();
+        goose_attack_run_state.arrival_timer = std::time::Instant::now();
+
+        let mut scheduled = 0;
+        let mut started = 0;
+        while goose_attack_run_state.arrivals_due >= 1.0 {
+            goose_attack_run_state.arrivals_due -= 1.0;
+            scheduled += 1;
+
+            // Start the iteration on an idle GooseUser if there is one.
+            let idle_user = goose_attack_run_state
+                .arrival_tx
+                .as_ref()
+                .is_some_and(|arrival_tx| arrival_tx.try_send(true).is_ok());
+            if idle_user {
+                started += 1;
+            // Otherwise launch a new GooseUser, which immediately starts an iteration.
+            } else if goose_attack_run_state.active_users < self.test_plan.maximum_users {
+                // Replace any users shut down when the rate dropped.
+                if self.weighted_users.is_empty() {
+                    self.weighted_users = self.weight_scenario_users(
+                        self.test_plan.maximum_users - goose_attack_run_state.active_users,
+                    )?;
+                }
+                if !self.weighted_users.is_empty() {
+                    self.launch_user(goose_attack_run_state);
+                    started += 1;
+                }
+            }
+        }
+
+        // Track how many users were needed for the highest rate. When the rate drops, shut down
+        // users until the remaining users match the lower rate. Idle users exit immediately,
+        // busy users exit when they finish their current transaction.
+        if arrivals_per_second >= goose_attack_run_state.arrival_peak_rate {
+            goose_attack_run_state.arrival_peak_rate = arrivals_per_second;
+            goose_attack_run_state.arrival_peak_users = goose_attack_run_state.active_users;
+        } else {
+            let needed_users = (goose_attack_run_state.arrival_peak_users as f32
+                * arrivals_per_second
+                / goose_attack_run_state.arrival_peak_rate)
+                .ceil() as usize;
+            while goose_attack_run_state.active_users > needed_users
+                && !goose_attack_run_state.user_channels.is_empty()
+            {
+                debug!("shutting down a user, the arrival rate dropped");
+                goose_attack_run_state.stop_user();
+            }
+        }
+
+        if let Some(arrival_rate) = self.metrics.arrival_rate.as_mut() {
+            arrival_rate.scheduled += scheduled;
+            arrival_rate.started += started;
+            arrival_rate.dropped += scheduled - started;
+        }
+
+        // Sleep until the next iteration is due, waking up at least twice a second to handle
+        // messages and allow for a quick shutdown if the load test is canceled.
+        let maximum_sleep = 500;
+        let sleep_duration = if arrivals_per_second > 0.0 {
+            let next_arrival_in_ms =
+                (1.0 - goose_attack_run_state.arrivals_due) / arrivals_per_second * 1_000.0;
+            Duration::from_millis((next_arrival_in_ms as u64).clamp(1, maximum_sleep))
+        } else {
+            Duration::from_millis(maximum_sleep)
+        };
+        tokio::time::sleep(sleep_duration).await;
+
+        Ok(())
+    }
+
     // Quickly abort and shut down an active [`GooseAttack`](./struct.GooseAttack.html).
     async fn cancel_attack(
         &mut self,
@@ -1646,6 +2184,10 @@ impl GooseAttack {
             // Record how long the attack ran in this step.
             (0, 0),
         ];
+        self.test_plan.ramps = Vec::new();
+        self.test_plan.breakpoint = None;
+        // Stop checking abort-on-fail rules.
+        goose_attack_run_state.abort_snapshot = None;
         // Reset the current step to what was happening when canceled.
         self.test_plan.current = 0;
 
@@ -1665,6 +2207,42 @@ impl GooseAttack {
         Ok(())
     }
 
+    // Abort an active [`GooseAttack`](./struct.GooseAttack.html) if an abort-on-fail rule has
+    // been breached for long enough.
+    async fn check_abort_rules(
+        &mut self,
+        goose_attack_run_state: &mut GooseAttackRunState,
+    ) -> Result<(), GooseError> {
+        let reason = match (
+            self.configuration.abort_on_fail.as_ref(),
+            goose_attack_run_state.abort_snapshot.as_mut(),
+        ) {
+            (Some(abort_on_fail), Some(abort_snapshot)) => {
+                match abort_snapshot.check(abort_on_fail, &self.metrics.requests) {
+                    Some(rule) => format!("{} breached for {}s", rule.threshold, rule.duration),
+                    None => return Ok(()),
+                }
+            }
+            _ => return Ok(()),
+        };
+
+        warn!("aborting load test: {}", reason);
+
+        // Gracefully stop all users, the same as when canceled.
+        self.cancel_attack(goose_attack_run_state).await?;
+
+        // Record why the load test was aborted.
+        let step = self
+            .metrics
+            .history
+            .last_mut()
+            .expect("tried to abort load test with no history");
+        step.action = TestPlanStepAction::Aborting;
+        step.reason = Some(reason);
+
+        Ok(())
+    }
+
     // Cleanly shut down the [`GooseAttack`](./struct.GooseAttack.html).
     async fn stop_attack(&mut self) -> Result<(), GooseError> {
         // Run any configured test_stop() functions.
@@ -1700,6 +2278,28 @@ impl GooseAttack {
             }
             // Only display status codes if not disaled.
             self.metrics.display_status_codes = !self.configuration.no_status_codes;
+            self.metrics.phase_metrics = self.configuration.phase_metrics;
+            // Display the configured response time percentiles.
+            if let Some(percentiles) = self.configuration.percentiles.as_ref() {
+                self.metrics.percentiles = percentiles.clone();
+            }
+            // Score requests against the configured Apdex targets.
+            self.metrics.apdex = self.configuration.apdex.clone();
+            // Track scheduled and dropped iterations (or requests) if running an arrival rate
+            // test plan.
+            if self.test_plan.arrival_rate {
+                self.metrics.arrival_rate = Some(GooseArrivalRateMetrics {
+                    requests: self.configuration.arrival_requests,
+                    ..Default::default()
+                });
+            }
+            // Track each probed load level if running a breakpoint search.
+            if self.test_plan.breakpoint.is_some() {
+                self.metrics.breakpoint = Some(GooseBreakpointMetrics {
+                    arrival_rate: self.test_plan.arrival_rate,
+                    ..Default::default()
+                });
+            }
         }
 
         // Reset the run state.
@@ -1716,33 +2316,135 @@ impl GooseAttack {
         goose_attack_run_state.display_running_metrics = false;
         goose_attack_run_state.shutdown_after_stop = !self.configuration.no_autostart;
         goose_attack_run_state.all_users_spawned = false;
+        goose_attack_run_state.arrival_timer = std_now;
+        goose_attack_run_state.arrivals_due = 0.0;
+        goose_attack_run_state.arrival_peak_rate = 0.0;
+        goose_attack_run_state.arrival_peak_users = 0;
+        goose_attack_run_state.breakpoint_snapshot = self
+            .test_plan
+            .breakpoint
+            .map(|_| GooseBreakpointSnapshot::new(&self.metrics.requests));
+        goose_attack_run_state.abort_snapshot = self
+            .configuration
+            .abort_on_fail
+            .as_ref()
+            .map(|abort_on_fail| GooseAbortSnapshot::new(abort_on_fail, &self.metrics.requests));
+
+        // Track the users running each active scenario with its own test plan.
+        goose_attack_run_state.scenario_timer = std_now;
+        goose_attack_run_state.scenario_run_states = self
+            .scenarios
+            .iter()
+            .filter(|scenario| self.scenario_is_active(scenario))
+            .filter_map(|scenario| {
+                scenario
+                    .test_plan
+                    .as_ref()
+                    .map(|test_plan| ScenarioRunState {
+                        scenarios_index: scenario.scenarios_index,
+                        test_plan: test_plan.clone(),
+                        user_channels: Vec::new(),
+                    })
+            })
+            .collect();
+
+        // If running an arrival rate test plan, create a rendezvous channel so scenario
+        // iterations can only be handed to GooseUsers that are idle.
+        if self.test_plan.arrival_rate {
+            let (arrival_tx, arrival_rx) = flume::bounded(0);
+            goose_attack_run_state.arrival_tx = Some(arrival_tx);
+            goose_attack_run_state.arrival_rx = Some(arrival_rx);
+        }
 
-        // If enabled, spawn a logger thread.
-        let (logger_handle, all_threads_logger_tx) =
-            self.configuration.setup_loggers(&self.defaults).await?;
-        goose_attack_run_state.logger_handle = logger_handle;
-        goose_attack_run_state.all_threads_logger_tx = all_threads_logger_tx;
+        // If enabled, spawn a metrics sink thread.
+        goose_attack_run_state.metrics_sink =
+            self.configuration
+                .metrics_sink
+                .as_ref()
+                .map(|metrics_sink| {
+                    MetricsSink::spawn(
+                        metrics_sink,
+                        self.configuration
+                            .metrics_sink_interval
+                            .unwrap_or(sink::DEFAULT_INTERVAL),
+                    )
+                });
+
+        // If enabled, spawn an OpenTelemetry exporter thread.
+        if !self.configuration.otlp_endpoint.is_empty() {
+            goose_attack_run_state.otlp_exporter = Some(OtlpExporter::spawn(
+                &self.configuration.otlp_endpoint,
+                self.configuration.otlp_traces,
+                !self.configuration.no_transaction_metrics,
+            ));
+        }
 
         // If enabled, spawn a throttle thread.
         let (throttle_threads_tx, parent_to_throttle_tx) = self.setup_throttle().await;
         goose_attack_run_state.throttle_threads_tx = throttle_threads_tx;
         goose_attack_run_state.parent_to_throttle_tx = parent_to_throttle_tx;
 
-        // If enabled, try to create the report file to confirm access.
-        let _report_file = match self.prepare_report_file().await {
-            Ok(f) => f,
-            Err(e) => {
+        // If enabled, try to create the report files to confirm access.
+        for report_file in &self.configuration.report_file {
+            if let Err(e) = File::create(report_file).await {
                 return Err(GooseError::InvalidOption {
                     option: "--report-file".to_string(),
-                    value: self.get_report_file_path().unwrap(),
+                    value: report_file.to_string(),
                     detail: format!("Failed to create report file: {}", e),
-                })
+                });
             }
-        };
+        }
+
+        // If enabled, load the baseline to compare to. This happens first in case the
+        // same file is also used to save a new baseline.
+        if !self.configuration.baseline.is_empty() {
+            self.baseline = Some(GooseBaseline::load(&self.configuration.baseline).await?);
+        }
+
+        // If enabled, try to create the baseline file to confirm access.
+        if !self.configuration.baseline_file.is_empty() {
+            if let Err(e) = File::create(&self.configuration.baseline_file).await {
+                return Err(GooseError::InvalidOption {
+                    option: "--baseline-file".to_string(),
+                    value: self.configuration.baseline_file.to_string(),
+                    detail: format!("Failed to create baseline file: {}", e),
+                });
+            }
+        }
+
+        // If enabled, try to create the junit file to confirm access.
+        if !self.configuration.junit_file.is_empty() {
+            if let Err(e) = File::create(&self.configuration.junit_file).await {
+                return Err(GooseError::InvalidOption {
+                    option: "--junit-file".to_string(),
+                    value: self.configuration.junit_file.to_string(),
+                    detail: format!("Failed to create junit file: {}", e),
+                });
+            }
+        }
+
+        // If enabled, create the Locust-compatible CSV history file.
+        if !self.configuration.csv.is_empty() {
+            goose_attack_run_state.locust_history =
+                Some(LocustHistory::create(&self.configuration.csv).await?);
+        }
 
         // Record when the GooseAttack officially started.
         self.started = Some(time::Instant::now());
 
+        // If enabled, spawn a logger thread.
+        let started = time::SystemTime::now()
+            .duration_since(time::UNIX_EPOCH)
+            .map(|duration| duration.as_millis() as u64)
+            .unwrap_or_default();
+        let (logger_handle, all_threads_logger_tx) = self
+            .configuration
+            .setup_loggers(&self.defaults, started)
+            .await?;
+        goose_attack_run_state.logger_handle = logger_handle;
+        goose_attack_run_state.all_threads_logger_tx = all_threads_logger_tx;
+        goose_attack_run_state.logged_users = 0;
+
         Ok(())
     }
 
@@ -1758,6 +2460,14 @@ impl GooseAttack {
         // The Goose parent process GooseAttack loop runs until Goose shuts down. Goose enters
         // the loop in AttackPhase::Idle, and exits in AttackPhase::Shutdown.
         loop {
+            // Scenarios with their own test plan run alongside the global test plan.
+            if matches!(
+                self.attack_phase,
+                AttackPhase::Increase | AttackPhase::Maintain | AttackPhase::Decrease
+            ) {
+                self.adjust_scenario_users(&mut goose_attack_run_state)?;
+            }
+
             match self.attack_phase {
                 // In the Idle phase the Goose configuration can be changed by a Controller,
                 // and otherwise nothing happens but sleeping an checking for messages.
@@ -1780,13 +2490,32 @@ impl GooseAttack {
                     } else {
                         // Prepare to start the load test, resetting timers and counters.
                         self.reset_run_state(&mut goose_attack_run_state).await?;
-                        self.metrics
-                            .history
-                            .push(TestPlanHistory::step(TestPlanStepAction::Increasing, 0));
+                        self.metrics.history.push(TestPlanHistory::step(
+                            TestPlanStepAction::Increasing,
+                            0,
+                            self.test_plan.ramp(0),
+                        ));
                         //self.graph_data.set_starting(Utc::now());
                         self.set_attack_phase(&mut goose_attack_run_state, AttackPhase::Increase);
                     }
                 }
+                // When running an arrival rate test plan, Goose starts scenario iterations at the
+                // configured rate, launching GooseUser threads as needed.
+                AttackPhase::Increase | AttackPhase::Maintain | AttackPhase::Decrease
+                    if self.test_plan.arrival_rate =>
+                {
+                    self.update_duration();
+                    self.arrival_rate_attack(&mut goose_attack_run_state)
+                        .await?;
+                }
+                // When a test plan step oscillates, Goose launches and stops GooseUser threads
+                // throughout the step.
+                AttackPhase::Increase | AttackPhase::Maintain | AttackPhase::Decrease
+                    if self.test_plan.oscillating() =>
+                {
+                    self.update_duration();
+                    self.oscillate_attack(&mut goose_attack_run_state).await?;
+                }
                 // In the Increase phase, Goose launches GooseUser threads.
                 AttackPhase::Increase => {
                     self.update_duration();
@@ -1809,10 +2538,13 @@ impl GooseAttack {
                 AttackPhase::Shutdown => break,
             }
 
+            // If the logger thread is enabled, tell it how many users are running.
+            goose_attack_run_state.log_running_users();
+
             // Record current users for users per second graph in HTML report.
             if let Some(started) = self.started {
                 self.graph_data.record_users_per_second(
-                    goose_attack_run_state.active_users,
+                    goose_attack_run_state.running_users(),
                     started.elapsed().as_secs() as usize,
                 );
             };
@@ -1821,6 +2553,9 @@ impl GooseAttack {
             self.sync_metrics(&mut goose_attack_run_state, false)
                 .await?;
 
+            // Abort the load test if an abort-on-fail rule is breached.
+            self.check_abort_rules(&mut goose_attack_run_state).await?;
+
             // Check if a Controller has made a request.
             self.handle_controller_requests(&mut goose_attack_run_state)
                 .await?;
@@ -1832,7 +2567,7 @@ impl GooseAttack {
                     .insert(message.expect("failed to wrap OK message"));
 
                 // In Stand-alone mode, all users are started.
-                if goose_attack_run_state.users_shutdown.len() == self.test_plan.total_users() {
+                if goose_attack_run_state.users_shutdown.len() == self.total_users() {
                     self.cancel_attack(&mut goose_attack_run_state).await?;
                 }
 
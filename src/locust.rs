@@ -0,0 +1,482 @@
+//! Optional Locust-compatible CSV statistics.
+//!
+//! When enabled with `--csv PREFIX`, Goose writes the same CSV files as Locust's `--csv`
+//! option so dashboards and scripts built for Locust can ingest Goose metrics. While the
+//! load test runs a row is appended to `PREFIX_stats_history.csv` for each request and for
+//! all requests aggregated together every second, from the same loop that displays running
+//! metrics. When the load test finishes, the final metrics are written to
+//! `PREFIX_stats.csv`, `PREFIX_failures.csv` and `PREFIX_exceptions.csv`.
+
+use itertools::Itertools;
+use std::collections::HashMap;
+use std::time::{SystemTime, UNIX_EPOCH};
+use tokio::io::AsyncWriteExt;
+
+use crate::logger::format_csv_row;
+use crate::metrics::{
+    self, GooseMetrics, GooseRequestMetricAggregate, GooseRequestMetricTimingData,
+};
+use crate::util;
+use crate::GooseError;
+
+/// How often a row is appended to the history file, in seconds, as Locust does by default.
+const HISTORY_INTERVAL: usize = 1;
+
+/// The response time percentiles included in Locust CSV files.
+const PERCENTILES: [f32; 11] = [
+    50.0, 66.0, 75.0, 80.0, 90.0, 95.0, 98.0, 99.0, 99.9, 99.99, 100.0,
+];
+
+/// The aggregated metrics of a single request, or of all requests.
+struct LocustRow {
+    /// The request method, empty for the aggregated row.
+    method: String,
+    /// The request name, `Aggregated` for the aggregated row.
+    name: String,
+    /// How many requests were made.
+    count: usize,
+    /// How many requests failed.
+    failures: usize,
+    /// The response times of the requests.
+    times: GooseRequestMetricTimingData,
+    /// Total number of bytes received in response bodies.
+    response_bytes: usize,
+}
+impl LocustRow {
+    /// The average response time, in milliseconds.
+    fn average(&self) -> f32 {
+        if self.times.counter > 0 {
+            self.times.total_time as f32 / self.times.counter as f32
+        } else {
+            0.0
+        }
+    }
+
+    /// The average response body size, in bytes.
+    fn average_content_size(&self) -> f32 {
+        if self.count > 0 {
+            self.response_bytes as f32 / self.count as f32
+        } else {
+            0.0
+        }
+    }
+
+    /// The response time of each percentile, `N/A` if no requests were made.
+    fn percentiles(&self) -> Vec<String> {
+        if self.times.counter > 0 {
+            self.times
+                .percentiles(&PERCENTILES)
+                .iter()
+                .map(|percentile| percentile.response_time.to_string())
+                .collect()
+        } else {
+            vec!["N/A".to_string(); PERCENTILES.len()]
+        }
+    }
+
+    /// The median response time, in milliseconds.
+    fn median(&self) -> usize {
+        self.times
+            .percentiles(&[50.0])
+            .first()
+            .map_or(0, |percentile| percentile.response_time)
+    }
+}
+
+/// Flatten the request metrics sorted by name, followed by all requests aggregated
+/// together.
+fn locust_rows(requests: &HashMap<String, GooseRequestMetricAggregate>) -> Vec<LocustRow> {
+    let mut aggregate = LocustRow {
+        method: "".to_string(),
+        name: "Aggregated".to_string(),
+        count: 0,
+        failures: 0,
+        times: GooseRequestMetricTimingData::new(metrics::DEFAULT_SIGNIFICANT_DIGITS),
+        response_bytes: 0,
+    };
+    let mut rows = Vec::new();
+    for request in requests
+        .values()
+        .sorted_by(|a, b| (&a.path, &a.method).cmp(&(&b.path, &b.method)))
+    {
+        let count = request.success_count + request.fail_count;
+        aggregate.count += count;
+        aggregate.failures += request.fail_count;
+        aggregate.times.merge(&request.raw_data);
+        aggregate.response_bytes += request.response_bytes;
+        rows.push(LocustRow {
+            method: request.method.to_string(),
+            name: request.path.to_string(),
+            count,
+            failures: request.fail_count,
+            times: request.raw_data.clone(),
+            response_bytes: request.response_bytes,
+        });
+    }
+    rows.push(aggregate);
+    rows
+}
+
+/// Format a CSV line with a variable number of fields, such as one per percentile,
+/// escaping each field with `format_csv_row!`.
+fn csv_line<S: AsRef<str>>(fields: &[S]) -> String {
+    let mut line = fields
+        .iter()
+        .map(|field| format_csv_row!(field.as_ref()))
+        .join(",");
+    line.push('\n');
+    line
+}
+
+/// The header of each percentile column.
+fn percentile_headers() -> Vec<String> {
+    PERCENTILES
+        .iter()
+        .map(|percentile| format!("{}%", percentile))
+        .collect()
+}
+
+/// Build the contents of `PREFIX_stats.csv` from the final metrics.
+pub(crate) fn build_stats(metrics: &GooseMetrics) -> String {
+    let mut header = [
+        "Type",
+        "Name",
+        "Request Count",
+        "Failure Count",
+        "Median Response Time",
+        "Average Response Time",
+        "Min Response Time",
+        "Max Response Time",
+        "Average Content Size",
+        "Requests/s",
+        "Failures/s",
+    ]
+    .iter()
+    .map(|field| field.to_string())
+    .collect::<Vec<String>>();
+    header.extend(percentile_headers());
+
+    let mut stats = csv_line(&header);
+    for row in locust_rows(&metrics.requests) {
+        let (requests_per_second, failures_per_second) =
+            metrics::per_second_calculations(metrics.duration, row.count, row.failures);
+        let mut fields = vec![
+            row.method.to_string(),
+            row.name.to_string(),
+            row.count.to_string(),
+            row.failures.to_string(),
+            row.median().to_string(),
+            format!("{:.2}", row.average()),
+            row.times.minimum_time.to_string(),
+            row.times.maximum_time.to_string(),
+            format!("{:.2}", row.average_content_size()),
+            format!("{:.2}", requests_per_second),
+            format!("{:.2}", failures_per_second),
+        ];
+        fields.extend(row.percentiles());
+        stats.push_str(&csv_line(&fields));
+    }
+    stats
+}
+
+/// Build the contents of `PREFIX_failures.csv` from the final metrics.
+pub(crate) fn build_failures(metrics: &GooseMetrics) -> String {
+    let mut failures = format_csv_row!("Method", "Name", "Error", "Occurrences");
+    failures.push('\n');
+    for error in metrics.errors.values() {
+        failures.push_str(&format_csv_row!(
+            error.method,
+            error.name,
+            error.error,
+            error.occurrences
+        ));
+        failures.push('\n');
+    }
+    failures
+}
+
+/// Build the contents of `PREFIX_exceptions.csv` from the final metrics.
+///
+/// Locust lists unhandled Python exceptions raised by tasks in this file, the closest
+/// Goose equivalent is a transaction returning an error, so a row is written for each
+/// transaction that failed. Goose doesn't record tracebacks, and all transactions ran on
+/// the `local` node.
+pub(crate) fn build_exceptions(metrics: &GooseMetrics) -> String {
+    let mut exceptions = format_csv_row!("Count", "Message", "Traceback", "Nodes");
+    exceptions.push('\n');
+    for transaction in metrics.transactions.iter().flatten() {
+        if transaction.fail_count == 0 {
+            continue;
+        }
+        // Transactions are identified by their index if they aren't named.
+        let name = if transaction.transaction_name.is_empty() {
+            format!(
+                "{}.{}",
+                transaction.scenario_index, transaction.transaction_index
+            )
+        } else {
+            transaction.transaction_name.to_string()
+        };
+        exceptions.push_str(&format_csv_row!(
+            transaction.fail_count,
+            format!("Transaction {} {} failed", transaction.scenario_name, name),
+            "",
+            "local"
+        ));
+        exceptions.push('\n');
+    }
+    exceptions
+}
+
+/// The header of `PREFIX_stats_history.csv`.
+fn history_header() -> String {
+    let mut header = [
+        "Timestamp",
+        "User Count",
+        "Type",
+        "Name",
+        "Requests/s",
+        "Failures/s",
+    ]
+    .iter()
+    .map(|field| field.to_string())
+    .collect::<Vec<String>>();
+    header.extend(percentile_headers());
+    header.extend(
+        [
+            "Total Request Count",
+            "Total Failure Count",
+            "Total Median Response Time",
+            "Total Average Response Time",
+            "Total Min Response Time",
+            "Total Max Response Time",
+            "Total Average Content Size",
+        ]
+        .iter()
+        .map(|field| field.to_string()),
+    );
+    csv_line(&header)
+}
+
+/// Format the history rows of the current metrics.
+///
+/// As with Locust, the request rates and response time percentiles are calculated from the
+/// requests made in the `seconds` since the `previous` request metrics were collected,
+/// while the remaining columns are totals since the metrics were last reset.
+fn format_history(
+    requests: &HashMap<String, GooseRequestMetricAggregate>,
+    previous: &HashMap<String, GooseRequestMetricAggregate>,
+    seconds: f32,
+    users: usize,
+    timestamp: u64,
+) -> String {
+    let window = metrics::requests_since(previous, requests);
+    let mut window_rows: HashMap<(String, String), LocustRow> = locust_rows(&window)
+        .into_iter()
+        .map(|row| ((row.method.to_string(), row.name.to_string()), row))
+        .collect();
+
+    let mut history = String::new();
+    for row in locust_rows(requests) {
+        let window_row = window_rows.remove(&(row.method.to_string(), row.name.to_string()));
+        let (count, failures) = window_row
+            .as_ref()
+            .map_or((0, 0), |window_row| (window_row.count, window_row.failures));
+        let mut fields = vec![
+            timestamp.to_string(),
+            users.to_string(),
+            row.method.to_string(),
+            row.name.to_string(),
+            format!("{:.2}", count as f32 / seconds.max(1.0)),
+            format!("{:.2}", failures as f32 / seconds.max(1.0)),
+        ];
+        match window_row {
+            Some(window_row) => fields.extend(window_row.percentiles()),
+            None => fields.extend(vec!["N/A".to_string(); PERCENTILES.len()]),
+        }
+        fields.extend(vec![
+            row.count.to_string(),
+            row.failures.to_string(),
+            row.median().to_string(),
+            format!("{:.2}", row.average()),
+            row.times.minimum_time.to_string(),
+            row.times.maximum_time.to_string(),
+            format!("{:.2}", row.average_content_size()),
+        ]);
+        history.push_str(&csv_line(&fields));
+    }
+    history
+}
+
+/// The `PREFIX_stats_history.csv` file rows are appended to while a load test runs, owned
+/// by the parent process.
+#[derive(Debug)]
+pub(crate) struct LocustHistory {
+    /// The path of the history file.
+    path: String,
+    /// The open history file.
+    file: tokio::fs::File,
+    /// Timer tracking when a row was last appended.
+    timer: std::time::Instant,
+    /// The request metrics collected when a row was last appended.
+    previous: HashMap<String, GooseRequestMetricAggregate>,
+}
+impl LocustHistory {
+    /// Create the history file and write its header.
+    pub(crate) async fn create(prefix: &str) -> Result<Self, GooseError> {
+        let path = format!("{}_stats_history.csv", prefix);
+        let invalid = |e: std::io::Error| GooseError::InvalidOption {
+            option: "--csv".to_string(),
+            value: prefix.to_string(),
+            detail: format!("Failed to create csv file {}: {}", path, e),
+        };
+        let mut file = tokio::fs::File::create(&path).await.map_err(invalid)?;
+        file.write_all(history_header().as_bytes())
+            .await
+            .map_err(invalid)?;
+        Ok(LocustHistory {
+            path,
+            file,
+            timer: std::time::Instant::now(),
+            previous: HashMap::new(),
+        })
+    }
+
+    /// Append rows if the interval has passed since rows were last appended.
+    pub(crate) async fn append_if_due(&mut self, metrics: &GooseMetrics, users: usize) {
+        if util::timer_expired(self.timer, HISTORY_INTERVAL) {
+            self.append(metrics, users).await;
+        }
+    }
+
+    /// Append rows with the current metrics.
+    pub(crate) async fn append(&mut self, metrics: &GooseMetrics, users: usize) {
+        let timestamp = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map(|duration| duration.as_secs())
+            .unwrap_or_default();
+        let history = format_history(
+            &metrics.requests,
+            &self.previous,
+            self.timer.elapsed().as_secs_f32(),
+            users,
+            timestamp,
+        );
+        self.timer = std::time::Instant::now();
+        self.previous = metrics.requests.clone();
+        if let Err(e) = self.file.write_all(history.as_bytes()).await {
+            warn!("failed to write to {}: {}", self.path, e);
+        }
+    }
+
+    /// Append rows with the metrics collected so far before they are reset, then start
+    /// counting again.
+    pub(crate) async fn reset(&mut self, metrics: &GooseMetrics, users: usize) {
+        self.append(metrics, users).await;
+        self.previous.clear();
+    }
+
+    /// Append rows with the final metrics, then flush the history file.
+    pub(crate) async fn shutdown(mut self, metrics: &GooseMetrics, users: usize) {
+        self.append(metrics, users).await;
+        if let Err(e) = self.file.flush().await {
+            warn!("failed to write to {}: {}", self.path, e);
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::goose::GooseMethod;
+    use crate::metrics::TransactionMetricAggregate;
+
+    fn build_requests(
+        times: &[u64],
+        failures: usize,
+    ) -> HashMap<String, GooseRequestMetricAggregate> {
+        let mut index = GooseRequestMetricAggregate::new("/", GooseMethod::Get, 0, 3);
+        for time in times {
+            index.record_time(*time, false);
+            index.success_count += 1;
+        }
+        index.fail_count += failures;
+        index.response_bytes = 100 * (times.len() + failures);
+        let mut about = GooseRequestMetricAggregate::new("/about, us", GooseMethod::Post, 0, 3);
+        about.record_time(40, false);
+        about.success_count += 1;
+        let mut requests = HashMap::new();
+        requests.insert("GET /".to_string(), index);
+        requests.insert("POST /about, us".to_string(), about);
+        requests
+    }
+
+    #[test]
+    fn format_locust_csv() {
+        let mut metrics = GooseMetrics {
+            duration: 2,
+            requests: build_requests(&[10, 20], 0),
+            ..Default::default()
+        };
+        metrics.requests.get_mut("GET /").unwrap().fail_count = 2;
+        let stats = build_stats(&metrics);
+        let lines: Vec<&str> = stats.lines().collect();
+        assert_eq!(lines.len(), 4);
+        assert_eq!(
+            lines[0],
+            "Type,Name,Request Count,Failure Count,Median Response Time,Average Response Time,\
+            Min Response Time,Max Response Time,Average Content Size,Requests/s,Failures/s,\
+            50%,66%,75%,80%,90%,95%,98%,99%,99.9%,99.99%,100%"
+        );
+        assert_eq!(
+            lines[1],
+            "GET,/,4,2,10,15.00,10,20,50.00,2.00,1.00,10,10,20,20,20,20,20,20,20,20,20"
+        );
+        // Fields containing a separator are quoted.
+        assert!(lines[2].starts_with("POST,\"/about, us\",1,0,40,40.00,40,40,"));
+        assert!(lines[3].starts_with(",Aggregated,5,2,20,23.33,10,40,"));
+
+        assert_eq!(
+            build_exceptions(&metrics),
+            "Count,Message,Traceback,Nodes\n".to_string()
+        );
+        let mut login = TransactionMetricAggregate::new(0, "LoadTest", 1, "login", 3);
+        login.set_time(20, false);
+        login.set_time(10, false);
+        let mut about = TransactionMetricAggregate::new(0, "LoadTest", 2, "", 3);
+        about.set_time(20, true);
+        metrics.transactions.push(vec![login, about]);
+        assert_eq!(
+            build_exceptions(&metrics),
+            "Count,Message,Traceback,Nodes\n2,Transaction LoadTest login failed,,local\n"
+                .to_string()
+        );
+    }
+
+    #[test]
+    fn format_locust_history() {
+        let header = history_header();
+        assert!(header.starts_with("Timestamp,User Count,Type,Name,Requests/s,Failures/s,50%,"));
+        assert!(header.ends_with(
+            ",100%,Total Request Count,Total Failure Count,Total Median Response Time,\
+            Total Average Response Time,Total Min Response Time,Total Max Response Time,\
+            Total Average Content Size\n"
+        ));
+
+        // Rates and percentiles only include requests made since the previous rows.
+        let previous = build_requests(&[10, 20], 0);
+        let requests = build_requests(&[10, 20, 60, 60], 1);
+        let history = format_history(&requests, &previous, 2.0, 3, 1_000);
+        let lines: Vec<&str> = history.lines().collect();
+        assert_eq!(lines.len(), 3);
+        assert_eq!(
+            lines[0],
+            "1000,3,GET,/,1.50,0.50,60,60,60,60,60,60,60,60,60,60,60,5,1,20,37.50,10,60,100.00"
+        );
+        // No requests were made since the previous rows.
+        assert!(lines[1].starts_with(
+            "1000,3,POST,\"/about, us\",0.00,0.00,N/A,N/A,N/A,N/A,N/A,N/A,N/A,N/A,N/A,N/A,N/A,1,0,"
+        ));
+        assert!(lines[2].starts_with("1000,3,,Aggregated,1.50,0.50,60,"));
+    }
+}
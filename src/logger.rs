@@ -8,6 +8,9 @@
 //! (the standard debug output of a Rust structure), using the following run time optios:
 //!  - `--debug-format`, `--request-format`, `--transaction-format`, `--scenario-format`
 //!
+//! The request log can also be written in JMeter's CSV `jtl` format, allowing JMeter reporting
+//! tools and dashboards to consume Goose load tests.
+//!
 //! All of these loggers use a single shared logger thread, with
 //! [`GooseUser`](../goose/struct.GooseUser.html)s sending log messages through the same shared
 //! channel. The logger determines which log file to write the message to based on the message
@@ -195,6 +198,28 @@ pub enum GooseLog {
     Request(GooseRequestMetric),
     Transaction(TransactionMetric),
     Scenario(ScenarioMetric),
+    /// How many users are running, sent by the parent each time it changes.
+    RunningUsers(usize),
+}
+
+/// The state of the running load test tracked by the logger thread, included in
+/// JTL-formatted request logs.
+#[derive(Debug)]
+pub(crate) struct GooseLoggerRunState {
+    /// When the load test started, in milliseconds since the Unix epoch.
+    started: u64,
+    /// How many users are running.
+    running_users: usize,
+}
+
+/// The error returned when a log other than the request log is configured to use the jtl
+/// format.
+pub(crate) fn jtl_unsupported(option: &str) -> GooseError {
+    GooseError::InvalidOption {
+        option: option.to_string(),
+        value: "jtl".to_string(),
+        detail: "The jtl format is only supported by the request log.".to_string(),
+    }
 }
 
 /// Defines the formats logs can be written to file.
@@ -204,6 +229,8 @@ pub enum GooseLogFormat {
     Json,
     Raw,
     Pretty,
+    /// JMeter's CSV results format, only supported by the request log.
+    Jtl,
 }
 /// Allow setting log formats from the command line by impleenting [`FromStr`].
 impl FromStr for GooseLogFormat {
@@ -217,6 +244,7 @@ impl FromStr for GooseLogFormat {
             r"(?i)^(json|jsn)$",
             r"(?i)^raw$",
             r"(?i)^pretty$",
+            r"(?i)^jtl$",
         ])
         .expect("failed to compile log_format RegexSet");
         let matches = log_format.matches(s);
@@ -228,11 +256,13 @@ impl FromStr for GooseLogFormat {
             Ok(GooseLogFormat::Raw)
         } else if matches.matched(3) {
             Ok(GooseLogFormat::Pretty)
+        } else if matches.matched(4) {
+            Ok(GooseLogFormat::Jtl)
         } else {
             Err(GooseError::InvalidOption {
                 option: format!("GooseLogFormat::{:?}", s),
                 value: s.to_string(),
-                detail: "Invalid log_format, expected: csv, json, or raw".to_string(),
+                detail: "Invalid log_format, expected: csv, json, raw, pretty or jtl".to_string(),
             })
         }
     }
@@ -274,6 +304,36 @@ fn requests_csv_header() -> String {
         "error",
         "coordinated_omission_elapsed",
         "user_cadence",
+        "request_bytes",
+        "response_bytes",
+        "compressed_bytes",
+        "dns_time",
+        "download_time",
+        "connection_reused",
+        "timestamp",
+    )
+}
+
+// The columns JMeter writes to CSV results files by default.
+fn requests_jtl_header() -> String {
+    format_csv_row!(
+        "timeStamp",
+        "elapsed",
+        "label",
+        "responseCode",
+        "responseMessage",
+        "threadName",
+        "dataType",
+        "success",
+        "failureMessage",
+        "bytes",
+        "sentBytes",
+        "grpThreads",
+        "allThreads",
+        "URL",
+        "Latency",
+        "IdleTime",
+        "Connect",
     )
 }
 
@@ -295,18 +355,68 @@ fn scenarios_csv_header() -> String {
     format_csv_row!("elapsed", "name", "index", "run_time", "user",)
 }
 
+/// Formats a request as a row of JMeter's CSV results format.
+///
+/// JMeter's `elapsed` time includes downloading the response body, while its `Latency` is the
+/// time until the response headers were received, Goose's `response_time`. The request started
+/// `message.elapsed` milliseconds after the load test, and `Connect` only includes the time
+/// spent resolving the host name.
+fn requests_jtl_row(message: &GooseRequestMetric, run_state: &GooseLoggerRunState) -> String {
+    let elapsed = message.response_time + message.download_time;
+    let response_message = reqwest::StatusCode::from_u16(message.status_code)
+        .ok()
+        .and_then(|status_code| status_code.canonical_reason())
+        .unwrap_or("");
+    // The final URL isn't known if no response was received.
+    let url = if message.final_url.is_empty() {
+        &message.raw.url
+    } else {
+        &message.final_url
+    };
+    // Goose doesn't group users like JMeter thread groups, all users are in the same group.
+    let running_users = run_state.running_users;
+    format_csv_row!(
+        run_state.started + message.elapsed,
+        elapsed,
+        message.name,
+        message.status_code,
+        response_message,
+        format!("Goose Users 1-{}", message.user + 1),
+        "text",
+        message.success,
+        message.error,
+        message.compressed_bytes,
+        message.request_bytes,
+        running_users,
+        running_users,
+        url,
+        message.response_time,
+        0,
+        message.dns_time,
+    )
+}
+
 /// Two traits that must be implemented by all loggers provided through this thread.
 pub(crate) trait GooseLogger<T> {
-    /// Converts a rust structure to a formatted string.
+    /// Converts a rust structure to a formatted string, or returns an error if the log
+    /// format isn't supported by this log.
     /// @TODO: rework with .to_string()
-    fn format_message(&self, message: T) -> String;
+    fn format_message(
+        &self,
+        message: T,
+        run_state: &GooseLoggerRunState,
+    ) -> Result<String, GooseError>;
 }
 /// Traits for GooseDebug logs.
 impl GooseLogger<GooseDebug> for GooseConfiguration {
     /// Converts a GooseDebug structure to a formatted string.
-    fn format_message(&self, message: GooseDebug) -> String {
+    fn format_message(
+        &self,
+        message: GooseDebug,
+        _run_state: &GooseLoggerRunState,
+    ) -> Result<String, GooseError> {
         if let Some(debug_format) = self.debug_format.as_ref() {
-            match debug_format {
+            Ok(match debug_format {
                 // Use serde_json to create JSON.
                 GooseLogFormat::Json => json!(message).to_string(),
                 // Raw format is Debug output for GooseRawRequest structure.
@@ -323,7 +433,10 @@ impl GooseLogger<GooseDebug> for GooseConfiguration {
                         format!("{:?}", message.body)
                     )
                 }
-            }
+                // The jtl format is only supported by the request log, which is validated
+                // when the load test is configured.
+                GooseLogFormat::Jtl => return Err(jtl_unsupported("`configuration.debug_format`")),
+            })
         } else {
             // A log format is required.
             unreachable!()
@@ -333,9 +446,13 @@ impl GooseLogger<GooseDebug> for GooseConfiguration {
 /// Traits for GooseErrorMetric logs.
 impl GooseLogger<GooseErrorMetric> for GooseConfiguration {
     /// Converts a GooseErrorMetric structure to a formatted string.
-    fn format_message(&self, message: GooseErrorMetric) -> String {
+    fn format_message(
+        &self,
+        message: GooseErrorMetric,
+        _run_state: &GooseLoggerRunState,
+    ) -> Result<String, GooseError> {
         if let Some(error_format) = self.error_format.as_ref() {
-            match error_format {
+            Ok(match error_format {
                 // Use serde_json to create JSON.
                 GooseLogFormat::Json => json!(message).to_string(),
                 // Raw format is Debug output for GooseErrorMetric structure.
@@ -356,7 +473,10 @@ impl GooseLogger<GooseErrorMetric> for GooseConfiguration {
                         message.error,
                     )
                 }
-            }
+                // The jtl format is only supported by the request log, which is validated
+                // when the load test is configured.
+                GooseLogFormat::Jtl => return Err(jtl_unsupported("`configuration.error_format`")),
+            })
         } else {
             // A log format is required.
             unreachable!()
@@ -366,9 +486,13 @@ impl GooseLogger<GooseErrorMetric> for GooseConfiguration {
 /// Traits for GooseRequestMetric logs.
 impl GooseLogger<GooseRequestMetric> for GooseConfiguration {
     /// Converts a GooseRequestMetric structure to a formatted string.
-    fn format_message(&self, message: GooseRequestMetric) -> String {
+    fn format_message(
+        &self,
+        message: GooseRequestMetric,
+        run_state: &GooseLoggerRunState,
+    ) -> Result<String, GooseError> {
         if let Some(request_format) = self.request_format.as_ref() {
-            match request_format {
+            Ok(match request_format {
                 // Use serde_json to create JSON.
                 GooseLogFormat::Json => json!(message).to_string(),
                 // Raw format is Debug output for GooseRequestMetric structure.
@@ -391,9 +515,18 @@ impl GooseLogger<GooseRequestMetric> for GooseConfiguration {
                         message.error,
                         message.coordinated_omission_elapsed,
                         message.user_cadence,
+                        message.request_bytes,
+                        message.response_bytes,
+                        message.compressed_bytes,
+                        message.dns_time,
+                        message.download_time,
+                        message.connection_reused,
+                        message.timestamp,
                     )
                 }
-            }
+                // JMeter's CSV results format.
+                GooseLogFormat::Jtl => requests_jtl_row(&message, run_state),
+            })
         } else {
             // A log format is required.
             unreachable!()
@@ -403,9 +536,13 @@ impl GooseLogger<GooseRequestMetric> for GooseConfiguration {
 /// Traits for TransactionMetric logs.
 impl GooseLogger<TransactionMetric> for GooseConfiguration {
     /// Converts a TransactionMetric structure to a formatted string.
-    fn format_message(&self, message: TransactionMetric) -> String {
+    fn format_message(
+        &self,
+        message: TransactionMetric,
+        _run_state: &GooseLoggerRunState,
+    ) -> Result<String, GooseError> {
         if let Some(transaction_format) = self.transaction_format.as_ref() {
-            match transaction_format {
+            Ok(match transaction_format {
                 // Use serde_json to create JSON.
                 GooseLogFormat::Json => json!(message).to_string(),
                 // Raw format is Debug output for TransactionMetric structure.
@@ -424,7 +561,12 @@ impl GooseLogger<TransactionMetric> for GooseConfiguration {
                         message.user,
                     )
                 }
-            }
+                // The jtl format is only supported by the request log, which is validated
+                // when the load test is configured.
+                GooseLogFormat::Jtl => {
+                    return Err(jtl_unsupported("`configuration.transaction_format`"))
+                }
+            })
         } else {
             // A log format is required.
             unreachable!()
@@ -435,9 +577,13 @@ impl GooseLogger<TransactionMetric> for GooseConfiguration {
 /// Traits for ScenarioMetric logs.
 impl GooseLogger<ScenarioMetric> for GooseConfiguration {
     /// Converts a ScenarioMetric structure to a formatted string.
-    fn format_message(&self, message: ScenarioMetric) -> String {
+    fn format_message(
+        &self,
+        message: ScenarioMetric,
+        _run_state: &GooseLoggerRunState,
+    ) -> Result<String, GooseError> {
         if let Some(scenario_format) = self.scenario_format.as_ref() {
-            match scenario_format {
+            Ok(match scenario_format {
                 // Use serde_json to create JSON.
                 GooseLogFormat::Json => json!(message).to_string(),
                 // Raw format is Debug output for ScenarioMetric structure.
@@ -454,7 +600,12 @@ impl GooseLogger<ScenarioMetric> for GooseConfiguration {
                         message.user,
                     )
                 }
-            }
+                // The jtl format is only supported by the request log, which is validated
+                // when the load test is configured.
+                GooseLogFormat::Jtl => {
+                    return Err(jtl_unsupported("`configuration.scenario_format`"))
+                }
+            })
         } else {
             // A log format is required.
             unreachable!()
@@ -686,10 +837,12 @@ impl GooseConfiguration {
         ]);
     }
 
-    /// Spawns the logger thread if one or more loggers are enabled.
+    /// Spawns the logger thread if one or more loggers are enabled. The load test started
+    /// at `started`, in milliseconds since the Unix epoch.
     pub(crate) async fn setup_loggers(
         &mut self,
         defaults: &GooseDefaults,
+        started: u64,
     ) -> Result<(GooseLoggerJoinHandle, GooseLoggerTx), GooseError> {
         // Update the logger configuration, loading defaults if necessasry.
         self.configure_loggers(defaults);
@@ -711,7 +864,8 @@ impl GooseConfiguration {
         ) = flume::unbounded();
         // Launch a new thread for logging.
         let configuration = self.clone();
-        let logger_handle = tokio::spawn(async move { configuration.logger_main(logger_rx).await });
+        let logger_handle =
+            tokio::spawn(async move { configuration.logger_main(logger_rx, started).await });
         Ok((Some(logger_handle), Some(all_threads_logger_tx)))
     }
 
@@ -763,7 +917,13 @@ impl GooseConfiguration {
     pub(crate) async fn logger_main(
         self: GooseConfiguration,
         receiver: flume::Receiver<Option<GooseLog>>,
+        started: u64,
     ) -> Result<(), GooseError> {
+        let mut run_state = GooseLoggerRunState {
+            started,
+            running_users: 0,
+        };
+
         // If the debug_log is enabled, allocate a buffer and open the file.
         let mut debug_log = self
             .open_log_file(
@@ -819,6 +979,13 @@ impl GooseConfiguration {
                 self.write_to_log_file(log_file, requests_csv_header())
                     .await;
             }
+        // If the request_log is a JTL, write JMeter's header.
+        } else if self.request_format == Some(GooseLogFormat::Jtl) {
+            if let Some(log_file) = request_log.as_mut() {
+                // This will generate a warning if it fails to write to log file.
+                self.write_to_log_file(log_file, requests_jtl_header())
+                    .await;
+            }
         }
 
         // If the transaction_log is enabled, allocate a buffer and open the file.
@@ -853,25 +1020,31 @@ impl GooseConfiguration {
                 let formatted_message;
                 if let Some(log_file) = match message {
                     GooseLog::Debug(debug_message) => {
-                        formatted_message = self.format_message(debug_message).to_string();
+                        formatted_message = self.format_message(debug_message, &run_state)?;
                         debug_log.as_mut()
                     }
                     GooseLog::Error(error_message) => {
-                        formatted_message = self.format_message(error_message).to_string();
+                        formatted_message = self.format_message(error_message, &run_state)?;
                         error_log.as_mut()
                     }
                     GooseLog::Request(request_message) => {
-                        formatted_message = self.format_message(request_message).to_string();
+                        formatted_message = self.format_message(request_message, &run_state)?;
                         request_log.as_mut()
                     }
                     GooseLog::Transaction(transaction_message) => {
-                        formatted_message = self.format_message(transaction_message).to_string();
+                        formatted_message = self.format_message(transaction_message, &run_state)?;
                         transaction_log.as_mut()
                     }
                     GooseLog::Scenario(scenario_message) => {
-                        formatted_message = self.format_message(scenario_message).to_string();
+                        formatted_message = self.format_message(scenario_message, &run_state)?;
                         scenario_log.as_mut()
                     }
+                    // Nothing is logged, the number of running users is tracked for
+                    // JTL-formatted request logs.
+                    GooseLog::RunningUsers(running_users) => {
+                        run_state.running_users = running_users;
+                        continue;
+                    }
                 } {
                     // This will generate a warning if it fails to write to log file.
                     self.write_to_log_file(log_file, formatted_message).await;
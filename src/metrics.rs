@@ -10,16 +10,18 @@
 
 use crate::config::GooseDefaults;
 use crate::goose::{get_base_url, GooseMethod, Scenario};
+use crate::junit;
+use crate::locust;
 use crate::logger::GooseLog;
 use crate::report;
-use crate::test_plan::{TestPlanHistory, TestPlanStepAction};
+use crate::test_plan::{TestPlanHistory, TestPlanRamp, TestPlanStepAction};
 use crate::util;
 use crate::{GooseAttack, GooseAttackRunState, GooseConfiguration, GooseError};
 use chrono::prelude::*;
 use http::StatusCode;
 use itertools::Itertools;
 use num_format::{Locale, ToFormattedString};
-use regex::RegexSet;
+use regex::{Regex, RegexSet};
 use serde::ser::SerializeStruct;
 use serde::{Deserialize, Serialize, Serializer};
 use std::cmp::Ordering;
@@ -27,7 +29,6 @@ use std::collections::{BTreeMap, HashMap, HashSet};
 use std::fmt::Write;
 use std::str::FromStr;
 use std::{f32, fmt};
-use tokio::io::AsyncWriteExt;
 
 /// Used to send metrics from [`GooseUser`](../goose/struct.GooseUser.html) threads
 /// to the parent Goose process.
@@ -47,6 +48,7 @@ pub enum GooseMetric {
     Request(GooseRequestMetric),
     Transaction(TransactionMetric),
     Scenario(ScenarioMetric),
+    Custom(GooseCustomMetric),
 }
 
 /// THIS IS AN EXPERIMENTAL FEATURE, DISABLED BY DEFAULT. Optionally mitigate the loss of data
@@ -353,6 +355,30 @@ pub struct GooseRequestMetric {
     /// [`Transaction`](../goose/struct.Transaction.html)s by this
     /// [`GooseUser`](../goose/struct.GooseUser.html) thread.
     pub user_cadence: u64,
+    /// How many bytes were in the body of the request.
+    #[serde(default)]
+    pub request_bytes: u64,
+    /// How many bytes were in the body of the response, after it was decompressed.
+    #[serde(default)]
+    pub response_bytes: u64,
+    /// How many bytes were in the body of the response as it was received, before it was
+    /// decompressed. The same as `response_bytes` if the response wasn't compressed.
+    #[serde(default)]
+    pub compressed_bytes: u64,
+    /// How many milliseconds it took to resolve the host name, if the request opened a new
+    /// connection.
+    #[serde(default)]
+    pub dns_time: u64,
+    /// How many milliseconds it took to download the body of the response after the headers
+    /// were received, not included in `response_time`.
+    #[serde(default)]
+    pub download_time: u64,
+    /// Whether or not the request was made on a connection reused from the connection pool.
+    #[serde(default)]
+    pub connection_reused: bool,
+    /// When the request was made, in milliseconds since the Unix epoch.
+    #[serde(default)]
+    pub timestamp: u64,
 }
 impl GooseRequestMetric {
     pub(crate) fn new(raw: GooseRawRequest, name: &str, elapsed: u128, user: usize) -> Self {
@@ -370,6 +396,16 @@ impl GooseRequestMetric {
             error: "".to_string(),
             coordinated_omission_elapsed: 0,
             user_cadence: 0,
+            request_bytes: 0,
+            response_bytes: 0,
+            compressed_bytes: 0,
+            dns_time: 0,
+            download_time: 0,
+            connection_reused: false,
+            timestamp: std::time::SystemTime::now()
+                .duration_since(std::time::UNIX_EPOCH)
+                .map(|duration| duration.as_millis() as u64)
+                .unwrap_or_default(),
         }
     }
 
@@ -424,6 +460,44 @@ pub struct GooseRequestMetricAggregate {
     ///
     /// A count of how many requests resulted in a non-2xx status code.
     pub fail_count: usize,
+    /// Histogram of the response times of requests that failed, also included in
+    /// `raw_data`. Failed requests frustrate users regardless of how long they took, so
+    /// they are removed from the satisfied and tolerated responses of the Apdex score.
+    #[serde(default)]
+    pub fail_times: GooseHistogram,
+    /// Total number of bytes sent in the bodies of this path-method request.
+    #[serde(default)]
+    pub request_bytes: usize,
+    /// Total number of bytes received in the bodies of responses to this path-method request,
+    /// after they were decompressed.
+    #[serde(default)]
+    pub response_bytes: usize,
+    /// Total number of bytes received in the bodies of responses to this path-method request,
+    /// before they were decompressed.
+    #[serde(default)]
+    pub compressed_bytes: usize,
+    /// Total number of responses to this path-method request received on a new connection.
+    #[serde(default)]
+    pub new_connections: usize,
+    /// Total number of responses to this path-method request received on a connection reused
+    /// from the connection pool.
+    #[serde(default)]
+    pub reused_connections: usize,
+    /// Total milliseconds spent resolving host names when opening new connections for this
+    /// path-method request.
+    #[serde(default)]
+    pub dns_time: usize,
+    /// Total milliseconds spent downloading the bodies of responses to this path-method request.
+    #[serde(default)]
+    pub download_time: usize,
+    /// Total milliseconds until the response headers were received for responses to this
+    /// path-method request received on a new connection.
+    #[serde(default)]
+    pub new_connection_time: usize,
+    /// Total milliseconds until the response headers were received for responses to this
+    /// path-method request received on a reused connection.
+    #[serde(default)]
+    pub reused_connection_time: usize,
     /// Load test hash.
     ///
     /// The hash is primarily used when running a distributed Gaggle, allowing the Manager to confirm
@@ -432,16 +506,31 @@ pub struct GooseRequestMetricAggregate {
 }
 impl GooseRequestMetricAggregate {
     /// Create a new GooseRequestMetricAggregate object.
-    pub(crate) fn new(path: &str, method: GooseMethod, load_test_hash: u64) -> Self {
+    pub(crate) fn new(
+        path: &str,
+        method: GooseMethod,
+        load_test_hash: u64,
+        significant_digits: usize,
+    ) -> Self {
         trace!("new request");
         GooseRequestMetricAggregate {
             path: path.to_string(),
             method,
-            raw_data: GooseRequestMetricTimingData::new(None),
+            raw_data: GooseRequestMetricTimingData::new(significant_digits),
             coordinated_omission_data: None,
             status_code_counts: HashMap::new(),
             success_count: 0,
             fail_count: 0,
+            fail_times: GooseHistogram::new(significant_digits),
+            request_bytes: 0,
+            response_bytes: 0,
+            compressed_bytes: 0,
+            new_connections: 0,
+            reused_connections: 0,
+            dns_time: 0,
+            download_time: 0,
+            new_connection_time: 0,
+            reused_connection_time: 0,
             load_test_hash,
         }
     }
@@ -484,6 +573,23 @@ impl GooseRequestMetricAggregate {
         self.status_code_counts.insert(status_code, counter);
         debug!("incremented {} counter: {}", status_code, counter);
     }
+
+    /// Record the connection used for a request and how long its phases took.
+    pub(crate) fn record_connection(&mut self, request_metric: &GooseRequestMetric) {
+        // No connection information is available if no response was received.
+        if request_metric.status_code == 0 {
+            return;
+        }
+        if request_metric.connection_reused {
+            self.reused_connections += 1;
+            self.reused_connection_time += request_metric.response_time as usize;
+        } else {
+            self.new_connections += 1;
+            self.dns_time += request_metric.dns_time as usize;
+            self.new_connection_time += request_metric.response_time as usize;
+        }
+        self.download_time += request_metric.download_time as usize;
+    }
 }
 
 /// Implement equality for GooseRequestMetricAggregate. We can't simply derive since
@@ -508,16 +614,693 @@ impl PartialOrd for GooseRequestMetricAggregate {
     }
 }
 
-/// Collects per-request timing metrics.
+/// The default number of significant digits preserved by a [`GooseHistogram`].
+pub const DEFAULT_SIGNIFICANT_DIGITS: usize = 3;
+
+/// A histogram of response or run times, in milliseconds, following the design of an
+/// [HDR Histogram](http://hdrhistogram.org/).
+///
+/// Times are counted in buckets that grow with the magnitude of the time, so each time
+/// is recorded with a fixed number of significant digits: with the default of 3
+/// significant digits all times up to 2,047 milliseconds are recorded exactly, and larger
+/// times are recorded within 0.1% of their actual value. This bounds the memory required
+/// regardless of how many different times are recorded, and histograms with the same
+/// precision are merged by simply adding together the counters of each bucket.
 #[derive(Debug, Clone, Eq, PartialEq, Serialize, Deserialize)]
+pub struct GooseHistogram {
+    /// How many significant digits of each time are preserved.
+    significant_digits: usize,
+    /// Per-bucket counters, keyed by the time all times in the bucket are counted as.
+    counts: BTreeMap<usize, usize>,
+}
+impl Default for GooseHistogram {
+    fn default() -> Self {
+        GooseHistogram::new(DEFAULT_SIGNIFICANT_DIGITS)
+    }
+}
+impl GooseHistogram {
+    /// Create a new empty histogram, preserving from 1 to 5 significant digits.
+    pub fn new(significant_digits: usize) -> Self {
+        GooseHistogram {
+            significant_digits: significant_digits.clamp(1, 5),
+            counts: BTreeMap::new(),
+        }
+    }
+
+    /// How many significant digits of each time are preserved.
+    pub fn significant_digits(&self) -> usize {
+        self.significant_digits
+    }
+
+    /// Returns the time that all times in the same bucket as `value` are counted as.
+    pub fn bucket(&self, value: usize) -> usize {
+        // All times up to 2 * 10^significant_digits are tracked with single unit resolution,
+        // larger times are right-shifted so the same number of bits are preserved.
+        let largest_exact = 2 * 10_usize.pow(self.significant_digits as u32);
+        let preserved_bits = usize::BITS - (largest_exact - 1).leading_zeros();
+        let bits = usize::BITS - value.leading_zeros();
+        if bits <= preserved_bits {
+            value
+        } else {
+            let shift = bits - preserved_bits;
+            // Count times as the middle of their bucket.
+            ((value >> shift) << shift) + (1 << (shift - 1))
+        }
+    }
+
+    /// Record a time.
+    pub fn record(&mut self, value: usize) {
+        self.record_n(value, 1);
+    }
+
+    /// Record the same time `count` times.
+    pub fn record_n(&mut self, value: usize, count: usize) {
+        let bucket = self.bucket(value);
+        *self.counts.entry(bucket).or_insert(0) += count;
+    }
+
+    /// Merge another histogram into this one.
+    pub fn merge(&mut self, other: &GooseHistogram) {
+        // An empty histogram takes on the precision of the histogram merged into it.
+        if self.counts.is_empty() {
+            self.significant_digits = other.significant_digits;
+        }
+        if self.significant_digits == other.significant_digits {
+            for (value, count) in &other.counts {
+                *self.counts.entry(*value).or_insert(0) += count;
+            }
+        } else {
+            for (value, count) in &other.counts {
+                self.record_n(*value, *count);
+            }
+        }
+    }
+
+    /// Remove the times recorded in an earlier copy of this histogram.
+    pub(crate) fn subtract(&mut self, other: &GooseHistogram) {
+        for (value, count) in &other.counts {
+            let bucket = self.bucket(*value);
+            if let Some(counter) = self.counts.get_mut(&bucket) {
+                *counter = counter.saturating_sub(*count);
+            }
+        }
+        self.counts.retain(|_, count| *count > 0);
+    }
+
+    /// How many times were recorded in the same bucket as `value`.
+    pub fn count(&self, value: usize) -> usize {
+        self.counts.get(&self.bucket(value)).copied().unwrap_or(0)
+    }
+
+    /// How many times were recorded in the same bucket as `value` or in a faster bucket.
+    pub fn count_up_to(&self, value: usize) -> usize {
+        self.counts
+            .range(..=self.bucket(value))
+            .map(|(_, count)| count)
+            .sum()
+    }
+
+    /// How many buckets have times recorded in them.
+    pub fn len(&self) -> usize {
+        self.counts.len()
+    }
+
+    /// Whether or not any times were recorded.
+    pub fn is_empty(&self) -> bool {
+        self.counts.is_empty()
+    }
+
+    /// The per-bucket counters, keyed by the time all times in the bucket are counted as.
+    pub fn counts(&self) -> &BTreeMap<usize, usize> {
+        &self.counts
+    }
+
+    /// Iterate over each bucket in order, returning the time all times in the bucket are
+    /// counted as, and how many times were recorded.
+    pub fn iter(&self) -> std::collections::btree_map::Iter<'_, usize, usize> {
+        self.counts.iter()
+    }
+}
+impl<'a> IntoIterator for &'a GooseHistogram {
+    type Item = (&'a usize, &'a usize);
+    type IntoIter = std::collections::btree_map::Iter<'a, usize, usize>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.counts.iter()
+    }
+}
+
+/// The response time percentiles displayed by default.
+pub const DEFAULT_PERCENTILES: [f32; 6] = [50.0, 75.0, 98.0, 99.0, 99.9, 99.99];
+
+/// A list of response time percentiles to calculate, each a percent of requests from
+/// greater than 0 up to 100.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct GoosePercentiles {
+    pub percentiles: Vec<f32>,
+}
+impl Default for GoosePercentiles {
+    fn default() -> Self {
+        GoosePercentiles {
+            percentiles: DEFAULT_PERCENTILES.to_vec(),
+        }
+    }
+}
+/// Implement [`FromStr`] to convert a `"50,90,99,99.9"` comma separated string to a list
+/// of percentiles.
+impl FromStr for GoosePercentiles {
+    type Err = GooseError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut percentiles = Vec::new();
+        for percentile in s.split(',').map(str::trim).filter(|p| !p.is_empty()) {
+            match percentile.parse::<f32>() {
+                Ok(p) if p > 0.0 && p <= 100.0 => percentiles.push(p),
+                _ => {
+                    // Logger isn't initialized yet, provide helpful debug output.
+                    eprintln!(
+                        "ERROR: invalid `configuration.percentiles` value: '{}'",
+                        percentile
+                    );
+                    eprintln!("  Expected format: --percentiles \"{{percentile}},{{percentile}}\"");
+                    eprintln!(
+                        "    each {{percentile}} is greater than 0 and up to 100, ie \"99.9\""
+                    );
+                    return Err(GooseError::InvalidOption {
+                        option: "`configuration.percentiles`".to_string(),
+                        value: percentile.to_string(),
+                        detail: "invalid percentile, expected a number greater than 0 and up to 100, ie \"99.9\"".to_string(),
+                    });
+                }
+            }
+        }
+        if percentiles.is_empty() {
+            return Err(GooseError::InvalidOption {
+                option: "`configuration.percentiles`".to_string(),
+                value: s.to_string(),
+                detail: "at least one percentile is required, ie \"50,90,99\"".to_string(),
+            });
+        }
+        Ok(GoosePercentiles { percentiles })
+    }
+}
+impl fmt::Display for GoosePercentiles {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.percentiles.iter().join(","))
+    }
+}
+
+/// The response time within which a percent of requests completed.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct GoosePercentile {
+    /// The percent of requests, from greater than 0 up to 100.
+    pub percentile: f32,
+    /// The slowest response time of this percent of requests, in milliseconds.
+    pub response_time: usize,
+}
+
+/// How many bytes were sent and received by a request.
+///
+/// Only the bodies of requests and responses are counted, responses after they were
+/// decompressed unless otherwise noted.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct GooseBandwidth {
+    /// Total number of bytes sent.
+    pub request_bytes: usize,
+    /// Total number of bytes received.
+    pub response_bytes: usize,
+    /// Total number of bytes received, before responses were decompressed.
+    pub compressed_bytes: usize,
+    /// Average number of bytes sent per second.
+    pub request_bytes_per_second: f32,
+    /// Average number of bytes received per second.
+    pub response_bytes_per_second: f32,
+}
+impl GooseBandwidth {
+    /// Calculate the bandwidth of requests that sent and received the given number of bytes
+    /// during a load test that ran for `duration` seconds.
+    fn new(
+        request_bytes: usize,
+        response_bytes: usize,
+        compressed_bytes: usize,
+        duration: usize,
+    ) -> Self {
+        let (request_bytes_per_second, response_bytes_per_second) =
+            per_second_calculations(duration, request_bytes, response_bytes);
+        GooseBandwidth {
+            request_bytes,
+            response_bytes,
+            compressed_bytes,
+            request_bytes_per_second,
+            response_bytes_per_second,
+        }
+    }
+}
+
+/// Where the time of a request was spent, on average.
+///
+/// Requests made on a reused connection don't have to connect, so the difference between
+/// `connect_time` and `time_to_first_byte` is the time spent establishing new connections.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct GooseTimingBreakdown {
+    /// Number of responses received on a new connection.
+    pub new_connections: usize,
+    /// Number of responses received on a connection reused from the connection pool.
+    pub reused_connections: usize,
+    /// Percentage of responses received on a reused connection.
+    pub connection_reuse: f32,
+    /// Average milliseconds spent resolving the host name when opening a new connection.
+    pub dns_time: f32,
+    /// Average milliseconds from resolving the host name until the response headers were
+    /// received on a new connection, including the TCP connect and TLS handshake.
+    pub connect_time: f32,
+    /// Average milliseconds until the response headers were received on a reused connection.
+    pub time_to_first_byte: f32,
+    /// Average milliseconds spent downloading the response body.
+    pub download_time: f32,
+}
+impl GooseTimingBreakdown {
+    /// Calculate the timing breakdown of one or more requests combined.
+    fn new<'a>(requests: impl Iterator<Item = &'a GooseRequestMetricAggregate>) -> Self {
+        let (mut new_connections, mut reused_connections, mut dns_time, mut download_time) =
+            (0, 0, 0, 0);
+        let (mut new_connection_time, mut reused_connection_time) = (0, 0);
+        for request in requests {
+            new_connections += request.new_connections;
+            reused_connections += request.reused_connections;
+            dns_time += request.dns_time;
+            download_time += request.download_time;
+            new_connection_time += request.new_connection_time;
+            reused_connection_time += request.reused_connection_time;
+        }
+        let average = |total: usize, count: usize| {
+            if count > 0 {
+                total as f32 / count as f32
+            } else {
+                0.0
+            }
+        };
+        let connections = new_connections + reused_connections;
+        GooseTimingBreakdown {
+            new_connections,
+            reused_connections,
+            connection_reuse: average(reused_connections * 100, connections),
+            dns_time: average(dns_time, new_connections),
+            connect_time: average(
+                new_connection_time.saturating_sub(dns_time),
+                new_connections,
+            ),
+            time_to_first_byte: average(reused_connection_time, reused_connections),
+            download_time: average(download_time, connections),
+        }
+    }
+}
+
+/// An Apdex target response time, in milliseconds.
+///
+/// Targets are written as `[{request}:]{T}`, for example `500` sets a target of 500
+/// milliseconds for all requests, while `GET /:100` sets a target of 100 milliseconds for
+/// `GET /` requests only.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct GooseApdexTarget {
+    /// The request this target applies to, either by `{method} {name}` (ie `GET /`) or by
+    /// name (ie `/`). If not set, the target applies to all other requests.
+    pub request: Option<String>,
+    /// Responses up to this time satisfy users, and up to four times this time are
+    /// tolerated.
+    pub target: usize,
+}
+impl fmt::Display for GooseApdexTarget {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        if let Some(request) = self.request.as_ref() {
+            write!(f, "{}:", request)?;
+        }
+        write!(f, "{}", self.target)
+    }
+}
+impl FromStr for GooseApdexTarget {
+    type Err = GooseError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        // A request name can include ":" so match the target after the last ":", white space
+        // and an optional unit are ignored.
+        let re = Regex::new(r"^\s*((.+):)?\s*(\d+)\s*(ms)?\s*$").unwrap();
+        match re.captures(s) {
+            Some(cap) if &cap[3] != "0" => Ok(GooseApdexTarget {
+                request: cap
+                    .get(2)
+                    .map(|request| request.as_str().trim().to_string()),
+                target: cap[3]
+                    .parse::<usize>()
+                    .map_err(|_| GooseError::InvalidOption {
+                        option: "`configuration.apdex`".to_string(),
+                        value: s.to_string(),
+                        detail: "invalid target, too large".to_string(),
+                    })?,
+            }),
+            _ => Err(GooseError::InvalidOption {
+                option: "`configuration.apdex`".to_string(),
+                value: s.to_string(),
+                detail: "invalid target, expected format: \"{request}:{T}\", ie \"GET /:500\""
+                    .to_string(),
+            }),
+        }
+    }
+}
+
+/// A list of Apdex target response times, used to score how satisfied users are with the
+/// response times of each request.
+#[derive(Default, Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct GooseApdex {
+    pub targets: Vec<GooseApdexTarget>,
+}
+impl GooseApdex {
+    /// Returns the target for a request, preferring a target for its `{method} {name}`,
+    /// then a target for its name, and finally a target for all requests.
+    pub fn target(
+        &self,
+        request_key: &str,
+        request: &GooseRequestMetricAggregate,
+    ) -> Option<usize> {
+        let find = |request: Option<&str>| {
+            self.targets
+                .iter()
+                .find(|target| target.request.as_deref() == request)
+                .map(|target| target.target)
+        };
+        find(Some(request_key))
+            .or_else(|| find(Some(&request.path)))
+            .or_else(|| find(None))
+    }
+}
+/// Implement [`FromStr`] to convert a `"500;GET /:100"` semicolon separated string to a
+/// list of Apdex targets.
+impl FromStr for GooseApdex {
+    type Err = GooseError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut targets = Vec::new();
+        for line in s.split(';').filter(|line| !line.trim().is_empty()) {
+            match line.parse::<GooseApdexTarget>() {
+                Ok(target) => targets.push(target),
+                Err(e) => {
+                    // Logger isn't initialized yet, provide helpful debug output.
+                    eprintln!("ERROR: invalid `configuration.apdex` value: '{}'", line);
+                    eprintln!("  Expected format: --apdex \"{{target}};{{target}}\"");
+                    eprintln!("    each {{target}} is a response time in milliseconds greater than 0, ie \"500\"");
+                    eprintln!("    prefix with \"{{request}}:\" to only apply to one request, ie \"GET /:100\"");
+                    return Err(e);
+                }
+            }
+        }
+        if targets.is_empty() {
+            return Err(GooseError::InvalidOption {
+                option: "`configuration.apdex`".to_string(),
+                value: s.to_string(),
+                detail: "at least one target is required, ie \"500\"".to_string(),
+            });
+        }
+        Ok(GooseApdex { targets })
+    }
+}
+impl fmt::Display for GooseApdex {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.targets.iter().join(";"))
+    }
+}
+
+/// The [Apdex](https://en.wikipedia.org/wiki/Apdex) score of a request, from 0 when all
+/// users were frustrated up to 1 when all users were satisfied.
+///
+/// Responses up to the target time `T` are satisfying, up to `4T` are tolerated, and
+/// slower responses are frustrating. Failed requests are always frustrating.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct GooseApdexScore {
+    /// The target response time in milliseconds, or `None` when requests with different
+    /// targets were scored together.
+    pub target: Option<usize>,
+    /// Number of responses up to the target time.
+    pub satisfied: usize,
+    /// Number of responses slower than the target time, up to four times the target time.
+    pub tolerating: usize,
+    /// Number of responses slower than four times the target time.
+    pub frustrated: usize,
+    /// The Apdex score, `(satisfied + tolerating / 2) / total`.
+    pub score: f32,
+}
+impl GooseApdexScore {
+    /// Score the response times of a request against a target time, counting failed
+    /// requests as frustrated.
+    fn new(request: &GooseRequestMetricAggregate, target: usize) -> Self {
+        let count_up_to = |time: usize| {
+            request
+                .raw_data
+                .times
+                .count_up_to(time)
+                .saturating_sub(request.fail_times.count_up_to(time))
+        };
+        let satisfied = count_up_to(target);
+        let tolerating = count_up_to(target.saturating_mul(4)).saturating_sub(satisfied);
+        GooseApdexScore::from_counts(
+            Some(target),
+            satisfied,
+            tolerating,
+            request
+                .raw_data
+                .counter
+                .saturating_sub(satisfied + tolerating),
+        )
+    }
+
+    /// Calculate the score of already counted responses.
+    fn from_counts(
+        target: Option<usize>,
+        satisfied: usize,
+        tolerating: usize,
+        frustrated: usize,
+    ) -> Self {
+        let total = satisfied + tolerating + frustrated;
+        let score = if total > 0 {
+            (satisfied as f32 + tolerating as f32 / 2.0) / total as f32
+        } else {
+            0.0
+        };
+        GooseApdexScore {
+            target,
+            satisfied,
+            tolerating,
+            frustrated,
+            score,
+        }
+    }
+}
+
+/// The protocols supported by a [`GooseMetricsSink`].
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub enum GooseMetricsSinkProtocol {
+    /// StatsD metrics sent over UDP, configured with `statsd://HOST[:PORT]`.
+    Statsd,
+    /// InfluxDB line protocol sent over TCP, configured with `influx://HOST[:PORT]`.
+    InfluxTcp,
+    /// InfluxDB line protocol posted to an HTTP write endpoint, configured with
+    /// `http://HOST:PORT/PATH?QUERY` or `https://HOST:PORT/PATH?QUERY`.
+    InfluxHttp,
+}
+
+/// Where aggregated metrics are periodically pushed while a load test is running.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct GooseMetricsSink {
+    /// How metrics are sent to the sink.
+    pub protocol: GooseMetricsSinkProtocol,
+    /// The `HOST:PORT` metrics are sent to, or the full URL when posting over HTTP.
+    pub address: String,
+}
+impl FromStr for GooseMetricsSink {
+    type Err = GooseError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let invalid = |detail: &str| {
+            // Logger isn't initialized yet, provide helpful debug output.
+            eprintln!("ERROR: invalid `configuration.metrics_sink` value: '{}'", s);
+            eprintln!("  Expected format: --metrics-sink \"{{scheme}}://{{host}}[:{{port}}]\"");
+            eprintln!("    \"statsd://127.0.0.1:8125\" sends StatsD metrics over UDP");
+            eprintln!("    \"influx://127.0.0.1:8094\" sends InfluxDB line protocol over TCP");
+            eprintln!("    \"http://127.0.0.1:8086/write?db=goose\" posts InfluxDB line protocol");
+            GooseError::InvalidOption {
+                option: "`configuration.metrics_sink`".to_string(),
+                value: s.to_string(),
+                detail: detail.to_string(),
+            }
+        };
+        let url = url::Url::parse(s.trim()).map_err(|e| invalid(&e.to_string()))?;
+        let host = match url.host_str() {
+            Some(host) if !host.is_empty() => host,
+            _ => return Err(invalid("a host is required")),
+        };
+        let (protocol, default_port) = match url.scheme() {
+            "statsd" => (GooseMetricsSinkProtocol::Statsd, 8125),
+            "influx" => (GooseMetricsSinkProtocol::InfluxTcp, 8094),
+            "http" | "https" => {
+                return Ok(GooseMetricsSink {
+                    protocol: GooseMetricsSinkProtocol::InfluxHttp,
+                    address: url.to_string(),
+                })
+            }
+            scheme => {
+                return Err(invalid(&format!(
+                    "unsupported scheme '{}', expected statsd, influx, http or https",
+                    scheme
+                )))
+            }
+        };
+        Ok(GooseMetricsSink {
+            protocol,
+            address: format!("{}:{}", host, url.port().unwrap_or(default_port)),
+        })
+    }
+}
+impl fmt::Display for GooseMetricsSink {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self.protocol {
+            GooseMetricsSinkProtocol::Statsd => write!(f, "statsd://{}", self.address),
+            GooseMetricsSinkProtocol::InfluxTcp => write!(f, "influx://{}", self.address),
+            GooseMetricsSinkProtocol::InfluxHttp => write!(f, "{}", self.address),
+        }
+    }
+}
+
+/// The value of a custom metric recorded by a load test.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub enum GooseCustomMetricValue {
+    /// An amount added to a counter, recorded with
+    /// [`GooseUser::record_counter`](../goose/struct.GooseUser.html#method.record_counter).
+    Counter(u64),
+    /// The current value of a gauge, recorded with
+    /// [`GooseUser::record_gauge`](../goose/struct.GooseUser.html#method.record_gauge).
+    Gauge(f64),
+    /// How many milliseconds something took, recorded with
+    /// [`GooseUser::record_timing`](../goose/struct.GooseUser.html#method.record_timing).
+    Timing(u64),
+}
+
+/// A custom metric recorded by a load test, such as how many items are in a shopping cart
+/// or how long a call to a non-HTTP service took.
+///
+/// [`GooseUser`](../goose/struct.GooseUser.html) threads send these metrics to the parent
+/// process where they are aggregated by name into [`GooseMetrics::custom`].
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct GooseCustomMetric {
+    /// How many milliseconds the load test has been running.
+    pub elapsed: u64,
+    /// The name of the custom metric.
+    pub name: String,
+    /// The value recorded.
+    pub value: GooseCustomMetricValue,
+}
+
+/// A custom counter, gauge or timing aggregated from all [`GooseCustomMetric`]s recorded
+/// with the same name.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub enum GooseCustomMetricAggregate {
+    /// A counter, totaling all amounts added to it.
+    Counter(GooseCustomCounter),
+    /// A gauge, tracking the average, smallest and largest values it was set to.
+    Gauge(GooseCustomGauge),
+    /// A histogram of recorded times.
+    Timing(GooseRequestMetricTimingData),
+}
+impl GooseCustomMetricAggregate {
+    /// Create a new aggregate of the same kind as `value`.
+    pub(crate) fn new(value: &GooseCustomMetricValue, significant_digits: usize) -> Self {
+        match value {
+            GooseCustomMetricValue::Counter(_) => {
+                GooseCustomMetricAggregate::Counter(GooseCustomCounter::default())
+            }
+            GooseCustomMetricValue::Gauge(_) => {
+                GooseCustomMetricAggregate::Gauge(GooseCustomGauge::default())
+            }
+            GooseCustomMetricValue::Timing(_) => GooseCustomMetricAggregate::Timing(
+                GooseRequestMetricTimingData::new(significant_digits),
+            ),
+        }
+    }
+
+    /// Record a new value, returning false if it's not the same kind of metric.
+    pub(crate) fn record(&mut self, value: &GooseCustomMetricValue) -> bool {
+        match (self, value) {
+            (GooseCustomMetricAggregate::Counter(counter), GooseCustomMetricValue::Counter(v)) => {
+                counter.count += 1;
+                counter.total += v;
+            }
+            (GooseCustomMetricAggregate::Gauge(gauge), GooseCustomMetricValue::Gauge(v)) => {
+                if gauge.count == 0 || *v < gauge.minimum {
+                    gauge.minimum = *v;
+                }
+                if gauge.count == 0 || *v > gauge.maximum {
+                    gauge.maximum = *v;
+                }
+                gauge.count += 1;
+                gauge.total += v;
+            }
+            (GooseCustomMetricAggregate::Timing(timing), GooseCustomMetricValue::Timing(v)) => {
+                timing.record_time(*v);
+            }
+            _ => return false,
+        }
+        true
+    }
+
+    /// The kind of custom metric, as displayed in the metrics.
+    pub fn kind(&self) -> &'static str {
+        match self {
+            GooseCustomMetricAggregate::Counter(_) => "counter",
+            GooseCustomMetricAggregate::Gauge(_) => "gauge",
+            GooseCustomMetricAggregate::Timing(_) => "timing",
+        }
+    }
+}
+
+/// A custom counter, aggregated from all amounts added to it.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct GooseCustomCounter {
+    /// How many times an amount was added to the counter.
+    pub count: usize,
+    /// The total of all amounts added to the counter.
+    pub total: u64,
+}
+
+/// A custom gauge, aggregated from all values it was set to.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct GooseCustomGauge {
+    /// How many times the gauge was set.
+    pub count: usize,
+    /// The total of all values the gauge was set to, used to calculate the average.
+    pub total: f64,
+    /// The smallest value the gauge was set to.
+    pub minimum: f64,
+    /// The largest value the gauge was set to.
+    pub maximum: f64,
+}
+impl GooseCustomGauge {
+    /// The average value the gauge was set to.
+    pub fn average(&self) -> f64 {
+        if self.count > 0 {
+            self.total / self.count as f64
+        } else {
+            0.0
+        }
+    }
+}
+
+/// Collects per-request timing metrics.
+#[derive(Debug, Clone, Default, Eq, PartialEq, Serialize, Deserialize)]
 pub struct GooseRequestMetricTimingData {
-    /// Per-response-time counters, tracking how often pages are returned with this response time.
+    /// Histogram of response times, tracking how often pages are returned with each response time.
     ///
-    /// All response times between 1 and 100ms are stored without any rounding. Response times between
-    /// 100 and 500ms are rounded to the nearest 10ms and then stored. Response times betwee 500 and
-    /// 1000ms are rounded to the nearest 100ms. Response times larger than 1000ms are rounded to the
-    /// nearest 1000ms.
-    pub times: BTreeMap<usize, usize>,
+    /// Response times are recorded with the number of significant digits configured with
+    /// `--significant-digits`, by default all response times up to 2,047ms are stored exactly
+    /// and larger response times are stored within 0.1% of their actual value.
+    pub times: GooseHistogram,
     /// The shortest response time seen so far.
     ///
     /// For example a `min_response_time` of `3` means the quickest response for this method-path
@@ -538,21 +1321,15 @@ pub struct GooseRequestMetricTimingData {
     pub counter: usize,
 }
 impl GooseRequestMetricTimingData {
-    /// Create a new GooseRequestMetricAggregate object.
-    pub(crate) fn new(metric_data: Option<&GooseRequestMetricTimingData>) -> Self {
+    /// Create a new GooseRequestMetricTimingData object.
+    pub(crate) fn new(significant_digits: usize) -> Self {
         trace!("new GooseRequestMetricTimingData");
-        // Simply clone the exiting metric_data.
-        if let Some(data) = metric_data {
-            data.clone()
-        // Create a new empty metric_data.
-        } else {
-            GooseRequestMetricTimingData {
-                times: BTreeMap::new(),
-                minimum_time: 0,
-                maximum_time: 0,
-                total_time: 0,
-                counter: 0,
-            }
+        GooseRequestMetricTimingData {
+            times: GooseHistogram::new(significant_digits),
+            minimum_time: 0,
+            maximum_time: 0,
+            total_time: 0,
+            counter: 0,
         }
     }
 
@@ -577,39 +1354,35 @@ impl GooseRequestMetricTimingData {
         // Each time we store a new time, increment counter by one.
         self.counter += 1;
 
-        // Round the time so we can combine similar times together and
+        // Add the time to the histogram, which combines similar times together to
         // minimize required memory to store and push upstream to the parent.
-        // No rounding for 1-100ms times.
-        let rounded_time = if time_elapsed < 100 {
-            time
-        }
-        // Round to nearest 10 for 100-500ms times.
-        else if time_elapsed < 500 {
-            ((time_elapsed as f64 / 10.0).round() * 10.0) as usize
-        }
-        // Round to nearest 100 for 500-1000ms times.
-        else if time_elapsed < 1000 {
-            ((time_elapsed as f64 / 100.0).round() * 100.0) as usize
-        }
-        // Round to nearest 1000 for all larger times.
-        else {
-            ((time_elapsed as f64 / 1000.0).round() * 1000.0) as usize
-        };
+        self.times.record(time);
+    }
 
-        let counter = match self.times.get(&rounded_time) {
-            // We've seen this elapsed time before, increment counter.
-            Some(c) => {
-                debug!("got {:?} counter: {}", rounded_time, c);
-                *c + 1
-            }
-            // First time we've seen this elapsed time, initialize counter.
-            None => {
-                debug!("no match for counter: {}", rounded_time);
-                1
-            }
-        };
-        debug!("incremented {} counter: {}", rounded_time, counter);
-        self.times.insert(rounded_time, counter);
+    /// Merge the times of another request into this one.
+    pub(crate) fn merge(&mut self, other: &GooseRequestMetricTimingData) {
+        self.times.merge(&other.times);
+        self.minimum_time = update_min_time(self.minimum_time, other.minimum_time);
+        self.maximum_time = update_max_time(self.maximum_time, other.maximum_time);
+        self.total_time += other.total_time;
+        self.counter += other.counter;
+    }
+
+    /// Calculate the response time of each percentile.
+    pub fn percentiles(&self, percentiles: &[f32]) -> Vec<GoosePercentile> {
+        percentiles
+            .iter()
+            .map(|percentile| GoosePercentile {
+                percentile: *percentile,
+                response_time: response_time_percentile(
+                    &self.times,
+                    self.counter,
+                    self.minimum_time,
+                    self.maximum_time,
+                    percentile / 100.0,
+                ),
+            })
+            .collect()
     }
 }
 /// The per-scenario metrics collected each time a scenario is run.
@@ -707,8 +1480,8 @@ pub struct TransactionMetricAggregate {
     pub transaction_index: usize,
     /// An optional name for the transaction.
     pub transaction_name: String,
-    /// Per-run-time counters, tracking how often transactions take a given time to complete.
-    pub times: BTreeMap<usize, usize>,
+    /// Histogram of run-times, tracking how often transactions take a given time to complete.
+    pub times: GooseHistogram,
     /// The shortest run-time for this transaction.
     pub min_time: usize,
     /// The longest run-time for this transaction.
@@ -729,13 +1502,14 @@ impl TransactionMetricAggregate {
         scenario_name: &str,
         transaction_index: usize,
         transaction_name: &str,
+        significant_digits: usize,
     ) -> Self {
         TransactionMetricAggregate {
             scenario_index,
             scenario_name: scenario_name.to_string(),
             transaction_index,
             transaction_name: transaction_name.to_string(),
-            times: BTreeMap::new(),
+            times: GooseHistogram::new(significant_digits),
             min_time: 0,
             max_time: 0,
             total_time: 0,
@@ -772,27 +1546,9 @@ impl TransactionMetricAggregate {
             self.fail_count += 1;
         }
 
-        // Round the time so we can combine similar times together and
+        // Add the time to the histogram, which combines similar times together to
         // minimize required memory to store and push upstream to the parent.
-        let rounded_time = match time {
-            // No rounding for times 0-100 ms.
-            0..=100 => time_usize,
-            // Round to nearest 10 for times 100-500 ms.
-            101..=500 => ((time as f64 / 10.0).round() * 10.0) as usize,
-            // Round to nearest 100 for times 500-1000 ms.
-            501..=1000 => ((time as f64 / 100.0).round() * 10.0) as usize,
-            // Round to nearest 1000 for larger times.
-            _ => ((time as f64 / 1000.0).round() * 10.0) as usize,
-        };
-
-        let counter = match self.times.get(&rounded_time) {
-            // We've seen this time before, increment counter.
-            Some(c) => *c + 1,
-            // First time we've seen this time, initialize counter.
-            None => 1,
-        };
-        self.times.insert(rounded_time, counter);
-        debug!("incremented {} counter: {}", rounded_time, counter);
+        self.times.record(time_usize);
     }
 }
 /// Aggregated per-scenario metrics updated each time a scenario is run.
@@ -809,8 +1565,8 @@ pub struct ScenarioMetricAggregate {
     pub name: String,
     /// List of users running this scenario.
     pub users: HashSet<usize>,
-    /// Per-run-time counters, tracking how often scenario takes a given time to complete.
-    pub times: BTreeMap<usize, usize>,
+    /// Histogram of run-times, tracking how often scenario takes a given time to complete.
+    pub times: GooseHistogram,
     /// The shortest run-time for this scenario.
     pub min_time: usize,
     /// The longest run-time for this scenario.
@@ -822,12 +1578,12 @@ pub struct ScenarioMetricAggregate {
 }
 impl ScenarioMetricAggregate {
     /// Create a new ScenarioMetricAggregate.
-    pub(crate) fn new(index: usize, name: &str) -> Self {
+    pub(crate) fn new(index: usize, name: &str, significant_digits: usize) -> Self {
         ScenarioMetricAggregate {
             index,
             name: name.to_string(),
             users: HashSet::new(),
-            times: BTreeMap::new(),
+            times: GooseHistogram::new(significant_digits),
             min_time: 0,
             max_time: 0,
             total_time: 0,
@@ -859,48 +1615,159 @@ impl ScenarioMetricAggregate {
         // Each time we store a new time, increment counter by one.
         self.counter += 1;
 
-        // Round the time so we can combine similar times together and
+        // Add the time to the histogram, which combines similar times together to
         // minimize required memory to store and push upstream to the parent.
-        let rounded_time = match time {
-            // No rounding for times 0-100 ms.
-            0..=100 => time_usize,
-            // Round to nearest 10 for times 100-500 ms.
-            101..=500 => ((time as f64 / 10.0).round() * 10.0) as usize,
-            // Round to nearest 100 for times 500-1000 ms.
-            501..=1000 => ((time as f64 / 100.0).round() * 10.0) as usize,
-            // Round to nearest 1000 for larger times.
-            _ => ((time as f64 / 1000.0).round() * 10.0) as usize,
-        };
-
-        let counter = match self.times.get(&rounded_time) {
-            // We've seen this time before, increment counter.
-            Some(c) => *c + 1,
-            // First time we've seen this time, initialize counter.
-            None => 1,
-        };
-        self.times.insert(rounded_time, counter);
-        debug!("incremented {} counter: {}", rounded_time, counter);
+        self.times.record(time_usize);
     }
 }
-/// All metrics optionally collected during a Goose load test.
-///
-/// By default, Goose collects metrics during a load test in a `GooseMetrics` object
-/// that is returned by
-/// [`GooseAttack::execute()`](../struct.GooseAttack.html#method.execute) when a load
-/// test finishes.
-///
-/// # Example
-/// ```rust
-/// use goose::prelude::*;
+/// Request, transaction and scenario metrics collected during one step of the test plan.
 ///
-/// #[tokio::main]
-/// async fn main() -> Result<(), GooseError> {
-///     let goose_metrics: GooseMetrics = GooseAttack::initialize()?
-///         .register_scenario(scenario!("ExampleUsers")
-///             .register_transaction(transaction!(example_transaction))
-///         )
-///         // Set a default host so the load test will start.
-///         .set_default(GooseDefault::Host, "http://localhost/")?
+/// Metrics are recorded in the step that was running when the parent process received
+/// them, and are not reset after all users start.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct GoosePhaseMetrics {
+    /// An index into [`GooseMetrics::history`], indicating which test plan step this is.
+    pub step: usize,
+    /// What action happened during this test plan step.
+    pub action: TestPlanStepAction,
+    /// Request metrics collected during this test plan step.
+    pub requests: GooseRequestMetrics,
+    /// Transaction metrics collected during this test plan step.
+    pub transactions: TransactionMetrics,
+    /// Scenario metrics collected during this test plan step.
+    pub scenarios: ScenarioMetrics,
+}
+impl GoosePhaseMetrics {
+    /// Create empty metrics for a test plan step, tracking the same transactions and
+    /// scenarios as the load test.
+    pub(crate) fn new(
+        step: usize,
+        action: TestPlanStepAction,
+        transactions: &[Vec<TransactionMetricAggregate>],
+        scenarios: &[ScenarioMetricAggregate],
+    ) -> Self {
+        GoosePhaseMetrics {
+            step,
+            action,
+            requests: HashMap::new(),
+            transactions: transactions
+                .iter()
+                .map(|scenario| {
+                    scenario
+                        .iter()
+                        .map(|transaction| {
+                            TransactionMetricAggregate::new(
+                                transaction.scenario_index,
+                                &transaction.scenario_name,
+                                transaction.transaction_index,
+                                &transaction.transaction_name,
+                                transaction.times.significant_digits(),
+                            )
+                        })
+                        .collect()
+                })
+                .collect(),
+            scenarios: scenarios
+                .iter()
+                .map(|scenario| {
+                    ScenarioMetricAggregate::new(
+                        scenario.index,
+                        &scenario.name,
+                        scenario.times.significant_digits(),
+                    )
+                })
+                .collect(),
+        }
+    }
+
+    /// Summarize the requests made during this test plan step, which ran for `duration`
+    /// seconds, including all requests aggregated together if more than one request was made.
+    pub(crate) fn summarize(&self, duration: usize) -> Vec<GoosePhaseRequestSummary> {
+        let summary =
+            |name: &str, requests: usize, fails: usize, data: &GooseRequestMetricTimingData| {
+                GoosePhaseRequestSummary {
+                    name: name.to_string(),
+                    requests,
+                    fails,
+                    average: if data.counter > 0 {
+                        data.total_time as f32 / data.counter as f32
+                    } else {
+                        0.0
+                    },
+                    response_time_95: response_time_percentile(
+                        &data.times,
+                        data.counter,
+                        data.minimum_time,
+                        data.maximum_time,
+                        0.95,
+                    ),
+                    requests_per_second: per_second_calculations(duration, requests, fails).0,
+                }
+            };
+
+        let mut summaries = Vec::new();
+        let mut aggregate_data = GooseRequestMetricTimingData::default();
+        let mut aggregate_requests = 0;
+        let mut aggregate_fails = 0;
+        for (request_key, request) in self.requests.iter().sorted() {
+            let requests = request.success_count + request.fail_count;
+            summaries.push(summary(
+                request_key,
+                requests,
+                request.fail_count,
+                &request.raw_data,
+            ));
+            aggregate_data.merge(&request.raw_data);
+            aggregate_requests += requests;
+            aggregate_fails += request.fail_count;
+        }
+        if self.requests.len() > 1 {
+            summaries.push(summary(
+                "Aggregated",
+                aggregate_requests,
+                aggregate_fails,
+                &aggregate_data,
+            ));
+        }
+        summaries
+    }
+}
+
+/// A summary of one request made during a test plan step, displayed in the per phase metrics.
+pub(crate) struct GoosePhaseRequestSummary {
+    /// The method and name of the request, or `Aggregated`.
+    pub name: String,
+    /// How many requests were made.
+    pub requests: usize,
+    /// How many requests failed.
+    pub fails: usize,
+    /// The average response time, in milliseconds.
+    pub average: f32,
+    /// The 95th percentile response time, in milliseconds.
+    pub response_time_95: usize,
+    /// How many requests were made per second.
+    pub requests_per_second: f32,
+}
+
+/// All metrics optionally collected during a Goose load test.
+///
+/// By default, Goose collects metrics during a load test in a `GooseMetrics` object
+/// that is returned by
+/// [`GooseAttack::execute()`](../struct.GooseAttack.html#method.execute) when a load
+/// test finishes.
+///
+/// # Example
+/// ```rust
+/// use goose::prelude::*;
+///
+/// #[tokio::main]
+/// async fn main() -> Result<(), GooseError> {
+///     let goose_metrics: GooseMetrics = GooseAttack::initialize()?
+///         .register_scenario(scenario!("ExampleUsers")
+///             .register_transaction(transaction!(example_transaction))
+///         )
+///         // Set a default host so the load test will start.
+///         .set_default(GooseDefault::Host, "http://localhost/")?
 ///         // Set a default run time so this test runs to completion.
 ///         .set_default(GooseDefault::RunTime, 1)?
 ///         .execute()
@@ -1029,11 +1896,62 @@ pub struct GooseMetrics {
     pub errors: GooseErrorMetrics,
     /// Tracks all hosts that the load test is run against.
     pub hosts: HashSet<String>,
+    /// Tracks how many scenario iterations were scheduled, started and dropped when running
+    /// an arrival rate test plan.
+    ///
+    /// Only set when the load test is configured with the `--arrival-rate` run-time option, or
+    /// with [GooseDefault::ArrivalRate](../config/enum.GooseDefault.html#variant.ArrivalRate).
+    pub arrival_rate: Option<GooseArrivalRateMetrics>,
+    /// Tracks each load level probed by a breakpoint search, and the maximum sustainable
+    /// capacity that was found.
+    ///
+    /// Only set when the load test is configured with the `--breakpoint` run-time option, or
+    /// with [GooseDefault::Breakpoint](../config/enum.GooseDefault.html#variant.Breakpoint).
+    pub breakpoint: Option<GooseBreakpointMetrics>,
+    /// The result of each pass/fail threshold, evaluated when the load test finishes.
+    ///
+    /// Thresholds are configured with the `--thresholds` run-time option, or with
+    /// [GooseDefault::Thresholds](../config/enum.GooseDefault.html#variant.Thresholds).
+    pub thresholds: Vec<GooseThresholdResult>,
+    /// How each request compares to a previous load test, compared when the load test
+    /// finishes.
+    ///
+    /// The baseline is loaded with the `--baseline` run-time option, or with
+    /// [GooseDefault::Baseline](../config/enum.GooseDefault.html#variant.Baseline).
+    pub baseline: Vec<GooseBaselineDelta>,
+    /// Which response time percentiles are displayed and calculated.
+    ///
+    /// Percentiles are configured with the `--percentiles` run-time option, or with
+    /// [GooseDefault::Percentiles](../config/enum.GooseDefault.html#variant.Percentiles).
+    pub percentiles: GoosePercentiles,
+    /// Target response times used to calculate the Apdex score of requests.
+    ///
+    /// Only set when the load test is configured with the `--apdex` run-time option, or
+    /// with [GooseDefault::Apdex](../config/enum.GooseDefault.html#variant.Apdex).
+    pub apdex: Option<GooseApdex>,
+    /// Request, transaction and scenario metrics collected during each test plan step.
+    ///
+    /// Unlike [`GooseMetrics::requests`], these metrics are not reset after all users
+    /// start, so they show how the load test behaved while users were increasing,
+    /// maintaining and decreasing.
+    ///
+    /// Only collected when the load test is configured with the `--phase-metrics` run-time
+    /// option, or with [GooseDefault::PhaseMetrics](../config/enum.GooseDefault.html#variant.PhaseMetrics).
+    pub phases: Vec<GoosePhaseMetrics>,
+    /// Custom counters, gauges and timings recorded by the load test, by name.
+    ///
+    /// Recorded with [`GooseUser::record_counter`](../goose/struct.GooseUser.html#method.record_counter),
+    /// [`GooseUser::record_gauge`](../goose/struct.GooseUser.html#method.record_gauge) and
+    /// [`GooseUser::record_timing`](../goose/struct.GooseUser.html#method.record_timing).
+    pub custom: BTreeMap<String, GooseCustomMetricAggregate>,
     /// Flag indicating whether or not these are the final metrics, used to determine
     /// which metrics should be displayed. Defaults to false.
     pub(crate) final_metrics: bool,
     /// Flag indicating whether or not to display status_codes. Defaults to false.
     pub(crate) display_status_codes: bool,
+    /// Flag indicating whether or not to collect and display per phase metrics. Defaults
+    /// to false.
+    pub(crate) phase_metrics: bool,
     /// Flag indicating whether or not to display metrics. This defaults to false on
     /// Workers, otherwise true.
     pub(crate) display_metrics: bool,
@@ -1059,6 +1977,9 @@ impl GooseMetrics {
                             &scenario.name,
                             transaction.transactions_index,
                             &transaction.name,
+                            config
+                                .significant_digits
+                                .unwrap_or(DEFAULT_SIGNIFICANT_DIGITS),
                         ));
                     }
                     self.transactions.push(transaction_vector);
@@ -1099,6 +2020,9 @@ impl GooseMetrics {
                 self.scenarios.push(ScenarioMetricAggregate::new(
                     scenario.scenarios_index,
                     &scenario.name,
+                    config
+                        .significant_digits
+                        .unwrap_or(DEFAULT_SIGNIFICANT_DIGITS),
                 ));
             }
         }
@@ -1431,7 +2355,7 @@ impl GooseMetrics {
             return Ok(());
         }
 
-        let mut aggregate_transaction_times: BTreeMap<usize, usize> = BTreeMap::new();
+        let mut aggregate_transaction_times = GooseHistogram::default();
         let mut aggregate_total_transaction_time: usize = 0;
         let mut aggregate_transaction_time_counter: usize = 0;
         let mut aggregate_min_transaction_time: usize = 0;
@@ -1472,8 +2396,7 @@ impl GooseMetrics {
                 }
 
                 // Iterate over user transaction times, and merge into global transaction times.
-                aggregate_transaction_times =
-                    merge_times(aggregate_transaction_times, transaction.times.clone());
+                aggregate_transaction_times.merge(&transaction.times);
 
                 // Increment total transaction time counter.
                 aggregate_total_transaction_time += &transaction.total_time;
@@ -1510,7 +2433,7 @@ impl GooseMetrics {
                     format_number(transaction.min_time),
                     format_number(transaction.max_time),
                     format_number(util::median(
-                        &transaction.times,
+                        transaction.times.counts(),
                         transaction.counter,
                         transaction.min_time,
                         transaction.max_time
@@ -1541,7 +2464,7 @@ impl GooseMetrics {
                 format_number(aggregate_min_transaction_time),
                 format_number(aggregate_max_transaction_time),
                 format_number(util::median(
-                    &aggregate_transaction_times,
+                    aggregate_transaction_times.counts(),
                     aggregate_transaction_time_counter,
                     aggregate_min_transaction_time,
                     aggregate_max_transaction_time
@@ -1625,6 +2548,295 @@ impl GooseMetrics {
         Ok(())
     }
 
+    /// Optionally prepares a table comparing the target and achieved arrival rate.
+    ///
+    /// This function is invoked by `GooseMetrics::print()` and
+    /// `GooseMetrics::print_running()`.
+    pub(crate) fn fmt_arrival_rate(&self, fmt: &mut fmt::Formatter<'_>) -> fmt::Result {
+        // If there's nothing to display, exit immediately.
+        let arrival_rate = match self.arrival_rate.as_ref() {
+            Some(arrival_rate) if self.display_metrics => arrival_rate,
+            _ => return Ok(()),
+        };
+
+        writeln!(
+            fmt,
+            "\n === ARRIVAL RATE METRICS ===\n ------------------------------------------------------------------------------"
+        )?;
+        writeln!(
+            fmt,
+            " {:<24} | {:>15} | {:>15} | {:>15}",
+            if arrival_rate.requests {
+                "Requests"
+            } else {
+                "Iterations"
+            },
+            "# scheduled",
+            "# started",
+            "# dropped"
+        )?;
+        writeln!(
+            fmt,
+            " ------------------------------------------------------------------------------"
+        )?;
+        let dropped_percent = if arrival_rate.scheduled > 0 {
+            arrival_rate.dropped as f32 / arrival_rate.scheduled as f32 * 100.0
+        } else {
+            0.0
+        };
+        let dropped_precision = determine_precision(dropped_percent);
+        writeln!(
+            fmt,
+            " {:<24} | {:>15} | {:>15} | {:>15}",
+            "Total",
+            format_number(arrival_rate.scheduled),
+            format_number(arrival_rate.started),
+            format!(
+                "{} ({:.*}%)",
+                format_number(arrival_rate.dropped),
+                dropped_precision,
+                dropped_percent
+            ),
+        )?;
+        let (target, achieved) =
+            per_second_calculations(self.duration, arrival_rate.scheduled, arrival_rate.started);
+        let (dropped, _) = per_second_calculations(self.duration, arrival_rate.dropped, 0);
+        writeln!(
+            fmt,
+            " {:<24} | {:>15.2} | {:>15.2} | {:>15.2}",
+            "Per second", target, achieved, dropped,
+        )?;
+
+        Ok(())
+    }
+
+    /// Evaluate each configured threshold against the final metrics.
+    pub(crate) fn evaluate_thresholds(&mut self, thresholds: Option<&GooseThresholds>) {
+        self.thresholds = match thresholds {
+            Some(thresholds) => thresholds
+                .thresholds
+                .iter()
+                .map(|threshold| {
+                    GooseThresholdResult::evaluate(threshold, &self.requests, self.duration)
+                })
+                .collect(),
+            None => Vec::new(),
+        };
+    }
+
+    /// Returns the thresholds that were not met, if any.
+    pub fn failed_thresholds(&self) -> Vec<&GooseThresholdResult> {
+        self.thresholds
+            .iter()
+            .filter(|result| !result.passed)
+            .collect()
+    }
+
+    /// Optionally prepares a table of pass/fail thresholds.
+    ///
+    /// This function is invoked by `GooseMetrics::print()`.
+    pub(crate) fn fmt_thresholds(&self, fmt: &mut fmt::Formatter<'_>) -> fmt::Result {
+        // If there's nothing to display, exit immediately.
+        if !self.display_metrics || !self.final_metrics || self.thresholds.is_empty() {
+            return Ok(());
+        }
+
+        writeln!(
+            fmt,
+            "\n === THRESHOLDS ===\n ------------------------------------------------------------------------------"
+        )?;
+        writeln!(
+            fmt,
+            " {:<24} | {:>15} | {:>15} | {:>15}",
+            "Threshold", "Limit", "Measured", "Result"
+        )?;
+        writeln!(
+            fmt,
+            " ------------------------------------------------------------------------------"
+        )?;
+        for result in &self.thresholds {
+            let threshold = match result.threshold.request.as_ref() {
+                Some(request) => format!("{}: {}", request, result.threshold.metric),
+                None => result.threshold.metric.to_string(),
+            };
+            let comparison = if result.threshold.greater_than {
+                ">"
+            } else {
+                "<"
+            };
+            let measured = match result.value {
+                Some(value) => format!("{:.*}", determine_precision(value), value),
+                None => "-".to_string(),
+            };
+            writeln!(
+                fmt,
+                " {:<24} | {:>15} | {:>15} | {:>15}",
+                util::truncate_string(&threshold, 24),
+                format!("{} {}", comparison, result.threshold.value),
+                measured,
+                if result.passed { "PASS" } else { "FAIL" },
+            )?;
+        }
+
+        Ok(())
+    }
+
+    /// Compare each request to a previous load test.
+    pub(crate) fn compare_baseline(&mut self, baseline: Option<&GooseBaseline>, tolerance: usize) {
+        self.baseline = match baseline {
+            Some(baseline) => {
+                // Compare each request made by either load test, and then all requests.
+                let mut requests: Vec<Option<&str>> = self
+                    .requests
+                    .keys()
+                    .chain(baseline.requests.keys())
+                    .sorted()
+                    .dedup()
+                    .map(|request| Some(request.as_str()))
+                    .collect();
+                requests.push(None);
+                // Compare the average, then each configured percentile response time.
+                let mut metrics = vec![GooseBaselineMetric::Average];
+                metrics.extend(
+                    self.percentiles
+                        .percentiles
+                        .iter()
+                        .map(|percentile| GooseBaselineMetric::Percentile(*percentile)),
+                );
+                metrics.push(GooseBaselineMetric::ErrorRate);
+                metrics.push(GooseBaselineMetric::RequestsPerSecond);
+                let mut deltas = Vec::new();
+                for request in requests {
+                    for metric in &metrics {
+                        deltas.push(GooseBaselineDelta::compare(
+                            request,
+                            *metric,
+                            baseline,
+                            &self.requests,
+                            self.duration,
+                            tolerance,
+                        ));
+                    }
+                }
+                deltas
+            }
+            None => Vec::new(),
+        };
+    }
+
+    /// Optionally prepares a table comparing each request to a previous load test.
+    ///
+    /// This function is invoked by `GooseMetrics::print()`.
+    pub(crate) fn fmt_baseline(&self, fmt: &mut fmt::Formatter<'_>) -> fmt::Result {
+        // If there's nothing to display, exit immediately.
+        if !self.display_metrics || !self.final_metrics || self.baseline.is_empty() {
+            return Ok(());
+        }
+
+        writeln!(
+            fmt,
+            "\n === BASELINE COMPARISON ===\n ------------------------------------------------------------------------------"
+        )?;
+        writeln!(
+            fmt,
+            " {:<24} | {:>11} | {:>11} | {:>11} | {:>8}",
+            "Metric", "Baseline", "Current", "Change", "Result"
+        )?;
+        writeln!(
+            fmt,
+            " ------------------------------------------------------------------------------"
+        )?;
+        let format_value = |value: Option<f32>| match value {
+            Some(value) => format!("{:.*}", determine_precision(value), value),
+            None => "-".to_string(),
+        };
+        for delta in &self.baseline {
+            let metric = format!(
+                "{}: {}",
+                delta.request.as_deref().unwrap_or("Aggregated"),
+                delta.metric
+            );
+            writeln!(
+                fmt,
+                " {:<24} | {:>11} | {:>11} | {:>11} | {:>8}",
+                util::truncate_string(&metric, 24),
+                format_value(delta.baseline),
+                format_value(delta.current),
+                delta.fmt_change(),
+                if delta.regression { "WORSE" } else { "OK" },
+            )?;
+        }
+
+        Ok(())
+    }
+
+    /// Optionally prepares a table of the load levels probed by a breakpoint search.
+    ///
+    /// This function is invoked by `GooseMetrics::print()` and
+    /// `GooseMetrics::print_running()`.
+    pub(crate) fn fmt_breakpoint(&self, fmt: &mut fmt::Formatter<'_>) -> fmt::Result {
+        // If there's nothing to display, exit immediately.
+        let breakpoint = match self.breakpoint.as_ref() {
+            Some(breakpoint) if self.display_metrics && !breakpoint.probes.is_empty() => breakpoint,
+            _ => return Ok(()),
+        };
+
+        let (level, unit) = if breakpoint.arrival_rate
+            && self
+                .arrival_rate
+                .as_ref()
+                .is_some_and(|arrival_rate| arrival_rate.requests)
+        {
+            ("Requests per second", "requests per second")
+        } else if breakpoint.arrival_rate {
+            ("Iterations per second", "iterations per second")
+        } else {
+            ("Users", "users")
+        };
+
+        writeln!(
+            fmt,
+            "\n === BREAKPOINT METRICS ===\n ------------------------------------------------------------------------------"
+        )?;
+        writeln!(
+            fmt,
+            " {:<24} | {:>15} | {:>15} | {:>15}",
+            level, "req/s", "error %", "95th %ile (ms)"
+        )?;
+        writeln!(
+            fmt,
+            " ------------------------------------------------------------------------------"
+        )?;
+        for probe in &breakpoint.probes {
+            writeln!(
+                fmt,
+                " {:<24} | {:>15.2} | {:>15.2} | {:>15}",
+                format_number(probe.level),
+                probe.requests_per_second,
+                probe.error_rate,
+                format_number(probe.response_time_95),
+            )?;
+        }
+        writeln!(
+            fmt,
+            " ------------------------------------------------------------------------------"
+        )?;
+        match breakpoint.capacity {
+            Some(capacity) => writeln!(
+                fmt,
+                " Max sustainable capacity: {} {}",
+                format_number(capacity),
+                unit
+            )?,
+            None => writeln!(fmt, " Max sustainable capacity: not found")?,
+        }
+        if let Some(reason) = breakpoint.reason.as_ref() {
+            writeln!(fmt, " Stopped: {}", reason)?;
+        }
+
+        Ok(())
+    }
+
     /// Optionally prepares a table of scenario times.
     ///
     /// This function is invoked by `GooseMetrics::print()` and
@@ -1635,7 +2847,7 @@ impl GooseMetrics {
             return Ok(());
         }
 
-        let mut aggregate_scenario_times: BTreeMap<usize, usize> = BTreeMap::new();
+        let mut aggregate_scenario_times = GooseHistogram::default();
         let mut aggregate_total_scenario_time: usize = 0;
         let mut aggregate_scenario_time_counter: usize = 0;
         let mut aggregate_min_scenario_time: usize = 0;
@@ -1655,8 +2867,7 @@ impl GooseMetrics {
         )?;
         for scenario in &self.scenarios {
             // Iterate over user transaction times, and merge into global transaction times.
-            aggregate_scenario_times =
-                merge_times(aggregate_scenario_times, scenario.times.clone());
+            aggregate_scenario_times.merge(&scenario.times);
 
             // Increment total scenario time counter.
             aggregate_total_scenario_time += &scenario.total_time;
@@ -1686,7 +2897,7 @@ impl GooseMetrics {
                 format_number(scenario.min_time),
                 format_number(scenario.max_time),
                 format_number(util::median(
-                    &scenario.times,
+                    scenario.times.counts(),
                     scenario.counter,
                     scenario.min_time,
                     scenario.max_time
@@ -1713,7 +2924,7 @@ impl GooseMetrics {
                 format_number(aggregate_min_scenario_time),
                 format_number(aggregate_max_scenario_time),
                 format_number(util::median(
-                    &aggregate_scenario_times,
+                    aggregate_scenario_times.counts(),
                     aggregate_scenario_time_counter,
                     aggregate_min_scenario_time,
                     aggregate_max_scenario_time
@@ -1735,23 +2946,42 @@ impl GooseMetrics {
             return Ok(());
         }
 
-        let mut aggregate_raw_times: BTreeMap<usize, usize> = BTreeMap::new();
+        let mut aggregate_raw_times = GooseHistogram::default();
         let mut aggregate_raw_total_time: usize = 0;
         let mut aggregate_raw_counter: usize = 0;
         let mut aggregate_raw_min_time: usize = 0;
         let mut aggregate_raw_max_time: usize = 0;
+        // If Apdex targets are configured, add a column with the Apdex score of each request.
+        let apdex_scores = self.apdex_scores();
+        let (apdex_header, apdex_line, apdex_separator) = if self.apdex.is_some() {
+            (format!(" | {:>6}", "Apdex"), "---------", "-+-------")
+        } else {
+            (String::new(), "", "")
+        };
+        let apdex_cell = |request_key: &str| match self.apdex {
+            Some(_) => format!(
+                " | {:>6}",
+                apdex_scores
+                    .get(request_key)
+                    .map(|apdex| format!("{:.2}", apdex.score))
+                    .unwrap_or_else(|| "-".to_string())
+            ),
+            None => String::new(),
+        };
         writeln!(
             fmt,
-            " ------------------------------------------------------------------------------"
+            " ------------------------------------------------------------------------------{}",
+            apdex_line
         )?;
         writeln!(
             fmt,
-            " {:<24} | {:>11} | {:>10} | {:>11} | {:>10}",
-            "Name", "Avg (ms)", "Min", "Max", "Median"
+            " {:<24} | {:>11} | {:>10} | {:>11} | {:>10}{}",
+            "Name", "Avg (ms)", "Min", "Max", "Median", apdex_header
         )?;
         writeln!(
             fmt,
-            " ------------------------------------------------------------------------------"
+            " ------------------------------------------------------------------------------{}",
+            apdex_line
         )?;
 
         // First display the raw data, as it always exists.
@@ -1768,7 +2998,7 @@ impl GooseMetrics {
             let raw_average_precision = determine_precision(raw_average);
 
             // Merge in all times from this request into an aggregate.
-            aggregate_raw_times = merge_times(aggregate_raw_times, request.raw_data.times.clone());
+            aggregate_raw_times.merge(&request.raw_data.times);
             // Increment total response time counter.
             aggregate_raw_total_time += &request.raw_data.total_time;
             // Increment counter tracking individual response times seen.
@@ -1782,17 +3012,18 @@ impl GooseMetrics {
 
             writeln!(
                 fmt,
-                " {:<24} | {:>11.raw_avg_precision$} | {:>10} | {:>11} | {:>10}",
+                " {:<24} | {:>11.raw_avg_precision$} | {:>10} | {:>11} | {:>10}{}",
                 util::truncate_string(request_key, 24),
                 raw_average,
                 format_number(request.raw_data.minimum_time),
                 format_number(request.raw_data.maximum_time),
                 format_number(util::median(
-                    &request.raw_data.times,
+                    request.raw_data.times.counts(),
                     request.raw_data.counter,
                     request.raw_data.minimum_time,
                     request.raw_data.maximum_time,
                 )),
+                apdex_cell(request_key),
                 raw_avg_precision = raw_average_precision,
             )?;
         }
@@ -1807,21 +3038,23 @@ impl GooseMetrics {
         if self.requests.len() > 1 {
             writeln!(
                 fmt,
-                " -------------------------+-------------+------------+-------------+-----------"
+                " -------------------------+-------------+------------+-------------+-----------{}",
+                apdex_separator
             )?;
             writeln!(
                 fmt,
-                " {:<24} | {:>11.avg_precision$} | {:>10} | {:>11} | {:>10}",
+                " {:<24} | {:>11.avg_precision$} | {:>10} | {:>11} | {:>10}{}",
                 "Aggregated",
                 raw_average,
                 format_number(aggregate_raw_min_time),
                 format_number(aggregate_raw_max_time),
                 format_number(util::median(
-                    &aggregate_raw_times,
+                    aggregate_raw_times.counts(),
                     aggregate_raw_counter,
                     aggregate_raw_min_time,
                     aggregate_raw_max_time
                 )),
+                apdex_cell("Aggregated"),
                 avg_precision = raw_average_precision,
             )?;
         }
@@ -1837,7 +3070,7 @@ impl GooseMetrics {
         )?;
         writeln!(fmt, " Adjusted for Coordinated Omission:")?;
 
-        let mut aggregate_co_times: BTreeMap<usize, usize> = BTreeMap::new();
+        let mut aggregate_co_times = GooseHistogram::default();
         let mut aggregate_co_total_time: usize = 0;
         let mut aggregate_co_counter: usize = 0;
         let mut aggregate_co_min_time: usize = 0;
@@ -1872,7 +3105,7 @@ impl GooseMetrics {
                     _ => co_data.total_time as f32 / co_data.counter as f32,
                 };
                 standard_deviation = util::standard_deviation(raw_average, co_average);
-                aggregate_co_times = merge_times(aggregate_co_times, co_data.times.clone());
+                aggregate_co_times.merge(&co_data.times);
                 aggregate_co_counter += co_data.counter;
                 // If user had new fastest response time, update global fastest response time.
                 aggregate_co_min_time =
@@ -1902,7 +3135,7 @@ impl GooseMetrics {
                     standard_deviation,
                     format_number(co_maximum),
                     format_number(util::median(
-                        &co_data.times,
+                        co_data.times.counts(),
                         co_data.counter,
                         co_minimum,
                         co_maximum,
@@ -1946,7 +3179,7 @@ impl GooseMetrics {
                 standard_deviation,
                 format_number(aggregate_co_max_time),
                 format_number(util::median(
-                    &aggregate_co_times,
+                    aggregate_co_times.counts(),
                     aggregate_co_counter,
                     aggregate_co_min_time,
                     aggregate_co_max_time
@@ -1959,342 +3192,481 @@ impl GooseMetrics {
         Ok(())
     }
 
-    /// Optionally prepares a table of slowest response times within several percentiles.
+    /// Calculate the configured response time percentiles of each request, and of all
+    /// requests aggregated together.
+    pub fn response_time_percentiles(&self) -> BTreeMap<String, Vec<GoosePercentile>> {
+        let mut response_time_percentiles = BTreeMap::new();
+        let mut aggregate_data = GooseRequestMetricTimingData::default();
+        for (request_key, request) in &self.requests {
+            aggregate_data.merge(&request.raw_data);
+            response_time_percentiles.insert(
+                request_key.to_string(),
+                request.raw_data.percentiles(&self.percentiles.percentiles),
+            );
+        }
+        if !self.requests.is_empty() {
+            response_time_percentiles.insert(
+                "Aggregated".to_string(),
+                aggregate_data.percentiles(&self.percentiles.percentiles),
+            );
+        }
+        response_time_percentiles
+    }
+
+    /// Calculates how many bytes were sent and received by each request, and by all requests
+    /// aggregated together.
+    ///
+    /// Included in the json-serialized metrics as `bandwidth`.
+    pub fn bandwidth(&self) -> BTreeMap<String, GooseBandwidth> {
+        let mut bandwidth = BTreeMap::new();
+        let mut aggregate_request_bytes = 0;
+        let mut aggregate_response_bytes = 0;
+        let mut aggregate_compressed_bytes = 0;
+        for (request_key, request) in &self.requests {
+            aggregate_request_bytes += request.request_bytes;
+            aggregate_response_bytes += request.response_bytes;
+            aggregate_compressed_bytes += request.compressed_bytes;
+            bandwidth.insert(
+                request_key.to_string(),
+                GooseBandwidth::new(
+                    request.request_bytes,
+                    request.response_bytes,
+                    request.compressed_bytes,
+                    self.duration,
+                ),
+            );
+        }
+        if !self.requests.is_empty() {
+            bandwidth.insert(
+                "Aggregated".to_string(),
+                GooseBandwidth::new(
+                    aggregate_request_bytes,
+                    aggregate_response_bytes,
+                    aggregate_compressed_bytes,
+                    self.duration,
+                ),
+            );
+        }
+        bandwidth
+    }
+
+    /// Calculates where the time of each request was spent, and of all requests aggregated
+    /// together.
+    ///
+    /// Included in the json-serialized metrics as `timing_breakdown`.
+    pub fn timing_breakdown(&self) -> BTreeMap<String, GooseTimingBreakdown> {
+        let mut timing_breakdown: BTreeMap<String, GooseTimingBreakdown> = self
+            .requests
+            .iter()
+            .map(|(request_key, request)| {
+                (
+                    request_key.to_string(),
+                    GooseTimingBreakdown::new(std::iter::once(request)),
+                )
+            })
+            .collect();
+        if !self.requests.is_empty() {
+            timing_breakdown.insert(
+                "Aggregated".to_string(),
+                GooseTimingBreakdown::new(self.requests.values()),
+            );
+        }
+        timing_breakdown
+    }
+
+    /// Calculates the Apdex score of each request with a configured target, and of all
+    /// these requests scored together.
+    ///
+    /// Included in the json-serialized metrics as `apdex_scores`.
+    pub fn apdex_scores(&self) -> BTreeMap<String, GooseApdexScore> {
+        let apdex = match self.apdex.as_ref() {
+            Some(apdex) => apdex,
+            None => return BTreeMap::new(),
+        };
+        let mut apdex_scores: BTreeMap<String, GooseApdexScore> = self
+            .requests
+            .iter()
+            .filter(|(_, request)| request.raw_data.counter > 0)
+            .filter_map(|(request_key, request)| {
+                apdex.target(request_key, request).map(|target| {
+                    (
+                        request_key.to_string(),
+                        GooseApdexScore::new(request, target),
+                    )
+                })
+            })
+            .collect();
+        if !apdex_scores.is_empty() {
+            // Requests can have different targets, so aggregate how many responses were
+            // satisfying, tolerated and frustrating rather than the response times.
+            let (satisfied, tolerating, frustrated) =
+                apdex_scores.values().fold((0, 0, 0), |totals, score| {
+                    (
+                        totals.0 + score.satisfied,
+                        totals.1 + score.tolerating,
+                        totals.2 + score.frustrated,
+                    )
+                });
+            apdex_scores.insert(
+                "Aggregated".to_string(),
+                GooseApdexScore::from_counts(None, satisfied, tolerating, frustrated),
+            );
+        }
+        apdex_scores
+    }
+
+    /// Optionally prepares a table of bytes sent and received by each request.
     ///
     /// This function is invoked by `GooseMetrics::print()` and
     /// `GooseMetrics::print_running()`.
-    pub(crate) fn fmt_percentiles(&self, fmt: &mut fmt::Formatter<'_>) -> fmt::Result {
-        // Only include percentiles when displaying the final metrics report.
-        if !self.final_metrics {
+    pub(crate) fn fmt_bandwidth(&self, fmt: &mut fmt::Formatter<'_>) -> fmt::Result {
+        // If there's nothing to display, exit immediately.
+        if self.requests.is_empty() {
             return Ok(());
         }
 
-        let mut raw_aggregate_response_times: BTreeMap<usize, usize> = BTreeMap::new();
-        let mut raw_aggregate_total_response_time: usize = 0;
-        let mut raw_aggregate_response_time_counter: usize = 0;
-        let mut raw_aggregate_min_response_time: usize = 0;
-        let mut raw_aggregate_max_response_time: usize = 0;
-        writeln!(
-            fmt,
-            " ------------------------------------------------------------------------------"
-        )?;
-        writeln!(
-            fmt,
-            " Slowest page load within specified percentile of requests (in ms):"
-        )?;
         writeln!(
             fmt,
-            " ------------------------------------------------------------------------------"
+            "\n === PER REQUEST BANDWIDTH ===\n ------------------------------------------------------------------------------"
         )?;
         writeln!(
             fmt,
-            " {:<24} | {:>6} | {:>6} | {:>6} | {:>6} | {:>6} | {:>6}",
-            "Name", "50%", "75%", "98%", "99%", "99.9%", "99.99%"
+            " {:<24} | {:>10} | {:>10} | {:>10} | {:>10}",
+            "Name", "sent", "received", "sent/s", "recv/s"
         )?;
         writeln!(
             fmt,
             " ------------------------------------------------------------------------------"
         )?;
-        // Track whether or not Coordinated Omission Mitigation kicked in.
-        let mut co_data = false;
-        for (request_key, request) in self.requests.iter().sorted() {
-            if !co_data && request.coordinated_omission_data.is_some() {
-                co_data = true;
-            }
-
-            // Iterate over user response times, and merge into global response times.
-            raw_aggregate_response_times =
-                merge_times(raw_aggregate_response_times, request.raw_data.times.clone());
-
-            // Increment total response time counter.
-            raw_aggregate_total_response_time += &request.raw_data.total_time;
+        let row = |fmt: &mut fmt::Formatter<'_>, name: &str, bandwidth: &GooseBandwidth| {
+            writeln!(
+                fmt,
+                " {:<24} | {:>10} | {:>10} | {:>10} | {:>10}",
+                util::truncate_string(name, 24),
+                format_bytes(bandwidth.request_bytes as f32),
+                format_bytes(bandwidth.response_bytes as f32),
+                format_bytes(bandwidth.request_bytes_per_second),
+                format_bytes(bandwidth.response_bytes_per_second),
+            )
+        };
+        let mut bandwidth = self.bandwidth();
+        let aggregated = bandwidth.remove("Aggregated");
+        for (request_key, request) in &bandwidth {
+            row(fmt, request_key, request)?;
+        }
+        if let Some(aggregated) = aggregated.filter(|_| self.requests.len() > 1) {
+            writeln!(
+                fmt,
+                " -------------------------+------------+------------+------------+-----------"
+            )?;
+            row(fmt, "Aggregated", &aggregated)?;
+        }
 
-            // Increment counter tracking individual response times seen.
-            raw_aggregate_response_time_counter += &request.raw_data.counter;
+        Ok(())
+    }
 
-            // If user had new fastest response time, update global fastest response time.
-            raw_aggregate_min_response_time = update_min_time(
-                raw_aggregate_min_response_time,
-                request.raw_data.minimum_time,
-            );
+    /// Optionally prepares a table of where the time of each request was spent.
+    ///
+    /// This function is invoked by `GooseMetrics::print()` and
+    /// `GooseMetrics::print_running()`.
+    pub(crate) fn fmt_timing_breakdown(&self, fmt: &mut fmt::Formatter<'_>) -> fmt::Result {
+        // If there's nothing to display, exit immediately.
+        if self.requests.is_empty() {
+            return Ok(());
+        }
 
-            // If user had new slowest response time, update global slowest response time.
-            raw_aggregate_max_response_time = update_max_time(
-                raw_aggregate_max_response_time,
-                request.raw_data.maximum_time,
-            );
-            // Sort response times so we can calculate a mean.
+        writeln!(
+            fmt,
+            "\n === PER REQUEST TIMING BREAKDOWN ===\n -------------------------------------------------------------------------------------------"
+        )?;
+        writeln!(
+            fmt,
+            " {:<24} | {:>10} | {:>10} | {:>10} | {:>10} | {:>10}",
+            "Name", "DNS", "Connect", "TTFB", "Download", "Reused"
+        )?;
+        writeln!(
+            fmt,
+            " -------------------------------------------------------------------------------------------"
+        )?;
+        let row = |fmt: &mut fmt::Formatter<'_>, name: &str, timing: &GooseTimingBreakdown| {
             writeln!(
                 fmt,
-                " {:<24} | {:>6} | {:>6} | {:>6} | {:>6} | {:>6} | {:>6}",
-                util::truncate_string(request_key, 24),
-                calculate_response_time_percentile(
-                    &request.raw_data.times,
-                    request.raw_data.counter,
-                    request.raw_data.minimum_time,
-                    request.raw_data.maximum_time,
-                    0.5
-                ),
-                calculate_response_time_percentile(
-                    &request.raw_data.times,
-                    request.raw_data.counter,
-                    request.raw_data.minimum_time,
-                    request.raw_data.maximum_time,
-                    0.75
-                ),
-                calculate_response_time_percentile(
-                    &request.raw_data.times,
-                    request.raw_data.counter,
-                    request.raw_data.minimum_time,
-                    request.raw_data.maximum_time,
-                    0.98
-                ),
-                calculate_response_time_percentile(
-                    &request.raw_data.times,
-                    request.raw_data.counter,
-                    request.raw_data.minimum_time,
-                    request.raw_data.maximum_time,
-                    0.99
-                ),
-                calculate_response_time_percentile(
-                    &request.raw_data.times,
-                    request.raw_data.counter,
-                    request.raw_data.minimum_time,
-                    request.raw_data.maximum_time,
-                    0.999
-                ),
-                calculate_response_time_percentile(
-                    &request.raw_data.times,
-                    request.raw_data.counter,
-                    request.raw_data.minimum_time,
-                    request.raw_data.maximum_time,
-                    0.9999
-                ),
-            )?;
+                " {:<24} | {:>10.2} | {:>10.2} | {:>10.2} | {:>10.2} | {:>9.1}%",
+                util::truncate_string(name, 24),
+                timing.dns_time,
+                timing.connect_time,
+                timing.time_to_first_byte,
+                timing.download_time,
+                timing.connection_reuse,
+            )
+        };
+        let mut timing_breakdown = self.timing_breakdown();
+        let aggregated = timing_breakdown.remove("Aggregated");
+        for (request_key, request) in &timing_breakdown {
+            row(fmt, request_key, request)?;
         }
-        if self.requests.len() > 1 {
+        if let Some(aggregated) = aggregated.filter(|_| self.requests.len() > 1) {
             writeln!(
                 fmt,
-                " -------------------------+--------+--------+--------+--------+--------+-------"
+                " -------------------------+------------+------------+------------+------------+-----------"
             )?;
+            row(fmt, "Aggregated", &aggregated)?;
+        }
+
+        Ok(())
+    }
+
+    /// Optionally prepares a table of custom counters, gauges and timings.
+    ///
+    /// This function is invoked by `GooseMetrics::print()` and
+    /// `GooseMetrics::print_running()`.
+    pub(crate) fn fmt_custom_metrics(&self, fmt: &mut fmt::Formatter<'_>) -> fmt::Result {
+        // If there's nothing to display, exit immediately.
+        if self.custom.is_empty() {
+            return Ok(());
+        }
+
+        writeln!(
+            fmt,
+            "\n === CUSTOM METRICS ===\n ------------------------------------------------------------------------------"
+        )?;
+        writeln!(
+            fmt,
+            " {:<24} | {:>7} | {:>7} | {:>9} | {:>8} | {:>8}",
+            "Name", "type", "#", "value", "min", "max"
+        )?;
+        writeln!(
+            fmt,
+            " ------------------------------------------------------------------------------"
+        )?;
+        for (name, custom) in &self.custom {
+            let (count, value, minimum, maximum) = custom_metric_columns(custom);
             writeln!(
                 fmt,
-                " {:<24} | {:>6} | {:>6} | {:>6} | {:>6} | {:>6} | {:>6}",
-                "Aggregated",
-                calculate_response_time_percentile(
-                    &raw_aggregate_response_times,
-                    raw_aggregate_response_time_counter,
-                    raw_aggregate_min_response_time,
-                    raw_aggregate_max_response_time,
-                    0.5
-                ),
-                calculate_response_time_percentile(
-                    &raw_aggregate_response_times,
-                    raw_aggregate_response_time_counter,
-                    raw_aggregate_min_response_time,
-                    raw_aggregate_max_response_time,
-                    0.75
-                ),
-                calculate_response_time_percentile(
-                    &raw_aggregate_response_times,
-                    raw_aggregate_response_time_counter,
-                    raw_aggregate_min_response_time,
-                    raw_aggregate_max_response_time,
-                    0.98
-                ),
-                calculate_response_time_percentile(
-                    &raw_aggregate_response_times,
-                    raw_aggregate_response_time_counter,
-                    raw_aggregate_min_response_time,
-                    raw_aggregate_max_response_time,
-                    0.99
-                ),
-                calculate_response_time_percentile(
-                    &raw_aggregate_response_times,
-                    raw_aggregate_response_time_counter,
-                    raw_aggregate_min_response_time,
-                    raw_aggregate_max_response_time,
-                    0.999
-                ),
-                calculate_response_time_percentile(
-                    &raw_aggregate_response_times,
-                    raw_aggregate_response_time_counter,
-                    raw_aggregate_min_response_time,
-                    raw_aggregate_max_response_time,
-                    0.9999
-                ),
+                " {:<24} | {:>7} | {:>7} | {:>9} | {:>8} | {:>8}",
+                util::truncate_string(name, 24),
+                custom.kind(),
+                count,
+                value,
+                minimum,
+                maximum,
             )?;
         }
 
-        // If there's no Coordinated Omission Mitigation data to display, exit.
-        if !co_data {
+        Ok(())
+    }
+
+    /// Optionally prepares a table of slowest response times within several percentiles.
+    ///
+    /// This function is invoked by `GooseMetrics::print()` and
+    /// `GooseMetrics::print_running()`.
+    pub(crate) fn fmt_percentiles(&self, fmt: &mut fmt::Formatter<'_>) -> fmt::Result {
+        // Only include percentiles when displaying the final metrics report.
+        if !self.final_metrics {
             return Ok(());
         }
 
-        let mut co_aggregate_response_times: BTreeMap<usize, usize> = BTreeMap::new();
-        let mut co_aggregate_total_response_time: usize = 0;
-        let mut co_aggregate_response_time_counter: usize = 0;
-        let mut co_aggregate_min_response_time: usize = 0;
-        let mut co_aggregate_max_response_time: usize = 0;
+        let percentiles = &self.percentiles.percentiles;
+        // Build one row of the table, with a column for each configured percentile.
+        let row = |name: &str, values: Vec<String>| -> String {
+            let mut row = format!(" {:<24}", name);
+            for value in values {
+                row.push_str(&format!(" | {:>6}", value));
+            }
+            row
+        };
+        let header = row(
+            "Name",
+            percentiles
+                .iter()
+                .map(|percentile| format!("{}%", percentile))
+                .collect(),
+        );
+        let mut separator = format!(
+            " -------------------------{}",
+            "+--------".repeat(percentiles.len())
+        );
+        separator.pop();
+        let values = |data: &GooseRequestMetricTimingData| -> Vec<String> {
+            data.percentiles(percentiles)
+                .iter()
+                .map(|percentile| format_number(percentile.response_time))
+                .collect()
+        };
 
         writeln!(
             fmt,
             " ------------------------------------------------------------------------------"
         )?;
-        writeln!(fmt, " Adjusted for Coordinated Omission:")?;
         writeln!(
             fmt,
-            " ------------------------------------------------------------------------------"
+            " Slowest page load within specified percentile of requests (in ms):"
         )?;
         writeln!(
             fmt,
-            " {:<24} | {:>6} | {:>6} | {:>6} | {:>6} | {:>6} | {:>6}",
-            "Name", "50%", "75%", "98%", "99%", "99.9%", "99.99%"
+            " ------------------------------------------------------------------------------"
         )?;
+        writeln!(fmt, "{}", header)?;
         writeln!(
             fmt,
             " ------------------------------------------------------------------------------"
         )?;
+        let mut raw_aggregate_data = GooseRequestMetricTimingData::default();
+        // Track whether or not Coordinated Omission Mitigation kicked in.
+        let mut co_data = false;
         for (request_key, request) in self.requests.iter().sorted() {
-            if let Some(coordinated_omission_data) = request.coordinated_omission_data.as_ref() {
-                // Iterate over user response times, and merge into global response times.
-                co_aggregate_response_times = merge_times(
-                    co_aggregate_response_times,
-                    coordinated_omission_data.times.clone(),
-                );
+            if !co_data && request.coordinated_omission_data.is_some() {
+                co_data = true;
+            }
 
-                // Increment total response time counter.
-                co_aggregate_total_response_time += &coordinated_omission_data.total_time;
+            // Merge the response times of this request into the aggregate.
+            raw_aggregate_data.merge(&request.raw_data);
 
-                // Increment counter tracking individual response times seen.
-                co_aggregate_response_time_counter += &coordinated_omission_data.counter;
+            writeln!(
+                fmt,
+                "{}",
+                row(
+                    &util::truncate_string(request_key, 24),
+                    values(&request.raw_data)
+                )
+            )?;
+        }
+        if self.requests.len() > 1 {
+            writeln!(fmt, "{}", separator)?;
+            writeln!(fmt, "{}", row("Aggregated", values(&raw_aggregate_data)))?;
+        }
 
-                // If user had new fastest response time, update global fastest response time.
-                co_aggregate_min_response_time = update_min_time(
-                    co_aggregate_min_response_time,
-                    coordinated_omission_data.minimum_time,
-                );
+        // If there's no Coordinated Omission Mitigation data to display, exit.
+        if !co_data {
+            return Ok(());
+        }
 
-                // If user had new slowest response time, update global slowest response time.
-                co_aggregate_max_response_time = update_max_time(
-                    co_aggregate_max_response_time,
-                    coordinated_omission_data.maximum_time,
-                );
+        writeln!(
+            fmt,
+            " ------------------------------------------------------------------------------"
+        )?;
+        writeln!(fmt, " Adjusted for Coordinated Omission:")?;
+        writeln!(
+            fmt,
+            " ------------------------------------------------------------------------------"
+        )?;
+        writeln!(fmt, "{}", header)?;
+        writeln!(
+            fmt,
+            " ------------------------------------------------------------------------------"
+        )?;
+        let mut co_aggregate_data = GooseRequestMetricTimingData::default();
+        for (request_key, request) in self.requests.iter().sorted() {
+            let request_key = util::truncate_string(request_key, 24);
+            if let Some(coordinated_omission_data) = request.coordinated_omission_data.as_ref() {
+                // Merge the response times of this request into the aggregate.
+                co_aggregate_data.merge(coordinated_omission_data);
 
-                // Sort response times so we can calculate a mean.
                 writeln!(
                     fmt,
-                    " {:<24} | {:>6} | {:>6} | {:>6} | {:>6} | {:>6} | {:>6}",
-                    util::truncate_string(request_key, 24),
-                    calculate_response_time_percentile(
-                        &coordinated_omission_data.times,
-                        coordinated_omission_data.counter,
-                        coordinated_omission_data.minimum_time,
-                        coordinated_omission_data.maximum_time,
-                        0.5
-                    ),
-                    calculate_response_time_percentile(
-                        &coordinated_omission_data.times,
-                        coordinated_omission_data.counter,
-                        coordinated_omission_data.minimum_time,
-                        coordinated_omission_data.maximum_time,
-                        0.75
-                    ),
-                    calculate_response_time_percentile(
-                        &coordinated_omission_data.times,
-                        coordinated_omission_data.counter,
-                        coordinated_omission_data.minimum_time,
-                        coordinated_omission_data.maximum_time,
-                        0.98
-                    ),
-                    calculate_response_time_percentile(
-                        &coordinated_omission_data.times,
-                        coordinated_omission_data.counter,
-                        coordinated_omission_data.minimum_time,
-                        coordinated_omission_data.maximum_time,
-                        0.99
-                    ),
-                    calculate_response_time_percentile(
-                        &coordinated_omission_data.times,
-                        coordinated_omission_data.counter,
-                        coordinated_omission_data.minimum_time,
-                        coordinated_omission_data.maximum_time,
-                        0.999
-                    ),
-                    calculate_response_time_percentile(
-                        &coordinated_omission_data.times,
-                        coordinated_omission_data.counter,
-                        coordinated_omission_data.minimum_time,
-                        coordinated_omission_data.maximum_time,
-                        0.9999
-                    ),
+                    "{}",
+                    row(&request_key, values(coordinated_omission_data))
                 )?;
             } else {
                 writeln!(
                     fmt,
-                    " {:<24} | {:>6} | {:>6} | {:>6} | {:>6} | {:>6} | {:>6}",
-                    util::truncate_string(request_key, 24),
-                    "-",
-                    "-",
-                    "-",
-                    "-",
-                    "-",
-                    "-"
+                    "{}",
+                    row(&request_key, vec!["-".to_string(); percentiles.len()])
                 )?;
             }
         }
         if self.requests.len() > 1 {
+            writeln!(fmt, "{}", separator)?;
+            writeln!(fmt, "{}", row("Aggregated", values(&co_aggregate_data)))?;
+        }
+
+        Ok(())
+    }
+
+    /// Returns the metrics of the test plan step that is currently running, creating them
+    /// the first time metrics are recorded during the step. Returns `None` if per phase
+    /// metrics are not enabled.
+    pub(crate) fn current_phase(&mut self) -> Option<&mut GoosePhaseMetrics> {
+        if !self.phase_metrics {
+            return None;
+        }
+        // Metrics received after the load test finished belong to the previous step.
+        let step = match self.history.last()?.action {
+            TestPlanStepAction::Finished => self.history.len().checked_sub(2)?,
+            _ => self.history.len() - 1,
+        };
+        if !matches!(self.phases.last(), Some(phase) if phase.step == step) {
+            let phase = GoosePhaseMetrics::new(
+                step,
+                self.history[step].action.clone(),
+                &self.transactions,
+                &self.scenarios,
+            );
+            self.phases.push(phase);
+        }
+        self.phases.last_mut()
+    }
+
+    /// How many seconds a test plan step ran, or has been running so far.
+    pub(crate) fn phase_duration(&self, phase: &GoosePhaseMetrics) -> usize {
+        let started = self.history[phase.step].timestamp;
+        let stopped = match self.history.get(phase.step + 1) {
+            Some(next) => next.timestamp,
+            None => Utc::now(),
+        };
+        (stopped.timestamp() - started.timestamp()).max(0) as usize
+    }
+
+    /// Optionally prepares a table of request metrics for each test plan step.
+    ///
+    /// This function is invoked by `GooseMetrics::print()`.
+    pub(crate) fn fmt_phases(&self, fmt: &mut fmt::Formatter<'_>) -> fmt::Result {
+        // Only display per phase metrics in the final metrics, if enabled.
+        if !self.final_metrics || !self.phase_metrics || self.phases.is_empty() {
+            return Ok(());
+        }
+
+        writeln!(
+            fmt,
+            "\n === PER PHASE METRICS ===\n ------------------------------------------------------------------------------"
+        )?;
+        writeln!(
+            fmt,
+            " {:<24} | {:>8} | {:>8} | {:>8} | {:>8} | {:>7}",
+            "Name", "# reqs", "# fails", "avg (ms)", "p95 (ms)", "req/s"
+        )?;
+        for phase in &self.phases {
+            let duration = self.phase_duration(phase);
             writeln!(
                 fmt,
-                " -------------------------+--------+--------+--------+--------+--------+-------"
+                " ------------------------------------------------------------------------------"
             )?;
             writeln!(
                 fmt,
-                " {:<24} | {:>6} | {:>6} | {:>6} | {:>6} | {:>6} | {:>6}",
-                "Aggregated",
-                calculate_response_time_percentile(
-                    &co_aggregate_response_times,
-                    co_aggregate_response_time_counter,
-                    co_aggregate_min_response_time,
-                    co_aggregate_max_response_time,
-                    0.5
-                ),
-                calculate_response_time_percentile(
-                    &co_aggregate_response_times,
-                    co_aggregate_response_time_counter,
-                    co_aggregate_min_response_time,
-                    co_aggregate_max_response_time,
-                    0.75
-                ),
-                calculate_response_time_percentile(
-                    &co_aggregate_response_times,
-                    co_aggregate_response_time_counter,
-                    co_aggregate_min_response_time,
-                    co_aggregate_max_response_time,
-                    0.98
-                ),
-                calculate_response_time_percentile(
-                    &co_aggregate_response_times,
-                    co_aggregate_response_time_counter,
-                    co_aggregate_min_response_time,
-                    co_aggregate_max_response_time,
-                    0.99
-                ),
-                calculate_response_time_percentile(
-                    &co_aggregate_response_times,
-                    co_aggregate_response_time_counter,
-                    co_aggregate_min_response_time,
-                    co_aggregate_max_response_time,
-                    0.999
-                ),
-                calculate_response_time_percentile(
-                    &co_aggregate_response_times,
-                    co_aggregate_response_time_counter,
-                    co_aggregate_min_response_time,
-                    co_aggregate_max_response_time,
-                    0.9999
-                ),
+                " {}: {:?} ({:02}:{:02}:{:02}, {} users)",
+                phase.step + 1,
+                phase.action,
+                duration / 3600,
+                (duration / 60) % 60,
+                duration % 60,
+                self.history[phase.step].users,
+            )?;
+            writeln!(
+                fmt,
+                " ------------------------------------------------------------------------------"
             )?;
+            for summary in phase.summarize(duration) {
+                writeln!(
+                    fmt,
+                    " {:<24} | {:>8} | {:>8} | {:>8.2} | {:>8} | {:>7.2}",
+                    util::truncate_string(&summary.name, 24),
+                    format_number(summary.requests),
+                    format_number(summary.fails),
+                    summary.average,
+                    format_number(summary.response_time_95),
+                    summary.requests_per_second,
+                )?;
+            }
         }
 
         Ok(())
@@ -2439,12 +3811,17 @@ impl GooseMetrics {
                 .unwrap()
                 .format("%Y-%m-%d %H:%M:%S")
                 .to_string();
+            // Only show the ramp shape if it's not the default linear ramp.
+            let ramp = match step[0].ramp {
+                TestPlanRamp::Linear => "".to_string(),
+                ramp => format!(", {}", ramp),
+            };
             match &step[0].action {
                 // For maintaining just show the current number of users.
                 TestPlanStepAction::Maintaining => {
                     writeln!(
                         fmt,
-                        " {:<12} {} - {} ({:02}:{:02}:{:02}, {})",
+                        " {:<12} {} - {} ({:02}:{:02}:{:02}, {}{})",
                         format!("{:?}:", step[0].action),
                         started,
                         stopped,
@@ -2452,13 +3829,14 @@ impl GooseMetrics {
                         minutes,
                         seconds,
                         step[0].users,
+                        ramp,
                     )?;
                 }
                 // For increasing show the current number of users to the new number of users.
                 TestPlanStepAction::Increasing => {
                     writeln!(
                         fmt,
-                        " {:<12} {} - {} ({:02}:{:02}:{:02}, {} -> {})",
+                        " {:<12} {} - {} ({:02}:{:02}:{:02}, {} -> {}{})",
                         format!("{:?}:", step[0].action),
                         started,
                         stopped,
@@ -2467,13 +3845,16 @@ impl GooseMetrics {
                         seconds,
                         step[0].users,
                         step[1].users,
+                        ramp,
                     )?;
                 }
                 // For decreasing show the new number of users from the current number of users.
-                TestPlanStepAction::Decreasing | TestPlanStepAction::Canceling => {
+                TestPlanStepAction::Decreasing
+                | TestPlanStepAction::Canceling
+                | TestPlanStepAction::Aborting => {
                     writeln!(
                         fmt,
-                        " {:<12} {} - {} ({:02}:{:02}:{:02}, {} <- {})",
+                        " {:<12} {} - {} ({:02}:{:02}:{:02}, {} <- {}{})",
                         format!("{:?}:", step[0].action),
                         started,
                         stopped,
@@ -2482,12 +3863,17 @@ impl GooseMetrics {
                         seconds,
                         step[1].users,
                         step[0].users,
+                        ramp,
                     )?;
                 }
                 TestPlanStepAction::Finished => {
                     unreachable!("there shouldn't be a step after finished");
                 }
             }
+            // Explain why the step happened, for example why the load test was aborted.
+            if let Some(reason) = &step[0].reason {
+                writeln!(fmt, " {:<12} {}", "", reason)?;
+            }
         }
 
         match self.hosts.len() {
@@ -2530,7 +3916,7 @@ impl Serialize for GooseMetrics {
     where
         S: Serializer,
     {
-        let mut s = serializer.serialize_struct("GooseMetrics", 10)?;
+        let mut s = serializer.serialize_struct("GooseMetrics", 22)?;
         s.serialize_field("hash", &self.hash)?;
         s.serialize_field("duration", &self.duration)?;
         s.serialize_field("maximum_users", &self.maximum_users)?;
@@ -2538,6 +3924,25 @@ impl Serialize for GooseMetrics {
         s.serialize_field("requests", &self.requests)?;
         s.serialize_field("transactions", &self.transactions)?;
         s.serialize_field("errors", &self.errors)?;
+        s.serialize_field("arrival_rate", &self.arrival_rate)?;
+        s.serialize_field("breakpoint", &self.breakpoint)?;
+        s.serialize_field("thresholds", &self.thresholds)?;
+        s.serialize_field("baseline", &self.baseline)?;
+        s.serialize_field("percentiles", &self.percentiles)?;
+        s.serialize_field(
+            "response_time_percentiles",
+            &self.response_time_percentiles(),
+        )?;
+        s.serialize_field("apdex", &self.apdex)?;
+        s.serialize_field("apdex_scores", &self.apdex_scores())?;
+        if self.phases.is_empty() {
+            s.skip_field("phases")?;
+        } else {
+            s.serialize_field("phases", &self.phases)?;
+        }
+        s.serialize_field("bandwidth", &self.bandwidth())?;
+        s.serialize_field("timing_breakdown", &self.timing_breakdown())?;
+        s.serialize_field("custom", &self.custom)?;
         s.serialize_field("final_metrics", &self.final_metrics)?;
         s.serialize_field("display_status_codes", &self.display_status_codes)?;
         s.serialize_field("display_metrics", &self.display_metrics)?;
@@ -2553,13 +3958,21 @@ impl fmt::Display for GooseMetrics {
         // flags are set.
         self.fmt_scenarios(fmt)?;
         self.fmt_scenario_times(fmt)?;
+        self.fmt_arrival_rate(fmt)?;
+        self.fmt_breakpoint(fmt)?;
         self.fmt_transactions(fmt)?;
         self.fmt_transaction_times(fmt)?;
         self.fmt_requests(fmt)?;
         self.fmt_response_times(fmt)?;
         self.fmt_percentiles(fmt)?;
         self.fmt_status_codes(fmt)?;
+        self.fmt_bandwidth(fmt)?;
+        self.fmt_timing_breakdown(fmt)?;
+        self.fmt_phases(fmt)?;
+        self.fmt_custom_metrics(fmt)?;
         self.fmt_errors(fmt)?;
+        self.fmt_thresholds(fmt)?;
+        self.fmt_baseline(fmt)?;
         self.fmt_overview(fmt)
     }
 }
@@ -2642,39 +4055,913 @@ impl GooseErrorMetricAggregate {
     }
 }
 
-impl GooseAttack {
-    // If metrics are enabled, synchronize metrics from child threads to the parent. If
-    // flush is true all metrics will be received regardless of how long it takes. If
-    // flush is false, metrics will only be received for up to 400 ms before exiting to
-    // continue on the next call to this function.
-    pub(crate) async fn sync_metrics(
-        &mut self,
-        goose_attack_run_state: &mut GooseAttackRunState,
-        flush: bool,
-    ) -> Result<(), GooseError> {
-        if !self.configuration.no_metrics {
-            // Update timers if displaying running metrics.
-            if let Some(running_metrics) = self.configuration.running_metrics {
-                if util::timer_expired(
-                    goose_attack_run_state.running_metrics_timer,
-                    running_metrics,
-                ) {
-                    goose_attack_run_state.running_metrics_timer = std::time::Instant::now();
-                    goose_attack_run_state.display_running_metrics = true;
-                }
-            };
-            // Load messages from user threads until the receiver queue is empty.
-            self.receive_metrics(goose_attack_run_state, flush).await?;
-        }
+/// Tracks scenario iterations started by an arrival rate test plan.
+///
+/// When a load test is configured with `--arrival-rate`, Goose schedules scenario iterations
+/// at the configured rate regardless of how quickly the load tested server responds. Each
+/// scheduled iteration is started by an idle [`GooseUser`](../goose/struct.GooseUser.html),
+/// or by launching a new one. If all users are busy and no more users can be launched, the
+/// iteration is dropped. With `--arrival-requests`, requests are scheduled instead of
+/// iterations.
+///
+/// # Example
+/// When viewed with [`std::fmt::Display`], [`GooseArrivalRateMetrics`] are displayed in
+/// a table:
+/// ```text
+///  === ARRIVAL RATE METRICS ===
+///  ------------------------------------------------------------------------------
+///  Iterations               |     # scheduled |       # started |       # dropped
+///  ------------------------------------------------------------------------------
+///  Total                    |           6,000 |           5,870 |     130 (2.17%)
+///  Per second               |          100.00 |           97.83 |            2.17
+/// ```
+#[derive(Debug, Clone, Default, Serialize, Deserialize, Eq, PartialEq)]
+pub struct GooseArrivalRateMetrics {
+    /// How many scenario iterations the test plan scheduled.
+    pub scheduled: usize,
+    /// How many scheduled scenario iterations were started by a GooseUser.
+    pub started: usize,
+    /// How many scheduled scenario iterations were dropped as no GooseUser was available.
+    pub dropped: usize,
+    /// Whether requests were scheduled instead of scenario iterations.
+    pub requests: bool,
+}
 
-        // If enabled, display running metrics after sync
-        if goose_attack_run_state.display_running_metrics {
-            goose_attack_run_state.display_running_metrics = false;
-            self.update_duration();
-            self.metrics.print_running();
-        }
+/// Tracks the load levels probed by a breakpoint search.
+///
+/// When a load test is configured with `--breakpoint`, Goose keeps stepping up the number of
+/// users (or with `--breakpoint-arrival-rate` the number of iterations started per second)
+/// until the error rate exceeds `--breakpoint-error-rate`, the 95th percentile response time
+/// exceeds `--breakpoint-response-time`, or throughput stops increasing for two probes in a row.
+/// The last level probed without hitting any of these limits (or when throughput stopped
+/// increasing, the level with the best throughput) is reported as the maximum sustainable
+/// capacity.
+///
+/// # Example
+/// When viewed with [`std::fmt::Display`], [`GooseBreakpointMetrics`] are displayed in
+/// a table:
+/// ```text
+///  === BREAKPOINT METRICS ===
+///  ------------------------------------------------------------------------------
+///  Users                    |           req/s |         error % |  95th %ile (ms)
+///  ------------------------------------------------------------------------------
+///  10                       |           98.50 |            0.00 |              12
+///  20                       |          196.20 |            0.00 |              14
+///  30                       |          211.90 |            7.35 |             120
+///  ------------------------------------------------------------------------------
+///  Max sustainable capacity: 20 users
+///  Stopped: error rate of 7.35% is above 5%
+/// ```
+#[derive(Debug, Clone, Default, Serialize, Deserialize, PartialEq)]
+pub struct GooseBreakpointMetrics {
+    /// Whether the breakpoint search stepped iterations per second instead of users.
+    pub arrival_rate: bool,
+    /// Each load level that was probed, in order.
+    pub probes: Vec<GooseBreakpointProbe>,
+    /// The highest load level that was probed without hitting a limit, if any.
+    pub capacity: Option<usize>,
+    /// Why the breakpoint search stopped, if it did.
+    pub reason: Option<String>,
+}
 
-        Ok(())
+/// The metrics collected while probing one load level of a breakpoint search.
+#[derive(Debug, Clone, Default, Serialize, Deserialize, PartialEq)]
+pub struct GooseBreakpointProbe {
+    /// How many users were running, or how many iterations were started per second.
+    pub level: usize,
+    /// How many requests per second completed while probing this level.
+    pub requests_per_second: f32,
+    /// The percentage of requests that failed while probing this level.
+    pub error_rate: f32,
+    /// The 95th percentile response time while probing this level, in milliseconds.
+    pub response_time_95: usize,
+}
+
+/// The request metrics collected when a breakpoint search started probing a load level, used
+/// to measure only the requests made while probing that level.
+#[derive(Debug, Clone)]
+pub(crate) struct GooseBreakpointSnapshot {
+    /// When probing started.
+    started: std::time::Instant,
+    /// How many requests had completed.
+    requests: usize,
+    /// How many requests had failed.
+    fails: usize,
+    /// Histogram of the response times of all requests.
+    times: GooseHistogram,
+}
+impl GooseBreakpointSnapshot {
+    /// Record the request metrics collected so far.
+    pub(crate) fn new(requests: &HashMap<String, GooseRequestMetricAggregate>) -> Self {
+        let mut snapshot = GooseBreakpointSnapshot {
+            started: std::time::Instant::now(),
+            requests: 0,
+            fails: 0,
+            times: GooseHistogram::default(),
+        };
+        for request in requests.values() {
+            snapshot.requests += request.success_count + request.fail_count;
+            snapshot.fails += request.fail_count;
+            snapshot.times.merge(&request.raw_data.times);
+        }
+        snapshot
+    }
+
+    /// Measure the requests made since this snapshot was recorded.
+    pub(crate) fn probe(
+        &self,
+        level: usize,
+        requests: &HashMap<String, GooseRequestMetricAggregate>,
+    ) -> GooseBreakpointProbe {
+        let current = GooseBreakpointSnapshot::new(requests);
+        let total_requests = current.requests.saturating_sub(self.requests);
+        let fails = current.fails.saturating_sub(self.fails);
+
+        // Only count response times of requests made while probing.
+        let mut times = current.times;
+        times.subtract(&self.times);
+
+        let seconds = self.started.elapsed().as_secs_f32();
+        GooseBreakpointProbe {
+            level,
+            requests_per_second: if seconds > 0.0 {
+                total_requests as f32 / seconds
+            } else {
+                0.0
+            },
+            error_rate: if total_requests > 0 {
+                fails as f32 / total_requests as f32 * 100.0
+            } else {
+                0.0
+            },
+            response_time_95: response_time_percentile(&times, total_requests, 0, usize::MAX, 0.95),
+        }
+    }
+}
+
+/// A metric that a [`GooseThreshold`] is evaluated against.
+#[derive(Debug, Clone, Copy, Eq, PartialEq, Serialize, Deserialize)]
+pub enum GooseThresholdMetric {
+    /// The 50th percentile response time, in milliseconds.
+    P50,
+    /// The 95th percentile response time, in milliseconds.
+    P95,
+    /// The 99th percentile response time, in milliseconds.
+    P99,
+    /// The slowest response time, in milliseconds.
+    Max,
+    /// The percentage of requests that failed.
+    ErrorRate,
+    /// How many requests completed per second.
+    RequestsPerSecond,
+}
+impl fmt::Display for GooseThresholdMetric {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            GooseThresholdMetric::P50 => write!(f, "p50"),
+            GooseThresholdMetric::P95 => write!(f, "p95"),
+            GooseThresholdMetric::P99 => write!(f, "p99"),
+            GooseThresholdMetric::Max => write!(f, "max"),
+            GooseThresholdMetric::ErrorRate => write!(f, "error-rate"),
+            GooseThresholdMetric::RequestsPerSecond => write!(f, "rps"),
+        }
+    }
+}
+impl FromStr for GooseThresholdMetric {
+    type Err = GooseError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_lowercase().as_str() {
+            "p50" | "median" => Ok(GooseThresholdMetric::P50),
+            "p95" => Ok(GooseThresholdMetric::P95),
+            "p99" => Ok(GooseThresholdMetric::P99),
+            "max" | "maximum" => Ok(GooseThresholdMetric::Max),
+            "error-rate" | "errors" => Ok(GooseThresholdMetric::ErrorRate),
+            "rps" | "requests-per-second" => Ok(GooseThresholdMetric::RequestsPerSecond),
+            _ => Err(GooseError::InvalidOption {
+                option: format!("GooseThresholdMetric::{:?}", s),
+                value: s.to_string(),
+                detail: "Invalid threshold metric, expected: p50, p95, p99, max, error-rate or rps"
+                    .to_string(),
+            }),
+        }
+    }
+}
+
+impl GooseThresholdMetric {
+    /// Measure this metric for the requests matching `request`, either by `{method} {name}`
+    /// (ie `GET /`) or by name (ie `/`), or for all requests if `request` is `None`. Returns
+    /// `None` if no matching requests were made.
+    pub(crate) fn measure(
+        &self,
+        request: Option<&str>,
+        requests: &HashMap<String, GooseRequestMetricAggregate>,
+        duration: usize,
+    ) -> Option<f32> {
+        GooseThresholdSample::collect(request, requests, self.uses_histogram())
+            .map(|sample| self.measure_sample(&sample, duration))
+    }
+
+    /// Whether this metric is measured from the response time histogram.
+    fn uses_histogram(&self) -> bool {
+        matches!(
+            self,
+            GooseThresholdMetric::P50
+                | GooseThresholdMetric::P95
+                | GooseThresholdMetric::P99
+                | GooseThresholdMetric::Max
+        )
+    }
+
+    /// Measure this metric for an already collected sample of requests.
+    fn measure_sample(&self, sample: &GooseThresholdSample, duration: usize) -> f32 {
+        match self {
+            GooseThresholdMetric::P50 => sample.percentile(50.0),
+            GooseThresholdMetric::P95 => sample.percentile(95.0),
+            GooseThresholdMetric::P99 => sample.percentile(99.0),
+            GooseThresholdMetric::Max => sample.maximum as f32,
+            GooseThresholdMetric::ErrorRate => sample.error_rate(),
+            GooseThresholdMetric::RequestsPerSecond => sample.requests_per_second(duration),
+        }
+    }
+}
+
+/// The combined request metrics a [`GooseThresholdMetric`] or [`GooseBaselineMetric`] is
+/// measured from.
+///
+/// Abort rules keep one sample per rule instead of a copy of all request metrics, and the
+/// response time histogram is only collected for metrics that need it.
+#[derive(Debug, Clone, Default)]
+pub(crate) struct GooseThresholdSample {
+    /// How many matching requests were made.
+    total: usize,
+    /// How many matching requests failed.
+    fails: usize,
+    /// Combined response time of all matching requests.
+    total_time: usize,
+    /// How many response times were recorded.
+    counter: usize,
+    /// The shortest response time.
+    minimum: usize,
+    /// The longest response time.
+    maximum: usize,
+    /// Histogram of response times, empty if the metric doesn't need it.
+    times: GooseHistogram,
+}
+impl GooseThresholdSample {
+    /// Combine the request metrics matching `request`, either by `{method} {name}` (ie
+    /// `GET /`) or by name (ie `/`), or all requests if `request` is `None`. Returns `None`
+    /// if no matching requests were made.
+    fn collect(
+        request: Option<&str>,
+        requests: &HashMap<String, GooseRequestMetricAggregate>,
+        histogram: bool,
+    ) -> Option<Self> {
+        // Match requests by "{method} {name}", or otherwise only by name.
+        let matching: Vec<&GooseRequestMetricAggregate> = match request {
+            Some(request) => match requests.get(request) {
+                Some(aggregate) => vec![aggregate],
+                None => requests
+                    .values()
+                    .filter(|aggregate| aggregate.path == request)
+                    .collect(),
+            },
+            None => requests.values().collect(),
+        };
+
+        if matching.is_empty() {
+            return None;
+        }
+        let mut sample = GooseThresholdSample {
+            minimum: usize::MAX,
+            ..Default::default()
+        };
+        for aggregate in matching {
+            sample.total += aggregate.success_count + aggregate.fail_count;
+            sample.fails += aggregate.fail_count;
+            sample.total_time += aggregate.raw_data.total_time;
+            sample.counter += aggregate.raw_data.counter;
+            sample.minimum = sample.minimum.min(aggregate.raw_data.minimum_time);
+            sample.maximum = sample.maximum.max(aggregate.raw_data.maximum_time);
+            if histogram {
+                sample.times.merge(&aggregate.raw_data.times);
+            }
+        }
+        Some(sample)
+    }
+
+    /// The average response time, in milliseconds.
+    fn average(&self) -> f32 {
+        if self.counter > 0 {
+            self.total_time as f32 / self.counter as f32
+        } else {
+            0.0
+        }
+    }
+
+    /// The response time of a percentile, from greater than 0 up to 100, in milliseconds.
+    fn percentile(&self, percentile: f32) -> f32 {
+        response_time_percentile(
+            &self.times,
+            self.total,
+            self.minimum,
+            self.maximum,
+            percentile / 100.0,
+        ) as f32
+    }
+
+    /// The percentage of requests that failed.
+    fn error_rate(&self) -> f32 {
+        if self.total > 0 {
+            self.fails as f32 / self.total as f32 * 100.0
+        } else {
+            0.0
+        }
+    }
+
+    /// How many requests completed per second.
+    fn requests_per_second(&self, duration: usize) -> f32 {
+        per_second_calculations(duration, self.total, 0).0
+    }
+
+    /// Returns the requests made since the `previous` sample was collected, or `None` if no
+    /// requests were made.
+    fn since(&self, previous: &GooseThresholdSample) -> Option<Self> {
+        let total = self.total.saturating_sub(previous.total);
+        if total == 0 {
+            return None;
+        }
+        let mut times = self.times.clone();
+        times.subtract(&previous.times);
+        // The exact minimum and maximum are lost, use the histogram buckets instead.
+        let minimum = times.iter().next().map_or(0, |(time, _)| *time);
+        let maximum = times.iter().next_back().map_or(0, |(time, _)| *time);
+        Some(GooseThresholdSample {
+            total,
+            fails: self.fails.saturating_sub(previous.fails),
+            total_time: self.total_time.saturating_sub(previous.total_time),
+            counter: self.counter.saturating_sub(previous.counter),
+            minimum,
+            maximum,
+            times,
+        })
+    }
+}
+
+/// A pass/fail threshold, evaluated against the metrics of a completed load test.
+///
+/// Thresholds are written as `[{request}:]{metric}{<|>}{value}`, for example `p95<500`
+/// requires the 95th percentile response time of all requests to be less than 500
+/// milliseconds, while `GET /:error-rate<1` requires less than 1% of `GET /` requests to
+/// fail. Response times are in milliseconds, the error rate is a percentage, and `rps` is
+/// requests per second.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct GooseThreshold {
+    /// The request this threshold applies to, either by `{method} {name}` (ie `GET /`) or by
+    /// name (ie `/`). If not set, the threshold applies to all requests.
+    pub request: Option<String>,
+    /// The metric that is evaluated.
+    pub metric: GooseThresholdMetric,
+    /// If true the metric must be greater than the value, otherwise it must be less.
+    pub greater_than: bool,
+    /// The value the metric is compared to.
+    pub value: f32,
+}
+impl fmt::Display for GooseThreshold {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        if let Some(request) = self.request.as_ref() {
+            write!(f, "{}:", request)?;
+        }
+        let comparison = if self.greater_than { ">" } else { "<" };
+        write!(f, "{}{}{}", self.metric, comparison, self.value)
+    }
+}
+impl GooseThreshold {
+    /// Whether the measured value meets this threshold.
+    pub(crate) fn passes(&self, value: f32) -> bool {
+        if self.greater_than {
+            value > self.value
+        } else {
+            value < self.value
+        }
+    }
+}
+impl FromStr for GooseThreshold {
+    type Err = GooseError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        // A request name can include ":" so match the metric after the last ":", white space
+        // and an optional unit are ignored.
+        let re =
+            Regex::new(r"^\s*((.+):)?\s*([a-zA-Z0-9-]+)\s*(<|>)\s*(\d+(\.\d+)?)\s*(ms|%)?\s*$")
+                .unwrap();
+        if let Some(cap) = re.captures(s) {
+            Ok(GooseThreshold {
+                request: cap
+                    .get(2)
+                    .map(|request| request.as_str().trim().to_string()),
+                metric: cap[3].parse::<GooseThresholdMetric>()?,
+                greater_than: &cap[4] == ">",
+                value: cap[5].parse::<f32>().expect("failed to convert \\d to f32"),
+            })
+        } else {
+            Err(GooseError::InvalidOption {
+                option: "`configuration.thresholds`".to_string(),
+                value: s.to_string(),
+                detail: "invalid threshold, expected format: \"{request}:{metric}<{value}\", ie \"GET /:p95<500\"".to_string(),
+            })
+        }
+    }
+}
+
+/// A list of pass/fail thresholds, evaluated against the metrics of a completed load test.
+#[derive(Default, Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct GooseThresholds {
+    pub thresholds: Vec<GooseThreshold>,
+}
+/// Implement [`FromStr`] to convert a `"p95<500;error-rate<1"` semicolon separated string
+/// to a list of thresholds.
+impl FromStr for GooseThresholds {
+    type Err = GooseError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut thresholds = Vec::new();
+        for line in s.split(';').filter(|line| !line.trim().is_empty()) {
+            match line.parse::<GooseThreshold>() {
+                Ok(threshold) => thresholds.push(threshold),
+                Err(e) => {
+                    // Logger isn't initialized yet, provide helpful debug output.
+                    eprintln!(
+                        "ERROR: invalid `configuration.thresholds` value: '{}'",
+                        line
+                    );
+                    eprintln!("  Expected format: --thresholds \"{{threshold}};{{threshold}}\"");
+                    eprintln!("    each {{threshold}} is \"{{metric}}<{{value}}\" or \"{{metric}}>{{value}}\", ie \"p95<500\"");
+                    eprintln!("    {{metric}} can be p50, p95, p99, max, error-rate or rps");
+                    eprintln!("    prefix with \"{{request}}:\" to only apply to one request, ie \"GET /:p95<500\"");
+                    return Err(e);
+                }
+            }
+        }
+        Ok(GooseThresholds { thresholds })
+    }
+}
+
+/// The result of evaluating a [`GooseThreshold`] at the end of a load test.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct GooseThresholdResult {
+    /// The threshold that was evaluated.
+    pub threshold: GooseThreshold,
+    /// The measured value of the metric, or `None` if no matching requests were made.
+    pub value: Option<f32>,
+    /// Whether or not the threshold was met.
+    pub passed: bool,
+}
+impl GooseThresholdResult {
+    /// Evaluate a threshold against the requests made during a load test.
+    pub(crate) fn evaluate(
+        threshold: &GooseThreshold,
+        requests: &HashMap<String, GooseRequestMetricAggregate>,
+        duration: usize,
+    ) -> Self {
+        let value = threshold
+            .metric
+            .measure(threshold.request.as_deref(), requests, duration);
+
+        // A threshold can't be met if no matching requests were made.
+        let passed = value.is_some_and(|value| threshold.passes(value));
+
+        GooseThresholdResult {
+            threshold: threshold.clone(),
+            value,
+            passed,
+        }
+    }
+}
+
+/// A rule that aborts a running load test if a [`GooseThreshold`] is continuously breached.
+///
+/// Rules are written as `{threshold},{duration}`, for example `error-rate<50,30s` aborts the
+/// load test after more than 50% of requests have been failing for 30 seconds.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct GooseAbortRule {
+    /// The threshold that must be met while the load test is running.
+    pub threshold: GooseThreshold,
+    /// How many seconds the threshold must be breached before aborting the load test.
+    pub duration: usize,
+}
+impl fmt::Display for GooseAbortRule {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{},{}s", self.threshold, self.duration)
+    }
+}
+impl FromStr for GooseAbortRule {
+    type Err = GooseError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        // A request name can include "," so split the duration after the last ",".
+        let re = Regex::new(r"^(.+),\s*(\d+[hms]?(\d+[ms]?)?(\d+s?)?)\s*$").unwrap();
+        if let Some(cap) = re.captures(s) {
+            Ok(GooseAbortRule {
+                threshold: cap[1].parse::<GooseThreshold>()?,
+                duration: util::parse_timespan(&cap[2]),
+            })
+        } else {
+            Err(GooseError::InvalidOption {
+                option: "`configuration.abort_on_fail`".to_string(),
+                value: s.to_string(),
+                detail: "invalid abort rule, expected format: \"{threshold},{duration}\", ie \"error-rate<50,30s\"".to_string(),
+            })
+        }
+    }
+}
+
+/// A list of rules evaluated while a load test is running, aborting the load test if any
+/// threshold is continuously breached.
+#[derive(Default, Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct GooseAbortRules {
+    pub rules: Vec<GooseAbortRule>,
+}
+/// Implement [`FromStr`] to convert a `"error-rate<50,30s;p95<2000,1m"` semicolon separated
+/// string to a list of abort rules.
+impl FromStr for GooseAbortRules {
+    type Err = GooseError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut rules = Vec::new();
+        for line in s.split(';').filter(|line| !line.trim().is_empty()) {
+            match line.parse::<GooseAbortRule>() {
+                Ok(rule) => rules.push(rule),
+                Err(e) => {
+                    // Logger isn't initialized yet, provide helpful debug output.
+                    eprintln!(
+                        "ERROR: invalid `configuration.abort_on_fail` value: '{}'",
+                        line
+                    );
+                    eprintln!("  Expected format: --abort-on-fail \"{{threshold}},{{duration}};{{threshold}},{{duration}}\"");
+                    eprintln!("    each {{threshold}} is \"{{metric}}<{{value}}\" or \"{{metric}}>{{value}}\", ie \"error-rate<50\"");
+                    eprintln!("    {{metric}} can be p50, p95, p99, max, error-rate or rps");
+                    eprintln!("    {{duration}} is how long the threshold is breached before aborting, ie \"30s\"");
+                    return Err(e);
+                }
+            }
+        }
+        Ok(GooseAbortRules { rules })
+    }
+}
+
+/// Tracks abort rules while a load test is running.
+#[derive(Debug, Clone)]
+pub(crate) struct GooseAbortSnapshot {
+    /// When the rules were last checked.
+    checked: std::time::Instant,
+    /// For each rule, the matching requests collected when the rules were last checked.
+    samples: Vec<Option<GooseThresholdSample>>,
+    /// For each rule, when it started being breached and the matching requests collected at
+    /// that time.
+    breached: Vec<Option<(std::time::Instant, Option<GooseThresholdSample>)>>,
+}
+impl GooseAbortSnapshot {
+    /// Record the request metrics collected so far.
+    pub(crate) fn new(
+        rules: &GooseAbortRules,
+        requests: &HashMap<String, GooseRequestMetricAggregate>,
+    ) -> Self {
+        GooseAbortSnapshot {
+            checked: std::time::Instant::now(),
+            samples: GooseAbortSnapshot::collect(rules, requests),
+            breached: vec![None; rules.rules.len()],
+        }
+    }
+
+    /// Collect the requests matching each rule.
+    fn collect(
+        rules: &GooseAbortRules,
+        requests: &HashMap<String, GooseRequestMetricAggregate>,
+    ) -> Vec<Option<GooseThresholdSample>> {
+        rules
+            .rules
+            .iter()
+            .map(|rule| {
+                GooseThresholdSample::collect(
+                    rule.threshold.request.as_deref(),
+                    requests,
+                    rule.threshold.metric.uses_histogram(),
+                )
+            })
+            .collect()
+    }
+
+    /// Check each rule against the requests made since it was last checked, returning the
+    /// first rule that has been breached for longer than its duration.
+    pub(crate) fn check<'a>(
+        &mut self,
+        rules: &'a GooseAbortRules,
+        requests: &HashMap<String, GooseRequestMetricAggregate>,
+    ) -> Option<&'a GooseAbortRule> {
+        // Rules are checked at most once per second.
+        if !util::timer_expired(self.checked, 1) {
+            return None;
+        }
+
+        let seconds = self.checked.elapsed().as_secs() as usize;
+        let samples = GooseAbortSnapshot::collect(rules, requests);
+        let mut aborted = None;
+        for (((rule, breached), sample), previous_sample) in rules
+            .rules
+            .iter()
+            .zip(self.breached.iter_mut())
+            .zip(samples.iter())
+            .zip(self.samples.iter())
+        {
+            let threshold = &rule.threshold;
+            match breached {
+                // Once breached, the rule is evaluated against all requests made since then.
+                Some((started, previous)) => {
+                    let passed = match sample_since(sample, previous) {
+                        Some(since) => threshold.passes(
+                            threshold
+                                .metric
+                                .measure_sample(&since, started.elapsed().as_secs() as usize),
+                        ),
+                        None => false,
+                    };
+                    if passed {
+                        *breached = None;
+                    } else if aborted.is_none()
+                        && util::timer_expired(*started, rule.duration.max(1))
+                    {
+                        aborted = Some(rule);
+                    }
+                }
+                // Otherwise the rule is evaluated against requests made since the last check,
+                // ignoring checks where no matching requests were made.
+                None => {
+                    if let Some(window) = sample_since(sample, previous_sample) {
+                        let value = threshold.metric.measure_sample(&window, seconds);
+                        if !threshold.passes(value) {
+                            *breached = Some((self.checked, previous_sample.clone()));
+                        }
+                    }
+                }
+            }
+        }
+        self.checked = std::time::Instant::now();
+        self.samples = samples;
+        aborted
+    }
+}
+
+/// Returns the requests made since the `previous` sample was collected, if any.
+fn sample_since(
+    current: &Option<GooseThresholdSample>,
+    previous: &Option<GooseThresholdSample>,
+) -> Option<GooseThresholdSample> {
+    match (current, previous) {
+        (Some(current), Some(previous)) => current.since(previous),
+        (Some(current), None) if current.total > 0 => Some(current.clone()),
+        _ => None,
+    }
+}
+
+/// Returns the requests made since the `previous` request metrics were collected.
+pub(crate) fn requests_since(
+    previous: &HashMap<String, GooseRequestMetricAggregate>,
+    current: &HashMap<String, GooseRequestMetricAggregate>,
+) -> HashMap<String, GooseRequestMetricAggregate> {
+    let mut requests = HashMap::new();
+    for (key, request) in current {
+        let mut request = request.clone();
+        if let Some(previous) = previous.get(key) {
+            request.success_count = request.success_count.saturating_sub(previous.success_count);
+            request.fail_count = request.fail_count.saturating_sub(previous.fail_count);
+            request.fail_times.subtract(&previous.fail_times);
+            let raw_data = &mut request.raw_data;
+            raw_data.times.subtract(&previous.raw_data.times);
+            raw_data.counter = raw_data.counter.saturating_sub(previous.raw_data.counter);
+            raw_data.total_time = raw_data
+                .total_time
+                .saturating_sub(previous.raw_data.total_time);
+            // The exact minimum and maximum are lost, use the histogram buckets instead.
+            raw_data.minimum_time = raw_data.times.iter().next().map_or(0, |(time, _)| *time);
+            raw_data.maximum_time = raw_data
+                .times
+                .iter()
+                .next_back()
+                .map_or(0, |(time, _)| *time);
+        }
+        if request.success_count + request.fail_count > 0 {
+            requests.insert(key.to_string(), request);
+        }
+    }
+    requests
+}
+
+/// The request metrics of a previous load test, loaded from a baseline file to compare
+/// against.
+///
+/// A baseline file is the json-serialized [`GooseMetrics`] of a previous load test, as
+/// written with `--baseline-file`.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct GooseBaseline {
+    /// How many seconds the baseline load test ran.
+    pub duration: usize,
+    /// The request metrics of the baseline load test.
+    pub requests: HashMap<String, GooseRequestMetricAggregate>,
+}
+impl GooseBaseline {
+    /// Load a baseline file.
+    pub(crate) async fn load(path: &str) -> Result<Self, GooseError> {
+        let contents = match tokio::fs::read_to_string(path).await {
+            Ok(contents) => contents,
+            Err(e) => {
+                return Err(GooseError::InvalidOption {
+                    option: "--baseline".to_string(),
+                    value: path.to_string(),
+                    detail: format!("Failed to read baseline file: {}", e),
+                })
+            }
+        };
+        match serde_json::from_str(&contents) {
+            Ok(baseline) => Ok(baseline),
+            Err(e) => Err(GooseError::InvalidOption {
+                option: "--baseline".to_string(),
+                value: path.to_string(),
+                detail: format!("Failed to parse baseline file: {}", e),
+            }),
+        }
+    }
+}
+
+/// A metric that is compared to the baseline.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub enum GooseBaselineMetric {
+    /// The average response time, in milliseconds.
+    Average,
+    /// A configured percentile response time, in milliseconds.
+    Percentile(f32),
+    /// The percentage of requests that failed.
+    ErrorRate,
+    /// How many requests completed per second.
+    RequestsPerSecond,
+}
+impl fmt::Display for GooseBaselineMetric {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            GooseBaselineMetric::Average => write!(f, "avg"),
+            GooseBaselineMetric::Percentile(percentile) => write!(f, "p{}", percentile),
+            GooseBaselineMetric::ErrorRate => write!(f, "error-rate"),
+            GooseBaselineMetric::RequestsPerSecond => write!(f, "rps"),
+        }
+    }
+}
+impl GooseBaselineMetric {
+    /// Measure this metric for the requests matching `request`, or for all requests if
+    /// `request` is `None`. Returns `None` if no matching requests were made.
+    fn measure(
+        &self,
+        request: Option<&str>,
+        requests: &HashMap<String, GooseRequestMetricAggregate>,
+        duration: usize,
+    ) -> Option<f32> {
+        let histogram = matches!(self, GooseBaselineMetric::Percentile(_));
+        GooseThresholdSample::collect(request, requests, histogram).map(|sample| match self {
+            GooseBaselineMetric::Average => sample.average(),
+            GooseBaselineMetric::Percentile(percentile) => sample.percentile(*percentile),
+            GooseBaselineMetric::ErrorRate => sample.error_rate(),
+            GooseBaselineMetric::RequestsPerSecond => sample.requests_per_second(duration),
+        })
+    }
+}
+
+/// How one metric changed compared to the baseline.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct GooseBaselineDelta {
+    /// The request that was compared, or `None` for all requests aggregated together.
+    pub request: Option<String>,
+    /// The metric that was compared.
+    pub metric: GooseBaselineMetric,
+    /// The value measured by the baseline load test, if it made this request.
+    pub baseline: Option<f32>,
+    /// The value measured by this load test, if it made this request.
+    pub current: Option<f32>,
+    /// Whether or not the metric got worse by more than the configured tolerance.
+    pub regression: bool,
+}
+impl GooseBaselineDelta {
+    /// Compare one metric of a request to the baseline.
+    ///
+    /// Response times regress if they increase and requests per second regress if they
+    /// decrease by more than `tolerance` percent. The error rate is already a percentage,
+    /// so it regresses if it increases by more than `tolerance` percentage points.
+    pub(crate) fn compare(
+        request: Option<&str>,
+        metric: GooseBaselineMetric,
+        baseline: &GooseBaseline,
+        requests: &HashMap<String, GooseRequestMetricAggregate>,
+        duration: usize,
+        tolerance: usize,
+    ) -> Self {
+        let baseline_value = metric.measure(request, &baseline.requests, baseline.duration);
+        let current_value = metric.measure(request, requests, duration);
+        let tolerance = tolerance as f32;
+        let regression = match (baseline_value, current_value) {
+            (Some(baseline), Some(current)) => match metric {
+                GooseBaselineMetric::ErrorRate => current - baseline > tolerance,
+                GooseBaselineMetric::RequestsPerSecond => {
+                    current < baseline * (1.0 - tolerance / 100.0)
+                }
+                _ => current > baseline * (1.0 + tolerance / 100.0),
+            },
+            _ => false,
+        };
+        GooseBaselineDelta {
+            request: request.map(|request| request.to_string()),
+            metric,
+            baseline: baseline_value,
+            current: current_value,
+            regression,
+        }
+    }
+
+    /// How much the metric changed, as a percentage of the baseline, or in percentage
+    /// points for the error rate.
+    pub fn change(&self) -> Option<f32> {
+        match (self.baseline, self.current) {
+            (Some(baseline), Some(current)) => match self.metric {
+                GooseBaselineMetric::ErrorRate => Some(current - baseline),
+                _ if baseline > 0.0 => Some((current - baseline) / baseline * 100.0),
+                _ => None,
+            },
+            _ => None,
+        }
+    }
+
+    /// Format the change for display, ie `+12.50%` or `-0.50pp`.
+    pub(crate) fn fmt_change(&self) -> String {
+        match self.change() {
+            Some(change) => {
+                let unit = if self.metric == GooseBaselineMetric::ErrorRate {
+                    "pp"
+                } else {
+                    "%"
+                };
+                format!("{:+.2}{}", change, unit)
+            }
+            None => "-".to_string(),
+        }
+    }
+}
+
+impl GooseAttack {
+    // If metrics are enabled, synchronize metrics from child threads to the parent. If
+    // flush is true all metrics will be received regardless of how long it takes. If
+    // flush is false, metrics will only be received for up to 400 ms before exiting to
+    // continue on the next call to this function.
+    pub(crate) async fn sync_metrics(
+        &mut self,
+        goose_attack_run_state: &mut GooseAttackRunState,
+        flush: bool,
+    ) -> Result<(), GooseError> {
+        if !self.configuration.no_metrics {
+            // Update timers if displaying running metrics.
+            if let Some(running_metrics) = self.configuration.running_metrics {
+                if util::timer_expired(
+                    goose_attack_run_state.running_metrics_timer,
+                    running_metrics,
+                ) {
+                    goose_attack_run_state.running_metrics_timer = std::time::Instant::now();
+                    goose_attack_run_state.display_running_metrics = true;
+                }
+            };
+            // Load messages from user threads until the receiver queue is empty.
+            self.receive_metrics(goose_attack_run_state, flush).await?;
+
+            // If enabled, periodically push metrics to the metrics sink.
+            let users = goose_attack_run_state.running_users();
+            if let Some(metrics_sink) = goose_attack_run_state.metrics_sink.as_mut() {
+                metrics_sink.push_if_due(&self.metrics, users);
+            }
+
+            // If enabled, periodically export metrics to OpenTelemetry.
+            if let Some(otlp_exporter) = goose_attack_run_state.otlp_exporter.as_mut() {
+                otlp_exporter.export_if_due(&self.metrics, users);
+            }
+
+            // If enabled, periodically append to the Locust-compatible CSV history.
+            if let Some(locust_history) = goose_attack_run_state.locust_history.as_mut() {
+                locust_history.append_if_due(&self.metrics, users).await;
+            }
+        }
+
+        // If enabled, display running metrics after sync
+        if goose_attack_run_state.display_running_metrics {
+            goose_attack_run_state.display_running_metrics = false;
+            self.update_duration();
+            self.metrics.print_running();
+        }
+
+        Ok(())
     }
 
     // When the [`GooseAttack`](./struct.GooseAttack.html) goes from the `Increasing`
@@ -2689,8 +4976,12 @@ impl GooseAttack {
             self.sync_metrics(goose_attack_run_state, true).await?;
 
             goose_attack_run_state.all_users_spawned = true;
-            // Only reset metrics on startup if not using `--test-plan` or `--iterations`.
-            if self.configuration.test_plan.is_none() && self.configuration.iterations == 0 {
+            // Only reset metrics on startup if not using `--test-plan`, `--iterations` or
+            // `--breakpoint`.
+            if self.configuration.test_plan.is_none()
+                && self.configuration.iterations == 0
+                && self.configuration.breakpoint.is_none()
+            {
                 let users = self.configuration.users.unwrap();
                 // Only reset metrics on startup if not using `--no-reset-metrics`.
                 if !self.configuration.no_reset_metrics {
@@ -2713,7 +5004,22 @@ impl GooseAttack {
                         }
                     }
 
+                    // Push the metrics collected so far to the metrics sink, if enabled.
+                    let running_users = goose_attack_run_state.running_users();
+                    if let Some(metrics_sink) = goose_attack_run_state.metrics_sink.as_mut() {
+                        metrics_sink.reset(&self.metrics, running_users);
+                    }
+                    // Export the metrics collected so far to OpenTelemetry, if enabled.
+                    if let Some(otlp_exporter) = goose_attack_run_state.otlp_exporter.as_mut() {
+                        otlp_exporter.reset(&self.metrics, running_users);
+                    }
+                    // Append the metrics collected so far to the CSV history, if enabled.
+                    if let Some(locust_history) = goose_attack_run_state.locust_history.as_mut() {
+                        locust_history.reset(&self.metrics, running_users).await;
+                    }
+
                     self.metrics.requests = HashMap::new();
+                    self.metrics.custom = BTreeMap::new();
                     self.metrics
                         .initialize_scenario_metrics(&self.scenarios, &self.configuration);
                     self.metrics.initialize_transaction_metrics(
@@ -2724,6 +5030,14 @@ impl GooseAttack {
 
                     // Restart the timer now that all threads are launched.
                     self.started = Some(std::time::Instant::now());
+
+                    // Check abort-on-fail rules against the reset metrics.
+                    if let Some(abort_on_fail) = self.configuration.abort_on_fail.as_ref() {
+                        goose_attack_run_state.abort_snapshot = Some(GooseAbortSnapshot::new(
+                            abort_on_fail,
+                            &self.metrics.requests,
+                        ));
+                    }
                 } else if goose_attack_run_state.active_users < users {
                     println!(
                         "{} of {} users hatched, timer expired.\n",
@@ -2747,43 +5061,91 @@ impl GooseAttack {
     // `GooseMetrics.requests` `HashMap`, merging if already existing, or creating new.
     // Also writes it to the request_file if enabled.
     async fn record_request_metric(&mut self, request_metric: &GooseRequestMetric) {
-        let key = format!("{} {}", request_metric.raw.method, request_metric.name);
-        let mut merge_request = match self.metrics.requests.get(&key) {
-            Some(m) => m.clone(),
-            None => GooseRequestMetricAggregate::new(
-                &request_metric.name,
-                request_metric.raw.method.clone(),
-                0,
-            ),
-        };
+        let significant_digits = self
+            .configuration
+            .significant_digits
+            .unwrap_or(DEFAULT_SIGNIFICANT_DIGITS);
+        let status_codes = !self.configuration.no_status_codes;
+        merge_request_metric(
+            &mut self.metrics.requests,
+            request_metric,
+            significant_digits,
+            status_codes,
+        );
+        // Also record the request in the currently running test plan step.
+        if let Some(phase) = self.metrics.current_phase() {
+            merge_request_metric(
+                &mut phase.requests,
+                request_metric,
+                significant_digits,
+                status_codes,
+            );
+        }
+    }
 
-        // Handle a metrics update.
-        if request_metric.update {
-            if request_metric.success {
-                merge_request.success_count += 1;
-                merge_request.fail_count -= 1;
-            } else {
-                merge_request.success_count -= 1;
-                merge_request.fail_count += 1;
+    // Record a request in the metrics, and in the report graphs if enabled. If
+    // coordinated_omission_elapsed is non-zero, this was a statistically generated "request"
+    // to mitigate coordinated omission, so backfill with statistically generated metrics and
+    // return false. Otherwise return true as an actual request was recorded.
+    pub(crate) async fn record_request(&mut self, request_metric: &GooseRequestMetric) -> bool {
+        if request_metric.coordinated_omission_elapsed > 0 && request_metric.user_cadence > 0 {
+            // Build a statistically generated coordinated_omissiom metric starting with the
+            // metric that was sent by the affected GooseUser.
+            let mut co_metric = request_metric.clone();
+
+            // Use a signed integer as this value can drop below zero.
+            let mut response_time = request_metric.coordinated_omission_elapsed as i64
+                - request_metric.user_cadence as i64
+                - request_metric.response_time as i64;
+
+            loop {
+                // Backfill until reaching the expected request cadence.
+                if response_time > request_metric.response_time as i64 {
+                    co_metric.response_time = response_time as u64;
+                    self.record_request_metric(&co_metric).await;
+                    response_time -= request_metric.user_cadence as i64;
+                } else {
+                    break;
+                }
             }
+            return false;
         }
-        // Store a new metric.
-        else {
-            merge_request.record_time(
+
+        // Merge the `GooseRequestMetric` into a `GooseRequestMetricAggregate` in
+        // `GooseMetrics.requests`.
+        self.record_request_metric(request_metric).await;
+
+        if !self.configuration.report_file.is_empty() {
+            let seconds_since_start = (request_metric.elapsed / 1000) as usize;
+
+            let key = format!("{} {}", request_metric.raw.method, request_metric.name);
+            self.graph_data
+                .record_requests_per_second(&key, seconds_since_start);
+            self.graph_data.record_average_response_time_per_second(
+                key.clone(),
+                seconds_since_start,
                 request_metric.response_time,
-                request_metric.coordinated_omission_elapsed > 0,
             );
-            if !self.configuration.no_status_codes {
-                merge_request.set_status_code(request_metric.status_code);
-            }
-            if request_metric.success {
-                merge_request.success_count += 1;
-            } else {
-                merge_request.fail_count += 1;
+            self.graph_data.record_response_bytes_per_second(
+                &key,
+                seconds_since_start,
+                request_metric.response_bytes as usize,
+            );
+            // Response time percentiles are graphed in the second the response was received, so
+            // each second can be closed once no more responses are expected for it.
+            self.graph_data.record_response_time_percentiles_per_second(
+                &key,
+                ((request_metric.elapsed + request_metric.response_time) / 1000) as usize,
+                request_metric.response_time,
+            );
+
+            if !request_metric.success {
+                self.graph_data
+                    .record_errors_per_second(&key, seconds_since_start);
             }
         }
 
-        self.metrics.requests.insert(key, merge_request);
+        true
     }
 
     // Receive metrics from [`GooseUser`](./goose/struct.GooseUser.html) threads. If flush
@@ -2811,54 +5173,12 @@ impl GooseAttack {
                         self.record_error(&request_metric, goose_attack_run_state);
                     }
 
-                    // If coordinated_omission_elapsed is non-zero, this was a statistically
-                    // generated "request" to mitigate coordinated omission, loop to backfill
-                    // with statistically generated metrics.
-                    if request_metric.coordinated_omission_elapsed > 0
-                        && request_metric.user_cadence > 0
-                    {
-                        // Build a statistically generated coordinated_omissiom metric starting
-                        // with the metric that was sent by the affected GooseUser.
-                        let mut co_metric = request_metric.clone();
-
-                        // Use a signed integer as this value can drop below zero.
-                        let mut response_time = request_metric.coordinated_omission_elapsed as i64
-                            - request_metric.user_cadence as i64
-                            - request_metric.response_time as i64;
-
-                        loop {
-                            // Backfill until reaching the expected request cadence.
-                            if response_time > request_metric.response_time as i64 {
-                                co_metric.response_time = response_time as u64;
-                                self.record_request_metric(&co_metric).await;
-                                response_time -= request_metric.user_cadence as i64;
-                            } else {
-                                break;
-                            }
-                        }
-                    // Otherwise this is an actual request, record it normally.
-                    } else {
-                        // Merge the `GooseRequestMetric` into a `GooseRequestMetricAggregate` in
-                        // `GooseMetrics.requests`, and write to the requests log if enabled.
-                        self.record_request_metric(&request_metric).await;
-
-                        if !self.configuration.report_file.is_empty() {
-                            let seconds_since_start = (request_metric.elapsed / 1000) as usize;
-
-                            let key =
-                                format!("{} {}", request_metric.raw.method, request_metric.name);
-                            self.graph_data
-                                .record_requests_per_second(&key, seconds_since_start);
-                            self.graph_data.record_average_response_time_per_second(
-                                key.clone(),
-                                seconds_since_start,
-                                request_metric.response_time,
-                            );
-
-                            if !request_metric.success {
-                                self.graph_data
-                                    .record_errors_per_second(&key, seconds_since_start);
-                            }
+                    // Record the request, and if it was an actual request and not one
+                    // statistically generated to mitigate coordinated omission, export a span
+                    // for it if enabled.
+                    if self.record_request(&request_metric).await {
+                        if let Some(otlp_exporter) = goose_attack_run_state.otlp_exporter.as_mut() {
+                            otlp_exporter.record_request(&request_metric);
                         }
                     }
                 }
@@ -2867,6 +5187,23 @@ impl GooseAttack {
                     self.metrics.transactions[raw_transaction.scenario_index]
                         [raw_transaction.transaction_index]
                         .set_time(raw_transaction.run_time, raw_transaction.success);
+                    // Also record the transaction in the currently running test plan step.
+                    if let Some(transaction) = self.metrics.current_phase().and_then(|phase| {
+                        phase
+                            .transactions
+                            .get_mut(raw_transaction.scenario_index)?
+                            .get_mut(raw_transaction.transaction_index)
+                    }) {
+                        transaction.set_time(raw_transaction.run_time, raw_transaction.success);
+                    }
+
+                    // If enabled, export a span for the transaction.
+                    if let Some(otlp_exporter) = goose_attack_run_state.otlp_exporter.as_mut() {
+                        otlp_exporter.record_transaction(
+                            &raw_transaction,
+                            &self.scenarios[raw_transaction.scenario_index].name,
+                        );
+                    }
 
                     if !self.configuration.report_file.is_empty() {
                         self.graph_data.record_transactions_per_second(
@@ -2878,12 +5215,51 @@ impl GooseAttack {
                     // Store a new metric.
                     self.metrics.scenarios[raw_scenario.index]
                         .update(raw_scenario.run_time, raw_scenario.user);
+                    // Also record the scenario in the currently running test plan step.
+                    if let Some(scenario) = self
+                        .metrics
+                        .current_phase()
+                        .and_then(|phase| phase.scenarios.get_mut(raw_scenario.index))
+                    {
+                        scenario.update(raw_scenario.run_time, raw_scenario.user);
+                    }
 
                     if !self.configuration.report_file.is_empty() {
                         self.graph_data
                             .record_scenarios_per_second((raw_scenario.elapsed / 1000) as usize);
                     }
                 }
+                GooseMetric::Custom(custom_metric) => {
+                    let significant_digits = self
+                        .configuration
+                        .significant_digits
+                        .unwrap_or(DEFAULT_SIGNIFICANT_DIGITS);
+                    let aggregate = self
+                        .metrics
+                        .custom
+                        .entry(custom_metric.name.clone())
+                        .or_insert_with(|| {
+                            GooseCustomMetricAggregate::new(
+                                &custom_metric.value,
+                                significant_digits,
+                            )
+                        });
+                    // A custom metric name can only be used for one kind of metric.
+                    if !aggregate.record(&custom_metric.value) {
+                        warn!(
+                            "ignoring {:?} for custom metric {}, which is a {}",
+                            custom_metric.value,
+                            custom_metric.name,
+                            aggregate.kind()
+                        );
+                    } else if !self.configuration.report_file.is_empty() {
+                        self.graph_data.record_custom_metric_per_second(
+                            &custom_metric.name,
+                            (custom_metric.elapsed / 1000) as usize,
+                            &custom_metric.value,
+                        );
+                    }
+                }
             }
             // Unless flushing all metrics, break out of receive loop after timeout.
             if !flush && util::ms_timer_expired(receive_started, receive_timeout) {
@@ -2928,6 +5304,11 @@ impl GooseAttack {
             }
         }
 
+        self.aggregate_error(raw_request);
+    }
+
+    // Aggregate errors to display in the error summary, if enabled.
+    pub(crate) fn aggregate_error(&mut self, raw_request: &GooseRequestMetric) {
         // If the error summary is disabled, return without collecting errors.
         if self.configuration.no_error_summary {
             return;
@@ -2963,615 +5344,823 @@ impl GooseAttack {
         };
     }
 
-    // Write an HTML-formatted report, if enabled.
-    pub(crate) async fn write_html_report(&mut self) -> Result<(), GooseError> {
-        // If enabled, try to create the report file to confirm access.
-        let report_file = match self.prepare_report_file().await {
-            Ok(f) => f,
-            Err(e) => {
+    // Write reports, if enabled, in the format selected by the extension of each report file.
+    pub(crate) async fn write_reports(&mut self) -> Result<(), GooseError> {
+        // Only prepare the report data if reports are enabled.
+        if self.configuration.report_file.is_empty() {
+            return Ok(());
+        }
+
+        let report_data = self.prepare_report_data();
+        for report_file in &self.configuration.report_file {
+            let format = report::ReportFormat::from_path(report_file);
+            let report = match format {
+                report::ReportFormat::Html => self.build_html_report(&report_data),
+                report::ReportFormat::Markdown => report::build_markdown_report(&report_data),
+                report::ReportFormat::Json => match serde_json::to_string_pretty(&report_data) {
+                    Ok(report) => report,
+                    Err(e) => {
+                        return Err(GooseError::InvalidOption {
+                            option: "--report-file".to_string(),
+                            value: report_file.to_string(),
+                            detail: format!("Failed to serialize report: {}", e),
+                        })
+                    }
+                },
+            };
+
+            // Write the report to file.
+            if let Err(e) = tokio::fs::write(report_file, report).await {
                 return Err(GooseError::InvalidOption {
                     option: "--report-file".to_string(),
-                    value: self.get_report_file_path().unwrap(),
+                    value: report_file.to_string(),
                     detail: format!("Failed to create report file: {}", e),
-                })
+                });
             }
+
+            info!("{} report file written to: {}", format, report_file);
+        }
+
+        Ok(())
+    }
+
+    // Prepare the metrics included in every report format.
+    pub(crate) fn prepare_report_data(&self) -> report::ReportData {
+        // Prepare an overview of each test plan step.
+        let steps = self
+            .metrics
+            .history
+            .windows(2)
+            .map(|step| report::StepMetric {
+                action: step[0].action.clone(),
+                started: step[0].timestamp.with_timezone(&Local),
+                stopped: step[1].timestamp.with_timezone(&Local),
+                elapsed: step[1].timestamp.timestamp() - step[0].timestamp.timestamp(),
+                users: step[0].users,
+                target_users: step[1].users,
+                ramp: step[0].ramp,
+                reason: step[0].reason.clone(),
+            })
+            .collect();
+
+        // Include the configured percentiles, or the default report percentiles.
+        let percentiles = match self.configuration.percentiles.as_ref() {
+            Some(percentiles) => percentiles.percentiles.clone(),
+            None => report::DEFAULT_PERCENTILES.to_vec(),
         };
 
-        // Only write the report if enabled.
-        if let Some(mut report_file) = report_file {
-            let test_start_time = self.metrics.history.first().unwrap().timestamp;
-
-            // Prepare report summary variables.
-            let users = self.metrics.maximum_users.to_string();
-
-            let mut steps_overview = String::new();
-            for step in self.metrics.history.windows(2) {
-                let (seconds, minutes, hours) = self
-                    .metrics
-                    .get_seconds_minutes_hours(&step[0].timestamp, &step[1].timestamp);
-                let started = Local
-                    .timestamp_opt(step[0].timestamp.timestamp(), 0)
-                    // @TODO: error handling
-                    .unwrap()
-                    .format("%y-%m-%d %H:%M:%S");
-                let stopped = Local
-                    .timestamp_opt(step[1].timestamp.timestamp(), 0)
-                    // @TODO: error handling
-                    .unwrap()
-                    .format("%y-%m-%d %H:%M:%S");
-                match &step[0].action {
-                    // For maintaining just show the current number of users.
-                    TestPlanStepAction::Maintaining => {
-                        let _ = write!(steps_overview,
-                            "<tr><td>{:?}</td><td>{}</td><td>{}</td><td>{:02}:{:02}:{:02}</td><td>{}</td></tr>",
-                            step[0].action,
-                            started,
-                            stopped,
-                            hours,
-                            minutes,
-                            seconds,
-                            step[0].users,
-                        );
-                    }
-                    // For increasing show the current number of users to the new number of users.
-                    TestPlanStepAction::Increasing => {
-                        let _ = write!(steps_overview,
-                            "<tr><td>{:?}</td><td>{}</td><td>{}</td><td>{:02}:{:02}:{:02}</td><td>{} &rarr; {}</td></tr>",
-                            step[0].action,
-                            started,
-                            stopped,
-                            hours,
-                            minutes,
-                            seconds,
-                            step[0].users,
-                            step[1].users,
-                        );
-                    }
-                    // For decreasing show the new number of users from the current number of users.
-                    TestPlanStepAction::Decreasing | TestPlanStepAction::Canceling => {
-                        let _ = write!(steps_overview,
-                            "<tr><td>{:?}</td><td>{}</td><td>{}</td><td>{:02}:{:02}:{:02}</td><td>{} &larr; {}</td></tr>",
-                            step[0].action,
-                            started,
-                            stopped,
-                            hours,
-                            minutes,
-                            seconds,
-                            step[1].users,
-                            step[0].users,
-                        );
-                    }
-                    TestPlanStepAction::Finished => {
-                        unreachable!("there shouldn't be a step after finished");
-                    }
-                }
+        // If Apdex targets are configured, add the Apdex score of each request to the
+        // response times.
+        let apdex_scores = self.metrics.apdex_scores();
+        let apdex_score = |request_key: &str| {
+            self.metrics.apdex.as_ref().map(|_| {
+                apdex_scores
+                    .get(request_key)
+                    .map(|apdex| format!("{:.2}", apdex.score))
+                    .unwrap_or_else(|| "-".to_string())
+            })
+        };
+
+        // Prepare requests and responses variables.
+        let mut raw_request_metrics = Vec::new();
+        let mut co_request_metrics = Vec::new();
+        let mut raw_response_metrics = Vec::new();
+        let mut co_response_metrics = Vec::new();
+        let mut raw_aggregate_total_count = 0;
+        let mut co_aggregate_total_count = 0;
+        let mut raw_aggregate_fail_count = 0;
+        let mut raw_aggregate_response_time_counter: usize = 0;
+        let mut raw_aggregate_response_time_minimum: usize = 0;
+        let mut raw_aggregate_response_time_maximum: usize = 0;
+        let mut raw_aggregate_data = GooseRequestMetricTimingData::default();
+        let mut co_aggregate_response_time_counter: usize = 0;
+        let mut co_aggregate_response_time_maximum: usize = 0;
+        let mut co_aggregate_data = GooseRequestMetricTimingData::default();
+        let mut co_data = false;
+        for (request_key, request) in self.metrics.requests.iter().sorted() {
+            // Determine whether or not to include Coordinated Omission data.
+            if !co_data && request.coordinated_omission_data.is_some() {
+                co_data = true;
             }
+            let method = format!("{}", request.method);
+            // The request_key is "{method} {name}", so by stripping the "{method} "
+            // prefix we get the name.
+            let name = request_key
+                .strip_prefix(&format!("{} ", request.method))
+                .unwrap()
+                .to_string();
+            let total_request_count = request.success_count + request.fail_count;
+            let (requests_per_second, failures_per_second) = per_second_calculations(
+                self.metrics.duration,
+                total_request_count,
+                request.fail_count,
+            );
+            // Prepare per-request metrics.
+            raw_request_metrics.push(report::RequestMetric {
+                method: method.to_string(),
+                name: name.to_string(),
+                number_of_requests: total_request_count,
+                number_of_failures: request.fail_count,
+                response_time_average: format!(
+                    "{:.2}",
+                    request.raw_data.total_time as f32 / request.raw_data.counter as f32
+                ),
+                response_time_minimum: request.raw_data.minimum_time,
+                response_time_maximum: request.raw_data.maximum_time,
+                requests_per_second: format!("{:.2}", requests_per_second),
+                failures_per_second: format!("{:.2}", failures_per_second),
+            });
+
+            // Prepare per-response metrics.
+            let mut response_metric =
+                report::get_response_metric(&method, &name, &request.raw_data, &percentiles);
+            response_metric.apdex = apdex_score(request_key);
+            raw_response_metrics.push(response_metric);
+
+            // Collect aggregated request and response metrics.
+            raw_aggregate_total_count += total_request_count;
+            raw_aggregate_fail_count += request.fail_count;
+            raw_aggregate_response_time_counter += request.raw_data.total_time;
+            raw_aggregate_response_time_minimum = update_min_time(
+                raw_aggregate_response_time_minimum,
+                request.raw_data.minimum_time,
+            );
+            raw_aggregate_response_time_maximum = update_max_time(
+                raw_aggregate_response_time_maximum,
+                request.raw_data.maximum_time,
+            );
+            raw_aggregate_data.merge(&request.raw_data);
+        }
 
-            // Build a comma separated list of hosts.
-            let hosts = &self.metrics.hosts.clone().into_iter().join(", ");
-
-            // Prepare requests and responses variables.
-            let mut raw_request_metrics = Vec::new();
-            let mut co_request_metrics = Vec::new();
-            let mut raw_response_metrics = Vec::new();
-            let mut co_response_metrics = Vec::new();
-            let mut raw_aggregate_total_count = 0;
-            let mut co_aggregate_total_count = 0;
-            let mut raw_aggregate_fail_count = 0;
-            let mut raw_aggregate_response_time_counter: usize = 0;
-            let mut raw_aggregate_response_time_minimum: usize = 0;
-            let mut raw_aggregate_response_time_maximum: usize = 0;
-            let mut raw_aggregate_response_times: BTreeMap<usize, usize> = BTreeMap::new();
-            let mut co_aggregate_response_time_counter: usize = 0;
-            let mut co_aggregate_response_time_maximum: usize = 0;
-            let mut co_aggregate_response_times: BTreeMap<usize, usize> = BTreeMap::new();
-            let mut co_data = false;
+        // Prepare aggregate per-request metrics.
+        let (raw_aggregate_requests_per_second, raw_aggregate_failures_per_second) =
+            per_second_calculations(
+                self.metrics.duration,
+                raw_aggregate_total_count,
+                raw_aggregate_fail_count,
+            );
+        raw_request_metrics.push(report::RequestMetric {
+            method: "".to_string(),
+            name: "Aggregated".to_string(),
+            number_of_requests: raw_aggregate_total_count,
+            number_of_failures: raw_aggregate_fail_count,
+            response_time_average: format!(
+                "{:.2}",
+                raw_aggregate_response_time_counter as f32 / raw_aggregate_total_count as f32
+            ),
+            response_time_minimum: raw_aggregate_response_time_minimum,
+            response_time_maximum: raw_aggregate_response_time_maximum,
+            requests_per_second: format!("{:.2}", raw_aggregate_requests_per_second),
+            failures_per_second: format!("{:.2}", raw_aggregate_failures_per_second),
+        });
+
+        // Prepare aggregate per-response metrics.
+        let mut response_metric =
+            report::get_response_metric("", "Aggregated", &raw_aggregate_data, &percentiles);
+        response_metric.apdex = apdex_score("Aggregated");
+        raw_response_metrics.push(response_metric);
+
+        // Only include Coordinated Omission metrics if Coordinated Omission Mitigation was
+        // triggered.
+        let (co_requests, co_responses) = if co_data {
             for (request_key, request) in self.metrics.requests.iter().sorted() {
-                // Determine whether or not to include Coordinated Omission data.
-                if !co_data && request.coordinated_omission_data.is_some() {
-                    co_data = true;
+                if let Some(coordinated_omission_data) = request.coordinated_omission_data.as_ref()
+                {
+                    let method = format!("{}", request.method);
+                    // The request_key is "{method} {name}", so by stripping the "{method} "
+                    // prefix we get the name.
+                    let name = request_key
+                        .strip_prefix(&format!("{} ", request.method))
+                        .unwrap()
+                        .to_string();
+                    let raw_average =
+                        request.raw_data.total_time as f32 / request.raw_data.counter as f32;
+                    let co_average = coordinated_omission_data.total_time as f32
+                        / coordinated_omission_data.counter as f32;
+                    // Prepare per-request metrics.
+                    co_request_metrics.push(report::CORequestMetric {
+                        method: method.to_string(),
+                        name: name.to_string(),
+                        response_time_average: format!("{:.2}", co_average),
+                        response_time_standard_deviation: format!(
+                            "{:.2}",
+                            util::standard_deviation(raw_average, co_average)
+                        ),
+                        response_time_maximum: coordinated_omission_data.maximum_time,
+                    });
+
+                    // Prepare per-response metrics.
+                    co_response_metrics.push(report::get_response_metric(
+                        &method,
+                        &name,
+                        coordinated_omission_data,
+                        &percentiles,
+                    ));
+
+                    // Collect aggregated request and response metrics.
+                    co_aggregate_response_time_counter += coordinated_omission_data.total_time;
+                    co_aggregate_response_time_maximum = update_max_time(
+                        co_aggregate_response_time_maximum,
+                        coordinated_omission_data.maximum_time,
+                    );
+                    co_aggregate_data.merge(coordinated_omission_data);
                 }
-                let method = format!("{}", request.method);
-                // The request_key is "{method} {name}", so by stripping the "{method} "
-                // prefix we get the name.
-                let name = request_key
-                    .strip_prefix(&format!("{} ", request.method))
-                    .unwrap()
-                    .to_string();
                 let total_request_count = request.success_count + request.fail_count;
-                let (requests_per_second, failures_per_second) = per_second_calculations(
-                    self.metrics.duration,
-                    total_request_count,
-                    request.fail_count,
-                );
-                // Prepare per-request metrics.
-                raw_request_metrics.push(report::RequestMetric {
-                    method: method.to_string(),
-                    name: name.to_string(),
-                    number_of_requests: total_request_count,
-                    number_of_failures: request.fail_count,
-                    response_time_average: format!(
-                        "{:.2}",
-                        request.raw_data.total_time as f32 / request.raw_data.counter as f32
-                    ),
-                    response_time_minimum: request.raw_data.minimum_time,
-                    response_time_maximum: request.raw_data.maximum_time,
-                    requests_per_second: format!("{:.2}", requests_per_second),
-                    failures_per_second: format!("{:.2}", failures_per_second),
-                });
-
-                // Prepare per-response metrics.
-                raw_response_metrics.push(report::get_response_metric(
-                    &method,
-                    &name,
-                    &request.raw_data.times,
-                    request.raw_data.counter,
-                    request.raw_data.minimum_time,
-                    request.raw_data.maximum_time,
-                ));
-
-                // Collect aggregated request and response metrics.
-                raw_aggregate_total_count += total_request_count;
-                raw_aggregate_fail_count += request.fail_count;
-                raw_aggregate_response_time_counter += request.raw_data.total_time;
-                raw_aggregate_response_time_minimum = update_min_time(
-                    raw_aggregate_response_time_minimum,
-                    request.raw_data.minimum_time,
-                );
-                raw_aggregate_response_time_maximum = update_max_time(
-                    raw_aggregate_response_time_maximum,
-                    request.raw_data.maximum_time,
-                );
-                raw_aggregate_response_times =
-                    merge_times(raw_aggregate_response_times, request.raw_data.times.clone());
+                co_aggregate_total_count += total_request_count;
             }
-
-            // Prepare aggregate per-request metrics.
-            let (raw_aggregate_requests_per_second, raw_aggregate_failures_per_second) =
-                per_second_calculations(
-                    self.metrics.duration,
-                    raw_aggregate_total_count,
-                    raw_aggregate_fail_count,
-                );
-            raw_request_metrics.push(report::RequestMetric {
+            let co_average =
+                co_aggregate_response_time_counter as f32 / co_aggregate_total_count as f32;
+            let raw_average =
+                raw_aggregate_response_time_counter as f32 / raw_aggregate_total_count as f32;
+            co_request_metrics.push(report::CORequestMetric {
                 method: "".to_string(),
                 name: "Aggregated".to_string(),
-                number_of_requests: raw_aggregate_total_count,
-                number_of_failures: raw_aggregate_fail_count,
                 response_time_average: format!(
                     "{:.2}",
-                    raw_aggregate_response_time_counter as f32 / raw_aggregate_total_count as f32
+                    co_aggregate_response_time_counter as f32 / co_aggregate_total_count as f32
+                ),
+                response_time_standard_deviation: format!(
+                    "{:.2}",
+                    util::standard_deviation(raw_average, co_average),
                 ),
-                response_time_minimum: raw_aggregate_response_time_minimum,
-                response_time_maximum: raw_aggregate_response_time_maximum,
-                requests_per_second: format!("{:.2}", raw_aggregate_requests_per_second),
-                failures_per_second: format!("{:.2}", raw_aggregate_failures_per_second),
+                response_time_maximum: co_aggregate_response_time_maximum,
             });
 
             // Prepare aggregate per-response metrics.
-            raw_response_metrics.push(report::get_response_metric(
+            co_response_metrics.push(report::get_response_metric(
                 "",
                 "Aggregated",
-                &raw_aggregate_response_times,
-                raw_aggregate_total_count,
-                raw_aggregate_response_time_minimum,
-                raw_aggregate_response_time_maximum,
+                &co_aggregate_data,
+                &percentiles,
             ));
 
-            // Compile the request metrics template.
-            let mut raw_requests_rows = Vec::new();
-            for metric in raw_request_metrics {
-                raw_requests_rows.push(report::raw_request_metrics_row(metric));
+            (Some(co_request_metrics), Some(co_response_metrics))
+        } else {
+            (None, None)
+        };
+
+        // Only include transaction metrics if --no-transaction-metrics isn't enabled.
+        let transactions = if !self.configuration.no_transaction_metrics {
+            let mut transaction_metrics = Vec::new();
+            let mut aggregate_total_count = 0;
+            let mut aggregate_fail_count = 0;
+            let mut aggregate_transaction_time_counter: usize = 0;
+            let mut aggregate_transaction_time_minimum: usize = 0;
+            let mut aggregate_transaction_time_maximum: usize = 0;
+            let mut aggregate_transaction_times = GooseHistogram::default();
+            for (scenario_counter, scenario) in self.metrics.transactions.iter().enumerate() {
+                for (transaction_counter, transaction) in scenario.iter().enumerate() {
+                    if transaction_counter == 0 {
+                        // Only the scenario_name is used for scenarios.
+                        transaction_metrics.push(report::TransactionMetric {
+                            is_scenario: true,
+                            transaction: "".to_string(),
+                            name: transaction.scenario_name.to_string(),
+                            number_of_requests: 0,
+                            number_of_failures: 0,
+                            response_time_average: "".to_string(),
+                            response_time_minimum: 0,
+                            response_time_maximum: 0,
+                            requests_per_second: "".to_string(),
+                            failures_per_second: "".to_string(),
+                        });
+                    }
+                    let total_run_count = transaction.success_count + transaction.fail_count;
+                    let (requests_per_second, failures_per_second) = per_second_calculations(
+                        self.metrics.duration,
+                        total_run_count,
+                        transaction.fail_count,
+                    );
+                    let average = match transaction.counter {
+                        0 => 0.00,
+                        _ => transaction.total_time as f32 / transaction.counter as f32,
+                    };
+                    transaction_metrics.push(report::TransactionMetric {
+                        is_scenario: false,
+                        transaction: format!("{}.{}", scenario_counter, transaction_counter),
+                        name: transaction.transaction_name.to_string(),
+                        number_of_requests: total_run_count,
+                        number_of_failures: transaction.fail_count,
+                        response_time_average: format!("{:.2}", average),
+                        response_time_minimum: transaction.min_time,
+                        response_time_maximum: transaction.max_time,
+                        requests_per_second: format!("{:.2}", requests_per_second),
+                        failures_per_second: format!("{:.2}", failures_per_second),
+                    });
+
+                    aggregate_total_count += total_run_count;
+                    aggregate_fail_count += transaction.fail_count;
+                    aggregate_transaction_times.merge(&transaction.times);
+                    aggregate_transaction_time_counter += &transaction.counter;
+                    aggregate_transaction_time_minimum =
+                        update_min_time(aggregate_transaction_time_minimum, transaction.min_time);
+                    aggregate_transaction_time_maximum =
+                        update_max_time(aggregate_transaction_time_maximum, transaction.max_time);
+                }
             }
 
-            // Compile the response metrics template.
-            let mut raw_responses_rows = Vec::new();
-            for metric in raw_response_metrics {
-                raw_responses_rows.push(report::response_metrics_row(metric));
+            let (aggregate_requests_per_second, aggregate_failures_per_second) =
+                per_second_calculations(
+                    self.metrics.duration,
+                    aggregate_total_count,
+                    aggregate_fail_count,
+                );
+            transaction_metrics.push(report::TransactionMetric {
+                is_scenario: false,
+                transaction: "".to_string(),
+                name: "Aggregated".to_string(),
+                number_of_requests: aggregate_total_count,
+                number_of_failures: aggregate_fail_count,
+                response_time_average: format!(
+                    "{:.2}",
+                    raw_aggregate_response_time_counter as f32 / aggregate_total_count as f32
+                ),
+                response_time_minimum: aggregate_transaction_time_minimum,
+                response_time_maximum: aggregate_transaction_time_maximum,
+                requests_per_second: format!("{:.2}", aggregate_requests_per_second),
+                failures_per_second: format!("{:.2}", aggregate_failures_per_second),
+            });
+            Some(transaction_metrics)
+        } else {
+            None
+        };
+
+        // Only include scenario metrics if --no-scenario-metrics isn't enabled.
+        let scenarios = if !self.configuration.no_scenario_metrics {
+            let mut scenario_metrics = Vec::new();
+            let mut aggregate_users = 0;
+            let mut aggregate_count = 0;
+            let mut aggregate_scenario_time_counter: usize = 0;
+            let mut aggregate_scenario_time_minimum: usize = 0;
+            let mut aggregate_scenario_time_maximum: usize = 0;
+            let mut aggregate_scenario_times = GooseHistogram::default();
+            let mut aggregate_iterations = 0.0;
+            let mut aggregate_response_time_counter = 0.0;
+            for scenario in &self.metrics.scenarios {
+                let (count_per_second, _failures_per_second) =
+                    per_second_calculations(self.metrics.duration, scenario.counter, 0);
+                let average = match scenario.counter {
+                    0 => 0.00,
+                    _ => scenario.total_time as f32 / scenario.counter as f32,
+                };
+                let iterations = scenario.counter as f32 / scenario.users.len() as f32;
+                scenario_metrics.push(report::ScenarioMetric {
+                    name: scenario.name.to_string(),
+                    users: scenario.users.len(),
+                    count: scenario.counter,
+                    response_time_average: format!("{:.2}", average),
+                    response_time_minimum: scenario.min_time,
+                    response_time_maximum: scenario.max_time,
+                    count_per_second: format!("{:.2}", count_per_second),
+                    iterations: format!("{:.2}", iterations),
+                });
+
+                aggregate_users += scenario.users.len();
+                aggregate_count += scenario.counter;
+                aggregate_scenario_times.merge(&scenario.times);
+                aggregate_scenario_time_counter += &scenario.counter;
+                aggregate_scenario_time_minimum =
+                    update_min_time(aggregate_scenario_time_minimum, scenario.min_time);
+                aggregate_scenario_time_maximum =
+                    update_max_time(aggregate_scenario_time_maximum, scenario.max_time);
+                aggregate_iterations += iterations;
+                aggregate_response_time_counter += scenario.total_time as f32;
             }
 
-            let co_requests_template: String;
-            let co_responses_template: String;
-            if co_data {
-                for (request_key, request) in self.metrics.requests.iter().sorted() {
-                    if let Some(coordinated_omission_data) =
-                        request.coordinated_omission_data.as_ref()
-                    {
-                        let method = format!("{}", request.method);
-                        // The request_key is "{method} {name}", so by stripping the "{method} "
-                        // prefix we get the name.
-                        let name = request_key
-                            .strip_prefix(&format!("{} ", request.method))
-                            .unwrap()
-                            .to_string();
-                        let raw_average =
-                            request.raw_data.total_time as f32 / request.raw_data.counter as f32;
-                        let co_average = coordinated_omission_data.total_time as f32
-                            / coordinated_omission_data.counter as f32;
-                        // Prepare per-request metrics.
-                        co_request_metrics.push(report::CORequestMetric {
-                            method: method.to_string(),
-                            name: name.to_string(),
-                            response_time_average: format!("{:.2}", co_average),
-                            response_time_standard_deviation: format!(
-                                "{:.2}",
-                                util::standard_deviation(raw_average, co_average)
-                            ),
-                            response_time_maximum: coordinated_omission_data.maximum_time,
-                        });
+            let (aggregate_count_per_second, _aggregate_failures_per_second) =
+                per_second_calculations(self.metrics.duration, aggregate_count, 0);
+            scenario_metrics.push(report::ScenarioMetric {
+                name: "Aggregated".to_string(),
+                users: aggregate_users,
+                count: aggregate_count,
+                response_time_average: format!(
+                    "{:.2}",
+                    aggregate_response_time_counter / aggregate_count as f32
+                ),
+                response_time_minimum: aggregate_scenario_time_minimum,
+                response_time_maximum: aggregate_scenario_time_maximum,
+                count_per_second: format!("{:.2}", aggregate_count_per_second),
+                iterations: format!("{:.2}", aggregate_iterations),
+            });
+            Some(scenario_metrics)
+        } else {
+            None
+        };
 
-                        // Prepare per-response metrics.
-                        co_response_metrics.push(report::get_response_metric(
-                            &method,
-                            &name,
-                            &coordinated_omission_data.times,
-                            coordinated_omission_data.counter,
-                            coordinated_omission_data.minimum_time,
-                            coordinated_omission_data.maximum_time,
-                        ));
+        // Only include status codes if --no-status-codes is not enabled.
+        let status_codes = if !self.configuration.no_status_codes {
+            let mut status_code_metrics = Vec::new();
+            let mut aggregated_status_code_counts: HashMap<u16, usize> = HashMap::new();
+            for (request_key, request) in self.metrics.requests.iter().sorted() {
+                let method = format!("{}", request.method);
+                // The request_key is "{method} {name}", so by stripping the "{method} "
+                // prefix we get the name.
+                let name = request_key
+                    .strip_prefix(&format!("{} ", request.method))
+                    .unwrap()
+                    .to_string();
 
-                        // Collect aggregated request and response metrics.
-                        co_aggregate_response_time_counter += coordinated_omission_data.total_time;
-                        co_aggregate_response_time_maximum = update_max_time(
-                            co_aggregate_response_time_maximum,
-                            coordinated_omission_data.maximum_time,
-                        );
-                        co_aggregate_response_times = merge_times(
-                            co_aggregate_response_times,
-                            coordinated_omission_data.times.clone(),
-                        );
-                    }
-                    let total_request_count = request.success_count + request.fail_count;
-                    co_aggregate_total_count += total_request_count;
-                }
-                let co_average =
-                    co_aggregate_response_time_counter as f32 / co_aggregate_total_count as f32;
-                let raw_average =
-                    raw_aggregate_response_time_counter as f32 / raw_aggregate_total_count as f32;
-                co_request_metrics.push(report::CORequestMetric {
-                    method: "".to_string(),
-                    name: "Aggregated".to_string(),
-                    response_time_average: format!(
-                        "{:.2}",
-                        co_aggregate_response_time_counter as f32 / co_aggregate_total_count as f32
-                    ),
-                    response_time_standard_deviation: format!(
-                        "{:.2}",
-                        util::standard_deviation(raw_average, co_average),
-                    ),
-                    response_time_maximum: co_aggregate_response_time_maximum,
+                // Build a list of status codes, and update the aggregate record.
+                let codes = prepare_status_codes(
+                    &request.status_code_counts,
+                    &mut Some(&mut aggregated_status_code_counts),
+                );
+
+                // Add a row of data for the status code table.
+                status_code_metrics.push(report::StatusCodeMetric {
+                    method,
+                    name,
+                    status_codes: codes,
                 });
+            }
 
-                // Prepare aggregate per-response metrics.
-                co_response_metrics.push(report::get_response_metric(
-                    "",
-                    "Aggregated",
-                    &co_aggregate_response_times,
-                    co_aggregate_total_count,
-                    raw_aggregate_response_time_minimum,
-                    co_aggregate_response_time_maximum,
-                ));
+            // Build a list of aggregate status codes.
+            let aggregated_codes = prepare_status_codes(&aggregated_status_code_counts, &mut None);
 
-                // Compile the co_request metrics rows.
-                let mut co_request_rows = Vec::new();
-                for metric in co_request_metrics {
-                    co_request_rows.push(report::coordinated_omission_request_metrics_row(metric));
-                }
+            // Add a final row of aggregate data for the status code table.
+            status_code_metrics.push(report::StatusCodeMetric {
+                method: "".to_string(),
+                name: "Aggregated".to_string(),
+                status_codes: aggregated_codes,
+            });
+            Some(status_code_metrics)
+        } else {
+            None
+        };
 
-                // Compile the status_code metrics template.
-                co_requests_template = report::coordinated_omission_request_metrics_template(
-                    &co_request_rows.join("\n"),
+        report::ReportData {
+            version: env!("CARGO_PKG_VERSION").to_string(),
+            users: self.metrics.maximum_users,
+            hosts: self.metrics.hosts.iter().sorted().cloned().collect(),
+            duration: self.metrics.duration,
+            steps,
+            percentiles,
+            raw_requests: raw_request_metrics,
+            raw_responses: raw_response_metrics,
+            co_requests,
+            co_responses,
+            status_codes,
+            transactions,
+            scenarios,
+            errors: self.metrics.errors.values().cloned().collect(),
+        }
+    }
+
+    // Build an html-formatted report.
+    pub(crate) fn build_html_report(&self, report_data: &report::ReportData) -> String {
+        let test_start_time = self.metrics.history.first().unwrap().timestamp;
+
+        // Prepare report summary variables.
+        let users = report_data.users.to_string();
+
+        let mut steps_overview = String::new();
+        for step in &report_data.steps {
+            let _ = write!(
+                steps_overview,
+                "<tr><td>{:?}</td><td>{}</td><td>{}</td><td>{}</td><td>{}</td></tr>",
+                step.action,
+                step.started(),
+                step.stopped(),
+                step.elapsed(),
+                step.users("&rarr;", "&larr;"),
+            );
+            // Explain why the step happened, for example why the load test was aborted.
+            if let Some(reason) = &step.reason {
+                let _ = write!(
+                    steps_overview,
+                    r#"<tr><td colspan="5">{}</td></tr>"#,
+                    report::escape(reason)
                 );
+            }
+        }
 
-                // Compile the co_request metrics rows.
-                let mut co_response_rows = Vec::new();
-                for metric in co_response_metrics {
-                    co_response_rows
-                        .push(report::coordinated_omission_response_metrics_row(metric));
-                }
+        // Build a comma separated list of hosts.
+        let hosts = &report_data.hosts.join(", ");
 
-                // Compile the status_code metrics template.
-                co_responses_template = report::coordinated_omission_response_metrics_template(
-                    &co_response_rows.join("\n"),
-                );
-            } else {
-                // If --status-codes is not enabled, return an empty template.
-                co_requests_template = "".to_string();
-                co_responses_template = "".to_string();
-            }
-
-            // Only build the transactions template if --no-transaction-metrics isn't enabled.
-            let transactions_template: String;
-            if !self.configuration.no_transaction_metrics {
-                let mut transaction_metrics = Vec::new();
-                let mut aggregate_total_count = 0;
-                let mut aggregate_fail_count = 0;
-                let mut aggregate_transaction_time_counter: usize = 0;
-                let mut aggregate_transaction_time_minimum: usize = 0;
-                let mut aggregate_transaction_time_maximum: usize = 0;
-                let mut aggregate_transaction_times: BTreeMap<usize, usize> = BTreeMap::new();
-                for (scenario_counter, scenario) in self.metrics.transactions.iter().enumerate() {
-                    for (transaction_counter, transaction) in scenario.iter().enumerate() {
-                        if transaction_counter == 0 {
-                            // Only the scenario_name is used for scenarios.
-                            transaction_metrics.push(report::TransactionMetric {
-                                is_scenario: true,
-                                transaction: "".to_string(),
-                                name: transaction.scenario_name.to_string(),
-                                number_of_requests: 0,
-                                number_of_failures: 0,
-                                response_time_average: "".to_string(),
-                                response_time_minimum: 0,
-                                response_time_maximum: 0,
-                                requests_per_second: "".to_string(),
-                                failures_per_second: "".to_string(),
-                            });
-                        }
-                        let total_run_count = transaction.success_count + transaction.fail_count;
-                        let (requests_per_second, failures_per_second) = per_second_calculations(
-                            self.metrics.duration,
-                            total_run_count,
-                            transaction.fail_count,
-                        );
-                        let average = match transaction.counter {
-                            0 => 0.00,
-                            _ => transaction.total_time as f32 / transaction.counter as f32,
-                        };
-                        transaction_metrics.push(report::TransactionMetric {
-                            is_scenario: false,
-                            transaction: format!("{}.{}", scenario_counter, transaction_counter),
-                            name: transaction.transaction_name.to_string(),
-                            number_of_requests: total_run_count,
-                            number_of_failures: transaction.fail_count,
-                            response_time_average: format!("{:.2}", average),
-                            response_time_minimum: transaction.min_time,
-                            response_time_maximum: transaction.max_time,
-                            requests_per_second: format!("{:.2}", requests_per_second),
-                            failures_per_second: format!("{:.2}", failures_per_second),
-                        });
+        let responses_header = report::responses_header(&report_data.percentiles);
+        let raw_responses_header = match self.metrics.apdex {
+            Some(_) => format!("{}\n                    <th>Apdex</th>", responses_header),
+            None => responses_header.clone(),
+        };
 
-                        aggregate_total_count += total_run_count;
-                        aggregate_fail_count += transaction.fail_count;
-                        aggregate_transaction_times =
-                            merge_times(aggregate_transaction_times, transaction.times.clone());
-                        aggregate_transaction_time_counter += &transaction.counter;
-                        aggregate_transaction_time_minimum = update_min_time(
-                            aggregate_transaction_time_minimum,
-                            transaction.min_time,
-                        );
-                        aggregate_transaction_time_maximum = update_max_time(
-                            aggregate_transaction_time_maximum,
-                            transaction.max_time,
-                        );
-                    }
-                }
+        // Compile the request metrics template.
+        let raw_requests_rows: Vec<String> = report_data
+            .raw_requests
+            .iter()
+            .map(report::raw_request_metrics_row)
+            .collect();
+
+        // Compile the response metrics template.
+        let raw_responses_rows: Vec<String> = report_data
+            .raw_responses
+            .iter()
+            .map(report::response_metrics_row)
+            .collect();
+
+        // Compile the Coordinated Omission request metrics template.
+        let co_requests_template = match report_data.co_requests.as_ref() {
+            Some(co_requests) => {
+                let co_request_rows: Vec<String> = co_requests
+                    .iter()
+                    .map(report::coordinated_omission_request_metrics_row)
+                    .collect();
+                report::coordinated_omission_request_metrics_template(&co_request_rows.join("\n"))
+            }
+            None => "".to_string(),
+        };
 
-                let (aggregate_requests_per_second, aggregate_failures_per_second) =
-                    per_second_calculations(
-                        self.metrics.duration,
-                        aggregate_total_count,
-                        aggregate_fail_count,
-                    );
-                transaction_metrics.push(report::TransactionMetric {
-                    is_scenario: false,
-                    transaction: "".to_string(),
-                    name: "Aggregated".to_string(),
-                    number_of_requests: aggregate_total_count,
-                    number_of_failures: aggregate_fail_count,
-                    response_time_average: format!(
-                        "{:.2}",
-                        raw_aggregate_response_time_counter as f32 / aggregate_total_count as f32
-                    ),
-                    response_time_minimum: aggregate_transaction_time_minimum,
-                    response_time_maximum: aggregate_transaction_time_maximum,
-                    requests_per_second: format!("{:.2}", aggregate_requests_per_second),
-                    failures_per_second: format!("{:.2}", aggregate_failures_per_second),
-                });
-                let mut transactions_rows = Vec::new();
-                // Compile the transaction metrics template.
-                for metric in transaction_metrics {
-                    transactions_rows.push(report::transaction_metrics_row(metric));
-                }
+        // Compile the Coordinated Omission response metrics template.
+        let co_responses_template = match report_data.co_responses.as_ref() {
+            Some(co_responses) => {
+                let co_response_rows: Vec<String> = co_responses
+                    .iter()
+                    .map(report::coordinated_omission_response_metrics_row)
+                    .collect();
+                report::coordinated_omission_response_metrics_template(
+                    &co_response_rows.join("\n"),
+                    &responses_header,
+                )
+            }
+            None => "".to_string(),
+        };
 
-                transactions_template = report::transaction_metrics_template(
+        // Compile the transaction metrics template.
+        let transactions_template = match report_data.transactions.as_ref() {
+            Some(transactions) => {
+                let transactions_rows: Vec<String> = transactions
+                    .iter()
+                    .map(report::transaction_metrics_row)
+                    .collect();
+                report::transaction_metrics_template(
                     &transactions_rows.join("\n"),
                     self.graph_data
                         .get_transactions_per_second_graph(!self.configuration.no_granular_report)
                         .get_markup(&self.metrics.history, test_start_time),
-                );
-            } else {
-                transactions_template = "".to_string();
-            }
-
-            // Only build the scenarios template if --no-senario-metrics isn't enabled.
-            let scenarios_template: String;
-            if !self.configuration.no_scenario_metrics {
-                let mut scenario_metrics = Vec::new();
-                let mut aggregate_users = 0;
-                let mut aggregate_count = 0;
-                let mut aggregate_scenario_time_counter: usize = 0;
-                let mut aggregate_scenario_time_minimum: usize = 0;
-                let mut aggregate_scenario_time_maximum: usize = 0;
-                let mut aggregate_scenario_times: BTreeMap<usize, usize> = BTreeMap::new();
-                let mut aggregate_iterations = 0.0;
-                let mut aggregate_response_time_counter = 0.0;
-                for scenario in &self.metrics.scenarios {
-                    let (count_per_second, _failures_per_second) =
-                        per_second_calculations(self.metrics.duration, scenario.counter, 0);
-                    let average = match scenario.counter {
-                        0 => 0.00,
-                        _ => scenario.total_time as f32 / scenario.counter as f32,
-                    };
-                    let iterations = scenario.counter as f32 / scenario.users.len() as f32;
-                    scenario_metrics.push(report::ScenarioMetric {
-                        name: scenario.name.to_string(),
-                        users: scenario.users.len(),
-                        count: scenario.counter,
-                        response_time_average: format!("{:.2}", average),
-                        response_time_minimum: scenario.min_time,
-                        response_time_maximum: scenario.max_time,
-                        count_per_second: format!("{:.2}", count_per_second),
-                        iterations: format!("{:.2}", iterations),
-                    });
-
-                    aggregate_users += scenario.users.len();
-                    aggregate_count += scenario.counter;
-                    aggregate_scenario_times =
-                        merge_times(aggregate_scenario_times, scenario.times.clone());
-                    aggregate_scenario_time_counter += &scenario.counter;
-                    aggregate_scenario_time_minimum =
-                        update_min_time(aggregate_scenario_time_minimum, scenario.min_time);
-                    aggregate_scenario_time_maximum =
-                        update_max_time(aggregate_scenario_time_maximum, scenario.max_time);
-                    aggregate_iterations += iterations;
-                    aggregate_response_time_counter += scenario.total_time as f32;
-                }
-
-                let (aggregate_count_per_second, _aggregate_failures_per_second) =
-                    per_second_calculations(self.metrics.duration, aggregate_count, 0);
-                scenario_metrics.push(report::ScenarioMetric {
-                    name: "Aggregated".to_string(),
-                    users: aggregate_users,
-                    count: aggregate_count,
-                    response_time_average: format!(
-                        "{:.2}",
-                        aggregate_response_time_counter / aggregate_count as f32
-                    ),
-                    response_time_minimum: aggregate_scenario_time_minimum,
-                    response_time_maximum: aggregate_scenario_time_maximum,
-                    count_per_second: format!("{:.2}", aggregate_count_per_second),
-                    iterations: format!("{:.2}", aggregate_iterations),
-                });
-                let mut scenarios_rows = Vec::new();
-                // Compile the scenario metrics template.
-                for metric in scenario_metrics {
-                    scenarios_rows.push(report::scenario_metrics_row(metric));
-                }
+                )
+            }
+            None => "".to_string(),
+        };
 
-                scenarios_template = report::scenario_metrics_template(
+        // Compile the scenario metrics template.
+        let scenarios_template = match report_data.scenarios.as_ref() {
+            Some(scenarios) => {
+                let scenarios_rows: Vec<String> =
+                    scenarios.iter().map(report::scenario_metrics_row).collect();
+                report::scenario_metrics_template(
                     &scenarios_rows.join("\n"),
                     self.graph_data
                         .get_scenarios_per_second_graph(!self.configuration.no_granular_report)
                         .get_markup(&self.metrics.history, test_start_time),
-                );
-            } else {
-                scenarios_template = "".to_string();
+                )
             }
+            None => "".to_string(),
+        };
 
-            // Only build the transactions template if --no-transaction-metrics isn't enabled.
-            let errors_template: String = if !self.metrics.errors.is_empty() {
-                let mut error_rows = Vec::new();
-                for error in self.metrics.errors.values() {
-                    error_rows.push(report::error_row(error));
-                }
+        // Only build the errors template if there are errors.
+        let errors_template: String = if !report_data.errors.is_empty() {
+            let error_rows: Vec<String> =
+                report_data.errors.iter().map(report::error_row).collect();
+
+            report::errors_template(
+                &error_rows.join("\n"),
+                self.graph_data
+                    .get_errors_per_second_graph(!self.configuration.no_granular_report)
+                    .get_markup(&self.metrics.history, test_start_time),
+            )
+        } else {
+            "".to_string()
+        };
 
-                report::errors_template(
-                    &error_rows.join("\n"),
-                    self.graph_data
-                        .get_errors_per_second_graph(!self.configuration.no_granular_report)
-                        .get_markup(&self.metrics.history, test_start_time),
-                )
-            } else {
-                "".to_string()
-            };
+        // Compile the status_code metrics template.
+        let status_code_template = match report_data.status_codes.as_ref() {
+            Some(status_codes) => {
+                let status_code_rows: Vec<String> = status_codes
+                    .iter()
+                    .map(report::status_code_metrics_row)
+                    .collect();
+                report::status_code_metrics_template(&status_code_rows.join("\n"))
+            }
+            None => "".to_string(),
+        };
 
-            // Only build the status_code template if --no-status-codes is not enabled.
-            let status_code_template: String = if !self.configuration.no_status_codes {
-                let mut status_code_metrics = Vec::new();
-                let mut aggregated_status_code_counts: HashMap<u16, usize> = HashMap::new();
-                for (request_key, request) in self.metrics.requests.iter().sorted() {
-                    let method = format!("{}", request.method);
-                    // The request_key is "{method} {name}", so by stripping the "{method} "
-                    // prefix we get the name.
-                    let name = request_key
-                        .strip_prefix(&format!("{} ", request.method))
-                        .unwrap()
-                        .to_string();
+        // Only build the thresholds template if thresholds are configured.
+        let thresholds_template: String = if !self.metrics.thresholds.is_empty() {
+            let threshold_rows: Vec<String> = self
+                .metrics
+                .thresholds
+                .iter()
+                .map(report::threshold_row)
+                .collect();
+            report::thresholds_template(&threshold_rows.join("\n"))
+        } else {
+            "".to_string()
+        };
 
-                    // Build a list of status codes, and update the aggregate record.
-                    let codes = prepare_status_codes(
-                        &request.status_code_counts,
-                        &mut Some(&mut aggregated_status_code_counts),
-                    );
+        // Only build the baseline template if comparing to a baseline.
+        let baseline_template: String = if !self.metrics.baseline.is_empty() {
+            let baseline_rows: Vec<String> = self
+                .metrics
+                .baseline
+                .iter()
+                .map(report::baseline_row)
+                .collect();
+            report::baseline_template(&baseline_rows.join("\n"))
+        } else {
+            "".to_string()
+        };
 
-                    // Add a row of data for the status code table.
-                    status_code_metrics.push(report::StatusCodeMetric {
-                        method,
-                        name,
-                        status_codes: codes,
-                    });
+        // Only build the per phase template if metrics were collected per test plan step.
+        let phases_template: String = if !self.metrics.phases.is_empty() {
+            let mut phase_rows = Vec::new();
+            for phase in &self.metrics.phases {
+                let duration = self.metrics.phase_duration(phase);
+                for summary in phase.summarize(duration) {
+                    phase_rows.push(report::phase_row(phase, &summary));
                 }
+            }
+            report::phases_template(&phase_rows.join("\n"))
+        } else {
+            "".to_string()
+        };
 
-                // Build a list of aggregate status codes.
-                let aggregated_codes =
-                    prepare_status_codes(&aggregated_status_code_counts, &mut None);
+        // Compile the bandwidth template, listing the aggregate after all requests.
+        let mut bandwidth = self.metrics.bandwidth();
+        let aggregated = bandwidth.remove("Aggregated");
+        let mut bandwidth_rows: Vec<String> = bandwidth
+            .iter()
+            .map(|(request_key, request)| report::bandwidth_row(request_key, request))
+            .collect();
+        if let Some(aggregated) = aggregated {
+            bandwidth_rows.push(report::bandwidth_row("Aggregated", &aggregated));
+        }
+        let bandwidth_template = report::bandwidth_template(
+            &bandwidth_rows.join("\n"),
+            self.graph_data
+                .get_bandwidth_graph(!self.configuration.no_granular_report)
+                .get_markup(&self.metrics.history, test_start_time),
+        );
 
-                // Add a final row of aggregate data for the status code table.
-                status_code_metrics.push(report::StatusCodeMetric {
-                    method: "".to_string(),
-                    name: "Aggregated".to_string(),
-                    status_codes: aggregated_codes,
-                });
+        // Compile the timing breakdown template, listing the aggregate after all requests.
+        let mut timing_breakdown = self.metrics.timing_breakdown();
+        let aggregated = timing_breakdown.remove("Aggregated");
+        let mut timing_breakdown_rows: Vec<String> = timing_breakdown
+            .iter()
+            .map(|(request_key, request)| report::timing_breakdown_row(request_key, request))
+            .collect();
+        if let Some(aggregated) = aggregated {
+            timing_breakdown_rows.push(report::timing_breakdown_row("Aggregated", &aggregated));
+        }
+        let timing_breakdown_template =
+            report::timing_breakdown_template(&timing_breakdown_rows.join("\n"));
+
+        // Compile the custom metrics template, with a graph of each kind of custom metric
+        // recorded.
+        let custom_metrics_template = if !self.metrics.custom.is_empty() {
+            let granular_data = !self.configuration.no_granular_report;
+            let graphs = [
+                self.graph_data
+                    .get_custom_counters_graph(granular_data)
+                    .map(|graph| graph.get_markup(&self.metrics.history, test_start_time)),
+                self.graph_data
+                    .get_custom_gauges_graph(granular_data)
+                    .map(|graph| graph.get_markup(&self.metrics.history, test_start_time)),
+                self.graph_data
+                    .get_custom_timings_graph(granular_data)
+                    .map(|graph| graph.get_markup(&self.metrics.history, test_start_time)),
+            ];
+            let custom_metrics_rows: Vec<String> = self
+                .metrics
+                .custom
+                .iter()
+                .map(|(name, custom)| report::custom_metric_row(name, custom))
+                .collect();
+            report::custom_metrics_template(
+                &custom_metrics_rows.join("\n"),
+                &graphs.iter().flatten().join("\n"),
+            )
+        } else {
+            "".to_string()
+        };
 
-                // Compile the status_code metrics rows.
-                let mut status_code_rows = Vec::new();
-                for metric in status_code_metrics {
-                    status_code_rows.push(report::status_code_metrics_row(metric));
-                }
+        // Compile the report template.
+        report::build_report(
+            &users,
+            &steps_overview,
+            hosts,
+            report::GooseReportTemplates {
+                raw_requests_template: &raw_requests_rows.join("\n"),
+                raw_responses_template: &raw_responses_rows.join("\n"),
+                responses_header_template: &raw_responses_header,
+                co_requests_template: &co_requests_template,
+                co_responses_template: &co_responses_template,
+                transactions_template: &transactions_template,
+                scenarios_template: &scenarios_template,
+                status_codes_template: &status_code_template,
+                errors_template: &errors_template,
+                thresholds_template: &thresholds_template,
+                baseline_template: &baseline_template,
+                phases_template: &phases_template,
+                bandwidth_template: &bandwidth_template,
+                timing_breakdown_template: &timing_breakdown_template,
+                custom_metrics_template: &custom_metrics_template,
+                graph_rps_template: &self
+                    .graph_data
+                    .get_requests_per_second_graph(!self.configuration.no_granular_report)
+                    .get_markup(&self.metrics.history, test_start_time),
+                graph_average_response_time_template: &self
+                    .graph_data
+                    .get_average_response_time_graph(!self.configuration.no_granular_report)
+                    .get_markup(&self.metrics.history, test_start_time),
+                graph_response_time_percentiles_template: &self
+                    .graph_data
+                    .get_response_time_percentile_graphs(!self.configuration.no_granular_report)
+                    .into_iter()
+                    .map(|graph| graph.get_markup(&self.metrics.history, test_start_time))
+                    .collect::<Vec<String>>()
+                    .join("\n"),
+                graph_users_per_second: &self
+                    .graph_data
+                    .get_active_users_graph(!self.configuration.no_granular_report)
+                    .get_markup(&self.metrics.history, test_start_time),
+            },
+        )
+    }
 
-                // Compile the status_code metrics template.
-                report::status_code_metrics_template(&status_code_rows.join("\n"))
-            } else {
-                // If --status-codes is not enabled, return an empty template.
-                "".to_string()
-            };
+    // Write the metrics to a baseline file, if enabled.
+    pub(crate) async fn write_baseline_file(&mut self) -> Result<(), GooseError> {
+        if self.configuration.baseline_file.is_empty() {
+            return Ok(());
+        }
 
-            // Compile the report template.
-            let report = report::build_report(
-                &users,
-                &steps_overview,
-                hosts,
-                report::GooseReportTemplates {
-                    raw_requests_template: &raw_requests_rows.join("\n"),
-                    raw_responses_template: &raw_responses_rows.join("\n"),
-                    co_requests_template: &co_requests_template,
-                    co_responses_template: &co_responses_template,
-                    transactions_template: &transactions_template,
-                    scenarios_template: &scenarios_template,
-                    status_codes_template: &status_code_template,
-                    errors_template: &errors_template,
-                    graph_rps_template: &self
-                        .graph_data
-                        .get_requests_per_second_graph(!self.configuration.no_granular_report)
-                        .get_markup(&self.metrics.history, test_start_time),
-                    graph_average_response_time_template: &self
-                        .graph_data
-                        .get_average_response_time_graph(!self.configuration.no_granular_report)
-                        .get_markup(&self.metrics.history, test_start_time),
-                    graph_users_per_second: &self
-                        .graph_data
-                        .get_active_users_graph(!self.configuration.no_granular_report)
-                        .get_markup(&self.metrics.history, test_start_time),
-                },
-            );
+        let baseline = match serde_json::to_string(&self.metrics) {
+            Ok(baseline) => baseline,
+            Err(e) => {
+                return Err(GooseError::InvalidOption {
+                    option: "--baseline-file".to_string(),
+                    value: self.configuration.baseline_file.to_string(),
+                    detail: format!("Failed to serialize metrics: {}", e),
+                })
+            }
+        };
+        if let Err(e) = tokio::fs::write(&self.configuration.baseline_file, baseline).await {
+            return Err(GooseError::InvalidOption {
+                option: "--baseline-file".to_string(),
+                value: self.configuration.baseline_file.to_string(),
+                detail: format!("Failed to create baseline file: {}", e),
+            });
+        }
 
-            // Write the report to file.
-            if let Err(e) = report_file.write_all(report.as_ref()).await {
+        info!(
+            "baseline file written to: {}",
+            self.configuration.baseline_file
+        );
+
+        Ok(())
+    }
+
+    /// Write JUnit XML results to the configured `--junit-file`, if enabled.
+    pub(crate) async fn write_junit_file(&mut self) -> Result<(), GooseError> {
+        if self.configuration.junit_file.is_empty() {
+            return Ok(());
+        }
+
+        let junit = junit::build_junit(&self.metrics);
+        if let Err(e) = tokio::fs::write(&self.configuration.junit_file, junit).await {
+            return Err(GooseError::InvalidOption {
+                option: "--junit-file".to_string(),
+                value: self.configuration.junit_file.to_string(),
+                detail: format!("Failed to create junit file: {}", e),
+            });
+        }
+
+        info!("junit file written to: {}", self.configuration.junit_file);
+
+        Ok(())
+    }
+
+    /// Write Locust-compatible CSV files with the configured `--csv` prefix, if enabled.
+    pub(crate) async fn write_locust_csv(&mut self) -> Result<(), GooseError> {
+        if self.configuration.csv.is_empty() {
+            return Ok(());
+        }
+
+        for (suffix, csv) in [
+            ("stats", locust::build_stats(&self.metrics)),
+            ("failures", locust::build_failures(&self.metrics)),
+            ("exceptions", locust::build_exceptions(&self.metrics)),
+        ] {
+            let csv_file = format!("{}_{}.csv", self.configuration.csv, suffix);
+            if let Err(e) = tokio::fs::write(&csv_file, csv).await {
                 return Err(GooseError::InvalidOption {
-                    option: "--report-file".to_string(),
-                    value: self.get_report_file_path().unwrap(),
-                    detail: format!("Failed to create report file: {}", e),
+                    option: "--csv".to_string(),
+                    value: self.configuration.csv.to_string(),
+                    detail: format!("Failed to create csv file {}: {}", csv_file, e),
                 });
-            };
-            // Be sure the file flushes to disk.
-            report_file.flush().await?;
-
-            info!(
-                "html report file written to: {}",
-                self.get_report_file_path().unwrap()
-            );
+            }
         }
 
+        info!("csv files written with prefix: {}", self.configuration.csv);
+
         Ok(())
     }
 }
@@ -3598,30 +6187,116 @@ fn determine_precision(value: f32) -> usize {
     }
 }
 
+/// Merge a [`GooseRequestMetric`] into the matching [`GooseRequestMetricAggregate`],
+/// creating it if this is the first time the request was made.
+fn merge_request_metric(
+    requests: &mut GooseRequestMetrics,
+    request_metric: &GooseRequestMetric,
+    significant_digits: usize,
+    status_codes: bool,
+) {
+    let key = format!("{} {}", request_metric.raw.method, request_metric.name);
+    let merge_request = requests.entry(key).or_insert_with(|| {
+        GooseRequestMetricAggregate::new(
+            &request_metric.name,
+            request_metric.raw.method.clone(),
+            0,
+            significant_digits,
+        )
+    });
+
+    // Handle a metrics update. The request may have been recorded in a previous test plan
+    // step, so don't assume it was counted here.
+    if request_metric.update {
+        if request_metric.success {
+            merge_request.success_count += 1;
+            merge_request.fail_count = merge_request.fail_count.saturating_sub(1);
+        } else {
+            merge_request.success_count = merge_request.success_count.saturating_sub(1);
+            merge_request.fail_count += 1;
+        }
+    }
+    // Store a new metric.
+    else {
+        merge_request.record_time(
+            request_metric.response_time,
+            request_metric.coordinated_omission_elapsed > 0,
+        );
+        if status_codes {
+            merge_request.set_status_code(request_metric.status_code);
+        }
+        // Only count bytes actually transferred, not Coordinated Omission Mitigation backfill.
+        if request_metric.coordinated_omission_elapsed == 0 {
+            merge_request.request_bytes += request_metric.request_bytes as usize;
+            merge_request.response_bytes += request_metric.response_bytes as usize;
+            merge_request.compressed_bytes += request_metric.compressed_bytes as usize;
+            merge_request.record_connection(request_metric);
+        }
+        if request_metric.success {
+            merge_request.success_count += 1;
+        } else {
+            merge_request.fail_count += 1;
+            if request_metric.coordinated_omission_elapsed == 0 {
+                merge_request
+                    .fail_times
+                    .record(request_metric.response_time as usize);
+            }
+        }
+    }
+}
+
+/// Format the count, value, minimum and maximum of a custom metric for display. The value
+/// of a counter is its total, and of gauges and timings their average.
+pub(crate) fn custom_metric_columns(
+    custom: &GooseCustomMetricAggregate,
+) -> (String, String, String, String) {
+    match custom {
+        GooseCustomMetricAggregate::Counter(counter) => (
+            format_number(counter.count),
+            counter.total.to_formatted_string(&Locale::en),
+            "".to_string(),
+            "".to_string(),
+        ),
+        GooseCustomMetricAggregate::Gauge(gauge) => (
+            format_number(gauge.count),
+            format!("{:.2}", gauge.average()),
+            format!("{:.2}", gauge.minimum),
+            format!("{:.2}", gauge.maximum),
+        ),
+        GooseCustomMetricAggregate::Timing(timing) => (
+            format_number(timing.counter),
+            format!(
+                "{:.2}",
+                match timing.counter {
+                    0 => 0.0,
+                    _ => timing.total_time as f32 / timing.counter as f32,
+                }
+            ),
+            format_number(timing.minimum_time),
+            format_number(timing.maximum_time),
+        ),
+    }
+}
+
 /// Format large number in locale appropriate style.
 pub(crate) fn format_number(number: usize) -> String {
     (number).to_formatted_string(&Locale::en)
 }
 
-/// A helper function that merges together times.
-///
-/// Used in `lib.rs` to merge together per-thread times, and in `metrics.rs` to
-/// aggregate all times.
-pub(crate) fn merge_times(
-    mut global_response_times: BTreeMap<usize, usize>,
-    local_response_times: BTreeMap<usize, usize>,
-) -> BTreeMap<usize, usize> {
-    // Iterate over user response times, and merge into global response times.
-    for (response_time, count) in &local_response_times {
-        let counter = match global_response_times.get(response_time) {
-            // We've seen this response_time before, increment counter.
-            Some(c) => *c + count,
-            // First time we've seen this response time, initialize counter.
-            None => *count,
-        };
-        global_response_times.insert(*response_time, counter);
+/// Format a number of bytes in a human readable unit.
+pub(crate) fn format_bytes(bytes: f32) -> String {
+    let units = ["B", "KiB", "MiB", "GiB", "TiB"];
+    let mut value = bytes;
+    let mut unit = 0;
+    while value >= 1024.0 && unit < units.len() - 1 {
+        value /= 1024.0;
+        unit += 1;
+    }
+    if unit == 0 {
+        format!("{} {}", value.round() as usize, units[unit])
+    } else {
+        format!("{:.1} {}", value, units[unit])
     }
-    global_response_times
 }
 
 /// A helper function to update the global minimum time based on local time.
@@ -3641,13 +6316,13 @@ pub(crate) fn update_max_time(mut global_max: usize, max: usize) -> usize {
 }
 
 /// Get the response time that a certain number of percent of the requests finished within.
-pub(crate) fn calculate_response_time_percentile(
-    response_times: &BTreeMap<usize, usize>,
+pub(crate) fn response_time_percentile(
+    response_times: &GooseHistogram,
     total_requests: usize,
     min: usize,
     max: usize,
     percent: f32,
-) -> String {
+) -> usize {
     let percentile_request = (total_requests as f32 * percent).round() as usize;
     debug!(
         "percentile: {}, request {} of total {}",
@@ -3660,15 +6335,15 @@ pub(crate) fn calculate_response_time_percentile(
         total_count += counter;
         if total_count >= percentile_request {
             if *value < min {
-                return format_number(min);
+                return min;
             } else if *value > max {
-                return format_number(max);
+                return max;
             } else {
-                return format_number(*value);
+                return *value;
             }
         }
     }
-    format_number(0)
+    0
 }
 
 /// Helper to count and aggregate seen status codes.
@@ -3737,45 +6412,106 @@ mod test {
 
     #[test]
     fn response_time_merge() {
-        let mut global_response_times: BTreeMap<usize, usize> = BTreeMap::new();
-        let local_response_times: BTreeMap<usize, usize> = BTreeMap::new();
-        global_response_times = merge_times(global_response_times, local_response_times.clone());
-        // @TODO: how can we do useful testing of private method and objects?
+        let mut global_response_times = GooseHistogram::default();
+        let mut local_response_times = GooseHistogram::default();
+        global_response_times.merge(&local_response_times);
         assert_eq!(&global_response_times, &local_response_times);
+
+        // Merging adds together the counters of each bucket.
+        global_response_times.record(5);
+        local_response_times.record_n(5, 2);
+        local_response_times.record(3000);
+        global_response_times.merge(&local_response_times);
+        assert_eq!(global_response_times.len(), 2);
+        assert_eq!(global_response_times.count(5), 3);
+        assert_eq!(global_response_times.count(3000), 1);
+
+        // An empty histogram takes on the precision of the merged histogram.
+        let mut empty_response_times = GooseHistogram::default();
+        let precise_response_times = GooseHistogram::new(5);
+        empty_response_times.merge(&precise_response_times);
+        assert_eq!(empty_response_times.significant_digits(), 5);
+
+        // Times are re-recorded when merging histograms with a different precision.
+        let mut imprecise_response_times = GooseHistogram::new(1);
+        imprecise_response_times.record(1);
+        imprecise_response_times.merge(&global_response_times);
+        assert_eq!(imprecise_response_times.len(), 3);
+        assert_eq!(imprecise_response_times.count(3000), 1);
+        assert_eq!(
+            imprecise_response_times.iter().next_back(),
+            Some((&3008, &1))
+        );
+
+        // Subtracting removes times recorded in an earlier copy of the histogram.
+        let earlier_response_times = global_response_times.clone();
+        global_response_times.record(5);
+        global_response_times.subtract(&earlier_response_times);
+        assert_eq!(global_response_times.len(), 1);
+        assert_eq!(global_response_times.count(5), 1);
+    }
+
+    #[test]
+    fn histogram_buckets() {
+        // Precision is limited to from 1 to 5 significant digits.
+        assert_eq!(GooseHistogram::new(0).significant_digits(), 1);
+        assert_eq!(GooseHistogram::new(9).significant_digits(), 5);
+
+        // With 3 significant digits, times up to 2,047 milliseconds are exact.
+        let response_times = GooseHistogram::default();
+        assert_eq!(response_times.significant_digits(), 3);
+        for time in [0, 1, 100, 101, 155, 999, 1001, 2047] {
+            assert_eq!(response_times.bucket(time), time);
+        }
+        // Larger times share buckets, counted as the middle of the bucket.
+        assert_eq!(response_times.bucket(2048), 2049);
+        assert_eq!(response_times.bucket(2049), 2049);
+        assert_eq!(response_times.bucket(5000), 5002);
+        // Times are always within 0.1% of their actual value.
+        for time in [2345, 10_000, 123_456, 987_654_321] {
+            let bucket = response_times.bucket(time);
+            assert!((bucket as f64 - time as f64).abs() / (time as f64) < 0.001);
+        }
+
+        // With 1 significant digit, times up to 31 milliseconds are exact.
+        let response_times = GooseHistogram::new(1);
+        assert_eq!(response_times.bucket(31), 31);
+        assert_eq!(response_times.bucket(32), 33);
+        assert_eq!(response_times.bucket(1000), 1008);
     }
 
     #[test]
     fn max_response_time_percentile() {
-        let mut response_times: BTreeMap<usize, usize> = BTreeMap::new();
-        response_times.insert(1, 1);
-        response_times.insert(2, 1);
-        response_times.insert(3, 1);
+        let mut response_times = GooseHistogram::default();
+        response_times.record(1);
+        response_times.record(2);
+        response_times.record(3);
         // 3 * .5 = 1.5, rounds to 2.
-        assert!(calculate_response_time_percentile(&response_times, 3, 1, 3, 0.5) == "2");
-        response_times.insert(3, 2);
+        assert!(response_time_percentile(&response_times, 3, 1, 3, 0.5) == 2);
+        response_times.record(3);
         // 4 * .5 = 2
-        assert!(calculate_response_time_percentile(&response_times, 4, 1, 3, 0.5) == "2");
+        assert!(response_time_percentile(&response_times, 4, 1, 3, 0.5) == 2);
         // 4 * .25 = 1
-        assert!(calculate_response_time_percentile(&response_times, 4, 1, 3, 0.25) == "1");
+        assert!(response_time_percentile(&response_times, 4, 1, 3, 0.25) == 1);
         // 4 * .75 = 3
-        assert!(calculate_response_time_percentile(&response_times, 4, 1, 3, 0.75) == "3");
+        assert!(response_time_percentile(&response_times, 4, 1, 3, 0.75) == 3);
         // 4 * 1 = 4 (and the 4th response time is also 3)
-        assert!(calculate_response_time_percentile(&response_times, 4, 1, 3, 1.0) == "3");
+        assert!(response_time_percentile(&response_times, 4, 1, 3, 1.0) == 3);
 
         // 4 * .5 = 2, but uses specified minimum of 2
-        assert!(calculate_response_time_percentile(&response_times, 4, 2, 3, 0.25) == "2");
+        assert!(response_time_percentile(&response_times, 4, 2, 3, 0.25) == 2);
         // 4 * .75 = 3, but uses specified maximum of 2
-        assert!(calculate_response_time_percentile(&response_times, 4, 1, 2, 0.75) == "2");
-
-        response_times.insert(10, 25);
-        response_times.insert(20, 25);
-        response_times.insert(30, 25);
-        response_times.insert(50, 25);
-        response_times.insert(100, 10);
-        response_times.insert(200, 1);
-        assert!(calculate_response_time_percentile(&response_times, 115, 1, 200, 0.9) == "50");
-        assert!(calculate_response_time_percentile(&response_times, 115, 1, 200, 0.99) == "100");
-        assert!(calculate_response_time_percentile(&response_times, 115, 1, 200, 0.999) == "200");
+        assert!(response_time_percentile(&response_times, 4, 1, 2, 0.75) == 2);
+
+        response_times.record_n(10, 25);
+        response_times.record_n(20, 25);
+        response_times.record_n(30, 25);
+        response_times.record_n(50, 25);
+        response_times.record_n(100, 10);
+        response_times.record(200);
+        assert!(response_time_percentile(&response_times, 115, 1, 200, 0.9) == 50);
+        assert!(response_time_percentile(&response_times, 115, 1, 200, 0.99) == 100);
+        assert!(response_time_percentile(&response_times, 115, 1, 200, 0.999) == 200);
     }
 
     #[test]
@@ -3839,7 +6575,8 @@ mod test {
 
     #[test]
     fn goose_request() {
-        let mut request = GooseRequestMetricAggregate::new("/", GooseMethod::Get, 0);
+        let mut request =
+            GooseRequestMetricAggregate::new("/", GooseMethod::Get, 0, DEFAULT_SIGNIFICANT_DIGITS);
         assert_eq!(request.path, "/".to_string());
         assert_eq!(request.method, GooseMethod::Get);
         assert_eq!(request.raw_data.times.len(), 0);
@@ -3856,7 +6593,7 @@ mod test {
         // We've seen only one response time so far.
         assert_eq!(request.raw_data.times.len(), 1);
         // We've seen one response time of length 1.
-        assert_eq!(request.raw_data.times[&1], 1);
+        assert_eq!(request.raw_data.times.count(1), 1);
         // The minimum response time seen so far is 1.
         assert_eq!(request.raw_data.minimum_time, 1);
         // The maximum response time seen so far is 1.
@@ -3877,7 +6614,7 @@ mod test {
         // We've added a new unique response time.
         assert_eq!(request.raw_data.times.len(), 2);
         // We've seen the 10 ms response time 1 time.
-        assert_eq!(request.raw_data.times[&10], 1);
+        assert_eq!(request.raw_data.times.count(10), 1);
         // Minimum doesn't change.
         assert_eq!(request.raw_data.minimum_time, 1);
         // Maximum is new response time.
@@ -3898,7 +6635,7 @@ mod test {
         // We've incremented the counter of an existing response time.
         assert_eq!(request.raw_data.times.len(), 2);
         // We've seen the 10 ms response time 2 times.
-        assert_eq!(request.raw_data.times[&10], 2);
+        assert_eq!(request.raw_data.times.count(10), 2);
         // Minimum doesn't change.
         assert_eq!(request.raw_data.minimum_time, 1);
         // Maximum doesn't change.
@@ -3912,8 +6649,8 @@ mod test {
         request.record_time(101, false);
         // We've added a new response time for the first time.
         assert_eq!(request.raw_data.times.len(), 3);
-        // The response time was internally rounded to 100, which we've seen once.
-        assert_eq!(request.raw_data.times[&100], 1);
+        // The response time was recorded exactly, and we've seen it once.
+        assert_eq!(request.raw_data.times.count(101), 1);
         // Minimum doesn't change.
         assert_eq!(request.raw_data.minimum_time, 1);
         // Maximum increases to actual maximum, not rounded maximum.
@@ -3925,10 +6662,10 @@ mod test {
 
         // Tracking another response time updates all related fields.
         request.record_time(102, false);
-        // Due to rounding, this increments the existing 100 ms response time.
-        assert_eq!(request.raw_data.times.len(), 3);
-        // The response time was internally rounded to 100, which we've now seen twice.
-        assert_eq!(request.raw_data.times[&100], 2);
+        // Times up to 2,047 ms aren't rounded, so this adds a new response time.
+        assert_eq!(request.raw_data.times.len(), 4);
+        // The response time was recorded exactly, and we've seen it once.
+        assert_eq!(request.raw_data.times.count(102), 1);
         // Minimum doesn't change.
         assert_eq!(request.raw_data.minimum_time, 1);
         // Maximum increases to actual maximum, not rounded maximum.
@@ -3941,9 +6678,9 @@ mod test {
         // Tracking another response time updates all related fields.
         request.record_time(155, false);
         // Adds a new response time.
-        assert_eq!(request.raw_data.times.len(), 4);
-        // The response time was internally rounded to 160, seen for the first time.
-        assert_eq!(request.raw_data.times[&160], 1);
+        assert_eq!(request.raw_data.times.len(), 5);
+        // The response time was recorded exactly, seen for the first time.
+        assert_eq!(request.raw_data.times.count(155), 1);
         // Minimum doesn't change.
         assert_eq!(request.raw_data.minimum_time, 1);
         // Maximum increases to actual maximum, not rounded maximum.
@@ -3956,9 +6693,9 @@ mod test {
         // Tracking another response time updates all related fields.
         request.record_time(2345, false);
         // Adds a new response time.
-        assert_eq!(request.raw_data.times.len(), 5);
-        // The response time was internally rounded to 2000, seen for the first time.
-        assert_eq!(request.raw_data.times[&2000], 1);
+        assert_eq!(request.raw_data.times.len(), 6);
+        // The response time was internally counted in a 2 ms wide bucket with 2344.
+        assert_eq!(request.raw_data.times.count(2344), 1);
         // Minimum doesn't change.
         assert_eq!(request.raw_data.minimum_time, 1);
         // Maximum increases to actual maximum, not rounded maximum.
@@ -3971,9 +6708,12 @@ mod test {
         // Tracking another response time updates all related fields.
         request.record_time(987654321, false);
         // Adds a new response time.
-        assert_eq!(request.raw_data.times.len(), 6);
-        // The response time was internally rounded to 987654000, seen for the first time.
-        assert_eq!(request.raw_data.times[&987654000], 1);
+        assert_eq!(request.raw_data.times.len(), 7);
+        // The response time was internally counted as 987496448, within 0.1% of the actual time.
+        assert_eq!(
+            request.raw_data.times.iter().next_back(),
+            Some((&987496448, &1))
+        );
         // Minimum doesn't change.
         assert_eq!(request.raw_data.minimum_time, 1);
         // Maximum increases to actual maximum, not rounded maximum.
@@ -3993,7 +6733,7 @@ mod test {
         assert_eq!(request.success_count, 0);
         assert_eq!(request.fail_count, 0);
         // Nothing else changes.
-        assert_eq!(request.raw_data.times.len(), 6);
+        assert_eq!(request.raw_data.times.len(), 7);
         assert_eq!(request.raw_data.minimum_time, 1);
         assert_eq!(request.raw_data.maximum_time, 987654321);
         assert_eq!(request.raw_data.total_time, 987657045);
@@ -4036,10 +6776,457 @@ mod test {
         // Nothing else changes.
         assert_eq!(request.success_count, 0);
         assert_eq!(request.fail_count, 0);
-        assert_eq!(request.raw_data.times.len(), 6);
+        assert_eq!(request.raw_data.times.len(), 7);
         assert_eq!(request.raw_data.minimum_time, 1);
         assert_eq!(request.raw_data.maximum_time, 987654321);
         assert_eq!(request.raw_data.total_time, 987657045);
         assert_eq!(request.raw_data.counter, 8);
     }
+
+    #[test]
+    fn percentiles_from_str() {
+        let percentiles = "50, 90,99.9,100".parse::<GoosePercentiles>().unwrap();
+        assert_eq!(percentiles.percentiles, vec![50.0, 90.0, 99.9, 100.0]);
+        assert_eq!(percentiles.to_string(), "50,90,99.9,100");
+
+        // Percentiles must be greater than 0 and up to 100.
+        assert!("0".parse::<GoosePercentiles>().is_err());
+        assert!("100.1".parse::<GoosePercentiles>().is_err());
+        assert!("50,foo".parse::<GoosePercentiles>().is_err());
+        assert!("".parse::<GoosePercentiles>().is_err());
+
+        // Percentiles are calculated from the recorded response times.
+        let mut request = GooseRequestMetricTimingData::default();
+        for time in 1..=1000 {
+            request.record_time(time);
+        }
+        let calculated = request.percentiles(&percentiles.percentiles);
+        assert_eq!(calculated.len(), 4);
+        assert_eq!(calculated[0].response_time, 500);
+        assert_eq!(calculated[1].response_time, 900);
+        assert_eq!(calculated[2].response_time, 999);
+        assert_eq!(calculated[3].response_time, 1000);
+    }
+
+    #[test]
+    fn thresholds_from_str() {
+        let thresholds = "p95<500; GET /:error-rate < 1% ;rps>10.5"
+            .parse::<GooseThresholds>()
+            .unwrap()
+            .thresholds;
+        assert_eq!(thresholds.len(), 3);
+        assert_eq!(thresholds[0].request, None);
+        assert_eq!(thresholds[0].metric, GooseThresholdMetric::P95);
+        assert!(!thresholds[0].greater_than);
+        assert_eq!(thresholds[0].value, 500.0);
+        assert_eq!(thresholds[1].request, Some("GET /".to_string()));
+        assert_eq!(thresholds[1].metric, GooseThresholdMetric::ErrorRate);
+        assert_eq!(thresholds[1].value, 1.0);
+        assert_eq!(
+            thresholds[2].metric,
+            GooseThresholdMetric::RequestsPerSecond
+        );
+        assert!(thresholds[2].greater_than);
+        assert_eq!(thresholds[2].value, 10.5);
+        assert_eq!(thresholds[1].to_string(), "GET /:error-rate<1");
+
+        // Request names can include ":".
+        let threshold = "GET http://example.com/:max<1000"
+            .parse::<GooseThreshold>()
+            .unwrap();
+        assert_eq!(
+            threshold.request,
+            Some("GET http://example.com/".to_string())
+        );
+        assert_eq!(threshold.metric, GooseThresholdMetric::Max);
+
+        assert!("p95".parse::<GooseThreshold>().is_err());
+        assert!("p42<500".parse::<GooseThreshold>().is_err());
+        assert!("p95<fast".parse::<GooseThreshold>().is_err());
+    }
+
+    #[test]
+    fn abort_rules_from_str() {
+        let rules = "error-rate<50,30s; GET /a,b:p95 < 2000, 1m30s"
+            .parse::<GooseAbortRules>()
+            .unwrap()
+            .rules;
+        assert_eq!(rules.len(), 2);
+        assert_eq!(rules[0].threshold.metric, GooseThresholdMetric::ErrorRate);
+        assert_eq!(rules[0].duration, 30);
+        assert_eq!(rules[0].to_string(), "error-rate<50,30s");
+        // Request names can include ",".
+        assert_eq!(rules[1].threshold.request, Some("GET /a,b".to_string()));
+        assert_eq!(rules[1].threshold.value, 2000.0);
+        assert_eq!(rules[1].duration, 90);
+
+        assert!("error-rate<50".parse::<GooseAbortRule>().is_err());
+        assert!("error-rate,30s".parse::<GooseAbortRule>().is_err());
+    }
+
+    #[test]
+    fn evaluate_thresholds() {
+        let mut index =
+            GooseRequestMetricAggregate::new("/", GooseMethod::Get, 0, DEFAULT_SIGNIFICANT_DIGITS);
+        for time in [10, 20, 30, 40] {
+            index.record_time(time, false);
+            index.success_count += 1;
+        }
+        let mut about = GooseRequestMetricAggregate::new(
+            "/about",
+            GooseMethod::Get,
+            0,
+            DEFAULT_SIGNIFICANT_DIGITS,
+        );
+        for time in [50, 60, 70, 80] {
+            about.record_time(time, false);
+            about.fail_count += 1;
+        }
+        let mut metrics = GooseMetrics {
+            duration: 2,
+            ..Default::default()
+        };
+        metrics.requests.insert("GET /".to_string(), index);
+        metrics.requests.insert("GET /about".to_string(), about);
+
+        let thresholds =
+            "max<100;error-rate<10;GET /:error-rate<10;/about:p50<50;rps>3;GET /missing:p95<500"
+                .parse::<GooseThresholds>()
+                .unwrap();
+        metrics.evaluate_thresholds(Some(&thresholds));
+        let results: Vec<(Option<f32>, bool)> = metrics
+            .thresholds
+            .iter()
+            .map(|result| (result.value, result.passed))
+            .collect();
+        assert_eq!(
+            results,
+            vec![
+                (Some(80.0), true),
+                (Some(50.0), false),
+                (Some(0.0), true),
+                (Some(60.0), false),
+                (Some(4.0), true),
+                (None, false),
+            ]
+        );
+        assert_eq!(metrics.failed_thresholds().len(), 3);
+    }
+
+    #[test]
+    fn apdex_scores() {
+        let apdex = "500; GET http://example.com/:100ms"
+            .parse::<GooseApdex>()
+            .unwrap();
+        assert_eq!(apdex.targets.len(), 2);
+        assert_eq!(apdex.targets[0].request, None);
+        assert_eq!(apdex.targets[0].target, 500);
+        // Request names can include ":".
+        assert_eq!(
+            apdex.targets[1].request,
+            Some("GET http://example.com/".to_string())
+        );
+        assert_eq!(apdex.targets[1].target, 100);
+        assert_eq!(apdex.to_string(), "500;GET http://example.com/:100");
+        assert!("".parse::<GooseApdex>().is_err());
+        assert!("0".parse::<GooseApdex>().is_err());
+        assert!("GET /:fast".parse::<GooseApdex>().is_err());
+
+        let mut index =
+            GooseRequestMetricAggregate::new("/", GooseMethod::Get, 0, DEFAULT_SIGNIFICANT_DIGITS);
+        for time in [10, 20, 30, 200, 500] {
+            index.record_time(time, false);
+        }
+        let mut about = GooseRequestMetricAggregate::new(
+            "/about",
+            GooseMethod::Get,
+            0,
+            DEFAULT_SIGNIFICANT_DIGITS,
+        );
+        for time in [50, 60] {
+            about.record_time(time, false);
+        }
+        let mut metrics = GooseMetrics::default();
+        metrics.requests.insert("GET /".to_string(), index);
+        metrics.requests.insert("GET /about".to_string(), about);
+
+        // Nothing is scored unless Apdex targets are configured.
+        assert!(metrics.apdex_scores().is_empty());
+
+        // A target for "{method} {name}" is preferred over a target for the name, which is
+        // preferred over a target for all requests.
+        metrics.apdex = Some("50;/about:40;GET /about:60".parse::<GooseApdex>().unwrap());
+        let scores = metrics.apdex_scores();
+        assert_eq!(scores.len(), 3);
+        let index = &scores["GET /"];
+        assert_eq!(index.target, Some(50));
+        assert_eq!(
+            (index.satisfied, index.tolerating, index.frustrated),
+            (3, 1, 1)
+        );
+        assert_eq!(index.score, 0.7);
+        assert_eq!(scores["GET /about"].target, Some(60));
+        assert_eq!(scores["GET /about"].score, 1.0);
+        let aggregated = &scores["Aggregated"];
+        assert_eq!(aggregated.target, None);
+        assert_eq!(
+            (
+                aggregated.satisfied,
+                aggregated.tolerating,
+                aggregated.frustrated
+            ),
+            (5, 1, 1)
+        );
+
+        // Requests without a target aren't scored.
+        metrics.apdex = Some("/about:40".parse::<GooseApdex>().unwrap());
+        let scores = metrics.apdex_scores();
+        assert!(!scores.contains_key("GET /"));
+        assert_eq!(scores["GET /about"].score, 0.5);
+
+        // Failed requests are frustrated however quickly they failed.
+        let about = metrics.requests.get_mut("GET /about").unwrap();
+        about.record_time(10, false);
+        about.fail_times.record(10);
+        metrics.apdex = Some("GET /about:60".parse::<GooseApdex>().unwrap());
+        let about = &metrics.apdex_scores()["GET /about"];
+        assert_eq!(
+            (about.satisfied, about.tolerating, about.frustrated),
+            (2, 0, 1)
+        );
+    }
+
+    #[test]
+    fn compare_baseline() {
+        let mut baseline = GooseBaseline {
+            duration: 2,
+            ..Default::default()
+        };
+        let mut index =
+            GooseRequestMetricAggregate::new("/", GooseMethod::Get, 0, DEFAULT_SIGNIFICANT_DIGITS);
+        for time in [10, 20, 30, 40] {
+            index.record_time(time, false);
+            index.success_count += 1;
+        }
+        baseline.requests.insert("GET /".to_string(), index);
+
+        // The same requests were made, but slower and with one failure.
+        let mut metrics = GooseMetrics {
+            duration: 2,
+            ..Default::default()
+        };
+        let mut index =
+            GooseRequestMetricAggregate::new("/", GooseMethod::Get, 0, DEFAULT_SIGNIFICANT_DIGITS);
+        for time in [10, 20, 30, 100] {
+            index.record_time(time, false);
+            index.success_count += 1;
+        }
+        index.success_count -= 1;
+        index.fail_count += 1;
+        metrics.requests.insert("GET /".to_string(), index);
+
+        // Only compare the configured percentiles.
+        metrics.percentiles = "50,95,99".parse().unwrap();
+        metrics.compare_baseline(Some(&baseline), 10);
+        // Each metric is compared for the request, and then for all requests.
+        assert_eq!(metrics.baseline.len(), 12);
+        let results: Vec<(GooseBaselineMetric, String, bool)> = metrics.baseline[0..6]
+            .iter()
+            .map(|delta| (delta.metric, delta.fmt_change(), delta.regression))
+            .collect();
+        assert_eq!(
+            results,
+            vec![
+                (GooseBaselineMetric::Average, "+60.00%".to_string(), true),
+                (
+                    GooseBaselineMetric::Percentile(50.0),
+                    "+0.00%".to_string(),
+                    false
+                ),
+                (
+                    GooseBaselineMetric::Percentile(95.0),
+                    "+150.00%".to_string(),
+                    true
+                ),
+                (
+                    GooseBaselineMetric::Percentile(99.0),
+                    "+150.00%".to_string(),
+                    true
+                ),
+                (GooseBaselineMetric::ErrorRate, "+25.00pp".to_string(), true),
+                (
+                    GooseBaselineMetric::RequestsPerSecond,
+                    "+0.00%".to_string(),
+                    false
+                ),
+            ]
+        );
+        assert_eq!(metrics.baseline[6].request, None);
+
+        metrics.compare_baseline(None, 10);
+        assert!(metrics.baseline.is_empty());
+    }
+
+    #[test]
+    fn bandwidth() {
+        let mut index =
+            GooseRequestMetricAggregate::new("/", GooseMethod::Get, 0, DEFAULT_SIGNIFICANT_DIGITS);
+        index.request_bytes = 0;
+        index.response_bytes = 4096;
+        index.compressed_bytes = 1024;
+        let mut post =
+            GooseRequestMetricAggregate::new("/", GooseMethod::Post, 0, DEFAULT_SIGNIFICANT_DIGITS);
+        post.request_bytes = 300;
+        post.response_bytes = 100;
+        post.compressed_bytes = 100;
+        let mut metrics = GooseMetrics {
+            duration: 2,
+            ..Default::default()
+        };
+        metrics.requests.insert("GET /".to_string(), index);
+        metrics.requests.insert("POST /".to_string(), post);
+
+        let bandwidth = metrics.bandwidth();
+        assert_eq!(bandwidth.len(), 3);
+        assert_eq!(bandwidth["GET /"], GooseBandwidth::new(0, 4096, 1024, 2));
+        assert_eq!(bandwidth["POST /"].request_bytes_per_second, 150.0);
+        assert_eq!(bandwidth["Aggregated"].request_bytes, 300);
+        assert_eq!(bandwidth["Aggregated"].response_bytes, 4196);
+        assert_eq!(bandwidth["Aggregated"].compressed_bytes, 1124);
+        assert_eq!(bandwidth["Aggregated"].response_bytes_per_second, 2098.0);
+
+        assert_eq!(format_bytes(0.0), "0 B");
+        assert_eq!(format_bytes(1023.4), "1023 B");
+        assert_eq!(format_bytes(2098.0), "2.0 KiB");
+        assert_eq!(format_bytes(5.5 * 1024.0 * 1024.0), "5.5 MiB");
+    }
+
+    #[test]
+    fn timing_breakdown() {
+        let mut index =
+            GooseRequestMetricAggregate::new("/", GooseMethod::Get, 0, DEFAULT_SIGNIFICANT_DIGITS);
+        let mut request_metric = GooseRequestMetric::new(
+            GooseRawRequest::new(GooseMethod::Get, "/", vec![], ""),
+            "/",
+            0,
+            0,
+        );
+        request_metric.status_code = 200;
+
+        // A new connection, including the time to resolve the host name.
+        request_metric.response_time = 30;
+        request_metric.dns_time = 8;
+        request_metric.download_time = 6;
+        index.record_time(request_metric.response_time, false);
+        index.record_connection(&request_metric);
+
+        // Three requests on a reused connection.
+        request_metric.connection_reused = true;
+        request_metric.response_time = 10;
+        request_metric.dns_time = 0;
+        request_metric.download_time = 2;
+        for _ in 0..3 {
+            index.record_time(request_metric.response_time, false);
+            index.record_connection(&request_metric);
+        }
+
+        // A request that failed without a response.
+        request_metric.status_code = 0;
+        request_metric.connection_reused = false;
+        index.record_connection(&request_metric);
+
+        assert_eq!(index.new_connections, 1);
+        assert_eq!(index.reused_connections, 3);
+        assert_eq!(index.dns_time, 8);
+        assert_eq!(index.download_time, 12);
+        assert_eq!(index.new_connection_time, 30);
+        assert_eq!(index.reused_connection_time, 30);
+
+        let mut metrics = GooseMetrics::default();
+        metrics.requests.insert("GET /".to_string(), index);
+        metrics.requests.insert(
+            "GET /about".to_string(),
+            GooseRequestMetricAggregate::new(
+                "/about",
+                GooseMethod::Get,
+                0,
+                DEFAULT_SIGNIFICANT_DIGITS,
+            ),
+        );
+
+        let timing_breakdown = metrics.timing_breakdown();
+        assert_eq!(timing_breakdown.len(), 3);
+        let index = &timing_breakdown["GET /"];
+        assert_eq!(index.connection_reuse, 75.0);
+        assert_eq!(index.dns_time, 8.0);
+        assert_eq!(index.connect_time, 22.0);
+        assert_eq!(index.time_to_first_byte, 10.0);
+        assert_eq!(index.download_time, 3.0);
+        // Requests without responses don't divide by zero.
+        assert_eq!(timing_breakdown["GET /about"].download_time, 0.0);
+        assert_eq!(timing_breakdown["Aggregated"], *index);
+    }
+
+    #[test]
+    fn custom_metrics() {
+        let counter = GooseCustomMetricValue::Counter(2);
+        let mut cart_items = GooseCustomMetricAggregate::new(&counter, DEFAULT_SIGNIFICANT_DIGITS);
+        assert!(cart_items.record(&counter));
+        assert!(cart_items.record(&GooseCustomMetricValue::Counter(3)));
+        // A counter can't also be used as a gauge.
+        assert!(!cart_items.record(&GooseCustomMetricValue::Gauge(1.0)));
+        assert_eq!(cart_items.kind(), "counter");
+        assert_eq!(
+            cart_items,
+            GooseCustomMetricAggregate::Counter(GooseCustomCounter { count: 2, total: 5 })
+        );
+
+        let gauge = GooseCustomMetricValue::Gauge(-1.5);
+        let mut results = GooseCustomMetricAggregate::new(&gauge, DEFAULT_SIGNIFICANT_DIGITS);
+        assert!(results.record(&gauge));
+        assert!(results.record(&GooseCustomMetricValue::Gauge(7.5)));
+        assert!(results.record(&GooseCustomMetricValue::Gauge(3.0)));
+        if let GooseCustomMetricAggregate::Gauge(gauge) = &results {
+            assert_eq!(gauge.count, 3);
+            assert_eq!(gauge.minimum, -1.5);
+            assert_eq!(gauge.maximum, 7.5);
+            assert_eq!(gauge.average(), 3.0);
+        } else {
+            panic!("expected a gauge");
+        }
+
+        let timing = GooseCustomMetricValue::Timing(10);
+        let mut render = GooseCustomMetricAggregate::new(&timing, DEFAULT_SIGNIFICANT_DIGITS);
+        assert!(render.record(&timing));
+        assert!(render.record(&GooseCustomMetricValue::Timing(30)));
+        assert!(!render.record(&GooseCustomMetricValue::Counter(1)));
+
+        assert_eq!(
+            custom_metric_columns(&cart_items),
+            (
+                "2".to_string(),
+                "5".to_string(),
+                "".to_string(),
+                "".to_string()
+            )
+        );
+        assert_eq!(
+            custom_metric_columns(&results),
+            (
+                "3".to_string(),
+                "3.00".to_string(),
+                "-1.50".to_string(),
+                "7.50".to_string()
+            )
+        );
+        assert_eq!(
+            custom_metric_columns(&render),
+            (
+                "2".to_string(),
+                "20.00".to_string(),
+                "10".to_string(),
+                "30".to_string()
+            )
+        );
+    }
 }
@@ -0,0 +1,561 @@
+//! Optional OpenTelemetry exporter.
+//!
+//! When enabled with `--otlp-endpoint`, Goose periodically exports the number of running
+//! users and per-request metrics to an OpenTelemetry collector, using OTLP/HTTP with the
+//! JSON encoding. When `--otlp-traces` is also enabled, a span is exported for each request
+//! and each transaction.
+
+use itertools::Itertools;
+use serde_json::{json, Value};
+use std::collections::HashMap;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use crate::metrics::{GooseMetrics, GooseRequestMetric, TransactionMetric};
+use crate::prometheus::RESPONSE_TIME_BUCKETS;
+use crate::util;
+
+/// How often metrics and spans are exported, in seconds.
+const EXPORT_INTERVAL: usize = 10;
+
+/// How many spans are buffered before they're exported without waiting for the interval.
+const MAX_BUFFERED_SPANS: usize = 512;
+
+/// How many payloads can wait for the exporter thread before new ones are dropped, so a
+/// slow or unreachable collector can't use up all available memory.
+const MAX_QUEUED_PAYLOADS: usize = 64;
+
+/// The OTLP path metrics are posted to.
+const METRICS_PATH: &str = "/v1/metrics";
+
+/// The OTLP path spans are posted to.
+const TRACES_PATH: &str = "/v1/traces";
+
+/// OTLP aggregation temporality of metrics that are totals since a start time.
+const AGGREGATION_TEMPORALITY_CUMULATIVE: u8 = 2;
+
+/// OTLP span kind of an operation within Goose, such as a transaction.
+const SPAN_KIND_INTERNAL: u8 = 1;
+
+/// OTLP span kind of a request made to a remote server.
+const SPAN_KIND_CLIENT: u8 = 3;
+
+/// OTLP status code of a span that failed.
+const STATUS_CODE_ERROR: u8 = 2;
+
+/// The current time in nanoseconds since the Unix epoch.
+fn now_nanos() -> u128 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|duration| duration.as_nanos())
+        .unwrap_or_default()
+}
+
+/// Convert milliseconds to nanoseconds.
+fn ms_to_nanos(ms: u64) -> u128 {
+    ms as u128 * 1_000_000
+}
+
+/// A random identifier of the given number of bytes, encoded as hex as required by the
+/// OTLP JSON encoding.
+fn random_id(bytes: usize) -> String {
+    (0..bytes)
+        .map(|_| format!("{:02x}", rand::random::<u8>()))
+        .collect()
+}
+
+/// A string attribute.
+fn string_attribute(key: &str, value: &str) -> Value {
+    json!({ "key": key, "value": { "stringValue": value } })
+}
+
+/// An integer attribute, encoded as a string as required by the OTLP JSON encoding.
+fn int_attribute(key: &str, value: u64) -> Value {
+    json!({ "key": key, "value": { "intValue": value.to_string() } })
+}
+
+/// A boolean attribute.
+fn bool_attribute(key: &str, value: bool) -> Value {
+    json!({ "key": key, "value": { "boolValue": value } })
+}
+
+/// The resource everything is exported for.
+fn resource() -> Value {
+    json!({ "attributes": [string_attribute("service.name", "goose")] })
+}
+
+/// The instrumentation scope everything is exported from.
+fn scope() -> Value {
+    json!({ "name": "goose", "version": env!("CARGO_PKG_VERSION") })
+}
+
+/// Format the number of running users and the metrics of each request as an OTLP
+/// metrics request. Requests are counted since `start_time`.
+fn format_metrics(metrics: &GooseMetrics, users: usize, start_time: u128, time: u128) -> Value {
+    let mut requests = Vec::new();
+    let mut failures = Vec::new();
+    let mut durations = Vec::new();
+    for (_, request) in metrics.requests.iter().sorted_by(|a, b| a.0.cmp(b.0)) {
+        let attributes = json!([
+            string_attribute("http.request.method", &request.method.to_string()),
+            string_attribute("goose.request.name", &request.path),
+        ]);
+        requests.push(json!({
+            "attributes": attributes,
+            "startTimeUnixNano": start_time.to_string(),
+            "timeUnixNano": time.to_string(),
+            "asInt": (request.success_count + request.fail_count).to_string(),
+        }));
+        failures.push(json!({
+            "attributes": attributes,
+            "startTimeUnixNano": start_time.to_string(),
+            "timeUnixNano": time.to_string(),
+            "asInt": request.fail_count.to_string(),
+        }));
+
+        // Each bucket counts the response times greater than the previous bound, up to and
+        // including its own bound, with a final bucket for slower response times.
+        let data = &request.raw_data;
+        let mut counted = 0;
+        let mut bucket_counts = Vec::new();
+        for bound in RESPONSE_TIME_BUCKETS {
+            let count = data.times.count_up_to(bound);
+            bucket_counts.push((count - counted).to_string());
+            counted = count;
+        }
+        bucket_counts.push(data.counter.saturating_sub(counted).to_string());
+        let mut duration = json!({
+            "attributes": attributes,
+            "startTimeUnixNano": start_time.to_string(),
+            "timeUnixNano": time.to_string(),
+            "count": data.counter.to_string(),
+            "sum": data.total_time as f64,
+            "bucketCounts": bucket_counts,
+            "explicitBounds": RESPONSE_TIME_BUCKETS
+                .iter()
+                .map(|bound| *bound as f64)
+                .collect::<Vec<f64>>(),
+        });
+        if data.counter > 0 {
+            duration["min"] = json!(data.minimum_time as f64);
+            duration["max"] = json!(data.maximum_time as f64);
+        }
+        durations.push(duration);
+    }
+
+    json!({
+        "resourceMetrics": [{
+            "resource": resource(),
+            "scopeMetrics": [{
+                "scope": scope(),
+                "metrics": [
+                    {
+                        "name": "goose.users",
+                        "description": "Number of GooseUsers currently running.",
+                        "unit": "{user}",
+                        "gauge": {
+                            "dataPoints": [{
+                                "timeUnixNano": time.to_string(),
+                                "asInt": users.to_string(),
+                            }],
+                        },
+                    },
+                    {
+                        "name": "goose.requests",
+                        "description": "Number of requests made.",
+                        "unit": "{request}",
+                        "sum": {
+                            "aggregationTemporality": AGGREGATION_TEMPORALITY_CUMULATIVE,
+                            "isMonotonic": true,
+                            "dataPoints": requests,
+                        },
+                    },
+                    {
+                        "name": "goose.request.failures",
+                        "description": "Number of requests that failed.",
+                        "unit": "{request}",
+                        "sum": {
+                            "aggregationTemporality": AGGREGATION_TEMPORALITY_CUMULATIVE,
+                            "isMonotonic": true,
+                            "dataPoints": failures,
+                        },
+                    },
+                    {
+                        "name": "goose.request.duration",
+                        "description": "Response times of requests.",
+                        "unit": "ms",
+                        "histogram": {
+                            "aggregationTemporality": AGGREGATION_TEMPORALITY_CUMULATIVE,
+                            "dataPoints": durations,
+                        },
+                    },
+                ],
+            }],
+        }],
+    })
+}
+
+/// Identifies the span of a transaction: the trace ID and the span ID.
+type SpanContext = (String, String);
+
+/// A new trace ID and span ID.
+fn new_span_context() -> SpanContext {
+    (random_id(16), random_id(8))
+}
+
+/// Format a request as a child span of the transaction that made it. The load test
+/// started at `started`, in nanoseconds since the Unix epoch.
+fn request_span(
+    request_metric: &GooseRequestMetric,
+    started: u128,
+    parent: Option<&SpanContext>,
+) -> Value {
+    let start_time = started + ms_to_nanos(request_metric.elapsed);
+    let mut attributes = vec![
+        string_attribute(
+            "http.request.method",
+            &request_metric.raw.method.to_string(),
+        ),
+        string_attribute("url.full", &request_metric.final_url),
+        string_attribute("goose.request.name", &request_metric.name),
+        int_attribute("goose.user", request_metric.user as u64),
+        bool_attribute("goose.success", request_metric.success),
+    ];
+    // The status code is 0 if no response was received.
+    if request_metric.status_code > 0 {
+        attributes.push(int_attribute(
+            "http.response.status_code",
+            request_metric.status_code as u64,
+        ));
+    }
+    let mut span = json!({
+        "traceId": parent.map_or_else(|| random_id(16), |(trace_id, _)| trace_id.to_string()),
+        "spanId": random_id(8),
+        "name": format!("{} {}", request_metric.raw.method, request_metric.name),
+        "kind": SPAN_KIND_CLIENT,
+        "startTimeUnixNano": start_time.to_string(),
+        "endTimeUnixNano": (start_time + ms_to_nanos(request_metric.response_time)).to_string(),
+        "attributes": attributes,
+    });
+    if let Some((_, parent_span_id)) = parent {
+        span["parentSpanId"] = json!(parent_span_id);
+    }
+    if !request_metric.success {
+        span["status"] = json!({ "code": STATUS_CODE_ERROR, "message": request_metric.error });
+    }
+    span
+}
+
+/// Format a transaction as a span, identified by `context`. The load test started at
+/// `started`, in nanoseconds since the Unix epoch.
+fn transaction_span(
+    transaction_metric: &TransactionMetric,
+    scenario_name: &str,
+    started: u128,
+    context: SpanContext,
+) -> Value {
+    let start_time = started + ms_to_nanos(transaction_metric.elapsed);
+    let (trace_id, span_id) = context;
+    let mut span = json!({
+        "traceId": trace_id,
+        "spanId": span_id,
+        // Transactions are identified by their index if they aren't named.
+        "name": if transaction_metric.name.is_empty() {
+            format!(
+                "{} {}.{}",
+                scenario_name, transaction_metric.scenario_index, transaction_metric.transaction_index
+            )
+        } else {
+            format!("{} {}", scenario_name, transaction_metric.name)
+        },
+        "kind": SPAN_KIND_INTERNAL,
+        "startTimeUnixNano": start_time.to_string(),
+        "endTimeUnixNano": (start_time + ms_to_nanos(transaction_metric.run_time)).to_string(),
+        "attributes": [
+            int_attribute("goose.scenario.index", transaction_metric.scenario_index as u64),
+            string_attribute("goose.scenario.name", scenario_name),
+            int_attribute("goose.transaction.index", transaction_metric.transaction_index as u64),
+            string_attribute("goose.transaction.name", &transaction_metric.name),
+            int_attribute("goose.user", transaction_metric.user as u64),
+            bool_attribute("goose.success", transaction_metric.success),
+        ],
+    });
+    if !transaction_metric.success {
+        span["status"] = json!({ "code": STATUS_CODE_ERROR });
+    }
+    span
+}
+
+/// Wrap spans in an OTLP traces request.
+fn format_traces(spans: Vec<Value>) -> Value {
+    json!({
+        "resourceSpans": [{
+            "resource": resource(),
+            "scopeSpans": [{
+                "scope": scope(),
+                "spans": spans,
+            }],
+        }],
+    })
+}
+
+/// The state of an enabled OpenTelemetry exporter, owned by the parent process.
+#[derive(Debug)]
+pub(crate) struct OtlpExporter {
+    /// Whether or not a span is exported for each request and transaction.
+    traces: bool,
+    /// Whether or not transaction metrics are collected, so request spans can be children
+    /// of transaction spans.
+    transaction_spans: bool,
+    /// The span of the transaction each user is running, created by its first request and
+    /// exported when the transaction finishes. Each user sends its request and transaction
+    /// metrics in order on the same channel.
+    transactions: HashMap<usize, SpanContext>,
+    /// Timer tracking when metrics were last exported.
+    timer: std::time::Instant,
+    /// When the load test started, in nanoseconds since the Unix epoch. Request and
+    /// transaction times are relative to this.
+    started: u128,
+    /// When metrics were last reset, in nanoseconds since the Unix epoch.
+    start_time: u128,
+    /// Spans waiting to be exported.
+    spans: Vec<Value>,
+    /// Bounded sender to the exporter thread, `None` tells it to exit.
+    otlp_tx: flume::Sender<Option<(&'static str, String)>>,
+    /// The exporter thread.
+    otlp_handle: tokio::task::JoinHandle<()>,
+}
+impl OtlpExporter {
+    /// Spawn a thread exporting to an OpenTelemetry collector.
+    pub(crate) fn spawn(endpoint: &str, traces: bool, transaction_spans: bool) -> Self {
+        info!("exporting to OpenTelemetry collector at {}", endpoint);
+        let (otlp_tx, otlp_rx) = flume::bounded(MAX_QUEUED_PAYLOADS);
+        let otlp_handle = tokio::spawn(otlp_main(
+            endpoint.trim_end_matches('/').to_string(),
+            otlp_rx,
+        ));
+        let now = now_nanos();
+        OtlpExporter {
+            traces,
+            transaction_spans,
+            transactions: HashMap::new(),
+            timer: std::time::Instant::now(),
+            started: now,
+            start_time: now,
+            spans: Vec::new(),
+            otlp_tx,
+            otlp_handle,
+        }
+    }
+
+    /// Buffer a span for a request, if traces are enabled.
+    pub(crate) fn record_request(&mut self, request_metric: &GooseRequestMetric) {
+        // Requests updated with `set_success` or `set_failure` were already recorded.
+        if self.traces && !request_metric.update {
+            let parent = if self.transaction_spans {
+                Some(
+                    self.transactions
+                        .entry(request_metric.user)
+                        .or_insert_with(new_span_context),
+                )
+            } else {
+                None
+            };
+            let span = request_span(request_metric, self.started, parent.as_deref());
+            self.spans.push(span);
+            self.export_spans_if_full();
+        }
+    }
+
+    /// Buffer a span for a transaction, if traces are enabled.
+    pub(crate) fn record_transaction(
+        &mut self,
+        transaction_metric: &TransactionMetric,
+        scenario_name: &str,
+    ) {
+        if self.traces {
+            // Transactions that made no requests don't have a span yet.
+            let context = self
+                .transactions
+                .remove(&transaction_metric.user)
+                .unwrap_or_else(new_span_context);
+            self.spans.push(transaction_span(
+                transaction_metric,
+                scenario_name,
+                self.started,
+                context,
+            ));
+            self.export_spans_if_full();
+        }
+    }
+
+    /// Export buffered spans without waiting for the interval if too many are buffered.
+    fn export_spans_if_full(&mut self) {
+        if self.spans.len() >= MAX_BUFFERED_SPANS {
+            self.export_spans();
+        }
+    }
+
+    /// Export all buffered spans.
+    fn export_spans(&mut self) {
+        if !self.spans.is_empty() {
+            let traces = format_traces(std::mem::take(&mut self.spans));
+            self.send(TRACES_PATH, traces.to_string());
+        }
+    }
+
+    /// Queue a payload for the exporter thread, dropping it if the queue is full.
+    fn send(&self, path: &'static str, payload: String) {
+        if let Err(flume::TrySendError::Full(_)) = self.otlp_tx.try_send(Some((path, payload))) {
+            warn!(
+                "OpenTelemetry collector is falling behind, dropped payload for {}",
+                path
+            );
+        }
+    }
+
+    /// Export metrics and buffered spans if the interval has passed since the previous
+    /// export.
+    pub(crate) fn export_if_due(&mut self, metrics: &GooseMetrics, users: usize) {
+        if util::timer_expired(self.timer, EXPORT_INTERVAL) {
+            self.export(metrics, users);
+        }
+    }
+
+    /// Export metrics and buffered spans.
+    pub(crate) fn export(&mut self, metrics: &GooseMetrics, users: usize) {
+        self.timer = std::time::Instant::now();
+        let payload = format_metrics(metrics, users, self.start_time, now_nanos());
+        self.send(METRICS_PATH, payload.to_string());
+        self.export_spans();
+    }
+
+    /// Export the metrics collected so far before they are reset, then count from now on.
+    pub(crate) fn reset(&mut self, metrics: &GooseMetrics, users: usize) {
+        self.export(metrics, users);
+        self.start_time = now_nanos();
+    }
+
+    /// Export the final metrics and spans, then wait for the exporter thread to send them
+    /// and exit.
+    pub(crate) async fn shutdown(mut self, metrics: &GooseMetrics, users: usize) {
+        self.export(metrics, users);
+        if let Err(e) = self.otlp_tx.send_async(None).await {
+            warn!(
+                "unexpected error telling OpenTelemetry exporter to exit: {}",
+                e
+            );
+        }
+        let _ = self.otlp_handle.await;
+    }
+}
+
+/// OpenTelemetry exporter thread, posts the payloads it receives from the parent to the
+/// collector until told to exit.
+async fn otlp_main(endpoint: String, receiver: flume::Receiver<Option<(&'static str, String)>>) {
+    let client = reqwest::Client::new();
+
+    while let Ok(Some((path, payload))) = receiver.recv_async().await {
+        let url = format!("{}{}", endpoint, path);
+        match client
+            .post(&url)
+            .header("content-type", "application/json")
+            .body(payload)
+            .send()
+            .await
+        {
+            Ok(response) if !response.status().is_success() => {
+                warn!(
+                    "failed to export to {}: unexpected response status {}",
+                    url,
+                    response.status()
+                );
+            }
+            Ok(_) => (),
+            Err(e) => warn!("failed to export to {}: {}", url, e),
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::goose::GooseMethod;
+    use crate::metrics::{GooseRawRequest, GooseRequestMetricAggregate};
+
+    #[test]
+    fn format_otlp_metrics() {
+        let mut index = GooseRequestMetricAggregate::new("/", GooseMethod::Get, 0, 3);
+        for time in [3, 20, 700] {
+            index.record_time(time, false);
+            index.success_count += 1;
+        }
+        index.fail_count += 1;
+        let mut metrics = GooseMetrics::default();
+        metrics.requests.insert("GET /".to_string(), index);
+
+        let payload = format_metrics(&metrics, 2, 1_000, 2_000);
+        let otlp_metrics = &payload["resourceMetrics"][0]["scopeMetrics"][0]["metrics"];
+        assert_eq!(otlp_metrics[0]["name"], "goose.users");
+        assert_eq!(otlp_metrics[0]["gauge"]["dataPoints"][0]["asInt"], "2");
+        let requests = &otlp_metrics[1]["sum"]["dataPoints"][0];
+        assert_eq!(requests["asInt"], "4");
+        assert_eq!(requests["startTimeUnixNano"], "1000");
+        assert_eq!(requests["timeUnixNano"], "2000");
+        assert_eq!(
+            requests["attributes"][0],
+            json!({ "key": "http.request.method", "value": { "stringValue": "GET" } })
+        );
+        assert_eq!(otlp_metrics[2]["sum"]["dataPoints"][0]["asInt"], "1");
+        // Histogram buckets aren't cumulative, with an extra bucket for slower times.
+        let duration = &otlp_metrics[3]["histogram"]["dataPoints"][0];
+        assert_eq!(duration["count"], "3");
+        assert_eq!(duration["sum"], 723.0);
+        assert_eq!(
+            duration["bucketCounts"],
+            json!(["1", "0", "1", "0", "0", "0", "0", "1", "0", "0", "0", "0", "0"])
+        );
+        assert_eq!(duration["explicitBounds"][0], 5.0);
+    }
+
+    #[test]
+    fn format_otlp_spans() {
+        let raw = GooseRawRequest::new(GooseMethod::Get, "http://127.0.0.1/", vec![], "");
+        let mut request_metric = GooseRequestMetric::new(raw, "/", 1_500, 3);
+        request_metric.response_time = 20;
+        request_metric.status_code = 500;
+        request_metric.success = false;
+        request_metric.error = "500 Internal Server Error".to_string();
+
+        let span = request_span(&request_metric, 1_000, None);
+        assert_eq!(span["name"], "GET /");
+        assert_eq!(span["traceId"].as_str().unwrap().len(), 32);
+        assert_eq!(span["spanId"].as_str().unwrap().len(), 16);
+        assert!(span.get("parentSpanId").is_none());
+        assert_eq!(span["startTimeUnixNano"], "1500001000");
+        assert_eq!(span["endTimeUnixNano"], "1520001000");
+        assert_eq!(span["status"]["code"], STATUS_CODE_ERROR);
+        assert!(span["attributes"]
+            .as_array()
+            .unwrap()
+            .contains(&int_attribute("http.response.status_code", 500)));
+
+        // A request made by a transaction is part of the same trace.
+        let context = new_span_context();
+        let span = request_span(&request_metric, 1_000, Some(&context));
+        assert_eq!(span["traceId"], context.0);
+        assert_eq!(span["parentSpanId"], context.1);
+        assert_ne!(span["spanId"], context.1);
+
+        let transaction_metric = TransactionMetric::new(100, 0, 1, "login".to_string(), 3);
+        let span = transaction_span(&transaction_metric, "LoadTest", 0, context.clone());
+        assert_eq!(span["name"], "LoadTest login");
+        assert_eq!(span["traceId"], context.0);
+        assert_eq!(span["spanId"], context.1);
+        assert_eq!(span["startTimeUnixNano"], "100000000");
+        assert!(span["attributes"]
+            .as_array()
+            .unwrap()
+            .contains(&string_attribute("goose.scenario.name", "LoadTest")));
+        assert!(span.get("status").is_none());
+    }
+}
@@ -0,0 +1,440 @@
+//! Optional Prometheus endpoint serving live metrics.
+//!
+//! When enabled with `--prometheus-port`, Goose listens for HTTP requests and serves the
+//! metrics of the running load test at `/metrics` in the Prometheus text exposition format,
+//! so they can be scraped by Prometheus and graphed with tools such as Grafana.
+
+use itertools::Itertools;
+use std::fmt::Write;
+use std::io;
+use std::time::Duration;
+use tokio::io::{AsyncReadExt, AsyncWriteExt};
+use tokio::net::{TcpListener, TcpStream};
+
+use crate::config::GooseConfiguration;
+use crate::controller::{
+    ControllerCommand, ControllerRequest, ControllerRequestMessage, ControllerResponse,
+    ControllerResponseMessage,
+};
+use crate::metrics::{GooseMetrics, GooseRequestMetricAggregate};
+use crate::AttackPhase;
+
+/// The upper bounds of the response time histogram buckets, in milliseconds.
+pub(crate) const RESPONSE_TIME_BUCKETS: [usize; 12] = [
+    5, 10, 25, 50, 100, 250, 500, 1_000, 2_500, 5_000, 10_000, 30_000,
+];
+
+/// The content type of the Prometheus text exposition format.
+const CONTENT_TYPE: &str = "text/plain; version=0.0.4; charset=utf-8";
+
+/// The largest request head that is read from a client, larger requests are dropped.
+const MAX_REQUEST_HEAD: usize = 8192;
+
+/// How long a client has to send the request head before the connection is dropped.
+const REQUEST_TIMEOUT: Duration = Duration::from_secs(5);
+
+/// Listens for HTTP requests on the configured TCP port, serving the live metrics of the
+/// load test at `/metrics`. The metrics are requested from the parent process through the
+/// same channel used by the Controllers.
+pub(crate) async fn prometheus_main(
+    // Expose load test configuration to the Prometheus thread.
+    configuration: GooseConfiguration,
+    // For sending requests to the parent process.
+    channel_tx: flume::Sender<ControllerRequest>,
+) -> io::Result<()> {
+    let address = format!(
+        "{}:{}",
+        configuration.prometheus_host, configuration.prometheus_port
+    );
+    debug!("preparing to bind Prometheus endpoint to: {}", address);
+    let listener = TcpListener::bind(&address).await?;
+    info!(
+        "Prometheus endpoint listening on: http://{}/metrics",
+        address
+    );
+
+    // Counter increments each time a client connects, used to identify requests.
+    let mut client_id: u32 = 0;
+
+    // Wait for a connection.
+    while let Ok((stream, _)) = listener.accept().await {
+        client_id += 1;
+
+        // Each request is handled in its own thread, which exits after replying.
+        tokio::spawn(serve_request(stream, client_id, channel_tx.clone()));
+    }
+
+    Ok(())
+}
+
+/// Reply to a single HTTP request, then close the connection.
+async fn serve_request(
+    mut stream: TcpStream,
+    client_id: u32,
+    channel_tx: flume::Sender<ControllerRequest>,
+) {
+    let buf = match read_request_head(&mut stream, REQUEST_TIMEOUT).await {
+        Some(buf) => buf,
+        None => {
+            debug!(
+                "dropping incomplete Prometheus request from client {}",
+                client_id
+            );
+            return;
+        }
+    };
+    let head = String::from_utf8_lossy(&buf);
+    let mut request_line = head.lines().next().unwrap_or_default().split_whitespace();
+    let method = request_line.next().unwrap_or_default();
+    // Ignore the query string, if any.
+    let path = request_line
+        .next()
+        .unwrap_or_default()
+        .split('?')
+        .next()
+        .unwrap_or_default();
+
+    let (status, body) = if path != "/metrics" {
+        (
+            "404 Not Found",
+            "metrics are served at /metrics\n".to_string(),
+        )
+    } else if method != "GET" && method != "HEAD" {
+        (
+            "405 Method Not Allowed",
+            "only GET is supported\n".to_string(),
+        )
+    } else {
+        match request_metrics(client_id, &channel_tx).await {
+            Some(metrics) => ("200 OK", metrics),
+            None => (
+                "503 Service Unavailable",
+                "load test is no longer running\n".to_string(),
+            ),
+        }
+    };
+
+    let response = format!(
+        "HTTP/1.1 {}\r\nContent-Type: {}\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{}",
+        status,
+        CONTENT_TYPE,
+        body.len(),
+        // A HEAD request only returns the headers.
+        if method == "HEAD" { "" } else { &body },
+    );
+    if stream.write_all(response.as_bytes()).await.is_err() {
+        warn!("failed to write Prometheus metrics to socket");
+    }
+}
+
+/// Read until the end of the request head, only the request line is used.
+///
+/// Returns `None` if the client closes the connection, doesn't send the entire request head
+/// within the timeout, or sends a request head larger than `MAX_REQUEST_HEAD`, so the
+/// connection is dropped without a reply.
+async fn read_request_head(stream: &mut TcpStream, timeout: Duration) -> Option<Vec<u8>> {
+    let read = async {
+        let mut buf = vec![0; MAX_REQUEST_HEAD];
+        let mut length = 0;
+        while !buf[..length].windows(4).any(|window| window == b"\r\n\r\n") {
+            if length == buf.len() {
+                return None;
+            }
+            match stream.read(&mut buf[length..]).await {
+                Ok(0) | Err(_) => return None,
+                Ok(read) => length += read,
+            }
+        }
+        buf.truncate(length);
+        Some(buf)
+    };
+    tokio::time::timeout(timeout, read).await.ok().flatten()
+}
+
+/// Request the current metrics from the parent process, formatted for Prometheus.
+async fn request_metrics(
+    client_id: u32,
+    channel_tx: &flume::Sender<ControllerRequest>,
+) -> Option<String> {
+    // Create a one-shot channel to allow the parent to reply to our request.
+    let (response_tx, response_rx): (
+        tokio::sync::oneshot::Sender<ControllerResponse>,
+        tokio::sync::oneshot::Receiver<ControllerResponse>,
+    ) = tokio::sync::oneshot::channel();
+
+    channel_tx
+        .try_send(ControllerRequest {
+            response_channel: Some(response_tx),
+            client_id,
+            request: ControllerRequestMessage {
+                command: ControllerCommand::MetricsPrometheus,
+                value: None,
+            },
+        })
+        .ok()?;
+
+    // Await response from parent.
+    match response_rx.await {
+        Ok(ControllerResponse {
+            response: ControllerResponseMessage::Prometheus(metrics),
+            ..
+        }) => Some(metrics),
+        _ => None,
+    }
+}
+
+/// Escape a label value, as required by the Prometheus text exposition format.
+fn escape_label(value: &str) -> String {
+    value
+        .replace('\\', r"\\")
+        .replace('"', r#"\""#)
+        .replace('\n', r"\n")
+}
+
+/// Write the help and type lines that precede each metric.
+fn write_header(text: &mut String, name: &str, metric_type: &str, help: &str) {
+    let _ = writeln!(text, "# HELP {} {}", name, help);
+    let _ = writeln!(text, "# TYPE {} {}", name, metric_type);
+}
+
+/// Format the current metrics in the Prometheus text exposition format.
+///
+/// Response times are converted to seconds, as is the Prometheus convention. Counters
+/// restart from 0 when the metrics are reset after all users have started, unless the load
+/// test is started with `--no-reset-metrics`.
+pub(crate) fn format_metrics(
+    metrics: &GooseMetrics,
+    attack_phase: &AttackPhase,
+    users: usize,
+) -> String {
+    let mut text = String::new();
+
+    write_header(
+        &mut text,
+        "goose_users",
+        "gauge",
+        "Number of GooseUsers currently running.",
+    );
+    let _ = writeln!(text, "goose_users {}", users);
+
+    write_header(
+        &mut text,
+        "goose_attack_phase",
+        "gauge",
+        "The current phase of the load test, set to 1 for the current phase.",
+    );
+    for (phase, name) in [
+        (AttackPhase::Idle, "idle"),
+        (AttackPhase::Increase, "increase"),
+        (AttackPhase::Maintain, "maintain"),
+        (AttackPhase::Decrease, "decrease"),
+        (AttackPhase::Shutdown, "shutdown"),
+    ] {
+        let _ = writeln!(
+            text,
+            "goose_attack_phase{{phase=\"{}\"}} {}",
+            name,
+            (&phase == attack_phase) as u8
+        );
+    }
+
+    write_header(
+        &mut text,
+        "goose_duration_seconds",
+        "gauge",
+        "How long the load test has been running.",
+    );
+    let _ = writeln!(text, "goose_duration_seconds {}", metrics.duration);
+
+    // Requests are sorted so the output is stable between scrapes.
+    let requests: Vec<&GooseRequestMetricAggregate> = metrics
+        .requests
+        .iter()
+        .sorted_by(|a, b| a.0.cmp(b.0))
+        .map(|(_, request)| request)
+        .collect();
+    let labels: Vec<String> = requests
+        .iter()
+        .map(|request| {
+            format!(
+                "method=\"{}\",name=\"{}\"",
+                request.method,
+                escape_label(&request.path)
+            )
+        })
+        .collect();
+
+    write_header(
+        &mut text,
+        "goose_requests_total",
+        "counter",
+        "Number of requests made.",
+    );
+    for (request, labels) in requests.iter().zip(&labels) {
+        let _ = writeln!(
+            text,
+            "goose_requests_total{{{}}} {}",
+            labels,
+            request.success_count + request.fail_count
+        );
+    }
+
+    write_header(
+        &mut text,
+        "goose_request_failures_total",
+        "counter",
+        "Number of requests that failed.",
+    );
+    for (request, labels) in requests.iter().zip(&labels) {
+        let _ = writeln!(
+            text,
+            "goose_request_failures_total{{{}}} {}",
+            labels, request.fail_count
+        );
+    }
+
+    write_header(
+        &mut text,
+        "goose_errors_total",
+        "counter",
+        "Number of times each error occurred.",
+    );
+    for error in metrics.errors.values() {
+        let _ = writeln!(
+            text,
+            "goose_errors_total{{method=\"{}\",name=\"{}\",error=\"{}\"}} {}",
+            error.method,
+            escape_label(&error.name),
+            escape_label(&error.error),
+            error.occurrences
+        );
+    }
+
+    write_header(
+        &mut text,
+        "goose_response_time_seconds",
+        "histogram",
+        "Response times of requests.",
+    );
+    for (request, labels) in requests.iter().zip(&labels) {
+        let data = &request.raw_data;
+        for bucket in RESPONSE_TIME_BUCKETS {
+            let _ = writeln!(
+                text,
+                "goose_response_time_seconds_bucket{{{},le=\"{}\"}} {}",
+                labels,
+                bucket as f64 / 1_000.0,
+                data.times.count_up_to(bucket)
+            );
+        }
+        let _ = writeln!(
+            text,
+            "goose_response_time_seconds_bucket{{{},le=\"+Inf\"}} {}",
+            labels, data.counter
+        );
+        let _ = writeln!(
+            text,
+            "goose_response_time_seconds_sum{{{}}} {}",
+            labels,
+            data.total_time as f64 / 1_000.0
+        );
+        let _ = writeln!(
+            text,
+            "goose_response_time_seconds_count{{{}}} {}",
+            labels, data.counter
+        );
+    }
+
+    text
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::goose::GooseMethod;
+    use crate::metrics::GooseErrorMetricAggregate;
+
+    #[test]
+    fn format_prometheus_metrics() {
+        let mut index = GooseRequestMetricAggregate::new("/", GooseMethod::Get, 0, 3);
+        for time in [3, 20, 700] {
+            index.record_time(time, false);
+            index.success_count += 1;
+        }
+        index.fail_count += 1;
+        let mut metrics = GooseMetrics {
+            duration: 2,
+            ..Default::default()
+        };
+        metrics.requests.insert("GET /".to_string(), index);
+        let mut error = GooseErrorMetricAggregate::new(
+            GooseMethod::Get,
+            "/".to_string(),
+            "500 \"Internal Server Error\"".to_string(),
+        );
+        error.occurrences = 1;
+        metrics.errors.insert("GET / error".to_string(), error);
+
+        let text = format_metrics(&metrics, &AttackPhase::Maintain, 4);
+        assert!(text.contains("# TYPE goose_users gauge\ngoose_users 4\n"));
+        assert!(text.contains("goose_attack_phase{phase=\"increase\"} 0\n"));
+        assert!(text.contains("goose_attack_phase{phase=\"maintain\"} 1\n"));
+        assert!(text.contains("goose_requests_total{method=\"GET\",name=\"/\"} 4\n"));
+        assert!(text.contains("goose_request_failures_total{method=\"GET\",name=\"/\"} 1\n"));
+        // Label values are escaped.
+        assert!(text.contains(
+            "goose_errors_total{method=\"GET\",name=\"/\",error=\"500 \\\"Internal Server Error\\\"\"} 1\n"
+        ));
+        // Histogram buckets are cumulative, in seconds.
+        assert!(text.contains(
+            "goose_response_time_seconds_bucket{method=\"GET\",name=\"/\",le=\"0.005\"} 1\n"
+        ));
+        assert!(text.contains(
+            "goose_response_time_seconds_bucket{method=\"GET\",name=\"/\",le=\"0.025\"} 2\n"
+        ));
+        assert!(text.contains(
+            "goose_response_time_seconds_bucket{method=\"GET\",name=\"/\",le=\"1\"} 3\n"
+        ));
+        assert!(text.contains(
+            "goose_response_time_seconds_bucket{method=\"GET\",name=\"/\",le=\"+Inf\"} 3\n"
+        ));
+        assert!(text.contains("goose_response_time_seconds_sum{method=\"GET\",name=\"/\"} 0.723\n"));
+        assert!(text.contains("goose_response_time_seconds_count{method=\"GET\",name=\"/\"} 3\n"));
+
+        assert_eq!(escape_label("a\\b\nc"), "a\\\\b\\nc");
+    }
+
+    #[tokio::test]
+    async fn read_prometheus_request_head() {
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let address = listener.local_addr().unwrap();
+        let timeout = Duration::from_millis(100);
+
+        // A complete request head is returned.
+        let mut client = TcpStream::connect(address).await.unwrap();
+        let (mut server, _) = listener.accept().await.unwrap();
+        client
+            .write_all(b"GET /metrics HTTP/1.1\r\nHost: localhost\r\n\r\n")
+            .await
+            .unwrap();
+        let head = read_request_head(&mut server, timeout).await.unwrap();
+        assert!(head.starts_with(b"GET /metrics HTTP/1.1\r\n"));
+
+        // A request head that isn't finished in time is dropped.
+        let mut client = TcpStream::connect(address).await.unwrap();
+        let (mut server, _) = listener.accept().await.unwrap();
+        client
+            .write_all(b"GET /metrics HTTP/1.1\r\n")
+            .await
+            .unwrap();
+        assert!(read_request_head(&mut server, timeout).await.is_none());
+
+        // A request head larger than the limit is dropped.
+        let mut client = TcpStream::connect(address).await.unwrap();
+        let (mut server, _) = listener.accept().await.unwrap();
+        let mut request = b"GET /metrics HTTP/1.1\r\nX-Padding: ".to_vec();
+        request.resize(MAX_REQUEST_HEAD * 2, b'a');
+        client.write_all(&request).await.unwrap();
+        assert!(read_request_head(&mut server, timeout).await.is_none());
+    }
+}
@@ -0,0 +1,1052 @@
+//! Rebuild metrics and reports from a request log.
+//!
+//! A request log written with `--request-log` in the `json`, `csv`, `raw` or `pretty` format
+//! records every request made during a load test. [`GooseReport`] reads such a log after the
+//! load test has finished and rebuilds the [`GooseMetrics`], the console summary and the
+//! report files, optionally filtered by a window of time or by request name. This allows a
+//! long soak test to be re-sliced after the fact, for example to only report on the hour in
+//! which response times degraded.
+//!
+//! The same functionality is available from the command line with the `goose-report` binary:
+//!
+//! ```bash
+//! goose-report --request-log requests.json --from 3600 --to 7200 --report-file report.html
+//! ```
+//!
+//! The request log is read one request at a time, so it doesn't have to fit in memory. The
+//! `raw` and `pretty` formats are the `Debug` output of each request and are read on a
+//! best-effort basis, the `json` and `csv` formats are recommended for request logs that will
+//! be rebuilt.
+//!
+//! Only request metrics are logged, so rebuilt reports don't include transaction or scenario
+//! metrics. The number of users is estimated from how many different users made requests
+//! during each second, and the load test is assumed to have started when the earliest
+//! request was made less its elapsed time.
+//!
+//! [`GooseComparison`] compares two load tests side by side, each rebuilt from a request log
+//! or loaded from a metrics file written with `--baseline-file`:
+//!
+//! ```bash
+//! goose-report --compare baseline.log --compare requests.log --report-file compare.html
+//! ```
+
+use chrono::prelude::*;
+use serde_json::{Map, Value};
+use std::collections::{BTreeMap, HashSet};
+use tokio::io::{AsyncBufRead, AsyncBufReadExt};
+
+use crate::config::GooseConfiguration;
+use crate::graph::GraphData;
+use crate::metrics::{GooseBaseline, GooseMetrics, GooseRequestMetric};
+use crate::report;
+use crate::test_plan::{TestPlanHistory, TestPlanRamp, TestPlanStepAction};
+use crate::{GooseAttack, GooseError};
+
+/// Columns of the csv request log that are always strings, even if they look like numbers.
+const CSV_STRING_COLUMNS: [&str; 3] = ["name", "final_url", "error"];
+
+/// Metrics rebuilt from the requests recorded in a request log.
+///
+/// The request log is read one request at a time when the metrics are rebuilt, so even the
+/// request log of a long soak test doesn't have to fit in memory. Requests that don't match
+/// the configured filters are skipped as they are read.
+///
+/// # Example
+/// ```rust,no_run
+/// use goose::replay::GooseReport;
+///
+/// #[tokio::main]
+/// async fn main() -> Result<(), goose::GooseError> {
+///     // Only report on the `/` request made during the second hour of the load test.
+///     let metrics = GooseReport::from_request_log("requests.json")
+///         .await?
+///         .set_from(3_600)
+///         .set_to(7_200)
+///         .set_name("/")
+///         .write(&["report.html".to_string()])
+///         .await?;
+///     println!("{}", metrics);
+///
+///     Ok(())
+/// }
+/// ```
+#[derive(Clone, Debug)]
+pub struct GooseReport {
+    /// The request log the requests are read from, if any.
+    request_log: Option<String>,
+    /// The requests that were already loaded, if not read from a request log.
+    requests: Vec<GooseRequestMetric>,
+    /// Only include requests made this many seconds or more after the load test started.
+    from: Option<usize>,
+    /// Only include requests made less than this many seconds after the load test started.
+    to: Option<usize>,
+    /// Only include requests with one of these names, or all requests if empty.
+    names: Vec<String>,
+}
+impl GooseReport {
+    /// Load a request log, which is read when the metrics are rebuilt.
+    ///
+    /// The format of the request log is detected automatically, and can be `json`, `csv`,
+    /// `raw` or `pretty`. The `raw` and `pretty` formats are the `Debug` output of each
+    /// request, which isn't guaranteed to stay the same between versions of Rust, so they
+    /// are read on a best-effort basis. Prefer the `json` or `csv` format for request logs
+    /// that will be rebuilt.
+    pub async fn from_request_log(path: &str) -> Result<GooseReport, GooseError> {
+        if let Err(e) = tokio::fs::File::open(path).await {
+            return Err(GooseError::InvalidOption {
+                option: "--request-log".to_string(),
+                value: path.to_string(),
+                detail: format!("Failed to read request log: {}", e),
+            });
+        }
+        Ok(GooseReport {
+            request_log: Some(path.to_string()),
+            ..GooseReport::from_requests(Vec::new())
+        })
+    }
+
+    /// Rebuild metrics from requests that were already loaded, for example by deserializing
+    /// a custom log.
+    pub fn from_requests(requests: Vec<GooseRequestMetric>) -> GooseReport {
+        GooseReport {
+            request_log: None,
+            requests,
+            from: None,
+            to: None,
+            names: Vec::new(),
+        }
+    }
+
+    /// Only include requests made `seconds` or more after the load test started. Reports
+    /// start at this point in time.
+    pub fn set_from(mut self, seconds: usize) -> Self {
+        self.from = Some(seconds);
+        self
+    }
+
+    /// Only include requests made less than `seconds` after the load test started.
+    pub fn set_to(mut self, seconds: usize) -> Self {
+        self.to = Some(seconds);
+        self
+    }
+
+    /// Only include requests with this name. Can be invoked multiple times to include
+    /// requests with any of the names.
+    pub fn set_name(mut self, name: &str) -> Self {
+        self.names.push(name.to_string());
+        self
+    }
+
+    /// Whether or not a request matches the configured filters.
+    fn matches(&self, request: &GooseRequestMetric) -> bool {
+        request.elapsed >= self.from.unwrap_or(0) as u64 * 1_000
+            && !matches!(self.to, Some(to) if request.elapsed >= to as u64 * 1_000)
+            && (self.names.is_empty() || self.names.contains(&request.name))
+    }
+
+    /// Rebuild the metrics of the matching requests.
+    pub async fn metrics(self) -> Result<GooseMetrics, GooseError> {
+        Ok(self.rebuild(Vec::new()).await?.metrics)
+    }
+
+    /// Rebuild the metrics of the matching requests, and write them to report files in the
+    /// format selected by the extension of each file (`.html`, `.md` or `.json`).
+    pub async fn write(self, report_files: &[String]) -> Result<GooseMetrics, GooseError> {
+        let mut goose_attack = self.rebuild(report_files.to_vec()).await?;
+        goose_attack.write_reports().await?;
+        Ok(goose_attack.metrics)
+    }
+
+    /// Feed the matching requests through the same code that collects metrics while a load
+    /// test is running.
+    async fn rebuild(mut self, report_file: Vec<String>) -> Result<GooseAttack, GooseError> {
+        let configuration = GooseConfiguration {
+            report_file,
+            // Transactions and scenarios aren't recorded in the request log.
+            no_transaction_metrics: true,
+            no_scenario_metrics: true,
+            ..Default::default()
+        };
+        let mut goose_attack = GooseAttack::initialize_with_config(configuration)?;
+        goose_attack.metrics.display_metrics = true;
+        goose_attack.metrics.display_status_codes = true;
+        goose_attack.metrics.final_metrics = true;
+        let mut rebuilt = RebuiltLoadTest::new(goose_attack, self.from.unwrap_or(0));
+
+        match self.request_log.take() {
+            Some(path) => {
+                let file = match tokio::fs::File::open(&path).await {
+                    Ok(file) => file,
+                    Err(e) => {
+                        return Err(GooseError::InvalidOption {
+                            option: "--request-log".to_string(),
+                            value: path,
+                            detail: format!("Failed to read request log: {}", e),
+                        })
+                    }
+                };
+                let mut reader = RequestLogReader::new(tokio::io::BufReader::new(file));
+                loop {
+                    match reader.next().await {
+                        Ok(Some(request)) => {
+                            let matches = self.matches(&request);
+                            rebuilt.record(request, matches).await;
+                        }
+                        Ok(None) => break,
+                        Err(e) => {
+                            return Err(GooseError::InvalidOption {
+                                option: "--request-log".to_string(),
+                                value: path,
+                                detail: format!("Failed to parse request log: {}", e),
+                            })
+                        }
+                    }
+                }
+            }
+            None => {
+                for request in std::mem::take(&mut self.requests) {
+                    let matches = self.matches(&request);
+                    rebuilt.record(request, matches).await;
+                }
+            }
+        }
+
+        rebuilt.finish()
+    }
+}
+
+/// A load test being rebuilt from the requests in a request log, as they are read.
+struct RebuiltLoadTest {
+    /// The metrics being rebuilt.
+    goose_attack: GooseAttack,
+    /// How many milliseconds after the load test started the matching requests start.
+    from: u64,
+    /// How many requests were read, including requests that don't match the filters.
+    requests: usize,
+    /// How many requests matched the filters.
+    matched: usize,
+    /// When the load test started, in milliseconds since the Unix epoch, according to the
+    /// earliest request timestamp.
+    started: Option<u64>,
+    /// How many milliseconds after the load test started the last request finished.
+    last_finished: u64,
+    /// How many milliseconds after `from` the last matching request finished.
+    finished: u64,
+    /// Which users made matching requests during each second, to graph active users.
+    users_per_second: BTreeMap<usize, HashSet<usize>>,
+    /// All users that made matching requests.
+    users: HashSet<usize>,
+}
+impl RebuiltLoadTest {
+    fn new(goose_attack: GooseAttack, from: usize) -> Self {
+        RebuiltLoadTest {
+            goose_attack,
+            from: from as u64 * 1_000,
+            requests: 0,
+            matched: 0,
+            started: None,
+            last_finished: 0,
+            finished: 0,
+            users_per_second: BTreeMap::new(),
+            users: HashSet::new(),
+        }
+    }
+
+    /// Record a request read from the request log, only including it in the metrics if it
+    /// matches the filters.
+    async fn record(&mut self, mut request: GooseRequestMetric, matches: bool) {
+        self.requests += 1;
+        // Requests logged by older versions of Goose don't have a timestamp.
+        if request.timestamp > 0 {
+            let started = request.timestamp.saturating_sub(request.elapsed);
+            self.started = Some(self.started.map_or(started, |s| s.min(started)));
+        }
+        self.last_finished = self
+            .last_finished
+            .max(request.elapsed + request.response_time);
+        if !matches {
+            return;
+        }
+        self.matched += 1;
+
+        // Matching requests are relative to the start of the configured window of time.
+        request.elapsed -= self.from;
+        if !request.error.is_empty() {
+            self.goose_attack.aggregate_error(&request);
+        }
+        if self.goose_attack.record_request(&request).await {
+            self.users_per_second
+                .entry((request.elapsed / 1_000) as usize)
+                .or_default()
+                .insert(request.user);
+            self.users.insert(request.user);
+            if let Ok(url) = url::Url::parse(&request.raw.url) {
+                self.goose_attack
+                    .metrics
+                    .hosts
+                    .insert(url.origin().ascii_serialization());
+            }
+        }
+        self.finished = self.finished.max(request.elapsed + request.response_time);
+    }
+
+    /// Finish rebuilding the load test once all requests have been read.
+    fn finish(self) -> Result<GooseAttack, GooseError> {
+        let RebuiltLoadTest {
+            mut goose_attack,
+            from,
+            requests,
+            matched,
+            started,
+            last_finished,
+            finished,
+            users_per_second,
+            users,
+        } = self;
+        if matched == 0 {
+            return Err(GooseError::InvalidOption {
+                option: "--request-log".to_string(),
+                value: format!("{} requests", requests),
+                detail: "No requests match the configured filters.".to_string(),
+            });
+        }
+
+        for (second, users) in users_per_second {
+            goose_attack
+                .graph_data
+                .record_users_per_second(users.len(), second);
+        }
+
+        // 1.2 seconds will round down to 1 second. 1.6 seconds will round up to 2 seconds.
+        goose_attack.metrics.duration = (finished as f32 / 1_000.0).round() as usize;
+        goose_attack.metrics.maximum_users = users.len();
+        goose_attack.metrics.total_users = users.len();
+
+        // Without request timestamps, the load test is assumed to have just finished.
+        let started = started
+            .and_then(|started| Utc.timestamp_millis_opt(started as i64).single())
+            .unwrap_or_else(|| Utc::now() - chrono::Duration::milliseconds(last_finished as i64));
+        // Describe the re-sliced load test as a single step, so reports can show when it ran.
+        let started = started + chrono::Duration::milliseconds(from as i64);
+        goose_attack.metrics.history = vec![
+            TestPlanHistory {
+                action: TestPlanStepAction::Maintaining,
+                timestamp: started,
+                users: users.len(),
+                ramp: TestPlanRamp::default(),
+                reason: None,
+            },
+            TestPlanHistory {
+                action: TestPlanStepAction::Finished,
+                timestamp: started + chrono::Duration::milliseconds(finished as i64),
+                users: users.len(),
+                ramp: TestPlanRamp::default(),
+                reason: None,
+            },
+        ];
+
+        Ok(goose_attack)
+    }
+}
+
+/// One of the load tests being compared.
+struct ComparedLoadTest {
+    /// The file the load test was loaded from.
+    source: String,
+    /// How long the load test ran, and its request metrics.
+    metrics: GooseBaseline,
+    /// The per second data of the load test, if rebuilt from a request log.
+    graph_data: Option<GraphData>,
+    /// When the load test started, if rebuilt from a request log.
+    started: Option<DateTime<Utc>>,
+}
+impl ComparedLoadTest {
+    /// Load a metrics file written with `--baseline-file`, or rebuild the metrics of a
+    /// request log after applying `filter` to it.
+    async fn load(
+        path: &str,
+        report_file: &str,
+        filter: impl Fn(GooseReport) -> GooseReport,
+    ) -> Result<Self, GooseError> {
+        let contents = match tokio::fs::read_to_string(path).await {
+            Ok(contents) => contents,
+            Err(e) => {
+                return Err(GooseError::InvalidOption {
+                    option: "--compare".to_string(),
+                    value: path.to_string(),
+                    detail: format!("Failed to read file: {}", e),
+                })
+            }
+        };
+        if let Ok(metrics) = serde_json::from_str::<GooseBaseline>(&contents) {
+            return Ok(ComparedLoadTest {
+                source: path.to_string(),
+                metrics,
+                graph_data: None,
+                started: None,
+            });
+        }
+
+        let goose_report = filter(GooseReport::from_request_log(path).await?);
+        // Graph data is only recorded when writing a report.
+        let goose_attack = goose_report.rebuild(vec![report_file.to_string()]).await?;
+        Ok(ComparedLoadTest {
+            source: path.to_string(),
+            metrics: GooseBaseline {
+                duration: goose_attack.metrics.duration,
+                requests: goose_attack.metrics.requests,
+            },
+            graph_data: Some(goose_attack.graph_data),
+            started: goose_attack
+                .metrics
+                .history
+                .first()
+                .map(|step| step.timestamp),
+        })
+    }
+
+    /// A row of the overview of each load test being compared.
+    fn overview_row(&self, label: &str) -> String {
+        let fails = self
+            .metrics
+            .requests
+            .values()
+            .map(|request| request.fail_count)
+            .sum();
+        let total = self
+            .metrics
+            .requests
+            .values()
+            .map(|request| request.success_count)
+            .sum::<usize>()
+            + fails;
+        report::comparison_overview_row(label, &self.source, self.metrics.duration, total, fails)
+    }
+}
+
+/// Compares two load tests, each loaded from a metrics file written with `--baseline-file`
+/// or rebuilt from a request log.
+///
+/// The comparison is written as an html report, with a table comparing the average and
+/// percentile response times, error rate and requests per second of each request, and
+/// graphs overlaying the requests per second, response times, errors and users of both
+/// load tests. Metrics files don't include per second data, so graphs are only included
+/// when comparing two request logs.
+///
+/// # Example
+/// ```rust,no_run
+/// use goose::replay::GooseComparison;
+///
+/// #[tokio::main]
+/// async fn main() -> Result<(), goose::GooseError> {
+///     let metrics = GooseComparison::load("baseline.log", "requests.log", "compare.html")
+///         .await?
+///         .write()
+///         .await?;
+///     for delta in metrics.baseline.iter().filter(|delta| delta.regression) {
+///         println!("{:?} got worse", delta.request);
+///     }
+///
+///     Ok(())
+/// }
+/// ```
+pub struct GooseComparison {
+    /// The load test to compare to.
+    baseline: ComparedLoadTest,
+    /// The load test being compared.
+    current: ComparedLoadTest,
+    /// The html report file to write the comparison to.
+    report_file: String,
+    /// How many percent metrics can get worse before they are flagged as a regression.
+    tolerance: usize,
+}
+impl GooseComparison {
+    /// Load two load tests to compare.
+    pub async fn load(
+        baseline: &str,
+        current: &str,
+        report_file: &str,
+    ) -> Result<GooseComparison, GooseError> {
+        GooseComparison::load_with(baseline, current, report_file, |goose_report| goose_report)
+            .await
+    }
+
+    /// Load two load tests to compare, applying `filter` to each request log, for example
+    /// to only compare the same window of time.
+    pub async fn load_with(
+        baseline: &str,
+        current: &str,
+        report_file: &str,
+        filter: impl Fn(GooseReport) -> GooseReport,
+    ) -> Result<GooseComparison, GooseError> {
+        Ok(GooseComparison {
+            baseline: ComparedLoadTest::load(baseline, report_file, &filter).await?,
+            current: ComparedLoadTest::load(current, report_file, &filter).await?,
+            report_file: report_file.to_string(),
+            tolerance: 10,
+        })
+    }
+
+    /// How many percent metrics can get worse before they are flagged as a regression,
+    /// defaults to 10.
+    pub fn set_tolerance(mut self, tolerance: usize) -> Self {
+        self.tolerance = tolerance;
+        self
+    }
+
+    /// The metrics files being compared, which don't include per second data, so the
+    /// comparison only includes graphs if this is empty.
+    pub fn metrics_files(&self) -> Vec<&str> {
+        [&self.baseline, &self.current]
+            .iter()
+            .filter(|load_test| load_test.graph_data.is_none())
+            .map(|load_test| load_test.source.as_str())
+            .collect()
+    }
+
+    /// The metrics of the current load test, with each request and then all requests
+    /// compared to the baseline in [`GooseMetrics::baseline`].
+    pub fn metrics(&self) -> GooseMetrics {
+        let mut metrics = GooseMetrics {
+            duration: self.current.metrics.duration,
+            requests: self.current.metrics.requests.clone(),
+            final_metrics: true,
+            display_status_codes: true,
+            display_metrics: true,
+            ..Default::default()
+        };
+        metrics.compare_baseline(Some(&self.baseline.metrics), self.tolerance);
+        metrics
+    }
+
+    /// Write the comparison to the html report file.
+    pub async fn write(&self) -> Result<GooseMetrics, GooseError> {
+        let metrics = self.metrics();
+
+        let overview_rows = [
+            self.baseline.overview_row("Baseline"),
+            self.current.overview_row("Current"),
+        ];
+        let baseline_rows: Vec<String> =
+            metrics.baseline.iter().map(report::baseline_row).collect();
+        let graphs = match (
+            self.baseline.graph_data.as_ref(),
+            self.current.graph_data.as_ref(),
+            self.current.started,
+        ) {
+            (Some(baseline), Some(current), Some(started)) => {
+                Some(current.get_comparison_markup(baseline, started))
+            }
+            _ => {
+                warn!(
+                    "no per second data in {}, graphs are left out of the comparison",
+                    self.metrics_files().join(" and ")
+                );
+                None
+            }
+        };
+        let html = report::build_comparison_report(
+            &overview_rows.join("\n"),
+            &report::baseline_template(&baseline_rows.join("\n")),
+            &report::comparison_graphs_template(graphs),
+        );
+
+        if let Err(e) = tokio::fs::write(&self.report_file, html).await {
+            return Err(GooseError::InvalidOption {
+                option: "--report-file".to_string(),
+                value: self.report_file.to_string(),
+                detail: format!("Failed to create report file: {}", e),
+            });
+        }
+
+        Ok(metrics)
+    }
+}
+
+/// The format of a request log, detected from its first line.
+enum RequestLogFormat {
+    /// One json object per line.
+    Json,
+    /// One csv record per line, with the columns named in the header.
+    Csv(Vec<String>),
+    /// The `Debug` output of each request, on a single line.
+    Raw,
+    /// The pretty `Debug` output of each request, over multiple lines.
+    Pretty,
+}
+
+/// Reads the requests recorded in a request log one at a time, detecting which format it was
+/// written in.
+struct RequestLogReader<R> {
+    /// The lines of the request log.
+    lines: tokio::io::Lines<R>,
+    /// The number of the last line read.
+    line_number: usize,
+    /// The format of the request log, once detected.
+    format: Option<RequestLogFormat>,
+}
+impl<R: AsyncBufRead + Unpin> RequestLogReader<R> {
+    fn new(reader: R) -> Self {
+        RequestLogReader {
+            lines: reader.lines(),
+            line_number: 0,
+            format: None,
+        }
+    }
+
+    /// Read the next line of the request log, if any.
+    async fn next_line(&mut self) -> Result<Option<String>, String> {
+        self.line_number += 1;
+        self.lines
+            .next_line()
+            .await
+            .map_err(|e| format!("line {}: {}", self.line_number, e))
+    }
+
+    /// Read the next request in the request log, if any.
+    async fn next(&mut self) -> Result<Option<GooseRequestMetric>, String> {
+        loop {
+            let mut line = match self.next_line().await? {
+                Some(line) => line,
+                None => return Ok(None),
+            };
+            if line.trim().is_empty() {
+                continue;
+            }
+            let line_number = self.line_number;
+
+            let format = match self.format.as_ref() {
+                Some(format) => format,
+                None => {
+                    let trimmed = line.trim();
+                    self.format = Some(if trimmed.starts_with('{') {
+                        RequestLogFormat::Json
+                    } else if trimmed.starts_with("elapsed,") {
+                        RequestLogFormat::Csv(
+                            csv_records(trimmed).into_iter().next().unwrap_or_default(),
+                        )
+                    } else if trimmed == "GooseRequestMetric {" {
+                        RequestLogFormat::Pretty
+                    } else if trimmed.starts_with("GooseRequestMetric") {
+                        RequestLogFormat::Raw
+                    } else {
+                        return Err(
+                            "unrecognized format, expected: json, csv, raw or pretty".to_string()
+                        );
+                    });
+                    // The csv header doesn't include a request.
+                    if let Some(RequestLogFormat::Csv(_)) = self.format {
+                        continue;
+                    }
+                    self.format.as_ref().unwrap()
+                }
+            };
+
+            let request = match format {
+                RequestLogFormat::Json => serde_json::from_str(&line).map_err(|e| e.to_string()),
+                RequestLogFormat::Csv(header) => {
+                    let header = header.clone();
+                    // Quoted fields can include line breaks, so read until all quotes are closed.
+                    while line.matches('"').count() % 2 == 1 {
+                        match self.next_line().await? {
+                            Some(next) => {
+                                line.push('\n');
+                                line.push_str(&next);
+                            }
+                            None => break,
+                        }
+                    }
+                    csv_request(&header, &line)
+                }
+                RequestLogFormat::Raw => debug_request(&line),
+                RequestLogFormat::Pretty => {
+                    // Only the closing brace of each request isn't indented.
+                    while !line.ends_with("\n}") {
+                        match self.next_line().await? {
+                            Some(next) => {
+                                line.push('\n');
+                                line.push_str(&next);
+                            }
+                            None => break,
+                        }
+                    }
+                    debug_request(&line)
+                }
+            };
+            return request
+                .map(Some)
+                .map_err(|e| format!("line {}: {}", line_number, e));
+        }
+    }
+}
+
+/// Parse a request from a csv record of the request log.
+fn csv_request(header: &[String], record: &str) -> Result<GooseRequestMetric, String> {
+    let record = csv_records(record).into_iter().next().unwrap_or_default();
+    let mut object = Map::new();
+    for (column, field) in header.iter().zip(record) {
+        let value = if column == "raw" {
+            DebugParser::new(&field).parse_value()?
+        } else if CSV_STRING_COLUMNS.contains(&column.as_str()) {
+            Value::String(field)
+        } else if let Ok(boolean) = field.parse::<bool>() {
+            Value::Bool(boolean)
+        } else if let Ok(number) = field.parse::<u64>() {
+            Value::from(number)
+        } else {
+            Value::String(field)
+        };
+        object.insert(column.to_string(), value);
+    }
+    serde_json::from_value(Value::Object(object)).map_err(|e| e.to_string())
+}
+
+/// Parse a request from the `Debug` output written by the `raw` and `pretty` formats.
+fn debug_request(text: &str) -> Result<GooseRequestMetric, String> {
+    let mut parser = DebugParser::new(text);
+    let value = parser.parse_value()?;
+    if !parser.finished() {
+        return Err(parser.error("expected the end of the request"));
+    }
+    serde_json::from_value(value).map_err(|e| e.to_string())
+}
+
+/// Split csv into records of fields, removing the quotes added by `format_csv_row!`.
+fn csv_records(contents: &str) -> Vec<Vec<String>> {
+    let mut records = Vec::new();
+    let mut record = Vec::new();
+    let mut field = String::new();
+    let mut quoted = false;
+    let mut chars = contents.chars().peekable();
+    while let Some(c) = chars.next() {
+        match c {
+            '"' if quoted => {
+                // Quotes inside quoted fields are escaped by doubling them.
+                if chars.peek() == Some(&'"') {
+                    field.push(chars.next().unwrap());
+                } else {
+                    quoted = false;
+                }
+            }
+            '"' if field.is_empty() => quoted = true,
+            ',' if !quoted => record.push(std::mem::take(&mut field)),
+            '\n' if !quoted => {
+                record.push(std::mem::take(&mut field));
+                records.push(std::mem::take(&mut record));
+            }
+            '\r' if !quoted => {}
+            _ => field.push(c),
+        }
+    }
+    if !field.is_empty() || !record.is_empty() {
+        record.push(field);
+        records.push(record);
+    }
+    records
+}
+
+/// Parses the `Debug` output of Rust structures, as written by the `raw` and `pretty` log
+/// formats, into json values that can be deserialized with serde.
+struct DebugParser<'a> {
+    /// The remaining text to parse.
+    remaining: &'a str,
+}
+impl<'a> DebugParser<'a> {
+    fn new(text: &'a str) -> Self {
+        DebugParser { remaining: text }
+    }
+
+    /// Whether or not only whitespace remains to be parsed.
+    fn finished(&mut self) -> bool {
+        self.skip_whitespace();
+        self.remaining.is_empty()
+    }
+
+    fn skip_whitespace(&mut self) {
+        self.remaining = self.remaining.trim_start();
+    }
+
+    fn peek(&mut self) -> Option<char> {
+        self.skip_whitespace();
+        self.remaining.chars().next()
+    }
+
+    /// Consume the expected character, or return an error.
+    fn expect(&mut self, expected: char) -> Result<(), String> {
+        match self.peek() {
+            Some(c) if c == expected => {
+                self.remaining = &self.remaining[c.len_utf8()..];
+                Ok(())
+            }
+            _ => Err(self.error(&format!("expected '{}'", expected))),
+        }
+    }
+
+    /// Describe what went wrong, and where.
+    fn error(&self, detail: &str) -> String {
+        let context: String = self.remaining.chars().take(20).collect();
+        format!("{} at: {:?}", detail, context)
+    }
+
+    /// Consume characters for as long as they match.
+    fn take_while(&mut self, matches: impl Fn(char) -> bool) -> &'a str {
+        let end = self
+            .remaining
+            .find(|c: char| !matches(c))
+            .unwrap_or(self.remaining.len());
+        let (taken, remaining) = self.remaining.split_at(end);
+        self.remaining = remaining;
+        taken
+    }
+
+    fn parse_value(&mut self) -> Result<Value, String> {
+        match self.peek() {
+            Some('"') => self.parse_string().map(Value::String),
+            Some('[') => {
+                let values = self.parse_sequence('[', ']')?;
+                Ok(Value::Array(values))
+            }
+            Some(c) if c == '-' || c.is_ascii_digit() => self.parse_number(),
+            Some(c) if c.is_alphabetic() || c == '_' => self.parse_identifier(),
+            _ => Err(self.error("expected a value")),
+        }
+    }
+
+    /// Parse a comma separated sequence of values, allowing a trailing comma.
+    fn parse_sequence(&mut self, open: char, close: char) -> Result<Vec<Value>, String> {
+        self.expect(open)?;
+        let mut values = Vec::new();
+        while self.peek() != Some(close) {
+            values.push(self.parse_value()?);
+            if self.peek() == Some(',') {
+                self.expect(',')?;
+            } else {
+                break;
+            }
+        }
+        self.expect(close)?;
+        Ok(values)
+    }
+
+    /// Parse a struct (`Name { field: value }`), a tuple variant (`Some(value)`), a boolean
+    /// or a unit variant (`Get`).
+    fn parse_identifier(&mut self) -> Result<Value, String> {
+        let identifier = self.take_while(|c| c.is_alphanumeric() || c == '_');
+        match self.peek() {
+            Some('{') => {
+                self.expect('{')?;
+                let mut object = Map::new();
+                while self.peek() != Some('}') {
+                    self.skip_whitespace();
+                    let field = self.take_while(|c| c.is_alphanumeric() || c == '_');
+                    if field.is_empty() {
+                        return Err(self.error("expected a field name"));
+                    }
+                    self.expect(':')?;
+                    object.insert(field.to_string(), self.parse_value()?);
+                    if self.peek() == Some(',') {
+                        self.expect(',')?;
+                    } else {
+                        break;
+                    }
+                }
+                self.expect('}')?;
+                Ok(Value::Object(object))
+            }
+            Some('(') => {
+                let mut values = self.parse_sequence('(', ')')?;
+                let value = if values.len() == 1 {
+                    values.remove(0)
+                } else {
+                    Value::Array(values)
+                };
+                // Options are deserialized from their value.
+                if identifier == "Some" {
+                    Ok(value)
+                } else {
+                    let mut object = Map::new();
+                    object.insert(identifier.to_string(), value);
+                    Ok(Value::Object(object))
+                }
+            }
+            _ => Ok(match identifier {
+                "true" => Value::Bool(true),
+                "false" => Value::Bool(false),
+                "None" => Value::Null,
+                _ => Value::String(identifier.to_string()),
+            }),
+        }
+    }
+
+    fn parse_number(&mut self) -> Result<Value, String> {
+        let number = self.take_while(|c| c == '-' || c == '.' || c == 'e' || c.is_ascii_digit());
+        if let Ok(number) = number.parse::<u64>() {
+            Ok(Value::from(number))
+        } else if let Ok(number) = number.parse::<i64>() {
+            Ok(Value::from(number))
+        } else if let Ok(number) = number.parse::<f64>() {
+            Ok(Value::from(number))
+        } else {
+            Err(self.error(&format!("invalid number {:?}", number)))
+        }
+    }
+
+    /// Parse a string, unescaping the escapes added by `Debug`.
+    fn parse_string(&mut self) -> Result<String, String> {
+        self.expect('"')?;
+        let mut string = String::new();
+        let mut chars = self.remaining.char_indices();
+        while let Some((index, c)) = chars.next() {
+            match c {
+                '"' => {
+                    self.remaining = &self.remaining[index + 1..];
+                    return Ok(string);
+                }
+                '\\' => match chars.next() {
+                    Some((_, 'n')) => string.push('\n'),
+                    Some((_, 'r')) => string.push('\r'),
+                    Some((_, 't')) => string.push('\t'),
+                    Some((_, '0')) => string.push('\0'),
+                    Some((_, 'u')) => {
+                        // Unicode escapes look like `\u{1b}`.
+                        let escape: String = chars
+                            .by_ref()
+                            .map(|(_, c)| c)
+                            .take_while(|c| *c != '}')
+                            .filter(|c| *c != '{')
+                            .collect();
+                        match u32::from_str_radix(&escape, 16)
+                            .ok()
+                            .and_then(char::from_u32)
+                        {
+                            Some(c) => string.push(c),
+                            None => return Err(self.error("invalid unicode escape")),
+                        }
+                    }
+                    Some((_, c)) => string.push(c),
+                    None => break,
+                },
+                _ => string.push(c),
+            }
+        }
+        Err(self.error("unterminated string"))
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::goose::GooseMethod;
+    use crate::metrics::GooseRawRequest;
+    use serde_json::json;
+
+    #[test]
+    fn parse_debug() {
+        let mut parser = DebugParser::new(
+            r#"GooseRawRequest { method: Get, url: "http://127.0.0.1/\"a\",b", headers: ["x: \u{1b}y"], body: "" }"#,
+        );
+        let value = parser.parse_value().unwrap();
+        assert!(parser.finished());
+        assert_eq!(value["method"], "Get");
+        assert_eq!(value["url"], "http://127.0.0.1/\"a\",b");
+        assert_eq!(value["headers"][0], "x: \u{1b}y");
+        assert_eq!(value["body"], "");
+
+        // Pretty output includes newlines and trailing commas.
+        let mut parser = DebugParser::new(
+            "Foo {\n    bar: Some(\n        -1,\n    ),\n    baz: [],\n    qux: None,\n}\n",
+        );
+        let value = parser.parse_value().unwrap();
+        assert!(parser.finished());
+        assert_eq!(value["bar"], -1);
+        assert_eq!(value["baz"], Value::Array(Vec::new()));
+        assert_eq!(value["qux"], Value::Null);
+
+        assert!(DebugParser::new(r#"Foo { bar: "unterminated }"#)
+            .parse_value()
+            .is_err());
+    }
+
+    #[tokio::test]
+    async fn read_request_log() {
+        let request = GooseRequestMetric::new(
+            GooseRawRequest::new(GooseMethod::Get, "http://127.0.0.1/", vec![], ""),
+            "/",
+            1_500,
+            3,
+        );
+        let logs = vec![
+            format!("{}\n\n{}\n", json!(request), json!(request)),
+            format!("{:?}\n{:?}\n", request, request),
+            format!("{:#?}\n{:#?}\n", request, request),
+        ];
+        for log in logs {
+            let mut reader = RequestLogReader::new(log.as_bytes());
+            for _ in 0..2 {
+                let read = reader.next().await.unwrap().unwrap();
+                assert_eq!(read.elapsed, 1_500);
+                assert_eq!(read.user, 3);
+                assert_eq!(read.timestamp, request.timestamp);
+            }
+            assert!(reader.next().await.unwrap().is_none());
+        }
+
+        // Quoted csv fields can include line breaks.
+        let log = format!(
+            "{}\n{}\n",
+            crate::logger::format_csv_row!(
+                "elapsed",
+                "raw",
+                "name",
+                "final_url",
+                "redirected",
+                "response_time",
+                "status_code",
+                "success",
+                "update",
+                "user",
+                "error",
+                "coordinated_omission_elapsed",
+                "user_cadence",
+            ),
+            crate::logger::format_csv_row!(
+                1_500,
+                format!("{:?}", request.raw),
+                "/",
+                "",
+                false,
+                10,
+                500,
+                false,
+                false,
+                3,
+                "a\nb",
+                0,
+                0,
+            ),
+        );
+        let mut reader = RequestLogReader::new(log.as_bytes());
+        let read = reader.next().await.unwrap().unwrap();
+        assert_eq!(read.error, "a\nb");
+        assert_eq!(read.timestamp, 0);
+        assert!(reader.next().await.unwrap().is_none());
+
+        // Errors include the line number.
+        let mut reader = RequestLogReader::new("{}\n{\n".as_bytes());
+        assert!(reader.next().await.unwrap_err().starts_with("line 1:"));
+        let mut reader = RequestLogReader::new("unknown\n".as_bytes());
+        assert!(reader.next().await.is_err());
+    }
+
+    #[test]
+    fn parse_csv_records() {
+        let records = csv_records("a,\"b,\"\"c\"\"\",\"d\ne\"\n1,2,3\n");
+        assert_eq!(
+            records,
+            vec![
+                vec!["a".to_string(), "b,\"c\"".to_string(), "d\ne".to_string()],
+                vec!["1".to_string(), "2".to_string(), "3".to_string()],
+            ]
+        );
+    }
+}
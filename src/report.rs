@@ -1,25 +1,41 @@
-//! Optionally writes an html-formatted summary report after running a load test.
+//! Optionally writes a summary report after running a load test.
+//!
+//! Reports are html-formatted by default. Report files ending in `.md` are instead written
+//! as Markdown, for example to add to a pull request comment, and report files ending in
+//! `.json` are written as a json document.
+
+mod markdown;
 
 use crate::metrics;
+use crate::test_plan::{TestPlanRamp, TestPlanStepAction};
 
-use std::collections::BTreeMap;
-use std::mem;
+use chrono::{DateTime, Local};
+use serde::{Serialize, Serializer};
+use std::path::Path;
 
-use serde::Serialize;
+pub(crate) use markdown::build_markdown_report;
 
 /// The following templates are necessary to build an html-formatted summary report.
 #[derive(Debug)]
 pub(crate) struct GooseReportTemplates<'a> {
     pub raw_requests_template: &'a str,
     pub raw_responses_template: &'a str,
+    pub responses_header_template: &'a str,
     pub co_requests_template: &'a str,
     pub co_responses_template: &'a str,
     pub transactions_template: &'a str,
     pub scenarios_template: &'a str,
     pub status_codes_template: &'a str,
     pub errors_template: &'a str,
+    pub thresholds_template: &'a str,
+    pub baseline_template: &'a str,
+    pub phases_template: &'a str,
+    pub bandwidth_template: &'a str,
+    pub timing_breakdown_template: &'a str,
+    pub custom_metrics_template: &'a str,
     pub graph_rps_template: &'a str,
     pub graph_average_response_time_template: &'a str,
+    pub graph_response_time_percentiles_template: &'a str,
     pub graph_users_per_second: &'a str,
 }
 
@@ -52,14 +68,8 @@ pub(crate) struct CORequestMetric {
 pub(crate) struct ResponseMetric {
     pub method: String,
     pub name: String,
-    pub percentile_50: String,
-    pub percentile_60: String,
-    pub percentile_70: String,
-    pub percentile_80: String,
-    pub percentile_90: String,
-    pub percentile_95: String,
-    pub percentile_99: String,
-    pub percentile_100: String,
+    pub percentiles: Vec<String>,
+    pub apdex: Option<String>,
 }
 
 /// Defines the metrics reported about transactions.
@@ -91,50 +101,190 @@ pub(crate) struct ScenarioMetric {
 }
 
 /// Defines the metrics reported about status codes.
+#[derive(Debug, Clone, Serialize)]
 pub(crate) struct StatusCodeMetric {
     pub method: String,
     pub name: String,
     pub status_codes: String,
 }
 
+/// Defines the metrics reported about each test plan step.
+#[derive(Debug, Clone, Serialize)]
+pub(crate) struct StepMetric {
+    pub action: TestPlanStepAction,
+    #[serde(serialize_with = "serialize_timestamp")]
+    pub started: DateTime<Local>,
+    #[serde(serialize_with = "serialize_timestamp")]
+    pub stopped: DateTime<Local>,
+    /// How long the step lasted, in seconds.
+    pub elapsed: i64,
+    /// The number of users when the step started.
+    pub users: usize,
+    /// The number of users when the step stopped.
+    pub target_users: usize,
+    pub ramp: TestPlanRamp,
+    pub reason: Option<String>,
+}
+impl StepMetric {
+    /// Format when the step started.
+    pub(crate) fn started(&self) -> String {
+        self.started.format("%y-%m-%d %H:%M:%S").to_string()
+    }
+
+    /// Format when the step stopped.
+    pub(crate) fn stopped(&self) -> String {
+        self.stopped.format("%y-%m-%d %H:%M:%S").to_string()
+    }
+
+    /// Format how long the step lasted as `HH:MM:SS`.
+    pub(crate) fn elapsed(&self) -> String {
+        format!(
+            "{:02}:{:02}:{:02}",
+            self.elapsed / 60 / 60,
+            (self.elapsed / 60) % 60,
+            self.elapsed % 60
+        )
+    }
+
+    /// Format how the number of users changed during the step, ie `0 -> 10` while increasing
+    /// and `0 <- 10` while decreasing, using the provided arrows.
+    pub(crate) fn users(&self, right_arrow: &str, left_arrow: &str) -> String {
+        // Only show the ramp shape if it's not the default linear ramp.
+        let ramp = match self.ramp {
+            TestPlanRamp::Linear => "".to_string(),
+            ramp => format!(" ({})", ramp),
+        };
+        match self.action {
+            // For maintaining just show the current number of users, and how they oscillate.
+            TestPlanStepAction::Maintaining => format!("{}{}", self.users, ramp),
+            TestPlanStepAction::Finished => self.users.to_string(),
+            // For increasing show the current number of users to the new number of users.
+            TestPlanStepAction::Increasing => format!(
+                "{} {} {}{}",
+                self.users, right_arrow, self.target_users, ramp
+            ),
+            // For decreasing show the new number of users from the current number of users.
+            TestPlanStepAction::Decreasing
+            | TestPlanStepAction::Canceling
+            | TestPlanStepAction::Aborting => format!(
+                "{} {} {}{}",
+                self.target_users, left_arrow, self.users, ramp
+            ),
+        }
+    }
+}
+
+/// Serialize a timestamp in the RFC 3339 format.
+fn serialize_timestamp<S: Serializer>(
+    timestamp: &DateTime<Local>,
+    serializer: S,
+) -> Result<S::Ok, S::Error> {
+    serializer.serialize_str(&timestamp.to_rfc3339())
+}
+
+/// The metrics included in every report format, prepared from the metrics of a completed
+/// load test. Serialized as is for json reports.
+#[derive(Debug, Clone, Serialize)]
+pub(crate) struct ReportData {
+    pub version: String,
+    pub users: usize,
+    pub hosts: Vec<String>,
+    /// How long the load test ran, in seconds.
+    pub duration: usize,
+    pub steps: Vec<StepMetric>,
+    /// The response time percentiles included in `raw_responses` and `co_responses`.
+    pub percentiles: Vec<f32>,
+    pub raw_requests: Vec<RequestMetric>,
+    pub raw_responses: Vec<ResponseMetric>,
+    pub co_requests: Option<Vec<CORequestMetric>>,
+    pub co_responses: Option<Vec<ResponseMetric>>,
+    pub status_codes: Option<Vec<StatusCodeMetric>>,
+    pub transactions: Option<Vec<TransactionMetric>>,
+    pub scenarios: Option<Vec<ScenarioMetric>>,
+    pub errors: Vec<metrics::GooseErrorMetricAggregate>,
+}
+
+/// The formats a report can be written in.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum ReportFormat {
+    Html,
+    Markdown,
+    Json,
+}
+impl ReportFormat {
+    /// Select the format of a report by the extension of its file name, defaulting to html.
+    pub(crate) fn from_path(path: &str) -> Self {
+        match Path::new(path)
+            .extension()
+            .and_then(|extension| extension.to_str())
+            .map(|extension| extension.to_lowercase())
+            .as_deref()
+        {
+            Some("md") => ReportFormat::Markdown,
+            Some("json") => ReportFormat::Json,
+            _ => ReportFormat::Html,
+        }
+    }
+}
+impl std::fmt::Display for ReportFormat {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        match self {
+            ReportFormat::Html => write!(f, "html"),
+            ReportFormat::Markdown => write!(f, "markdown"),
+            ReportFormat::Json => write!(f, "json"),
+        }
+    }
+}
+
+/// Escape text so it can be safely included in the html report.
+pub(crate) fn escape(text: &str) -> String {
+    let mut escaped = String::with_capacity(text.len());
+    for c in text.chars() {
+        match c {
+            '&' => escaped.push_str("&amp;"),
+            '<' => escaped.push_str("&lt;"),
+            '>' => escaped.push_str("&gt;"),
+            '"' => escaped.push_str("&quot;"),
+            '\'' => escaped.push_str("&#39;"),
+            _ => escaped.push(c),
+        }
+    }
+    escaped
+}
+
+/// The response time percentiles included in the html report by default.
+pub(crate) const DEFAULT_PERCENTILES: [f32; 8] = [50.0, 60.0, 70.0, 80.0, 90.0, 95.0, 99.0, 100.0];
+
 /// Helper to generate a single response metric.
 pub(crate) fn get_response_metric(
     method: &str,
     name: &str,
-    response_times: &BTreeMap<usize, usize>,
-    total_request_count: usize,
-    response_time_minimum: usize,
-    response_time_maximum: usize,
+    data: &metrics::GooseRequestMetricTimingData,
+    percentiles: &[f32],
 ) -> ResponseMetric {
-    // Calculate percentiles in a loop.
-    let mut percentiles = Vec::new();
-    for percent in &[0.5, 0.6, 0.7, 0.8, 0.9, 0.95, 0.99, 1.0] {
-        percentiles.push(metrics::calculate_response_time_percentile(
-            response_times,
-            total_request_count,
-            response_time_minimum,
-            response_time_maximum,
-            *percent,
-        ));
-    }
-
-    // Now take the Strings out of the Vector and build a ResponseMetric object.
     ResponseMetric {
         method: method.to_string(),
         name: name.to_string(),
-        percentile_50: mem::take(&mut percentiles[0]),
-        percentile_60: mem::take(&mut percentiles[1]),
-        percentile_70: mem::take(&mut percentiles[2]),
-        percentile_80: mem::take(&mut percentiles[3]),
-        percentile_90: mem::take(&mut percentiles[4]),
-        percentile_95: mem::take(&mut percentiles[5]),
-        percentile_99: mem::take(&mut percentiles[6]),
-        percentile_100: mem::take(&mut percentiles[7]),
+        percentiles: data
+            .percentiles(percentiles)
+            .iter()
+            .map(|percentile| metrics::format_number(percentile.response_time))
+            .collect(),
+        apdex: None,
     }
 }
 
+/// Build the header cells of the response metrics tables in the html report.
+pub(crate) fn responses_header(percentiles: &[f32]) -> String {
+    percentiles
+        .iter()
+        .map(|percentile| format!("<th>{}%ile (ms)</th>", percentile))
+        .collect::<Vec<String>>()
+        .join("\n                    ")
+}
+
 /// Build an individual row of raw request metrics in the html report.
-pub(crate) fn raw_request_metrics_row(metric: RequestMetric) -> String {
+pub(crate) fn raw_request_metrics_row(metric: &RequestMetric) -> String {
     format!(
         r#"<tr>
         <td>{method}</td>
@@ -160,30 +310,22 @@ pub(crate) fn raw_request_metrics_row(metric: RequestMetric) -> String {
 }
 
 /// Build an individual row of response metrics in the html report.
-pub(crate) fn response_metrics_row(metric: ResponseMetric) -> String {
+pub(crate) fn response_metrics_row(metric: &ResponseMetric) -> String {
     format!(
         r#"<tr>
             <td>{method}</td>
             <td>{name}</td>
-            <td>{percentile_50}</td>
-            <td>{percentile_60}</td>
-            <td>{percentile_70}</td>
-            <td>{percentile_80}</td>
-            <td>{percentile_90}</td>
-            <td>{percentile_95}</td>
-            <td>{percentile_99}</td>
-            <td>{percentile_100}</td>
+            {percentiles}
         </tr>"#,
         method = metric.method,
         name = metric.name,
-        percentile_50 = metric.percentile_50,
-        percentile_60 = metric.percentile_60,
-        percentile_70 = metric.percentile_70,
-        percentile_80 = metric.percentile_80,
-        percentile_90 = metric.percentile_90,
-        percentile_95 = metric.percentile_95,
-        percentile_99 = metric.percentile_99,
-        percentile_100 = metric.percentile_100,
+        percentiles = metric
+            .percentiles
+            .iter()
+            .chain(metric.apdex.iter())
+            .map(|value| format!("<td>{}</td>", value))
+            .collect::<Vec<String>>()
+            .join("\n            "),
     )
 }
 
@@ -214,7 +356,7 @@ pub(crate) fn coordinated_omission_request_metrics_template(co_requests_rows: &s
 
 /// Build an individual row of Coordinated Omission Mitigation request metrics in
 /// the html report.
-pub(crate) fn coordinated_omission_request_metrics_row(metric: CORequestMetric) -> String {
+pub(crate) fn coordinated_omission_request_metrics_row(metric: &CORequestMetric) -> String {
     format!(
         r#"<tr>
             <td>{method}</td>
@@ -233,7 +375,10 @@ pub(crate) fn coordinated_omission_request_metrics_row(metric: CORequestMetric)
 
 /// If Coordinated Omission Mitigation is triggered, add a relevant response table to the
 /// html report.
-pub(crate) fn coordinated_omission_response_metrics_template(co_responses_rows: &str) -> String {
+pub(crate) fn coordinated_omission_response_metrics_template(
+    co_responses_rows: &str,
+    responses_header: &str,
+) -> String {
     format!(
         r#"<div class="responses">
         <h2>Response Time Metrics With Coordinated Omission Mitigation</h2>
@@ -242,14 +387,7 @@ pub(crate) fn coordinated_omission_response_metrics_template(co_responses_rows:
                 <tr>
                     <th>Method</th>
                     <th>Name</th>
-                    <th>50%ile (ms)</th>
-                    <th>60%ile (ms)</th>
-                    <th>70%ile (ms)</th>
-                    <th>80%ile (ms)</th>
-                    <th>90%ile (ms)</th>
-                    <th>95%ile (ms)</th>
-                    <th>99%ile (ms)</th>
-                    <th>100%ile (ms)</th>
+                    {responses_header}
                 </tr>
             </thead>
             <tbody>
@@ -258,35 +396,27 @@ pub(crate) fn coordinated_omission_response_metrics_template(co_responses_rows:
         </table>
     </div>"#,
         co_responses_rows = co_responses_rows,
+        responses_header = responses_header,
     )
 }
 
 /// Build an individual row of Coordinated Omission Mitigation request metrics in
 /// the html report.
-pub(crate) fn coordinated_omission_response_metrics_row(metric: ResponseMetric) -> String {
+pub(crate) fn coordinated_omission_response_metrics_row(metric: &ResponseMetric) -> String {
     format!(
         r#"<tr>
             <td>{method}</td>
             <td>{name}</td>
-            <td>{percentile_50}</td>
-            <td>{percentile_60}</td>
-            <td>{percentile_70}</td>
-            <td>{percentile_80}</td>
-            <td>{percentile_90}</td>
-            <td>{percentile_95}</td>
-            <td>{percentile_99}</td>
-            <td>{percentile_100}</td>
+            {percentiles}
         </tr>"#,
         method = metric.method,
         name = metric.name,
-        percentile_50 = metric.percentile_50,
-        percentile_60 = metric.percentile_60,
-        percentile_70 = metric.percentile_70,
-        percentile_80 = metric.percentile_80,
-        percentile_90 = metric.percentile_90,
-        percentile_95 = metric.percentile_95,
-        percentile_99 = metric.percentile_99,
-        percentile_100 = metric.percentile_100,
+        percentiles = metric
+            .percentiles
+            .iter()
+            .map(|percentile| format!("<td>{}</td>", percentile))
+            .collect::<Vec<String>>()
+            .join("\n            "),
     )
 }
 
@@ -314,7 +444,7 @@ pub(crate) fn status_code_metrics_template(status_code_rows: &str) -> String {
 }
 
 /// Build an individual row of status code metrics in the html report.
-pub(crate) fn status_code_metrics_row(metric: StatusCodeMetric) -> String {
+pub(crate) fn status_code_metrics_row(metric: &StatusCodeMetric) -> String {
     format!(
         r#"<tr>
         <td>{method}</td>
@@ -359,7 +489,7 @@ pub(crate) fn transaction_metrics_template(transaction_rows: &str, graph: String
 }
 
 /// Build an individual row of transaction metrics in the html report.
-pub(crate) fn transaction_metrics_row(metric: TransactionMetric) -> String {
+pub(crate) fn transaction_metrics_row(metric: &TransactionMetric) -> String {
     if metric.is_scenario {
         format!(
             r#"<tr>
@@ -424,7 +554,7 @@ pub(crate) fn scenario_metrics_template(scenario_rows: &str, graph: String) -> S
 }
 
 /// Build an individual row of scenario metrics in the html report.
-pub(crate) fn scenario_metrics_row(metric: ScenarioMetric) -> String {
+pub(crate) fn scenario_metrics_row(metric: &ScenarioMetric) -> String {
     format!(
         r#"<tr>
             <td colspan="2">{name}</strong></td>
@@ -484,21 +614,309 @@ pub fn error_row(error: &metrics::GooseErrorMetricAggregate) -> String {
     )
 }
 
-/// Build the html report.
-pub(crate) fn build_report(
-    users: &str,
-    steps_rows: &str,
-    hosts: &str,
-    templates: GooseReportTemplates,
+/// If thresholds are configured, add a thresholds table to the html report.
+pub(crate) fn thresholds_template(threshold_rows: &str) -> String {
+    format!(
+        r#"<div class="thresholds">
+        <h2>Thresholds</h2>
+        <table>
+            <thead>
+                <tr>
+                    <th>Threshold</th>
+                    <th>Measured</th>
+                    <th>Result</th>
+                </tr>
+            </thead>
+            <tbody>
+                {threshold_rows}
+            </tbody>
+        </table>
+    </div>"#,
+        threshold_rows = threshold_rows,
+    )
+}
+
+/// Build an individual threshold row in the html report.
+pub(crate) fn threshold_row(result: &metrics::GooseThresholdResult) -> String {
+    let measured = match result.value {
+        Some(value) => format!("{:.2}", value),
+        None => "-".to_string(),
+    };
+    format!(
+        r#"<tr>
+        <td>{threshold}</td>
+        <td>{measured}</td>
+        <td>{passed}</td>
+    </tr>"#,
+        threshold = result.threshold,
+        measured = measured,
+        passed = if result.passed { "PASS" } else { "FAIL" },
+    )
+}
+
+/// If comparing to a baseline, add a comparison table to the html report.
+pub(crate) fn baseline_template(baseline_rows: &str) -> String {
+    format!(
+        r#"<div class="baseline">
+        <h2>Baseline Comparison</h2>
+        <table>
+            <thead>
+                <tr>
+                    <th>Request</th>
+                    <th>Metric</th>
+                    <th>Baseline</th>
+                    <th>Current</th>
+                    <th>Change</th>
+                    <th>Result</th>
+                </tr>
+            </thead>
+            <tbody>
+                {baseline_rows}
+            </tbody>
+        </table>
+    </div>"#,
+        baseline_rows = baseline_rows,
+    )
+}
+
+/// Build an individual baseline comparison row in the html report.
+pub(crate) fn baseline_row(delta: &metrics::GooseBaselineDelta) -> String {
+    let format_value = |value: Option<f32>| match value {
+        Some(value) => format!("{:.2}", value),
+        None => "-".to_string(),
+    };
+    format!(
+        r#"<tr>
+        <td>{request}</td>
+        <td>{metric}</td>
+        <td>{baseline}</td>
+        <td>{current}</td>
+        <td>{change}</td>
+        <td>{result}</td>
+    </tr>"#,
+        request = delta.request.as_deref().unwrap_or("Aggregated"),
+        metric = delta.metric,
+        baseline = format_value(delta.baseline),
+        current = format_value(delta.current),
+        change = delta.fmt_change(),
+        result = if delta.regression { "WORSE" } else { "OK" },
+    )
+}
+
+/// If the load test ran a test plan, add a table of request metrics per phase to the
+/// html report.
+pub(crate) fn phases_template(phase_rows: &str) -> String {
+    format!(
+        r#"<div class="phases">
+        <h2>Per Phase Metrics</h2>
+        <table>
+            <thead>
+                <tr>
+                    <th>Phase</th>
+                    <th>Name</th>
+                    <th># Requests</th>
+                    <th># Fails</th>
+                    <th>Average (ms)</th>
+                    <th>95%ile (ms)</th>
+                    <th>RPS</th>
+                </tr>
+            </thead>
+            <tbody>
+                {phase_rows}
+            </tbody>
+        </table>
+    </div>"#,
+        phase_rows = phase_rows,
+    )
+}
+
+/// Build an individual per phase request row in the html report.
+pub(crate) fn phase_row(
+    phase: &metrics::GoosePhaseMetrics,
+    summary: &metrics::GoosePhaseRequestSummary,
 ) -> String {
-    let pkg_name = env!("CARGO_PKG_NAME");
-    let pkg_version = env!("CARGO_PKG_VERSION");
+    format!(
+        r#"<tr>
+        <td>{step}: {action:?}</td>
+        <td>{name}</td>
+        <td>{requests}</td>
+        <td>{fails}</td>
+        <td>{average:.2}</td>
+        <td>{response_time_95}</td>
+        <td>{requests_per_second:.2}</td>
+    </tr>"#,
+        step = phase.step + 1,
+        action = phase.action,
+        name = summary.name,
+        requests = summary.requests,
+        fails = summary.fails,
+        average = summary.average,
+        response_time_95 = summary.response_time_95,
+        requests_per_second = summary.requests_per_second,
+    )
+}
 
+/// Add a table of bytes sent and received by each request to the html report.
+pub(crate) fn bandwidth_template(bandwidth_rows: &str, graph: String) -> String {
     format!(
-        r#"<!DOCTYPE html>
-<html>
-<head>
-    <title>Goose Attack Report</title>
+        r#"<div class="bandwidth">
+        <h2>Bandwidth Metrics</h2>
+        {graph}
+        <table>
+            <thead>
+                <tr>
+                    <th>Request</th>
+                    <th>Sent</th>
+                    <th>Received</th>
+                    <th>Compressed</th>
+                    <th>Sent/s</th>
+                    <th>Received/s</th>
+                </tr>
+            </thead>
+            <tbody>
+                {bandwidth_rows}
+            </tbody>
+        </table>
+    </div>"#,
+        bandwidth_rows = bandwidth_rows,
+        graph = graph,
+    )
+}
+
+/// Build an individual bandwidth row in the html report.
+pub(crate) fn bandwidth_row(request: &str, bandwidth: &metrics::GooseBandwidth) -> String {
+    format!(
+        r#"<tr>
+        <td>{request}</td>
+        <td>{request_bytes}</td>
+        <td>{response_bytes}</td>
+        <td>{compressed_bytes}</td>
+        <td>{request_bytes_per_second}</td>
+        <td>{response_bytes_per_second}</td>
+    </tr>"#,
+        request = request,
+        request_bytes = metrics::format_bytes(bandwidth.request_bytes as f32),
+        response_bytes = metrics::format_bytes(bandwidth.response_bytes as f32),
+        compressed_bytes = metrics::format_bytes(bandwidth.compressed_bytes as f32),
+        request_bytes_per_second = metrics::format_bytes(bandwidth.request_bytes_per_second),
+        response_bytes_per_second = metrics::format_bytes(bandwidth.response_bytes_per_second),
+    )
+}
+
+/// Add a table of where the time of each request was spent to the html report.
+pub(crate) fn timing_breakdown_template(timing_breakdown_rows: &str) -> String {
+    format!(
+        r#"<div class="timing-breakdown">
+        <h2>Request Timing Breakdown</h2>
+        <table>
+            <thead>
+                <tr>
+                    <th>Request</th>
+                    <th>Avg DNS (ms)</th>
+                    <th>Avg Connect (ms)</th>
+                    <th>Avg TTFB (ms)</th>
+                    <th>Avg Download (ms)</th>
+                    <th>New Connections</th>
+                    <th>Reused Connections</th>
+                </tr>
+            </thead>
+            <tbody>
+                {timing_breakdown_rows}
+            </tbody>
+        </table>
+    </div>"#,
+        timing_breakdown_rows = timing_breakdown_rows,
+    )
+}
+
+/// Build an individual timing breakdown row in the html report.
+pub(crate) fn timing_breakdown_row(
+    request: &str,
+    timing: &metrics::GooseTimingBreakdown,
+) -> String {
+    format!(
+        r#"<tr>
+        <td>{request}</td>
+        <td>{dns_time:.2}</td>
+        <td>{connect_time:.2}</td>
+        <td>{time_to_first_byte:.2}</td>
+        <td>{download_time:.2}</td>
+        <td>{new_connections}</td>
+        <td>{reused_connections} ({connection_reuse:.1}%)</td>
+    </tr>"#,
+        request = request,
+        dns_time = timing.dns_time,
+        connect_time = timing.connect_time,
+        time_to_first_byte = timing.time_to_first_byte,
+        download_time = timing.download_time,
+        new_connections = metrics::format_number(timing.new_connections),
+        reused_connections = metrics::format_number(timing.reused_connections),
+        connection_reuse = timing.connection_reuse,
+    )
+}
+
+/// Add a table of custom counters, gauges and timings to the html report.
+pub(crate) fn custom_metrics_template(custom_metrics_rows: &str, graphs: &str) -> String {
+    format!(
+        r#"<div class="custom">
+        <h2>Custom Metrics</h2>
+        {graphs}
+        <table>
+            <thead>
+                <tr>
+                    <th>Name</th>
+                    <th>Type</th>
+                    <th>#</th>
+                    <th>Value</th>
+                    <th>Min</th>
+                    <th>Max</th>
+                </tr>
+            </thead>
+            <tbody>
+                {custom_metrics_rows}
+            </tbody>
+        </table>
+    </div>"#,
+        custom_metrics_rows = custom_metrics_rows,
+        graphs = graphs,
+    )
+}
+
+/// Build an individual custom metric row in the html report.
+pub(crate) fn custom_metric_row(
+    name: &str,
+    custom: &metrics::GooseCustomMetricAggregate,
+) -> String {
+    let (count, value, minimum, maximum) = metrics::custom_metric_columns(custom);
+    format!(
+        r#"<tr>
+        <td>{name}</td>
+        <td>{kind}</td>
+        <td>{count}</td>
+        <td>{value}</td>
+        <td>{minimum}</td>
+        <td>{maximum}</td>
+    </tr>"#,
+        name = name,
+        kind = custom.kind(),
+        count = count,
+        value = value,
+        minimum = minimum,
+        maximum = maximum,
+    )
+}
+
+/// Build the head of an html report, including its styles.
+///
+/// @TODO: ECharts is loaded from `cdn.jsdelivr.net`, so graphs don't render when the report
+/// is opened offline. Vendor the Apache-2.0 licensed `echarts.min.js` 5.2.2 and its license
+/// notice under `src/report/`, inline it with `include_str!` into both the attack and the
+/// comparison report, and add a `--report-cdn` option and `GooseDefault::ReportCdn` to keep
+/// loading it from the CDN for smaller files.
+fn report_head(title: &str) -> String {
+    format!(
+        r#"<head>
+    <title>{title}</title>
     <style>
         .container {{
             width: 1000px;
@@ -558,7 +976,25 @@ pub(crate) fn build_report(
         }}
     </style>
     <script src="https://cdn.jsdelivr.net/npm/echarts@5.2.2/dist/echarts.min.js"></script>
-</head>
+</head>"#,
+        title = title,
+    )
+}
+
+/// Build the html report.
+pub(crate) fn build_report(
+    users: &str,
+    steps_rows: &str,
+    hosts: &str,
+    templates: GooseReportTemplates,
+) -> String {
+    let pkg_name = env!("CARGO_PKG_NAME");
+    let pkg_version = env!("CARGO_PKG_VERSION");
+
+    format!(
+        r#"<!DOCTYPE html>
+<html>
+{head}
 <body>
     <div class="container">
         <h1>Goose Attack Report</h1>
@@ -584,6 +1020,12 @@ pub(crate) fn build_report(
                 </table>
         </div>
 
+        {thresholds_template}
+
+        {baseline_template}
+
+        {phases_template}
+
         <div class="requests">
             <h2>Request Metrics</h2>
 
@@ -611,24 +1053,21 @@ pub(crate) fn build_report(
 
         {co_requests_template}
 
+        {bandwidth_template}
+
         <div class="responses">
             <h2>Response Time Metrics</h2>
 
             {graph_average_response_time_template}
 
+            {graph_response_time_percentiles_template}
+
             <table>
                 <thead>
                     <tr>
                         <th>Method</th>
                         <th>Name</th>
-                        <th>50%ile (ms)</th>
-                        <th>60%ile (ms)</th>
-                        <th>70%ile (ms)</th>
-                        <th>80%ile (ms)</th>
-                        <th>90%ile (ms)</th>
-                        <th>95%ile (ms)</th>
-                        <th>99%ile (ms)</th>
-                        <th>100%ile (ms)</th>
+                        {responses_header_template}
                     </tr>
                 </thead>
                 <tbody>
@@ -639,12 +1078,16 @@ pub(crate) fn build_report(
 
         {co_responses_template}
 
+        {timing_breakdown_template}
+
         {status_codes_template}
 
         {transactions_template}
 
         {scenarios_template}
 
+        {custom_metrics_template}
+
         <div class="users">
         <h2>User Metrics</h2>
             {graph_users_per_second}
@@ -655,6 +1098,7 @@ pub(crate) fn build_report(
     </div>
 </body>
 </html>"#,
+        head = report_head("Goose Attack Report"),
         users = users,
         steps_rows = steps_rows,
         hosts = hosts,
@@ -662,14 +1106,121 @@ pub(crate) fn build_report(
         pkg_version = pkg_version,
         raw_requests_template = templates.raw_requests_template,
         raw_responses_template = templates.raw_responses_template,
+        responses_header_template = templates.responses_header_template,
         co_requests_template = templates.co_requests_template,
         co_responses_template = templates.co_responses_template,
         transactions_template = templates.transactions_template,
         scenarios_template = templates.scenarios_template,
         status_codes_template = templates.status_codes_template,
         errors_template = templates.errors_template,
+        thresholds_template = templates.thresholds_template,
+        baseline_template = templates.baseline_template,
+        phases_template = templates.phases_template,
+        bandwidth_template = templates.bandwidth_template,
+        timing_breakdown_template = templates.timing_breakdown_template,
+        custom_metrics_template = templates.custom_metrics_template,
         graph_rps_template = templates.graph_rps_template,
         graph_average_response_time_template = templates.graph_average_response_time_template,
+        graph_response_time_percentiles_template =
+            templates.graph_response_time_percentiles_template,
         graph_users_per_second = templates.graph_users_per_second,
     )
 }
+
+/// Build an individual row of the overview of each load test being compared.
+pub(crate) fn comparison_overview_row(
+    label: &str,
+    source: &str,
+    duration: usize,
+    total: usize,
+    fails: usize,
+) -> String {
+    let (requests_per_second, failures_per_second) =
+        metrics::per_second_calculations(duration, total, fails);
+    format!(
+        r#"<tr>
+        <td>{label}</td>
+        <td>{source}</td>
+        <td>{duration}</td>
+        <td>{total}</td>
+        <td>{fails}</td>
+        <td>{requests_per_second:.2}</td>
+        <td>{failures_per_second:.2}</td>
+    </tr>"#,
+        label = label,
+        source = source,
+        duration = duration,
+        total = total,
+        fails = fails,
+        requests_per_second = requests_per_second,
+        failures_per_second = failures_per_second,
+    )
+}
+
+/// Add graphs overlaying the load tests being compared to the html report, if they were
+/// rebuilt from request logs.
+pub(crate) fn comparison_graphs_template(graphs: Option<String>) -> String {
+    format!(
+        r#"<div class="graphs">
+        <h2>Graphs</h2>
+        {graphs}
+    </div>"#,
+        graphs = graphs.unwrap_or_else(|| {
+            "<p>Graphs are only available when comparing request logs.</p>".to_string()
+        }),
+    )
+}
+
+/// Build the html report comparing two load tests.
+pub(crate) fn build_comparison_report(
+    overview_rows: &str,
+    baseline_template: &str,
+    graphs_template: &str,
+) -> String {
+    let pkg_name = env!("CARGO_PKG_NAME");
+    let pkg_version = env!("CARGO_PKG_VERSION");
+
+    format!(
+        r#"<!DOCTYPE html>
+<html>
+{head}
+<body>
+    <div class="container">
+        <h1>Goose Comparison Report</h1>
+
+        <div class="info">
+            <p><span><small><em>{pkg_name} v{pkg_version}</em></small></span></p>
+            <h2>Overview</h2>
+                <table>
+                    <thead>
+                        <tr>
+                            <th></th>
+                            <th>Source</th>
+                            <th>Duration (s)</th>
+                            <th># Requests</th>
+                            <th># Fails</th>
+                            <th>RPS</th>
+                            <th>Failures/s</th>
+                        </tr>
+                    </thead>
+                    <tbody>
+                        {overview_rows}
+                    </tbody>
+                </table>
+        </div>
+
+        {graphs_template}
+
+        {baseline_template}
+
+    </div>
+</body>
+</html>"#,
+        head = report_head("Goose Comparison Report"),
+        pkg_name = pkg_name,
+        pkg_version = pkg_version,
+        overview_rows = overview_rows,
+        graphs_template = graphs_template,
+        baseline_template = baseline_template,
+    )
+}
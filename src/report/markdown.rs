@@ -0,0 +1,293 @@
+//! Writes the summary report as Markdown, for example to add to a pull request comment.
+
+use std::fmt::Write;
+
+use super::{ReportData, ResponseMetric};
+use crate::metrics;
+
+/// Escape text so it can be used in a Markdown table cell.
+fn escape(text: &str) -> String {
+    text.replace('\\', "\\\\")
+        .replace('|', "\\|")
+        .replace(['\r', '\n'], " ")
+}
+
+/// Append a Markdown table with the provided header and rows.
+fn write_table(markdown: &mut String, header: &[String], rows: &[Vec<String>]) {
+    let _ = writeln!(markdown, "| {} |", header.join(" | "));
+    let _ = writeln!(
+        markdown,
+        "|{}|",
+        header
+            .iter()
+            .map(|_| " --- ")
+            .collect::<Vec<&str>>()
+            .join("|")
+    );
+    for row in rows {
+        let _ = writeln!(markdown, "| {} |", row.join(" | "));
+    }
+    markdown.push('\n');
+}
+
+/// Convert a list of header names to owned strings.
+fn header(names: &[&str]) -> Vec<String> {
+    names.iter().map(|name| name.to_string()).collect()
+}
+
+/// Build the Markdown report.
+pub(crate) fn build_markdown_report(data: &ReportData) -> String {
+    let mut markdown = String::new();
+
+    markdown.push_str("# Goose Attack Report\n\n");
+    let _ = writeln!(markdown, "- Users: {}", data.users);
+    let _ = writeln!(
+        markdown,
+        "- Target Host: {}",
+        escape(&data.hosts.join(", "))
+    );
+    let _ = writeln!(
+        markdown,
+        "- *{} v{}*\n",
+        env!("CARGO_PKG_NAME"),
+        data.version
+    );
+
+    markdown.push_str("## Plan Overview\n\n");
+    let steps: Vec<Vec<String>> = data
+        .steps
+        .iter()
+        .map(|step| {
+            vec![
+                format!("{:?}", step.action),
+                step.started(),
+                step.stopped(),
+                step.elapsed(),
+                step.users("→", "←"),
+            ]
+        })
+        .collect();
+    write_table(
+        &mut markdown,
+        &header(&["Action", "Started", "Stopped", "Elapsed", "Users"]),
+        &steps,
+    );
+    // Explain why steps happened, for example why the load test was aborted.
+    for step in &data.steps {
+        if let Some(reason) = &step.reason {
+            let _ = writeln!(markdown, "- {:?}: {}", step.action, escape(reason));
+        }
+    }
+    if data.steps.iter().any(|step| step.reason.is_some()) {
+        markdown.push('\n');
+    }
+
+    markdown.push_str("## Request Metrics\n\n");
+    let requests: Vec<Vec<String>> = data
+        .raw_requests
+        .iter()
+        .map(|metric| {
+            vec![
+                metric.method.to_string(),
+                escape(&metric.name),
+                metrics::format_number(metric.number_of_requests),
+                metrics::format_number(metric.number_of_failures),
+                metric.response_time_average.to_string(),
+                metric.response_time_minimum.to_string(),
+                metric.response_time_maximum.to_string(),
+                metric.requests_per_second.to_string(),
+                metric.failures_per_second.to_string(),
+            ]
+        })
+        .collect();
+    write_table(
+        &mut markdown,
+        &header(&[
+            "Method",
+            "Name",
+            "# Requests",
+            "# Fails",
+            "Average (ms)",
+            "Min (ms)",
+            "Max (ms)",
+            "RPS",
+            "Failures/s",
+        ]),
+        &requests,
+    );
+
+    // Response time tables include a column for each percentile, and optionally Apdex.
+    let mut responses_header = header(&["Method", "Name"]);
+    responses_header.extend(
+        data.percentiles
+            .iter()
+            .map(|percentile| format!("{}%ile (ms)", percentile)),
+    );
+    let responses = |responses: &[ResponseMetric]| -> Vec<Vec<String>> {
+        responses
+            .iter()
+            .map(|metric| {
+                let mut row = vec![metric.method.to_string(), escape(&metric.name)];
+                row.extend(metric.percentiles.iter().cloned());
+                row.extend(metric.apdex.iter().cloned());
+                row
+            })
+            .collect()
+    };
+
+    markdown.push_str("## Response Time Metrics\n\n");
+    let mut raw_responses_header = responses_header.clone();
+    if data
+        .raw_responses
+        .iter()
+        .any(|metric| metric.apdex.is_some())
+    {
+        raw_responses_header.push("Apdex".to_string());
+    }
+    write_table(
+        &mut markdown,
+        &raw_responses_header,
+        &responses(&data.raw_responses),
+    );
+
+    if let Some(co_requests) = &data.co_requests {
+        markdown.push_str("## Request Metrics With Coordinated Omission Mitigation\n\n");
+        let rows: Vec<Vec<String>> = co_requests
+            .iter()
+            .map(|metric| {
+                vec![
+                    metric.method.to_string(),
+                    escape(&metric.name),
+                    metric.response_time_average.to_string(),
+                    metric.response_time_standard_deviation.to_string(),
+                    metric.response_time_maximum.to_string(),
+                ]
+            })
+            .collect();
+        write_table(
+            &mut markdown,
+            &header(&[
+                "Method",
+                "Name",
+                "Average (ms)",
+                "Standard deviation (ms)",
+                "Max (ms)",
+            ]),
+            &rows,
+        );
+    }
+
+    if let Some(co_responses) = &data.co_responses {
+        markdown.push_str("## Response Time Metrics With Coordinated Omission Mitigation\n\n");
+        write_table(&mut markdown, &responses_header, &responses(co_responses));
+    }
+
+    if let Some(status_codes) = &data.status_codes {
+        markdown.push_str("## Status Code Metrics\n\n");
+        let rows: Vec<Vec<String>> = status_codes
+            .iter()
+            .map(|metric| {
+                vec![
+                    metric.method.to_string(),
+                    escape(&metric.name),
+                    metric.status_codes.to_string(),
+                ]
+            })
+            .collect();
+        write_table(
+            &mut markdown,
+            &header(&["Method", "Name", "Status Codes"]),
+            &rows,
+        );
+    }
+
+    if let Some(transactions) = &data.transactions {
+        markdown.push_str("## Transaction Metrics\n\n");
+        let rows: Vec<Vec<String>> = transactions
+            .iter()
+            .map(|metric| {
+                if metric.is_scenario {
+                    // Scenarios are a row of their own, followed by their transactions.
+                    let mut row = vec![format!("**{}**", escape(&metric.name))];
+                    row.extend((0..7).map(|_| "".to_string()));
+                    row
+                } else {
+                    vec![
+                        escape(format!("{} {}", metric.transaction, metric.name).trim()),
+                        metrics::format_number(metric.number_of_requests),
+                        metrics::format_number(metric.number_of_failures),
+                        metric.response_time_average.to_string(),
+                        metric.response_time_minimum.to_string(),
+                        metric.response_time_maximum.to_string(),
+                        metric.requests_per_second.to_string(),
+                        metric.failures_per_second.to_string(),
+                    ]
+                }
+            })
+            .collect();
+        write_table(
+            &mut markdown,
+            &header(&[
+                "Transaction",
+                "# Times Run",
+                "# Fails",
+                "Average (ms)",
+                "Min (ms)",
+                "Max (ms)",
+                "RPS",
+                "Failures/s",
+            ]),
+            &rows,
+        );
+    }
+
+    if let Some(scenarios) = &data.scenarios {
+        markdown.push_str("## Scenario Metrics\n\n");
+        let rows: Vec<Vec<String>> = scenarios
+            .iter()
+            .map(|metric| {
+                vec![
+                    escape(&metric.name),
+                    metrics::format_number(metric.users),
+                    metrics::format_number(metric.count),
+                    metric.response_time_average.to_string(),
+                    metric.response_time_minimum.to_string(),
+                    metric.response_time_maximum.to_string(),
+                    metric.count_per_second.to_string(),
+                    metric.iterations.to_string(),
+                ]
+            })
+            .collect();
+        write_table(
+            &mut markdown,
+            &header(&[
+                "Scenario",
+                "# Users",
+                "# Times Run",
+                "Average (ms)",
+                "Min (ms)",
+                "Max (ms)",
+                "Scenarios/s",
+                "Iterations",
+            ]),
+            &rows,
+        );
+    }
+
+    if !data.errors.is_empty() {
+        markdown.push_str("## Errors\n\n");
+        let rows: Vec<Vec<String>> = data
+            .errors
+            .iter()
+            .map(|error| {
+                vec![
+                    metrics::format_number(error.occurrences),
+                    escape(&error.error),
+                ]
+            })
+            .collect();
+        write_table(&mut markdown, &header(&["#", "Error"]), &rows);
+    }
+
+    markdown
+}
@@ -0,0 +1,520 @@
+//! Optional metrics sink pushing live metrics to StatsD or InfluxDB.
+//!
+//! When enabled with `--metrics-sink`, Goose periodically pushes the number of running
+//! users and aggregated request, transaction and scenario metrics to a StatsD server over
+//! UDP, or to InfluxDB (or Telegraf) using the line protocol over TCP or HTTP. Metrics are
+//! pushed from the same loop that displays running metrics, at the interval configured
+//! with `--metrics-sink-interval`.
+
+use itertools::Itertools;
+use std::collections::HashMap;
+use std::io;
+use std::time::{SystemTime, UNIX_EPOCH};
+use tokio::io::AsyncWriteExt;
+use tokio::net::{TcpStream, UdpSocket};
+
+use crate::metrics::{GooseMetrics, GooseMetricsSink, GooseMetricsSinkProtocol};
+use crate::util;
+
+/// How often metrics are pushed to the sink by default, in seconds.
+pub(crate) const DEFAULT_INTERVAL: usize = 10;
+
+/// The largest StatsD payload sent in a single UDP packet, small enough to avoid
+/// fragmentation on most networks.
+const MAX_UDP_PAYLOAD: usize = 1432;
+
+/// The aggregated metrics of a single request, transaction or scenario.
+struct SinkMetric {
+    /// What is measured: `requests`, `transactions` or `scenarios`.
+    kind: &'static str,
+    /// The tags identifying what is measured, such as the method and name of a request.
+    tags: Vec<(&'static str, String)>,
+    /// How many times it completed.
+    count: usize,
+    /// How many times it failed, if failures are tracked.
+    failures: Option<usize>,
+    /// How many milliseconds were spent in total.
+    total_time: usize,
+}
+
+/// Flatten the current metrics, in a stable order.
+fn sink_metrics(metrics: &GooseMetrics) -> Vec<SinkMetric> {
+    let mut sink_metrics = Vec::new();
+    for (_, request) in metrics.requests.iter().sorted_by(|a, b| a.0.cmp(b.0)) {
+        sink_metrics.push(SinkMetric {
+            kind: "requests",
+            tags: vec![
+                ("method", request.method.to_string()),
+                ("name", request.path.to_string()),
+            ],
+            count: request.success_count + request.fail_count,
+            failures: Some(request.fail_count),
+            total_time: request.raw_data.total_time,
+        });
+    }
+    for transaction in metrics.transactions.iter().flatten() {
+        sink_metrics.push(SinkMetric {
+            kind: "transactions",
+            tags: vec![
+                ("scenario", transaction.scenario_name.to_string()),
+                (
+                    "transaction",
+                    if transaction.transaction_name.is_empty() {
+                        transaction.transaction_index.to_string()
+                    } else {
+                        transaction.transaction_name.to_string()
+                    },
+                ),
+            ],
+            count: transaction.counter,
+            failures: Some(transaction.fail_count),
+            total_time: transaction.total_time,
+        });
+    }
+    for scenario in &metrics.scenarios {
+        sink_metrics.push(SinkMetric {
+            kind: "scenarios",
+            tags: vec![("scenario", scenario.name.to_string())],
+            count: scenario.counter,
+            failures: None,
+            total_time: scenario.total_time,
+        });
+    }
+    sink_metrics
+}
+
+/// Identifies a request, transaction or scenario by what is measured and its tags.
+type SinkKey = (&'static str, Vec<String>);
+
+/// A StatsD bucket, and the cumulative totals already pushed to it.
+#[derive(Debug)]
+struct StatsdBucket {
+    /// The unique name of the bucket.
+    name: String,
+    /// How many times it completed.
+    count: usize,
+    /// How many times it failed.
+    failures: usize,
+    /// How many milliseconds were spent in total.
+    total_time: usize,
+}
+
+/// Convert a tag into a StatsD bucket name segment.
+fn statsd_segment(tag: &str) -> String {
+    let segment = tag
+        .chars()
+        .map(|c| {
+            if c.is_ascii_alphanumeric() || c == '-' {
+                c.to_ascii_lowercase()
+            } else {
+                '_'
+            }
+        })
+        .collect::<String>();
+    let segment = segment.trim_matches('_');
+    // The name of the index page `/` would otherwise be empty.
+    if segment.is_empty() {
+        "root".to_string()
+    } else {
+        segment.to_string()
+    }
+}
+
+/// A short hash of the tags, stable across load tests, to tell apart buckets whose names
+/// would otherwise be the same (FNV-1a).
+fn statsd_hash(key: &SinkKey) -> String {
+    let mut hash: u32 = 0x811c_9dc5;
+    for byte in key.1.join("\0").bytes() {
+        hash ^= byte as u32;
+        hash = hash.wrapping_mul(0x0100_0193);
+    }
+    format!("{:08x}", hash)
+}
+
+/// Format the current metrics as StatsD lines.
+///
+/// StatsD counters are incremented, so only the requests, transactions and scenarios
+/// completed since the previous push are counted. The cumulative totals already pushed are
+/// tracked in `buckets`, and the average time is calculated for the same period.
+///
+/// Different tags can convert into the same bucket name, for example `/a.b` and `/a_b`, in
+/// which case a hash of the tags is appended to the name of the bucket seen last.
+fn format_statsd(
+    metrics: &GooseMetrics,
+    users: usize,
+    buckets: &mut HashMap<SinkKey, StatsdBucket>,
+) -> Vec<String> {
+    let mut lines = vec![format!("goose.users:{}|g", users)];
+    for metric in sink_metrics(metrics) {
+        let key: SinkKey = (
+            metric.kind,
+            metric.tags.iter().map(|(_, tag)| tag.to_string()).collect(),
+        );
+        if !buckets.contains_key(&key) {
+            let mut name = format!(
+                "goose.{}.{}",
+                metric.kind,
+                key.1.iter().map(|tag| statsd_segment(tag)).join(".")
+            );
+            if buckets.values().any(|bucket| bucket.name == name) {
+                name = format!("{}_{}", name, statsd_hash(&key));
+            }
+            buckets.insert(
+                key.clone(),
+                StatsdBucket {
+                    name,
+                    count: 0,
+                    failures: 0,
+                    total_time: 0,
+                },
+            );
+        }
+        // Bucket was inserted above if it didn't already exist.
+        let previous = buckets.get_mut(&key).unwrap();
+        let bucket = &previous.name;
+        let failures = metric.failures.unwrap_or_default();
+        // Nothing is subtracted if the metrics were reset since the previous push.
+        let (count, failed, total_time) = if previous.count <= metric.count {
+            (
+                metric.count - previous.count,
+                failures.saturating_sub(previous.failures),
+                metric.total_time.saturating_sub(previous.total_time),
+            )
+        } else {
+            (metric.count, failures, metric.total_time)
+        };
+        lines.push(format!("{}.count:{}|c", bucket, count));
+        if metric.failures.is_some() {
+            lines.push(format!("{}.failures:{}|c", bucket, failed));
+        }
+        if count > 0 {
+            lines.push(format!(
+                "{}.time_avg:{:.3}|g",
+                bucket,
+                total_time as f64 / count as f64
+            ));
+        }
+        previous.count = metric.count;
+        previous.failures = failures;
+        previous.total_time = metric.total_time;
+    }
+    lines
+}
+
+/// Escape a tag value, as required by the InfluxDB line protocol.
+fn escape_tag(value: &str) -> String {
+    let mut escaped = String::new();
+    for c in value.chars() {
+        match c {
+            ',' | '=' | ' ' | '\\' => {
+                escaped.push('\\');
+                escaped.push(c);
+            }
+            '\n' => escaped.push_str("\\n"),
+            _ => escaped.push(c),
+        }
+    }
+    escaped
+}
+
+/// Format the current metrics in the InfluxDB line protocol, with the cumulative totals
+/// since the metrics were last reset and a timestamp in nanoseconds.
+fn format_influx(metrics: &GooseMetrics, users: usize, timestamp: u128) -> Vec<String> {
+    let mut lines = vec![format!("goose_users value={}i {}", users, timestamp)];
+    for metric in sink_metrics(metrics) {
+        let mut fields = vec![format!("count={}i", metric.count)];
+        if let Some(failures) = metric.failures {
+            fields.push(format!("failures={}i", failures));
+        }
+        if metric.count > 0 {
+            fields.push(format!(
+                "time_avg={:.3}",
+                metric.total_time as f64 / metric.count as f64
+            ));
+        }
+        lines.push(format!(
+            "goose_{},{} {} {}",
+            metric.kind,
+            metric
+                .tags
+                .iter()
+                .map(|(key, value)| format!("{}={}", key, escape_tag(value)))
+                .join(","),
+            fields.join(","),
+            timestamp
+        ));
+    }
+    lines
+}
+
+/// The state of an enabled metrics sink, owned by the parent process.
+#[derive(Debug)]
+pub(crate) struct MetricsSink {
+    /// How metrics are sent to the sink.
+    protocol: GooseMetricsSinkProtocol,
+    /// How often metrics are pushed, in seconds.
+    interval: usize,
+    /// Timer tracking when metrics were last pushed.
+    timer: std::time::Instant,
+    /// StatsD buckets, with the cumulative totals already pushed to each.
+    buckets: HashMap<SinkKey, StatsdBucket>,
+    /// Unbounded sender to the sink thread, `None` tells it to exit.
+    sink_tx: flume::Sender<Option<Vec<String>>>,
+    /// The sink thread.
+    sink_handle: tokio::task::JoinHandle<()>,
+}
+impl MetricsSink {
+    /// Spawn a thread sending metrics to the sink.
+    pub(crate) fn spawn(sink: &GooseMetricsSink, interval: usize) -> Self {
+        info!("pushing metrics to {} every {} seconds", sink, interval);
+        let (sink_tx, sink_rx) = flume::unbounded();
+        let sink_handle = tokio::spawn(sink_main(sink.clone(), sink_rx));
+        MetricsSink {
+            protocol: sink.protocol.clone(),
+            interval,
+            timer: std::time::Instant::now(),
+            buckets: HashMap::new(),
+            sink_tx,
+            sink_handle,
+        }
+    }
+
+    /// Push the current metrics if the interval has passed since the previous push.
+    pub(crate) fn push_if_due(&mut self, metrics: &GooseMetrics, users: usize) {
+        if util::timer_expired(self.timer, self.interval) {
+            self.push(metrics, users);
+        }
+    }
+
+    /// Push the current metrics.
+    pub(crate) fn push(&mut self, metrics: &GooseMetrics, users: usize) {
+        self.timer = std::time::Instant::now();
+        let lines = match self.protocol {
+            GooseMetricsSinkProtocol::Statsd => format_statsd(metrics, users, &mut self.buckets),
+            GooseMetricsSinkProtocol::InfluxTcp | GooseMetricsSinkProtocol::InfluxHttp => {
+                let timestamp = SystemTime::now()
+                    .duration_since(UNIX_EPOCH)
+                    .map(|duration| duration.as_nanos())
+                    .unwrap_or_default();
+                format_influx(metrics, users, timestamp)
+            }
+        };
+        let _ = self.sink_tx.send(Some(lines));
+    }
+
+    /// Push the metrics collected so far before they are reset, then start counting again.
+    pub(crate) fn reset(&mut self, metrics: &GooseMetrics, users: usize) {
+        self.push(metrics, users);
+        // Bucket names are kept so they don't change after the metrics are reset.
+        for bucket in self.buckets.values_mut() {
+            bucket.count = 0;
+            bucket.failures = 0;
+            bucket.total_time = 0;
+        }
+    }
+
+    /// Push the final metrics, then wait for the sink thread to send them and exit.
+    pub(crate) async fn shutdown(mut self, metrics: &GooseMetrics, users: usize) {
+        self.push(metrics, users);
+        if let Err(e) = self.sink_tx.send(None) {
+            warn!(
+                "unexpected error telling metrics sink thread to exit: {}",
+                e
+            );
+        }
+        let _ = self.sink_handle.await;
+    }
+}
+
+/// Metrics sink thread, sends the lines it receives from the parent to the sink until
+/// told to exit.
+async fn sink_main(sink: GooseMetricsSink, receiver: flume::Receiver<Option<Vec<String>>>) {
+    let mut udp_socket = None;
+    let mut tcp_stream = None;
+    let client = reqwest::Client::new();
+
+    while let Ok(Some(lines)) = receiver.recv_async().await {
+        let result = match sink.protocol {
+            GooseMetricsSinkProtocol::Statsd => {
+                send_udp(&sink.address, &mut udp_socket, &lines).await
+            }
+            GooseMetricsSinkProtocol::InfluxTcp => {
+                send_tcp(&sink.address, &mut tcp_stream, &lines).await
+            }
+            GooseMetricsSinkProtocol::InfluxHttp => send_http(&sink.address, &client, &lines).await,
+        };
+        if let Err(e) = result {
+            warn!("failed to push metrics to {}: {}", sink, e);
+        }
+    }
+}
+
+/// Send lines to StatsD, packing as many as fit into each UDP packet.
+async fn send_udp(
+    address: &str,
+    udp_socket: &mut Option<UdpSocket>,
+    lines: &[String],
+) -> io::Result<()> {
+    if udp_socket.is_none() {
+        let destination = tokio::net::lookup_host(address)
+            .await?
+            .next()
+            .ok_or_else(|| io::Error::new(io::ErrorKind::NotFound, "failed to resolve address"))?;
+        let local = if destination.is_ipv6() {
+            "[::]:0"
+        } else {
+            "0.0.0.0:0"
+        };
+        let socket = UdpSocket::bind(local).await?;
+        socket.connect(destination).await?;
+        *udp_socket = Some(socket);
+    }
+    // Socket was created above if it didn't already exist.
+    let socket = udp_socket.as_ref().unwrap();
+
+    let mut packet = String::new();
+    for line in lines {
+        if !packet.is_empty() && packet.len() + 1 + line.len() > MAX_UDP_PAYLOAD {
+            socket.send(packet.as_bytes()).await?;
+            packet.clear();
+        }
+        if !packet.is_empty() {
+            packet.push('\n');
+        }
+        packet.push_str(line);
+    }
+    if !packet.is_empty() {
+        socket.send(packet.as_bytes()).await?;
+    }
+    Ok(())
+}
+
+/// Send lines over TCP, reconnecting on the next push if the connection was lost.
+async fn send_tcp(
+    address: &str,
+    tcp_stream: &mut Option<TcpStream>,
+    lines: &[String],
+) -> io::Result<()> {
+    if tcp_stream.is_none() {
+        *tcp_stream = Some(TcpStream::connect(address).await?);
+    }
+    // Stream was created above if it didn't already exist.
+    let stream = tcp_stream.as_mut().unwrap();
+
+    let mut payload = lines.join("\n");
+    payload.push('\n');
+    if let Err(e) = stream.write_all(payload.as_bytes()).await {
+        *tcp_stream = None;
+        return Err(e);
+    }
+    Ok(())
+}
+
+/// Post lines to an InfluxDB HTTP write endpoint.
+async fn send_http(url: &str, client: &reqwest::Client, lines: &[String]) -> io::Result<()> {
+    let response = client
+        .post(url)
+        .body(lines.join("\n"))
+        .send()
+        .await
+        .map_err(io::Error::other)?;
+    if !response.status().is_success() {
+        return Err(io::Error::other(format!(
+            "unexpected response status {}",
+            response.status()
+        )));
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::goose::GooseMethod;
+    use crate::metrics::{GooseRequestMetricAggregate, ScenarioMetricAggregate};
+
+    fn build_metrics(times: &[u64], failures: usize) -> GooseMetrics {
+        let mut index = GooseRequestMetricAggregate::new("/", GooseMethod::Get, 0, 3);
+        for time in times {
+            index.record_time(*time, false);
+            index.success_count += 1;
+        }
+        index.fail_count += failures;
+        let mut scenario = ScenarioMetricAggregate::new(0, "Load Test", 3);
+        scenario.counter = 1;
+        scenario.total_time = 100;
+        let mut metrics = GooseMetrics::default();
+        metrics.requests.insert("GET /".to_string(), index);
+        metrics.scenarios.push(scenario);
+        metrics
+    }
+
+    #[test]
+    fn format_sink_metrics() {
+        // StatsD counts what completed since the previous push.
+        let mut previous = HashMap::new();
+        let lines = format_statsd(&build_metrics(&[10, 20], 1), 2, &mut previous);
+        assert_eq!(
+            lines,
+            vec![
+                "goose.users:2|g",
+                "goose.requests.get.root.count:3|c",
+                "goose.requests.get.root.failures:1|c",
+                "goose.requests.get.root.time_avg:10.000|g",
+                "goose.scenarios.load_test.count:1|c",
+                "goose.scenarios.load_test.time_avg:100.000|g",
+            ]
+        );
+        let lines = format_statsd(&build_metrics(&[10, 20, 60], 1), 2, &mut previous);
+        assert!(lines.contains(&"goose.requests.get.root.count:1|c".to_string()));
+        assert!(lines.contains(&"goose.requests.get.root.failures:0|c".to_string()));
+        assert!(lines.contains(&"goose.requests.get.root.time_avg:60.000|g".to_string()));
+        assert!(lines.contains(&"goose.scenarios.load_test.count:0|c".to_string()));
+        // Counting starts over after the metrics are reset.
+        let lines = format_statsd(&build_metrics(&[30], 0), 2, &mut previous);
+        assert!(lines.contains(&"goose.requests.get.root.count:1|c".to_string()));
+
+        // Names that convert into the same bucket name are still counted separately.
+        let mut metrics = build_metrics(&[10], 0);
+        for path in ["/a.b", "/a_b"] {
+            let mut request = GooseRequestMetricAggregate::new(path, GooseMethod::Get, 0, 3);
+            request.record_time(10, false);
+            request.success_count += 1;
+            metrics.requests.insert(format!("GET {}", path), request);
+        }
+        let mut previous = HashMap::new();
+        let lines = format_statsd(&metrics, 2, &mut previous);
+        let hash = statsd_hash(&("requests", vec!["GET".to_string(), "/a_b".to_string()]));
+        assert!(lines.contains(&"goose.requests.get.a_b.count:1|c".to_string()));
+        assert!(lines.contains(&format!("goose.requests.get.a_b_{}.count:1|c", hash)));
+        let lines = format_statsd(&metrics, 2, &mut previous);
+        assert!(lines.contains(&"goose.requests.get.a_b.count:0|c".to_string()));
+        assert!(lines.contains(&format!("goose.requests.get.a_b_{}.count:0|c", hash)));
+
+        // The line protocol includes cumulative totals and escaped tags.
+        let lines = format_influx(&build_metrics(&[10, 20], 1), 2, 1_000);
+        assert_eq!(
+            lines,
+            vec![
+                "goose_users value=2i 1000",
+                "goose_requests,method=GET,name=/ count=3i,failures=1i,time_avg=10.000 1000",
+                "goose_scenarios,scenario=Load\\ Test count=1i,time_avg=100.000 1000",
+            ]
+        );
+    }
+
+    #[test]
+    fn parse_metrics_sink() {
+        let sink: GooseMetricsSink = "statsd://localhost".parse().unwrap();
+        assert_eq!(sink.protocol, GooseMetricsSinkProtocol::Statsd);
+        assert_eq!(sink.address, "localhost:8125");
+        let sink: GooseMetricsSink = "influx://127.0.0.1:9000".parse().unwrap();
+        assert_eq!(sink.protocol, GooseMetricsSinkProtocol::InfluxTcp);
+        assert_eq!(sink.to_string(), "influx://127.0.0.1:9000");
+        let sink: GooseMetricsSink = "http://127.0.0.1:8086/write?db=goose".parse().unwrap();
+        assert_eq!(sink.protocol, GooseMetricsSinkProtocol::InfluxHttp);
+        assert_eq!(sink.to_string(), "http://127.0.0.1:8086/write?db=goose");
+        assert!("graphite://127.0.0.1".parse::<GooseMetricsSink>().is_err());
+        assert!("127.0.0.1:8125".parse::<GooseMetricsSink>().is_err());
+    }
+}
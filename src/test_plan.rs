@@ -7,20 +7,32 @@ use gumdrop::Options;
 use regex::Regex;
 use serde::{Deserialize, Serialize};
 use std::cmp::Ordering;
+use std::fmt;
 use std::str::FromStr;
 use std::time;
 
 use crate::config::GooseConfiguration;
+use crate::metrics::{GooseBreakpointProbe, GooseBreakpointSnapshot};
 use crate::util;
 use crate::{AttackPhase, GooseAttack, GooseAttackRunState, GooseError};
 
 /// Internal data structure representing a test plan.
-#[derive(Options, Debug, Clone, Serialize, Deserialize)]
+#[derive(Options, Debug, Clone, Hash, Serialize, Deserialize)]
 pub(crate) struct TestPlan {
     // A test plan is a vector of tuples each indicating a # of users and milliseconds.
     pub(crate) steps: Vec<(usize, usize)>,
+    // The ramp shape of each step, steps without a shape are linear.
+    pub(crate) ramps: Vec<TestPlanRamp>,
     // Which step of the test_plan is currently running.
     pub(crate) current: usize,
+    // If true, each step defines how many scenario iterations to start per second instead of
+    // how many users to run.
+    pub(crate) arrival_rate: bool,
+    // The maximum number of users that can be launched to sustain an arrival rate test plan,
+    // or the maximum number of users a breakpoint search can step up to (0 if unlimited).
+    pub(crate) maximum_users: usize,
+    // If running a breakpoint search, how much to increase the load by with each step.
+    pub(crate) breakpoint: Option<usize>,
 }
 
 /// Automatically represent all load tests internally as a test plan.
@@ -32,13 +44,46 @@ impl TestPlan {
     pub(crate) fn new() -> TestPlan {
         TestPlan {
             steps: Vec::new(),
+            ramps: Vec::new(),
             current: 0,
+            arrival_rate: false,
+            maximum_users: 0,
+            breakpoint: None,
         }
     }
 
     /// Build a test plan from current configuration.
     pub(crate) fn build(configuration: &GooseConfiguration) -> TestPlan {
-        if let Some(test_plan) = configuration.test_plan.as_ref() {
+        if let Some(arrival_rate) = configuration.arrival_rate.as_ref() {
+            // Arrival rate was manually defined, steps are iterations started per second and
+            // users are launched as needed up to the configured number of users.
+            let mut test_plan = arrival_rate.clone();
+            test_plan.arrival_rate = true;
+            test_plan.maximum_users = configuration.users.unwrap_or_else(num_cpus::get);
+            test_plan
+        } else if let Some(breakpoint) = configuration.breakpoint.as_ref() {
+            // Breakpoint search was configured, start with a single step that is stepped up
+            // until a limit is hit. Each step changes the load all at once so the full step is
+            // spent measuring it.
+            let BreakpointStep { step, duration } = *breakpoint;
+            let (maximum_users, users) = if configuration.breakpoint_arrival_rate {
+                (configuration.users.unwrap_or_else(num_cpus::get), step)
+            } else {
+                // Don't step up beyond the configured number of users, if any.
+                match configuration.users {
+                    Some(users) if users > 0 => (users, step.min(users)),
+                    _ => (0, step),
+                }
+            };
+            TestPlan {
+                steps: vec![(users, duration)],
+                ramps: vec![TestPlanRamp::Spike],
+                current: 0,
+                arrival_rate: configuration.breakpoint_arrival_rate,
+                maximum_users,
+                breakpoint: Some(step),
+            }
+        } else if let Some(test_plan) = configuration.test_plan.as_ref() {
             // Test plan was manually defined, clone and return as is.
             test_plan.clone()
         } else {
@@ -76,23 +121,128 @@ impl TestPlan {
             }
 
             // Define test plan from options.
-            TestPlan { steps, current: 0 }
+            TestPlan {
+                steps,
+                ramps: Vec::new(),
+                current: 0,
+                arrival_rate: false,
+                maximum_users: 0,
+                breakpoint: None,
+            }
         }
     }
 
     // Determine the total number of users required by the test plan.
     pub(crate) fn total_users(&self) -> usize {
+        // An arrival rate test plan launches users as needed, up to the configured maximum.
+        if self.arrival_rate {
+            return self.maximum_users;
+        }
+
         let mut total_users: usize = 0;
         let mut previous: usize = 0;
-        for step in &self.steps {
+        for (index, step) in self.steps.iter().enumerate() {
             // Add to total_users every time there is an increase.
             if step.0 > previous {
                 total_users += step.0 - previous;
             }
+            // Sine ramps oscillate above the number of users configured for the step.
+            if let TestPlanRamp::Sine { amplitude, .. } = self.ramp(index) {
+                total_users += amplitude;
+            }
             previous = step.0
         }
         total_users
     }
+
+    // Determine the ramp shape of a test plan step.
+    pub(crate) fn ramp(&self, step: usize) -> TestPlanRamp {
+        self.ramps.get(step).copied().unwrap_or_default()
+    }
+
+    // Determine if the current test plan step oscillates, launching and stopping users
+    // throughout the step.
+    pub(crate) fn oscillating(&self) -> bool {
+        matches!(self.ramp(self.current), TestPlanRamp::Sine { .. })
+    }
+
+    // Determine how many milliseconds into the current step the next user should be launched
+    // or stopped, when `adjusted` of the `total` users changing in this step already were.
+    pub(crate) fn next_adjustment_in_ms(&self, adjusted: usize, total: usize) -> usize {
+        let progress = adjusted as f32 / total as f32;
+        (self.ramp(self.current).elapsed(progress) * self.steps[self.current].1 as f32) as usize
+    }
+
+    // Determine how many users should be running the given number of milliseconds after the
+    // test plan started, following the ramp shape of each step. After the last step the number
+    // of users configured in the last step continue running.
+    pub(crate) fn users_at(&self, elapsed: usize) -> usize {
+        let mut elapsed = elapsed;
+        let mut previous_users = 0;
+        for (step, (_, duration)) in self.steps.iter().enumerate() {
+            if elapsed < *duration {
+                return self.step_users_at(step, previous_users, elapsed);
+            }
+            elapsed -= duration;
+            previous_users = self.steps[step].0;
+        }
+        previous_users
+    }
+
+    // Determine how many users should be running the given number of milliseconds into the
+    // current test plan step.
+    pub(crate) fn current_users_at(&self, elapsed: usize) -> usize {
+        let previous_users = if self.current == 0 {
+            0
+        } else {
+            self.steps[self.current - 1].0
+        };
+        self.step_users_at(self.current, previous_users, elapsed)
+    }
+
+    // Determine how many users should be running the given number of milliseconds into a test
+    // plan step, ramping from the number of users running when the step started.
+    fn step_users_at(&self, step: usize, previous_users: usize, elapsed: usize) -> usize {
+        let (users, duration) = self.steps[step];
+        let ramp = self.ramp(step);
+        let progress = ramp.progress(elapsed as f32 / duration as f32);
+        let adjusted = (previous_users as f32 - users as f32).abs() * progress;
+        let users = if users > previous_users {
+            previous_users + adjusted.round() as usize
+        } else {
+            previous_users - adjusted.round() as usize
+        };
+        match ramp {
+            TestPlanRamp::Sine { .. } => {
+                (users as f32 + ramp.oscillation(elapsed)).max(0.0).round() as usize
+            }
+            _ => users,
+        }
+    }
+
+    // Determine how many scenario iterations (or requests) per second should be started by an
+    // arrival rate test plan, ramping from the previous step's rate to the current step's rate.
+    pub(crate) fn arrivals_per_second(&self, elapsed: usize) -> f32 {
+        // After the last step, continue at the final rate until the load test is stopped.
+        if self.current >= self.steps.len() {
+            return self.steps.last().map_or(0.0, |step| step.0 as f32);
+        }
+        // The first step ramps up from 0.
+        let previous_rate = if self.current == 0 {
+            0.0
+        } else {
+            self.steps[self.current - 1].0 as f32
+        };
+        let (rate, duration) = self.steps[self.current];
+        if duration == 0 || elapsed >= duration {
+            rate as f32
+        } else {
+            let ramp = self.ramp(self.current);
+            let progress = ramp.progress(elapsed as f32 / duration as f32);
+            (previous_rate + (rate as f32 - previous_rate) * progress + ramp.oscillation(elapsed))
+                .max(0.0)
+        }
+    }
 }
 
 /// Implement [`FromStr`] to convert `"users,timespan"` string formatted test plans to Goose's
@@ -103,14 +253,23 @@ impl TestPlan {
 /// Time span can be specified as an integer, indicating seconds. Or can use integers together
 /// with one or more of "h", "m", and "s", in that order, indicating "hours", "minutes", and
 /// "seconds". Valid formats include: 20, 20s, 3m, 2h, 1h20m, 3h30m10s, etc.
+///
+/// Each step can optionally be followed by a ramp shape, for example `"100,1m,exponential"`.
+/// Valid shapes are "linear" (the default), "exponential", "logarithmic", "s-curve", "spike",
+/// and "sine(amplitude,timespan)", for example `"100,10m,sine(20,1m)"`.
 impl FromStr for TestPlan {
     type Err = GooseError;
 
     fn from_str(s: &str) -> Result<Self, Self::Err> {
         // Convert string into a TestPlan.
         let mut steps: Vec<(usize, usize)> = Vec::new();
-        // Each line of the test plan must be in the format "{users},{timespan}", white space is ignored
-        let re = Regex::new(r"^\s*(\d+)\s*,\s*(\d+|((\d+?)h)?((\d+?)m)?((\d+?)s)?)\s*$").unwrap();
+        let mut ramps: Vec<TestPlanRamp> = Vec::new();
+        // Each line of the test plan must be in the format "{users},{timespan}", optionally followed
+        // by ",{ramp}", white space is ignored
+        let re = Regex::new(
+            r"^\s*(\d+)\s*,\s*(\d+|((\d+?)h)?((\d+?)m)?((\d+?)s)?)\s*(,\s*([a-zA-Z-]+(\([^)]*\))?)\s*)?$",
+        )
+        .unwrap();
         // A test plan can have multiple lines split by the semicolon ";".
         let lines = s.split(';');
         for line in lines {
@@ -119,13 +278,19 @@ impl FromStr for TestPlan {
                     .parse::<usize>()
                     .expect("failed to convert \\d to usize");
                 let right = util::parse_timespan(&cap[2]) * 1_000;
+                let ramp = match cap.get(10) {
+                    Some(ramp) => ramp.as_str().parse::<TestPlanRamp>()?,
+                    None => TestPlanRamp::Linear,
+                };
                 steps.push((left, right));
+                ramps.push(ramp);
             } else {
                 // Logger isn't initialized yet, provide helpful debug output.
                 eprintln!("ERROR: invalid `configuration.test_plan` value: '{}'", line);
                 eprintln!("  Expected format: --test-plan \"{{users}},{{timespan}};{{users}},{{timespan}}\"");
                 eprintln!("    {{users}} must be an integer, ie \"100\"");
                 eprintln!("    {{timespan}} can be integer seconds or \"30s\", \"20m\", \"3h\", \"1h30m\", etc");
+                eprintln!("    each step can optionally end with \",{{ramp}}\", ie \"100,1m,exponential\"");
                 return Err(GooseError::InvalidOption {
                     option: "`configuration.test_plan".to_string(),
                     value: line.to_string(),
@@ -134,12 +299,193 @@ impl FromStr for TestPlan {
             }
         }
         // The steps are only valid if the logic gets this far.
-        Ok(TestPlan { steps, current: 0 })
+        Ok(TestPlan {
+            steps,
+            ramps,
+            current: 0,
+            arrival_rate: false,
+            maximum_users: 0,
+            breakpoint: None,
+        })
+    }
+}
+
+/// Internal data structure representing the step of a breakpoint search.
+#[derive(Debug, Clone, Copy, Hash, PartialEq, Eq, Serialize, Deserialize)]
+pub(crate) struct BreakpointStep {
+    // How much to increase the load by with each step.
+    pub(crate) step: usize,
+    // How many milliseconds to measure each step.
+    pub(crate) duration: usize,
+}
+
+/// Implement [`FromStr`] to convert a `"users,timespan"` string formatted breakpoint search
+/// step to Goose's internal representation. Unlike a [`TestPlan`] only a single step is
+/// accepted, and it can't set a ramp as the load is always stepped up all at once.
+impl FromStr for BreakpointStep {
+    type Err = GooseError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        // The step must be in the format "{users},{timespan}", white space is ignored.
+        let re = Regex::new(r"^\s*(\d+)\s*,\s*(\d+|((\d+?)h)?((\d+?)m)?((\d+?)s)?)\s*$").unwrap();
+        match re.captures(s) {
+            Some(cap) => Ok(BreakpointStep {
+                step: cap[1]
+                    .parse::<usize>()
+                    .expect("failed to convert \\d to usize"),
+                duration: util::parse_timespan(&cap[2]) * 1_000,
+            }),
+            None => {
+                // Logger isn't initialized yet, provide helpful debug output.
+                eprintln!("ERROR: invalid `configuration.breakpoint` value: '{}'", s);
+                eprintln!("  Expected format: --breakpoint \"{{users}},{{timespan}}\"");
+                eprintln!("    {{users}} must be an integer, ie \"10\"");
+                eprintln!("    {{timespan}} can be integer seconds or \"30s\", \"20m\", \"3h\", \"1h30m\", etc");
+                Err(GooseError::InvalidOption {
+                    option: "`configuration.breakpoint".to_string(),
+                    value: s.to_string(),
+                    detail: "invalid `configuration.breakpoint` value, expected a single step without a ramp.".to_string(),
+                })
+            }
+        }
+    }
+}
+
+// How steep the exponential and logarithmic ramp curves are.
+const RAMP_CURVE: f32 = 5.0;
+
+/// The shape of the curve followed while increasing or decreasing the number of GooseUser
+/// threads (or iterations per second) during a test plan step.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum TestPlanRamp {
+    /// Adjust users at a constant rate throughout the step.
+    #[default]
+    Linear,
+    /// Adjust users slowly at first, accelerating towards the end of the step.
+    Exponential,
+    /// Adjust users quickly at first, slowing towards the end of the step.
+    Logarithmic,
+    /// Adjust users slowly at the start and end of the step, and quickly in the middle.
+    SCurve,
+    /// Adjust all users at once at the start of the step.
+    Spike,
+    /// Adjust users at a constant rate throughout the step, while oscillating up to `amplitude`
+    /// users above and below in a sine wave that repeats every `period` milliseconds.
+    Sine {
+        /// How many users are added and removed at the peaks of the wave.
+        amplitude: usize,
+        /// How many milliseconds each wave takes.
+        period: usize,
+    },
+}
+impl TestPlanRamp {
+    // Determine what fraction of the users have been adjusted after the given fraction of the
+    // step has elapsed.
+    pub(crate) fn progress(&self, elapsed: f32) -> f32 {
+        let elapsed = elapsed.clamp(0.0, 1.0);
+        match self {
+            TestPlanRamp::Linear => elapsed,
+            TestPlanRamp::Exponential => {
+                ((RAMP_CURVE * elapsed).exp() - 1.0) / (RAMP_CURVE.exp() - 1.0)
+            }
+            TestPlanRamp::Logarithmic => {
+                (1.0 + elapsed * (RAMP_CURVE.exp() - 1.0)).ln() / RAMP_CURVE
+            }
+            TestPlanRamp::SCurve => (1.0 - (std::f32::consts::PI * elapsed).cos()) / 2.0,
+            TestPlanRamp::Spike => 1.0,
+            // The oscillation is added separately by `oscillation()`.
+            TestPlanRamp::Sine { .. } => elapsed,
+        }
+    }
+
+    // Determine what fraction of the step must elapse before the given fraction of the users
+    // have been adjusted, the inverse of `progress()`.
+    pub(crate) fn elapsed(&self, progress: f32) -> f32 {
+        let progress = progress.clamp(0.0, 1.0);
+        match self {
+            TestPlanRamp::Linear => progress,
+            TestPlanRamp::Exponential => {
+                (1.0 + progress * (RAMP_CURVE.exp() - 1.0)).ln() / RAMP_CURVE
+            }
+            TestPlanRamp::Logarithmic => {
+                ((RAMP_CURVE * progress).exp() - 1.0) / (RAMP_CURVE.exp() - 1.0)
+            }
+            TestPlanRamp::SCurve => (1.0 - 2.0 * progress).acos() / std::f32::consts::PI,
+            TestPlanRamp::Spike => 0.0,
+            TestPlanRamp::Sine { .. } => progress,
+        }
+    }
+
+    // Determine how many users (or iterations per second) an oscillating ramp adds to or
+    // removes from the step the given number of milliseconds after it started.
+    pub(crate) fn oscillation(&self, elapsed: usize) -> f32 {
+        match self {
+            TestPlanRamp::Sine { amplitude, period } => {
+                let wave = (elapsed % period) as f32 / *period as f32;
+                *amplitude as f32 * (2.0 * std::f32::consts::PI * wave).sin()
+            }
+            _ => 0.0,
+        }
+    }
+}
+impl fmt::Display for TestPlanRamp {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            TestPlanRamp::Linear => write!(f, "linear"),
+            TestPlanRamp::Exponential => write!(f, "exponential"),
+            TestPlanRamp::Logarithmic => write!(f, "logarithmic"),
+            TestPlanRamp::SCurve => write!(f, "s-curve"),
+            TestPlanRamp::Spike => write!(f, "spike"),
+            TestPlanRamp::Sine { amplitude, period } => {
+                write!(f, "sine({},{}s)", amplitude, period / 1_000)
+            }
+        }
+    }
+}
+impl FromStr for TestPlanRamp {
+    type Err = GooseError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let s = s.to_lowercase();
+        // A sine ramp is configured with an amplitude and a timespan, ie "sine(20,1m)".
+        if s.starts_with("sine") {
+            let re =
+                Regex::new(r"^sine\(\s*(\d+)\s*,\s*(\d+|((\d+?)h)?((\d+?)m)?((\d+?)s)?)\s*\)$")
+                    .unwrap();
+            return match re.captures(&s) {
+                Some(cap) if util::parse_timespan(&cap[2]) > 0 => Ok(TestPlanRamp::Sine {
+                    amplitude: cap[1]
+                        .parse::<usize>()
+                        .expect("failed to convert \\d to usize"),
+                    period: util::parse_timespan(&cap[2]) * 1_000,
+                }),
+                _ => Err(GooseError::InvalidOption {
+                    option: "`configuration.test_plan".to_string(),
+                    value: s.to_string(),
+                    detail: "invalid sine ramp, expected sine(amplitude,timespan), ie sine(20,1m)."
+                        .to_string(),
+                }),
+            };
+        }
+        match s.as_str() {
+            "linear" => Ok(TestPlanRamp::Linear),
+            "exponential" | "exp" => Ok(TestPlanRamp::Exponential),
+            "logarithmic" | "log" => Ok(TestPlanRamp::Logarithmic),
+            "s-curve" | "scurve" => Ok(TestPlanRamp::SCurve),
+            "spike" => Ok(TestPlanRamp::Spike),
+            _ => Err(GooseError::InvalidOption {
+                option: "`configuration.test_plan".to_string(),
+                value: s.to_string(),
+                detail:
+                    "invalid ramp, expected linear, exponential, logarithmic, s-curve, spike or sine."
+                        .to_string(),
+            }),
+        }
     }
 }
 
 /// A test plan is a series of steps performing one of the following actions.
-#[derive(Clone, Debug)]
+#[derive(Clone, Debug, Serialize, Deserialize)]
 pub enum TestPlanStepAction {
     /// A test plan step that is increasing the number of GooseUser threads.
     Increasing,
@@ -149,6 +495,9 @@ pub enum TestPlanStepAction {
     Decreasing,
     /// A test plan step that is canceling all GooseUser threads.
     Canceling,
+    /// A test plan step that is stopping all GooseUser threads as an abort-on-fail rule
+    /// was breached.
+    Aborting,
     /// The final step indicating that the load test is finished.
     Finished,
 }
@@ -162,19 +511,174 @@ pub struct TestPlanHistory {
     pub timestamp: DateTime<Utc>,
     /// The number of users when the step started.
     pub users: usize,
+    /// The shape of the curve followed while adjusting users during the step.
+    pub ramp: TestPlanRamp,
+    /// Why the step happened, if not part of the configured test plan.
+    pub reason: Option<String>,
 }
 impl TestPlanHistory {
     /// A helper to record a new test plan step in the historical record.
-    pub(crate) fn step(action: TestPlanStepAction, users: usize) -> TestPlanHistory {
+    pub(crate) fn step(
+        action: TestPlanStepAction,
+        users: usize,
+        ramp: TestPlanRamp,
+    ) -> TestPlanHistory {
         TestPlanHistory {
             action,
             timestamp: Utc::now(),
             users,
+            ramp,
+            reason: None,
         }
     }
 }
 
+// How many probes in a row must not improve on the best throughput before a breakpoint search
+// stops, as throughput fluctuates from one probe to the next.
+const BREAKPOINT_STALLED_PROBES: usize = 2;
+
 impl GooseAttack {
+    // When running a breakpoint search, measure the load level that was just probed then either
+    // step up the load or shut down if a limit was hit.
+    pub(crate) async fn probe_breakpoint(
+        &mut self,
+        goose_attack_run_state: &mut GooseAttackRunState,
+    ) -> Result<(), GooseError> {
+        let step = match self.test_plan.breakpoint {
+            Some(step) => step,
+            None => return Ok(()),
+        };
+
+        // Only the last step is probed, and there's nothing to probe once shutting down.
+        let level = self.test_plan.steps[self.test_plan.current].0;
+        if self.test_plan.current != self.test_plan.steps.len() - 1 || level == 0 {
+            return Ok(());
+        }
+
+        // Receive all metrics collected while probing this level, then start measuring the next.
+        self.sync_metrics(goose_attack_run_state, true).await?;
+        let probe = match goose_attack_run_state.breakpoint_snapshot.as_ref() {
+            Some(snapshot) => snapshot.probe(level, &self.metrics.requests),
+            None => return Ok(()),
+        };
+        goose_attack_run_state.breakpoint_snapshot =
+            Some(GooseBreakpointSnapshot::new(&self.metrics.requests));
+        info!(
+            "breakpoint probe at {}: {:.2} requests per second, {:.2}% errors, 95th percentile {}ms",
+            level, probe.requests_per_second, probe.error_rate, probe.response_time_95
+        );
+
+        let breakpoint = match self.metrics.breakpoint.as_mut() {
+            Some(breakpoint) => breakpoint,
+            None => return Ok(()),
+        };
+        let previous_level = breakpoint.probes.last().map(|previous| previous.level);
+
+        // Find the probe with the best throughput so far, and determine if this probe is the
+        // last of too many in a row that didn't improve on it.
+        let best = breakpoint.probes.iter().enumerate().fold(
+            None,
+            |best: Option<(usize, &GooseBreakpointProbe)>, (index, earlier)| match best {
+                Some((_, best_probe))
+                    if earlier.requests_per_second <= best_probe.requests_per_second =>
+                {
+                    best
+                }
+                _ => Some((index, earlier)),
+            },
+        );
+        let stalled = best
+            .filter(|(index, best_probe)| {
+                probe.requests_per_second <= best_probe.requests_per_second
+                    && breakpoint.probes.len() - index >= BREAKPOINT_STALLED_PROBES
+            })
+            .map(|(_, best_probe)| (best_probe.level, best_probe.requests_per_second));
+
+        // Determine if this level hit a limit, and if so the last level that didn't.
+        let (reason, capacity) = if let Some(error_rate) = self
+            .configuration
+            .breakpoint_error_rate
+            .filter(|error_rate| probe.error_rate > *error_rate as f32)
+        {
+            (
+                Some(format!(
+                    "error rate of {:.2}% is above {}%",
+                    probe.error_rate, error_rate
+                )),
+                previous_level,
+            )
+        } else if let Some(response_time) = self
+            .configuration
+            .breakpoint_response_time
+            .filter(|response_time| probe.response_time_95 > *response_time)
+        {
+            (
+                Some(format!(
+                    "95th percentile response time of {}ms is above {}ms",
+                    probe.response_time_95, response_time
+                )),
+                previous_level,
+            )
+        } else if let Some((best_level, best_requests_per_second)) = stalled {
+            // The level with the best throughput is the last one worth stepping up to.
+            (
+                Some(format!(
+                    "throughput of {:.2} requests per second has not increased above {:.2} for {} probes",
+                    probe.requests_per_second, best_requests_per_second, BREAKPOINT_STALLED_PROBES
+                )),
+                Some(best_level),
+            )
+        } else {
+            (None, None)
+        };
+        breakpoint.probes.push(probe);
+
+        let maximum_users = if self.test_plan.arrival_rate {
+            0
+        } else {
+            self.test_plan.maximum_users
+        };
+        let next_level = if let Some(reason) = reason {
+            breakpoint.capacity = capacity;
+            breakpoint.reason = Some(reason);
+            0
+        } else if maximum_users > 0 && level >= maximum_users {
+            // No more users can be launched.
+            breakpoint.capacity = Some(level);
+            breakpoint.reason = Some(format!("reached the maximum of {} users", maximum_users));
+            0
+        } else {
+            breakpoint.capacity = Some(level);
+            if maximum_users > 0 {
+                (level + step).min(maximum_users)
+            } else {
+                level + step
+            }
+        };
+
+        if let Some(reason) = breakpoint.reason.as_ref() {
+            info!(
+                "breakpoint search stopped, {}: max sustainable capacity {:?}",
+                reason, breakpoint.capacity
+            );
+        }
+
+        // Add the next step to the test plan, or shut down.
+        if next_level > 0 {
+            let duration = self.test_plan.steps[self.test_plan.current].1;
+            self.test_plan.steps.push((next_level, duration));
+            // Allocate the users needed to step up the load.
+            if !self.test_plan.arrival_rate {
+                self.weighted_users = self.weight_scenario_users(next_level - level)?;
+            }
+        } else {
+            self.test_plan.steps.push((0, 0));
+        }
+        self.test_plan.ramps.push(TestPlanRamp::Spike);
+
+        Ok(())
+    }
+
     // Advance the active [`GooseAttack`](./struct.GooseAttack.html) to the next TestPlan step.
     pub(crate) fn advance_test_plan(&mut self, goose_attack_run_state: &mut GooseAttackRunState) {
         // Record the instant this new step starts, for use with timers.
@@ -215,13 +719,166 @@ impl GooseAttack {
             unreachable!("Advanced 2 steps beyond the end of the TestPlan.")
         };
 
-        // Record details about new new TestPlan step that is starting.
-        self.metrics.history.push(TestPlanHistory::step(
-            action,
-            self.test_plan.steps[self.test_plan.current].0,
-        ));
+        // Record details about new new TestPlan step that is starting. An arrival rate test plan
+        // launches users as needed, so record how many are actually running. Also include users
+        // running scenarios with their own test plan.
+        let users = if self.test_plan.arrival_rate {
+            goose_attack_run_state.active_users
+        } else if self.has_weighted_scenarios() {
+            self.test_plan.steps[self.test_plan.current].0
+        } else {
+            0
+        } + goose_attack_run_state.scenario_users();
+        let ramp = self.test_plan.ramp(self.test_plan.current + 1);
+        self.metrics
+            .history
+            .push(TestPlanHistory::step(action, users, ramp));
 
         // Always advance the TestPlan step
         self.test_plan.current += 1;
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_plan_ramps() {
+        // Steps without a ramp are linear.
+        let test_plan = "10,1m;10,5m;0,0".parse::<TestPlan>().unwrap();
+        assert_eq!(test_plan.steps, vec![(10, 60_000), (10, 300_000), (0, 0)]);
+        assert_eq!(test_plan.ramp(0), TestPlanRamp::Linear);
+        assert_eq!(test_plan.ramp(2), TestPlanRamp::Linear);
+
+        // Ramps can be set per step, white space and case are ignored.
+        let test_plan = "10,1m,exponential; 100 , 30s , Spike ;10,5m;0,1m,s-curve"
+            .parse::<TestPlan>()
+            .unwrap();
+        assert_eq!(
+            test_plan.steps,
+            vec![(10, 60_000), (100, 30_000), (10, 300_000), (0, 60_000)]
+        );
+        assert_eq!(
+            test_plan.ramps,
+            vec![
+                TestPlanRamp::Exponential,
+                TestPlanRamp::Spike,
+                TestPlanRamp::Linear,
+                TestPlanRamp::SCurve
+            ]
+        );
+        // Steps beyond the test plan are linear.
+        assert_eq!(test_plan.ramp(4), TestPlanRamp::Linear);
+
+        // Invalid ramps are rejected.
+        assert!("10,1m,invalid".parse::<TestPlan>().is_err());
+        assert!("10,1m,".parse::<TestPlan>().is_err());
+
+        // Sine ramps require an amplitude and a period.
+        let test_plan = "10,1m;10,10m,sine(5, 1m);0,0".parse::<TestPlan>().unwrap();
+        assert_eq!(
+            test_plan.ramp(1),
+            TestPlanRamp::Sine {
+                amplitude: 5,
+                period: 60_000
+            }
+        );
+        assert_eq!(test_plan.ramp(1).to_string(), "sine(5,60s)");
+        assert_eq!(test_plan.total_users(), 15);
+        assert!("10,1m,sine".parse::<TestPlan>().is_err());
+        assert!("10,1m,sine(5)".parse::<TestPlan>().is_err());
+        assert!("10,1m,sine(5,0)".parse::<TestPlan>().is_err());
+    }
+
+    #[test]
+    fn breakpoint_step() {
+        let step = "10,30s".parse::<BreakpointStep>().unwrap();
+        assert_eq!(
+            step,
+            BreakpointStep {
+                step: 10,
+                duration: 30_000
+            }
+        );
+        assert_eq!(
+            " 5 , 1m ".parse::<BreakpointStep>().unwrap().duration,
+            60_000
+        );
+
+        // A breakpoint search is a single step that can't set a ramp.
+        assert!("10,30s,spike".parse::<BreakpointStep>().is_err());
+        assert!("10,30s;0,0".parse::<BreakpointStep>().is_err());
+        assert!("10".parse::<BreakpointStep>().is_err());
+    }
+
+    #[test]
+    fn ramp_curves() {
+        for ramp in [
+            TestPlanRamp::Linear,
+            TestPlanRamp::Exponential,
+            TestPlanRamp::Logarithmic,
+            TestPlanRamp::SCurve,
+        ] {
+            // All curves start at 0 and end at 1.
+            assert!(ramp.progress(0.0).abs() < 0.001);
+            assert!((ramp.progress(1.0) - 1.0).abs() < 0.001);
+            // The elapsed time is the inverse of the progress.
+            for elapsed in [0.1, 0.25, 0.5, 0.75, 0.9] {
+                assert!((ramp.elapsed(ramp.progress(elapsed)) - elapsed).abs() < 0.001);
+            }
+        }
+
+        // Exponential ramps start slowly, logarithmic ramps start quickly.
+        assert!(TestPlanRamp::Exponential.progress(0.5) < 0.5);
+        assert!(TestPlanRamp::Logarithmic.progress(0.5) > 0.5);
+        // S-curve ramps are symmetrical.
+        assert!((TestPlanRamp::SCurve.progress(0.5) - 0.5).abs() < 0.001);
+        // Spikes adjust all users immediately.
+        assert_eq!(TestPlanRamp::Spike.progress(0.0), 1.0);
+        assert_eq!(TestPlanRamp::Spike.elapsed(1.0), 0.0);
+    }
+
+    #[test]
+    fn users_at() {
+        let test_plan = "10,10s;10,10s;0,10s,spike".parse::<TestPlan>().unwrap();
+
+        // Linear ramp up to 10 users over 10 seconds.
+        assert_eq!(test_plan.users_at(0), 0);
+        assert_eq!(test_plan.users_at(5_000), 5);
+        // Maintain 10 users for 10 seconds.
+        assert_eq!(test_plan.users_at(10_000), 10);
+        assert_eq!(test_plan.users_at(19_999), 10);
+        // Spike down to 0 users.
+        assert_eq!(test_plan.users_at(20_000), 0);
+        assert_eq!(test_plan.users_at(60_000), 0);
+
+        // Users configured in the last step continue running.
+        let test_plan = "5,1s".parse::<TestPlan>().unwrap();
+        assert_eq!(test_plan.users_at(60_000), 5);
+
+        // Sine ramps oscillate above and below the step's users, once every 20 seconds.
+        let test_plan = "10,10s;10,40s,sine(4,20s)".parse::<TestPlan>().unwrap();
+        assert_eq!(test_plan.users_at(10_000), 10);
+        assert_eq!(test_plan.users_at(15_000), 14);
+        assert_eq!(test_plan.users_at(25_000), 6);
+        assert_eq!(test_plan.users_at(30_000), 10);
+        assert_eq!(test_plan.users_at(45_000), 6);
+        // After the step the configured number of users continue running.
+        assert_eq!(test_plan.users_at(60_000), 10);
+    }
+
+    #[test]
+    fn next_adjustment() {
+        let mut test_plan = "10,10s;10,10s;0,10s,spike".parse::<TestPlan>().unwrap();
+
+        // Linear ramps launch a user every second.
+        assert_eq!(test_plan.next_adjustment_in_ms(1, 10), 1_000);
+        assert_eq!(test_plan.next_adjustment_in_ms(5, 10), 5_000);
+
+        // Spikes stop all users immediately.
+        test_plan.current = 2;
+        assert_eq!(test_plan.next_adjustment_in_ms(1, 10), 0);
+        assert_eq!(test_plan.next_adjustment_in_ms(9, 10), 0);
+    }
+}
@@ -42,6 +42,17 @@ pub(crate) async fn user_main(
     // If normal transactions are defined, loop launching transactions until parent tells us to stop.
     if !thread_scenario.weighted_transactions.is_empty() {
         'launch_transactions: loop {
+            // When running an arrival rate test plan, the first iteration starts as soon as the
+            // user launches and each following iteration waits to be scheduled by the parent.
+            // With --arrival-requests each request is scheduled instead.
+            if thread_user.iterations > 0 && !thread_user.config.arrival_requests {
+                if let Some(arrival_channel) = thread_user.arrival_channel.clone() {
+                    if !received_arrival(&arrival_channel, &thread_receiver).await {
+                        break 'launch_transactions;
+                    }
+                }
+            }
+
             // Tracks the time it takes to loop through all Transactions when Coordinated Omission
             // Mitigation is enabled.
             thread_user.update_request_cadence(thread_number).await;
@@ -65,7 +76,8 @@ pub(crate) async fn user_main(
                 )
                 .await;
 
-                if received_exit(&thread_receiver) {
+                // Also exit if shut down while waiting to make a request.
+                if received_exit(&thread_receiver) || thread_user.arrival_exited {
                     break 'launch_transactions;
                 }
 
@@ -193,6 +205,28 @@ fn received_exit(thread_receiver: &flume::Receiver<GooseUserCommand>) -> bool {
     false
 }
 
+// Wait until the parent schedules the next scenario iteration (or request). Returns false if
+// the parent sent a GooseUserCommand::Exit message, or if the load test has ended.
+pub(crate) async fn received_arrival(
+    arrival_channel: &flume::Receiver<bool>,
+    thread_receiver: &flume::Receiver<GooseUserCommand>,
+) -> bool {
+    loop {
+        tokio::select! {
+            arrival = arrival_channel.recv_async() => {
+                return matches!(arrival, Ok(true));
+            }
+            command = thread_receiver.recv_async() => match command {
+                // GooseUserCommand::Exit received, or the parent has gone away.
+                Ok(GooseUserCommand::Exit) | Err(_) => return false,
+                Ok(command) => {
+                    debug!("ignoring unexpected GooseUserCommand: {:?}", command);
+                }
+            }
+        }
+    }
+}
+
 // Send scenario metric to parent and logger when enabled.
 async fn record_scenario(
     thread_scenario: &Scenario,
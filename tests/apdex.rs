@@ -0,0 +1,115 @@
+/// Validate that requests are scored against the configured Apdex targets.
+use httpmock::{Method::GET, Mock, MockServer};
+use serial_test::serial;
+use std::time::Duration;
+
+mod common;
+
+use goose::config::GooseConfiguration;
+use goose::prelude::*;
+
+// Paths used in load tests performed during these tests.
+const INDEX_PATH: &str = "/";
+const SLOW_PATH: &str = "/slow";
+
+// Indexes to the above paths.
+const INDEX_KEY: usize = 0;
+const SLOW_KEY: usize = 1;
+
+// Files used in load tests performed during these tests.
+const REPORT_FILE: &str = "test-apdex-report.html";
+
+// Test transaction.
+pub async fn get_index(user: &mut GooseUser) -> TransactionResult {
+    let _goose = user.get(INDEX_PATH).await?;
+    Ok(())
+}
+
+// Test transaction.
+pub async fn get_slow(user: &mut GooseUser) -> TransactionResult {
+    let _goose = user.get(SLOW_PATH).await?;
+    Ok(())
+}
+
+// All tests in this file run against common endpoints.
+fn setup_mock_server_endpoints(server: &MockServer) -> Vec<Mock<'_>> {
+    vec![
+        // First set up INDEX_PATH, store in vector at INDEX_KEY.
+        server.mock(|when, then| {
+            when.method(GET).path(INDEX_PATH);
+            then.status(200);
+        }),
+        // Next set up SLOW_PATH, store in vector at SLOW_KEY.
+        server.mock(|when, then| {
+            when.method(GET).path(SLOW_PATH);
+            then.status(200).delay(Duration::from_millis(100));
+        }),
+    ]
+}
+
+// Build appropriate configuration for these tests.
+fn common_build_configuration(server: &MockServer, custom: Vec<&str>) -> GooseConfiguration {
+    let mut configuration = vec!["--users", "2", "--hatch-rate", "2", "--no-reset-metrics"];
+    configuration.extend_from_slice(&custom);
+    common::build_configuration(server, configuration)
+}
+
+// Build a load test that loads a fast and a slow page.
+fn build_load_test(configuration: GooseConfiguration) -> GooseAttack {
+    common::build_load_test(
+        configuration,
+        vec![scenario!("LoadTest")
+            .register_transaction(transaction!(get_index))
+            .register_transaction(transaction!(get_slow))],
+        None,
+        None,
+    )
+}
+
+#[tokio::test]
+#[serial]
+/// Score, display, serialize and report how satisfying response times were.
+async fn test_apdex() {
+    // Be sure there's no report file from a previous test.
+    common::cleanup_files(vec![REPORT_FILE]);
+
+    let server = MockServer::start();
+    let mock_endpoints = setup_mock_server_endpoints(&server);
+    // The slow page frustrates users, and the index satisfies them.
+    let goose_metrics = common::run_load_test(
+        build_load_test(common_build_configuration(
+            &server,
+            vec!["--apdex", "1000;/slow:10", "--report-file", REPORT_FILE],
+        )),
+        None,
+    )
+    .await;
+    assert!(mock_endpoints[INDEX_KEY].hits() > 0);
+    assert!(mock_endpoints[SLOW_KEY].hits() > 0);
+
+    // Each request and all requests aggregated together are scored.
+    let scores = goose_metrics.apdex_scores();
+    assert!(scores.len() == 3);
+    assert!(scores["GET /"].target == Some(1000));
+    assert!(scores["GET /"].score == 1.0);
+    assert!(scores["GET /slow"].target == Some(10));
+    assert!(scores["GET /slow"].score == 0.0);
+    let aggregated = &scores["Aggregated"];
+    assert!(aggregated.score > 0.0 && aggregated.score < 1.0);
+
+    // The Apdex score is displayed with the response times.
+    let metrics = goose_metrics.to_string();
+    assert!(metrics.contains("Apdex"));
+
+    // Apdex targets and scores are serialized.
+    let json: serde_json::Value =
+        serde_json::from_str(&serde_json::to_string(&goose_metrics).unwrap()).unwrap();
+    assert!(json["apdex"]["targets"].as_array().unwrap().len() == 2);
+    assert!(json["apdex_scores"]["GET /"]["score"].as_f64().unwrap() == 1.0);
+
+    // The Apdex score is included in the html report.
+    let report = std::fs::read_to_string(REPORT_FILE).expect("failed to read report file");
+    assert!(report.contains("<th>Apdex</th>"));
+
+    common::cleanup_files(vec![REPORT_FILE]);
+}
@@ -0,0 +1,234 @@
+use httpmock::{Method::GET, Mock, MockServer};
+use serial_test::serial;
+use std::time::Duration;
+
+mod common;
+
+use goose::config::GooseConfiguration;
+use goose::prelude::*;
+
+// Paths used in load tests performed during these tests.
+const INDEX_PATH: &str = "/";
+const SLOW_PATH: &str = "/slow";
+
+// Indexes to the above paths.
+const INDEX_KEY: usize = 0;
+const SLOW_KEY: usize = 1;
+
+// Start 5 iterations per second after ramping up for 1 second, maintain for 2 seconds, then stop.
+const ARRIVAL_RATE: &str = "5,1;5,2;0,0";
+
+// Start 10 iterations per second, then drop to 1 iteration per second.
+const ARRIVAL_RATE_DROP: &str = "10,0;10,2;1,0;1,3;0,0";
+
+// Test transaction.
+pub async fn get_index(user: &mut GooseUser) -> TransactionResult {
+    let _goose = user.get(INDEX_PATH).await?;
+    Ok(())
+}
+
+// Test transaction.
+pub async fn get_slow(user: &mut GooseUser) -> TransactionResult {
+    let _goose = user.get(SLOW_PATH).await?;
+    Ok(())
+}
+
+// All tests in this file run against common endpoints.
+fn setup_mock_server_endpoints(server: &MockServer) -> Vec<Mock<'_>> {
+    vec![
+        // First set up INDEX_PATH, store in vector at INDEX_KEY.
+        server.mock(|when, then| {
+            when.method(GET).path(INDEX_PATH);
+            then.status(200);
+        }),
+        // Next set up SLOW_PATH, store in vector at SLOW_KEY.
+        server.mock(|when, then| {
+            when.method(GET).path(SLOW_PATH);
+            then.status(200).delay(Duration::from_secs(2));
+        }),
+    ]
+}
+
+// Build appropriate configuration for these tests.
+fn common_build_configuration(
+    server: &MockServer,
+    arrival_rate: &str,
+    users: usize,
+) -> GooseConfiguration {
+    let mut configuration = common::build_configuration(
+        server,
+        vec![
+            "--arrival-rate",
+            arrival_rate,
+            "--users",
+            &users.to_string(),
+        ],
+    );
+
+    // Do not set --run-time or --hatch-rate with --arrival-rate.
+    configuration.run_time = "".to_string();
+    configuration.hatch_rate = None;
+
+    configuration
+}
+
+#[tokio::test]
+#[serial]
+/// Confirm an arrival rate test plan starts scenario iterations at the configured rate.
+async fn test_arrival_rate() {
+    // Start the mock server.
+    let server = MockServer::start();
+
+    // Setup the endpoints needed for this test on the mock server.
+    let mock_endpoints = setup_mock_server_endpoints(&server);
+
+    // Run the Goose Attack.
+    let goose_metrics = common::run_load_test(
+        common::build_load_test(
+            common_build_configuration(&server, ARRIVAL_RATE, 5),
+            vec![scenario!("LoadTest").register_transaction(transaction!(get_index))],
+            None,
+            None,
+        ),
+        None,
+    )
+    .await;
+
+    let arrival_rate = goose_metrics
+        .arrival_rate
+        .expect("arrival rate metrics not collected");
+
+    // Ramping up to 5 iterations per second over 1 second then maintaining that rate for
+    // 2 seconds schedules roughly 12 iterations.
+    assert!(arrival_rate.scheduled >= 10);
+    assert!(arrival_rate.scheduled <= 14);
+
+    // The server responds quickly, so no iterations are dropped.
+    assert!(arrival_rate.dropped == 0);
+    assert!(arrival_rate.started == arrival_rate.scheduled);
+
+    // Each started iteration makes one request.
+    assert!(mock_endpoints[INDEX_KEY].hits() == arrival_rate.started);
+
+    // A fast server doesn't require launching all available users.
+    assert!(goose_metrics.maximum_users <= 5);
+}
+
+#[tokio::test]
+#[serial]
+/// Confirm an arrival rate test plan drops iterations when all users are busy.
+async fn test_arrival_rate_dropped() {
+    // Start the mock server.
+    let server = MockServer::start();
+
+    // Setup the endpoints needed for this test on the mock server.
+    let mock_endpoints = setup_mock_server_endpoints(&server);
+
+    // Run the Goose Attack.
+    let goose_metrics = common::run_load_test(
+        common::build_load_test(
+            common_build_configuration(&server, ARRIVAL_RATE, 2),
+            vec![scenario!("LoadTest").register_transaction(transaction!(get_slow))],
+            None,
+            None,
+        ),
+        None,
+    )
+    .await;
+
+    let arrival_rate = goose_metrics
+        .arrival_rate
+        .expect("arrival rate metrics not collected");
+
+    // Iterations are still scheduled at the configured rate, regardless of how slowly the
+    // server responds.
+    assert!(arrival_rate.scheduled >= 10);
+    assert!(arrival_rate.scheduled <= 14);
+
+    // Only 2 users could be launched, and each request takes 2 seconds, so most iterations
+    // are dropped.
+    assert!(goose_metrics.maximum_users == 2);
+    assert!(arrival_rate.started < arrival_rate.scheduled);
+    assert!(arrival_rate.started + arrival_rate.dropped == arrival_rate.scheduled);
+    assert!(mock_endpoints[SLOW_KEY].hits() <= arrival_rate.started);
+}
+
+#[tokio::test]
+#[serial]
+/// Confirm --arrival-requests starts requests instead of scenario iterations at the
+/// configured rate.
+async fn test_arrival_requests() {
+    // Start the mock server.
+    let server = MockServer::start();
+
+    // Setup the endpoints needed for this test on the mock server.
+    let mock_endpoints = setup_mock_server_endpoints(&server);
+
+    // Each iteration makes two requests.
+    let mut configuration = common_build_configuration(&server, ARRIVAL_RATE, 5);
+    configuration.arrival_requests = true;
+
+    // Run the Goose Attack.
+    let goose_metrics = common::run_load_test(
+        common::build_load_test(
+            configuration,
+            vec![scenario!("LoadTest")
+                .register_transaction(transaction!(get_index))
+                .register_transaction(transaction!(get_index))],
+            None,
+            None,
+        ),
+        None,
+    )
+    .await;
+
+    let arrival_rate = goose_metrics
+        .arrival_rate
+        .expect("arrival rate metrics not collected");
+    assert!(arrival_rate.requests);
+
+    // Ramping up to 5 requests per second over 1 second then maintaining that rate for
+    // 2 seconds schedules roughly 12 requests.
+    assert!(arrival_rate.scheduled >= 10);
+    assert!(arrival_rate.scheduled <= 14);
+    assert!(arrival_rate.dropped == 0);
+
+    // Each started arrival makes one request, not one iteration of two requests.
+    assert!(mock_endpoints[INDEX_KEY].hits() == arrival_rate.started);
+}
+
+#[tokio::test]
+#[serial]
+/// Confirm idle users are shut down when the arrival rate drops.
+async fn test_arrival_rate_drop() {
+    // Start the mock server.
+    let server = MockServer::start();
+
+    // Setup the endpoints needed for this test on the mock server.
+    let _mock_endpoints = setup_mock_server_endpoints(&server);
+
+    // Each iteration takes 2 seconds.
+    let configuration = common_build_configuration(&server, ARRIVAL_RATE_DROP, 30);
+
+    // Run the Goose Attack.
+    let goose_metrics = common::run_load_test(
+        common::build_load_test(
+            configuration,
+            vec![scenario!("LoadTest").register_transaction(transaction!(get_slow))],
+            None,
+            None,
+        ),
+        None,
+    )
+    .await;
+
+    // Roughly 20 users are needed to sustain 10 iterations per second, but after dropping to 1
+    // iteration per second the idle users are shut down.
+    let users: Vec<usize> = goose_metrics
+        .history
+        .iter()
+        .map(|history| history.users)
+        .collect();
+    assert!(goose_metrics.maximum_users >= 15);
+    assert!(users[users.len() - 2] < goose_metrics.maximum_users / 2);
+}
@@ -0,0 +1,215 @@
+/// Validate that the bytes sent and received by each request are tracked.
+use httpmock::{
+    Method::{GET, POST},
+    Mock, MockServer,
+};
+use serial_test::serial;
+use std::io::Write;
+
+mod common;
+
+use goose::config::GooseConfiguration;
+use goose::prelude::*;
+
+// Paths used in load tests performed during these tests.
+const INDEX_PATH: &str = "/";
+const POST_PATH: &str = "/post";
+const GZIP_PATH: &str = "/gzip";
+
+// Indexes to the above paths.
+const INDEX_KEY: usize = 0;
+const POST_KEY: usize = 1;
+const GZIP_KEY: usize = 2;
+
+// Bodies sent and received by the above paths.
+const INDEX_BODY: &str = "<html><body>Hello, world!</body></html>";
+const POST_BODY: &str = "name=goose";
+
+// Files used in load tests performed during these tests.
+const REPORT_FILE: &str = "test-bandwidth-report.html";
+
+// Test transaction.
+pub async fn get_index(user: &mut GooseUser) -> TransactionResult {
+    let goose = user.get(INDEX_PATH).await?;
+    // The response body can still be read after it was counted.
+    let body = goose.response.unwrap().text().await.unwrap();
+    assert_eq!(body, INDEX_BODY);
+    Ok(())
+}
+
+// Test transaction.
+pub async fn get_gzip(user: &mut GooseUser) -> TransactionResult {
+    let goose = user.get(GZIP_PATH).await?;
+    // The response body was decompressed after it was counted.
+    let body = goose.response.unwrap().text().await.unwrap();
+    assert_eq!(body, INDEX_BODY.repeat(10));
+    Ok(())
+}
+
+// Test transaction.
+pub async fn post_form(user: &mut GooseUser) -> TransactionResult {
+    let _goose = user.post(POST_PATH, POST_BODY).await?;
+    Ok(())
+}
+
+// All tests in this file run against common endpoints.
+fn setup_mock_server_endpoints(server: &MockServer) -> Vec<Mock<'_>> {
+    vec![
+        // First set up INDEX_PATH, store in vector at INDEX_KEY.
+        server.mock(|when, then| {
+            when.method(GET).path(INDEX_PATH);
+            then.status(200).body(INDEX_BODY);
+        }),
+        // Next set up POST_PATH, store in vector at POST_KEY.
+        server.mock(|when, then| {
+            when.method(POST).path(POST_PATH);
+            then.status(200);
+        }),
+        // Next set up GZIP_PATH, store in vector at GZIP_KEY.
+        server.mock(|when, then| {
+            when.method(GET)
+                .path(GZIP_PATH)
+                .header("accept-encoding", "gzip");
+            then.status(200)
+                .header("content-encoding", "gzip")
+                .body(gzip_body());
+        }),
+    ]
+}
+
+// A gzip-compressed response body.
+fn gzip_body() -> Vec<u8> {
+    let mut encoder = flate2::write::GzEncoder::new(Vec::new(), flate2::Compression::default());
+    encoder.write_all(INDEX_BODY.repeat(10).as_bytes()).unwrap();
+    encoder.finish().unwrap()
+}
+
+// Build appropriate configuration for these tests.
+fn common_build_configuration(server: &MockServer, custom: Vec<&str>) -> GooseConfiguration {
+    let mut configuration = vec!["--users", "2", "--hatch-rate", "2", "--no-reset-metrics"];
+    configuration.extend_from_slice(&custom);
+    common::build_configuration(server, configuration)
+}
+
+// Build a load test that loads the index and posts a form.
+fn build_load_test(configuration: GooseConfiguration) -> GooseAttack {
+    common::build_load_test(
+        configuration,
+        vec![scenario!("LoadTest")
+            .register_transaction(transaction!(get_index))
+            .register_transaction(transaction!(post_form))],
+        None,
+        None,
+    )
+}
+
+#[tokio::test]
+#[serial]
+/// Count, display, serialize and report the bytes sent and received.
+async fn test_bandwidth() {
+    // Be sure there's no report file from a previous test.
+    common::cleanup_files(vec![REPORT_FILE]);
+
+    let server = MockServer::start();
+    let mock_endpoints = setup_mock_server_endpoints(&server);
+    let goose_metrics = common::run_load_test(
+        build_load_test(common_build_configuration(
+            &server,
+            vec!["--report-file", REPORT_FILE],
+        )),
+        None,
+    )
+    .await;
+    let index_hits = mock_endpoints[INDEX_KEY].hits();
+    let post_hits = mock_endpoints[POST_KEY].hits();
+    assert!(index_hits > 0);
+    assert!(post_hits > 0);
+
+    // The bodies of all requests and responses were counted.
+    let index = goose_metrics.requests.get("GET /").unwrap();
+    assert!(index.request_bytes == 0);
+    assert!(index.response_bytes == index_hits * INDEX_BODY.len());
+    assert!(index.compressed_bytes == index.response_bytes);
+    let post = goose_metrics.requests.get("POST /post").unwrap();
+    assert!(post.request_bytes == post_hits * POST_BODY.len());
+    assert!(post.response_bytes == 0);
+
+    // Bandwidth is displayed.
+    let metrics = goose_metrics.to_string();
+    assert!(metrics.contains("PER REQUEST BANDWIDTH"));
+
+    // Bandwidth of each request, and of all requests, is serialized.
+    let json: serde_json::Value =
+        serde_json::from_str(&serde_json::to_string(&goose_metrics).unwrap()).unwrap();
+    let bandwidth = json["bandwidth"].as_object().unwrap();
+    assert!(bandwidth.len() == 3);
+    assert!(
+        bandwidth["Aggregated"]["response_bytes"].as_u64().unwrap()
+            == (index_hits * INDEX_BODY.len()) as u64
+    );
+    assert!(
+        bandwidth["Aggregated"]["response_bytes_per_second"]
+            .as_f64()
+            .unwrap()
+            > 0.0
+    );
+
+    // Bandwidth is included in the html report.
+    let report = std::fs::read_to_string(REPORT_FILE).expect("failed to read report file");
+    assert!(report.contains("<h2>Bandwidth Metrics</h2>"));
+    assert!(report.contains(r#"id="graph-bandwidth""#));
+
+    common::cleanup_files(vec![REPORT_FILE]);
+}
+
+#[tokio::test]
+#[serial]
+/// Count gzip-encoded responses both before and after they were decompressed, when response
+/// bodies are read as requests are made.
+async fn test_bandwidth_gzip() {
+    let server = MockServer::start();
+    let mock_endpoints = setup_mock_server_endpoints(&server);
+    let goose_metrics = common::run_load_test(
+        common::build_load_test(
+            common_build_configuration(&server, vec!["--buffer-responses"]),
+            vec![scenario!("LoadTest").register_transaction(transaction!(get_gzip))],
+            None,
+            None,
+        ),
+        None,
+    )
+    .await;
+    let gzip_hits = mock_endpoints[GZIP_KEY].hits();
+    assert!(gzip_hits > 0);
+
+    let gzip = goose_metrics.requests.get("GET /gzip").unwrap();
+    assert!(gzip.response_bytes == gzip_hits * INDEX_BODY.len() * 10);
+    assert!(gzip.compressed_bytes == gzip_hits * gzip_body().len());
+    assert!(gzip.compressed_bytes < gzip.response_bytes);
+    let bandwidth = goose_metrics.bandwidth();
+    assert!(bandwidth["GET /gzip"].compressed_bytes == gzip.compressed_bytes);
+}
+
+#[tokio::test]
+#[serial]
+/// Gzip-encoded responses are streamed and decompressed by Reqwest by default, so their size
+/// isn't known.
+async fn test_bandwidth_gzip_streamed() {
+    let server = MockServer::start();
+    let mock_endpoints = setup_mock_server_endpoints(&server);
+    let goose_metrics = common::run_load_test(
+        common::build_load_test(
+            common_build_configuration(&server, vec![]),
+            vec![scenario!("LoadTest").register_transaction(transaction!(get_gzip))],
+            None,
+            None,
+        ),
+        None,
+    )
+    .await;
+    assert!(mock_endpoints[GZIP_KEY].hits() > 0);
+
+    let gzip = goose_metrics.requests.get("GET /gzip").unwrap();
+    assert!(gzip.response_bytes == 0);
+    assert!(gzip.compressed_bytes == 0);
+}
@@ -0,0 +1,178 @@
+/// Validate that metrics can be saved as a baseline, and compared to by a later load test.
+use httpmock::{Method::GET, Mock, MockServer};
+use serial_test::serial;
+use std::time::Duration;
+
+mod common;
+
+use goose::config::GooseConfiguration;
+use goose::prelude::*;
+
+// Paths used in load tests performed during these tests.
+const INDEX_PATH: &str = "/";
+const ABOUT_PATH: &str = "/about.html";
+
+// Indexes to the above paths.
+const INDEX_KEY: usize = 0;
+const ABOUT_KEY: usize = 1;
+
+// Files used in load tests performed during these tests.
+const BASELINE_FILE: &str = "test-baseline.json";
+
+// Test transaction.
+pub async fn get_index(user: &mut GooseUser) -> TransactionResult {
+    let _goose = user.get(INDEX_PATH).await?;
+    Ok(())
+}
+
+// Test transaction.
+pub async fn get_about(user: &mut GooseUser) -> TransactionResult {
+    let _goose = user.get(ABOUT_PATH).await?;
+    Ok(())
+}
+
+// All tests in this file run against common endpoints, optionally slowing down the index.
+fn setup_mock_server_endpoints(server: &MockServer, delay: u64) -> Vec<Mock<'_>> {
+    vec![
+        // First set up INDEX_PATH, store in vector at INDEX_KEY.
+        server.mock(|when, then| {
+            when.method(GET).path(INDEX_PATH);
+            then.status(200).delay(Duration::from_millis(delay));
+        }),
+        // Next set up ABOUT_PATH, store in vector at ABOUT_KEY.
+        server.mock(|when, then| {
+            when.method(GET).path(ABOUT_PATH);
+            then.status(200);
+        }),
+    ]
+}
+
+// Build appropriate configuration for these tests.
+fn common_build_configuration(server: &MockServer, custom: Vec<&str>) -> GooseConfiguration {
+    let mut configuration = vec!["--users", "2", "--hatch-rate", "2", "--run-time", "2"];
+    configuration.extend_from_slice(&custom);
+    common::build_configuration(server, configuration)
+}
+
+// Build a load test that loads both the index and the about page.
+fn build_load_test(configuration: GooseConfiguration) -> GooseAttack {
+    common::build_load_test(
+        configuration,
+        vec![scenario!("LoadTest")
+            .register_transaction(transaction!(get_index))
+            .register_transaction(transaction!(get_about))
+            .set_wait_time(Duration::from_millis(50), Duration::from_millis(50))
+            .unwrap()],
+        None,
+        None,
+    )
+}
+
+#[tokio::test]
+#[serial]
+/// Save a baseline, then flag requests that got slower in a later load test.
+async fn test_baseline() {
+    // Be sure there's no baseline file from a previous test.
+    common::cleanup_files(vec![BASELINE_FILE]);
+
+    // Run a first load test against fast endpoints, saving a baseline.
+    let server = MockServer::start();
+    let mock_endpoints = setup_mock_server_endpoints(&server, 0);
+    let baseline_metrics = common::run_load_test(
+        build_load_test(common_build_configuration(
+            &server,
+            vec!["--baseline-file", BASELINE_FILE],
+        )),
+        None,
+    )
+    .await;
+    assert!(mock_endpoints[INDEX_KEY].hits() > 0);
+    assert!(mock_endpoints[ABOUT_KEY].hits() > 0);
+    assert!(std::path::Path::new(BASELINE_FILE).exists());
+
+    // There's nothing to compare to in the first load test.
+    assert!(baseline_metrics.baseline.is_empty());
+
+    // Run a second load test where the index is much slower, comparing to the baseline.
+    let server = MockServer::start();
+    let mock_endpoints = setup_mock_server_endpoints(&server, 200);
+    let goose_metrics = common::run_load_test(
+        build_load_test(common_build_configuration(
+            &server,
+            vec![
+                "--baseline",
+                BASELINE_FILE,
+                "--baseline-tolerance",
+                "20",
+                "--percentiles",
+                "50,99.9",
+            ],
+        )),
+        None,
+    )
+    .await;
+    assert!(mock_endpoints[INDEX_KEY].hits() > 0);
+    assert!(mock_endpoints[ABOUT_KEY].hits() > 0);
+
+    // Each metric of both requests and the aggregate were compared to the baseline, including
+    // only the configured percentiles.
+    assert!(goose_metrics.baseline.len() == 15);
+    let delta = |request: Option<&str>, metric: &str| {
+        goose_metrics
+            .baseline
+            .iter()
+            .find(|delta| delta.request.as_deref() == request && delta.metric.to_string() == metric)
+            .expect("baseline delta not found")
+    };
+
+    // The index got slower, and is flagged as a regression.
+    let index = delta(Some("GET /"), "avg");
+    assert!(index.baseline.unwrap() < 200.0);
+    assert!(index.current.unwrap() >= 200.0);
+    assert!(index.change().unwrap() > 20.0);
+    assert!(index.regression);
+    assert!(delta(Some("GET /"), "rps").regression);
+    assert!(delta(Some("GET /"), "p99.9").regression);
+    assert!(!goose_metrics
+        .baseline
+        .iter()
+        .any(|delta| delta.metric.to_string() == "p95"));
+
+    // Nothing failed in either load test.
+    assert!(!delta(Some("GET /"), "error-rate").regression);
+    assert!(!delta(None, "error-rate").regression);
+
+    common::cleanup_files(vec![BASELINE_FILE]);
+}
+
+#[tokio::test]
+#[serial]
+/// A baseline file that doesn't exist or can't be parsed is rejected.
+async fn test_baseline_invalid() {
+    let server = MockServer::start();
+    let _mock_endpoints = setup_mock_server_endpoints(&server, 0);
+
+    // The baseline file doesn't exist.
+    let goose_attack = build_load_test(common_build_configuration(
+        &server,
+        vec!["--baseline", BASELINE_FILE],
+    ));
+    assert!(goose_attack.execute().await.is_err());
+
+    // The baseline file isn't json-serialized metrics.
+    std::fs::write(BASELINE_FILE, "not a baseline").expect("failed to write file");
+    let goose_attack = build_load_test(common_build_configuration(
+        &server,
+        vec!["--baseline", BASELINE_FILE],
+    ));
+    assert!(goose_attack.execute().await.is_err());
+
+    // A tolerance requires a baseline.
+    let goose_attack = build_load_test(common_build_configuration(
+        &server,
+        vec!["--baseline-tolerance", "5"],
+    ));
+    assert!(goose_attack.execute().await.is_err());
+
+    common::cleanup_files(vec![BASELINE_FILE]);
+}
@@ -0,0 +1,264 @@
+/// Validate that a breakpoint search steps up the load until a limit is hit.
+use httpmock::{Method::GET, Mock, MockServer};
+use serial_test::serial;
+use std::time::Duration;
+
+mod common;
+
+use goose::config::GooseConfiguration;
+use goose::prelude::*;
+
+// Paths used in load tests performed during these tests.
+const INDEX_PATH: &str = "/";
+const ERROR_PATH: &str = "/error";
+const SLOW_PATH: &str = "/slow";
+
+// Indexes to the above paths.
+const INDEX_KEY: usize = 0;
+const ERROR_KEY: usize = 1;
+
+// Test transaction.
+pub async fn get_index(user: &mut GooseUser) -> TransactionResult {
+    let _goose = user.get(INDEX_PATH).await?;
+    Ok(())
+}
+
+// Test transaction, only the first two users launched load the index without errors.
+pub async fn get_index_or_error(user: &mut GooseUser) -> TransactionResult {
+    if user.weighted_users_index < 2 {
+        let _goose = user.get(INDEX_PATH).await?;
+    } else {
+        let _goose = user.get(ERROR_PATH).await?;
+    }
+    Ok(())
+}
+
+// Test transaction.
+pub async fn get_slow(user: &mut GooseUser) -> TransactionResult {
+    let _goose = user.get(SLOW_PATH).await?;
+    Ok(())
+}
+
+// All tests in this file run against common endpoints.
+fn setup_mock_server_endpoints(server: &MockServer) -> Vec<Mock<'_>> {
+    vec![
+        // First set up INDEX_PATH, store in vector at INDEX_KEY.
+        server.mock(|when, then| {
+            when.method(GET).path(INDEX_PATH);
+            then.status(200);
+        }),
+        // Next set up ERROR_PATH, store in vector at ERROR_KEY.
+        server.mock(|when, then| {
+            when.method(GET).path(ERROR_PATH);
+            then.status(500);
+        }),
+        // Next set up SLOW_PATH, store in vector at SLOW_KEY.
+        server.mock(|when, then| {
+            when.method(GET).path(SLOW_PATH);
+            then.status(200).delay(Duration::from_millis(500));
+        }),
+    ]
+}
+
+// Build appropriate configuration for these tests.
+fn common_build_configuration(server: &MockServer, custom: Vec<&str>) -> GooseConfiguration {
+    let mut configuration = common::build_configuration(server, custom);
+
+    // Do not set --run-time or --hatch-rate with --breakpoint.
+    configuration.run_time = "".to_string();
+    configuration.hatch_rate = None;
+
+    configuration
+}
+
+#[tokio::test]
+#[serial]
+/// Step up users until the configured number of users are running.
+async fn test_breakpoint_maximum_users() {
+    // Start the mock server.
+    let server = MockServer::start();
+
+    // Setup the endpoints needed for this test on the mock server.
+    let mock_endpoints = setup_mock_server_endpoints(&server);
+
+    // Run the Goose Attack.
+    let goose_metrics = common::run_load_test(
+        common::build_load_test(
+            common_build_configuration(&server, vec!["--breakpoint", "1,1s", "--users", "3"]),
+            vec![scenario!("LoadTest")
+                .register_transaction(transaction!(get_index))
+                .set_wait_time(Duration::from_millis(100), Duration::from_millis(100))
+                .unwrap()],
+            None,
+            None,
+        ),
+        None,
+    )
+    .await;
+
+    assert!(mock_endpoints[INDEX_KEY].hits() > 0);
+
+    let breakpoint = goose_metrics
+        .breakpoint
+        .expect("breakpoint metrics not collected");
+
+    // Each level of users was probed, and all were healthy.
+    let levels: Vec<usize> = breakpoint.probes.iter().map(|probe| probe.level).collect();
+    assert!(levels == vec![1, 2, 3]);
+    assert!(breakpoint.capacity == Some(3));
+    assert!(breakpoint
+        .reason
+        .expect("breakpoint search didn't stop")
+        .contains("maximum of 3 users"));
+    assert!(!breakpoint.arrival_rate);
+
+    // Each probe was recorded in the test plan history.
+    for users in 1..=3 {
+        assert!(goose_metrics.history.iter().any(|step| step.users == users));
+    }
+    assert!(goose_metrics.maximum_users == 3);
+}
+
+#[tokio::test]
+#[serial]
+/// Stop stepping up users when the error rate is too high.
+async fn test_breakpoint_error_rate() {
+    // Start the mock server.
+    let server = MockServer::start();
+
+    // Setup the endpoints needed for this test on the mock server.
+    let mock_endpoints = setup_mock_server_endpoints(&server);
+
+    // Run the Goose Attack.
+    let goose_metrics = common::run_load_test(
+        common::build_load_test(
+            common_build_configuration(
+                &server,
+                vec![
+                    "--breakpoint",
+                    "2,1s",
+                    "--breakpoint-error-rate",
+                    "10",
+                    "--users",
+                    "10",
+                ],
+            ),
+            vec![scenario!("LoadTest")
+                .register_transaction(transaction!(get_index_or_error))
+                .set_wait_time(Duration::from_millis(100), Duration::from_millis(100))
+                .unwrap()],
+            None,
+            None,
+        ),
+        None,
+    )
+    .await;
+
+    assert!(mock_endpoints[INDEX_KEY].hits() > 0);
+    assert!(mock_endpoints[ERROR_KEY].hits() > 0);
+
+    let breakpoint = goose_metrics
+        .breakpoint
+        .expect("breakpoint metrics not collected");
+
+    // The first 2 users don't generate errors, but with 4 users half the requests fail.
+    assert!(breakpoint.probes.len() == 2);
+    assert!(breakpoint.probes[0].error_rate == 0.0);
+    assert!(breakpoint.probes[1].error_rate > 10.0);
+    assert!(breakpoint.capacity == Some(2));
+    assert!(breakpoint
+        .reason
+        .expect("breakpoint search didn't stop")
+        .contains("error rate"));
+
+    // The search stopped long before the maximum number of users was reached.
+    assert!(goose_metrics.maximum_users == 4);
+}
+
+#[tokio::test]
+#[serial]
+/// Stop stepping up the arrival rate when responses are too slow.
+async fn test_breakpoint_arrival_rate_response_time() {
+    // Start the mock server.
+    let server = MockServer::start();
+
+    // Setup the endpoints needed for this test on the mock server.
+    let _mock_endpoints = setup_mock_server_endpoints(&server);
+
+    // Run the Goose Attack.
+    let goose_metrics = common::run_load_test(
+        common::build_load_test(
+            common_build_configuration(
+                &server,
+                vec![
+                    "--breakpoint",
+                    "5,1s",
+                    "--breakpoint-arrival-rate",
+                    "--breakpoint-response-time",
+                    "100",
+                    "--users",
+                    "10",
+                ],
+            ),
+            vec![scenario!("LoadTest").register_transaction(transaction!(get_slow))],
+            None,
+            None,
+        ),
+        None,
+    )
+    .await;
+
+    let breakpoint = goose_metrics
+        .breakpoint
+        .expect("breakpoint metrics not collected");
+
+    // The very first level was too slow, so no sustainable capacity was found.
+    assert!(breakpoint.arrival_rate);
+    assert!(breakpoint.probes.len() == 1);
+    assert!(breakpoint.probes[0].level == 5);
+    assert!(breakpoint.probes[0].response_time_95 >= 500);
+    assert!(breakpoint.capacity.is_none());
+    assert!(breakpoint
+        .reason
+        .expect("breakpoint search didn't stop")
+        .contains("response time"));
+}
+
+#[tokio::test]
+#[serial]
+/// Invalid breakpoint searches are rejected.
+async fn test_breakpoint_invalid() {
+    // Start the mock server.
+    let server = MockServer::start();
+
+    // A breakpoint search must step up the load, and can't be combined with a test plan or run
+    // time.
+    for custom in [
+        vec!["--breakpoint", "0,30s"],
+        vec!["--breakpoint", "10,30s", "--test-plan", "10,30s"],
+        vec!["--breakpoint", "10,30s", "--run-time", "30"],
+        vec!["--breakpoint-error-rate", "5"],
+    ] {
+        let mut configuration = common::build_configuration(&server, custom.clone());
+        if !custom.contains(&"--run-time") {
+            configuration.run_time = "".to_string();
+        }
+        configuration.hatch_rate = None;
+        let goose_attack = common::build_load_test(
+            configuration,
+            vec![scenario!("LoadTest").register_transaction(transaction!(get_index))],
+            None,
+            None,
+        );
+        assert!(goose_attack.execute().await.is_err());
+    }
+
+    // A breakpoint search is a single step, and always steps up the load all at once so it
+    // can't set a ramp.
+    for breakpoint in ["10,30s;0,0", "10,30s,exponential"] {
+        assert!(GooseAttack::initialize()
+            .unwrap()
+            .set_default(GooseDefault::Breakpoint, breakpoint)
+            .is_err());
+    }
+}
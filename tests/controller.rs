@@ -45,8 +45,8 @@ enum TestType {
 
 // State machine for tracking Controller state during tests.
 struct TestState {
-    // A buffer for the telnet Controller.
-    buf: [u8; 2048],
+    // A buffer for the telnet Controller, large enough to hold the json-serialized metrics.
+    buf: Vec<u8>,
     // Track iterations through ControllerCommands.
     position: usize,
     // Track the steps within a given iteration.
@@ -574,6 +574,21 @@ async fn run_standalone_test(test_type: TestType) {
                         }
                     }
                 }
+                ControllerCommand::MetricsPrometheus => {
+                    match test_state.step {
+                        // Request the running metrics in Prometheus format.
+                        0 => {
+                            make_request(&mut test_state, "metrics-prometheus\r\n").await;
+                        }
+                        // Confirm the metrics are returned in Prometheus format.
+                        _ => {
+                            assert!(response.contains("# TYPE goose_requests_total counter"));
+
+                            // Move onto the next command.
+                            test_state = update_state(Some(test_state), &test_type).await;
+                        }
+                    }
+                }
                 ControllerCommand::Start => {
                     match test_state.step {
                         // Try to stop an idle load test.
@@ -671,7 +686,7 @@ async fn run_standalone_test(test_type: TestType) {
                 }
             }
             // Flush the buffer.
-            test_state.buf = [0; 2048];
+            test_state.buf = vec![0; 16384];
 
             // Give the parent process time to catch up.
             tokio::time::sleep(time::Duration::from_millis(100)).await;
@@ -716,6 +731,7 @@ async fn update_state(test_state: Option<TestState>, test_type: &TestType) -> Te
         ControllerCommand::ConfigJson,
         ControllerCommand::Metrics,
         ControllerCommand::MetricsJson,
+        ControllerCommand::MetricsPrometheus,
         ControllerCommand::Stop,
         ControllerCommand::Shutdown,
     ];
@@ -765,7 +781,7 @@ async fn update_state(test_state: Option<TestState>, test_type: &TestType) -> Te
         };
 
         TestState {
-            buf: [0; 2048],
+            buf: vec![0; 16384],
             position: 0,
             step: 0,
             command: commands_to_test.first().unwrap().clone(),
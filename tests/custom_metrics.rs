@@ -0,0 +1,127 @@
+/// Validate that custom metrics recorded by transactions are aggregated and reported.
+use httpmock::{Method::GET, Mock, MockServer};
+use serial_test::serial;
+use std::time::Duration;
+
+mod common;
+
+use goose::config::GooseConfiguration;
+use goose::metrics::GooseCustomMetricAggregate;
+use goose::prelude::*;
+
+// Paths used in load tests performed during these tests.
+const INDEX_PATH: &str = "/";
+
+// Indexes to the above paths.
+const INDEX_KEY: usize = 0;
+
+// Files used in load tests performed during these tests.
+const REPORT_FILE: &str = "test-custom-metrics-report.html";
+
+// Test transaction.
+pub async fn get_index(user: &mut GooseUser) -> TransactionResult {
+    let _goose = user.get(INDEX_PATH).await?;
+    user.record_counter("cart_items", 2)?;
+    user.record_gauge("search_results_count", 5.0)?;
+    user.record_timing("render", Duration::from_millis(3))?;
+    // A counter can't also be used as a gauge, this is ignored.
+    user.record_gauge("cart_items", 1.0)?;
+    Ok(())
+}
+
+// All tests in this file run against common endpoints.
+fn setup_mock_server_endpoints(server: &MockServer) -> Vec<Mock<'_>> {
+    vec![
+        // First set up INDEX_PATH, store in vector at INDEX_KEY.
+        server.mock(|when, then| {
+            when.method(GET).path(INDEX_PATH);
+            then.status(200);
+        }),
+    ]
+}
+
+// Build appropriate configuration for these tests.
+fn common_build_configuration(server: &MockServer, custom: Vec<&str>) -> GooseConfiguration {
+    let mut configuration = vec!["--users", "2", "--hatch-rate", "2", "--no-reset-metrics"];
+    configuration.extend_from_slice(&custom);
+    common::build_configuration(server, configuration)
+}
+
+// Build a load test that loads the index and records custom metrics.
+fn build_load_test(configuration: GooseConfiguration) -> GooseAttack {
+    common::build_load_test(
+        configuration,
+        vec![scenario!("LoadTest").register_transaction(transaction!(get_index))],
+        None,
+        None,
+    )
+}
+
+#[tokio::test]
+#[serial]
+/// Aggregate, display, serialize and report custom counters, gauges and timings.
+async fn test_custom_metrics() {
+    // Be sure there's no report file from a previous test.
+    common::cleanup_files(vec![REPORT_FILE]);
+
+    let server = MockServer::start();
+    let mock_endpoints = setup_mock_server_endpoints(&server);
+    let goose_metrics = common::run_load_test(
+        build_load_test(common_build_configuration(
+            &server,
+            vec!["--report-file", REPORT_FILE],
+        )),
+        None,
+    )
+    .await;
+    let index_hits = mock_endpoints[INDEX_KEY].hits();
+    assert!(index_hits > 0);
+
+    // Each custom metric was recorded once for each request.
+    assert!(goose_metrics.custom.len() == 3);
+    match &goose_metrics.custom["cart_items"] {
+        GooseCustomMetricAggregate::Counter(counter) => {
+            assert!(counter.count == index_hits);
+            assert!(counter.total == 2 * index_hits as u64);
+        }
+        _ => panic!("cart_items should be a counter"),
+    }
+    match &goose_metrics.custom["search_results_count"] {
+        GooseCustomMetricAggregate::Gauge(gauge) => {
+            assert!(gauge.count == index_hits);
+            assert!(gauge.average() == 5.0);
+        }
+        _ => panic!("search_results_count should be a gauge"),
+    }
+    match &goose_metrics.custom["render"] {
+        GooseCustomMetricAggregate::Timing(timing) => {
+            assert!(timing.counter == index_hits);
+            assert!(timing.minimum_time == 3 && timing.maximum_time == 3);
+        }
+        _ => panic!("render should be a timing"),
+    }
+
+    // Custom metrics are displayed.
+    let metrics = goose_metrics.to_string();
+    assert!(metrics.contains("CUSTOM METRICS"));
+    assert!(metrics.contains("search_results_count"));
+
+    // Custom metrics are serialized.
+    let json: serde_json::Value =
+        serde_json::from_str(&serde_json::to_string(&goose_metrics).unwrap()).unwrap();
+    assert!(
+        json["custom"]["cart_items"]["Counter"]["total"]
+            .as_u64()
+            .unwrap()
+            == 2 * index_hits as u64
+    );
+
+    // Custom metrics are included in the html report, with a graph of each kind.
+    let report = std::fs::read_to_string(REPORT_FILE).expect("failed to read report file");
+    assert!(report.contains("<h2>Custom Metrics</h2>"));
+    assert!(report.contains(r#"id="graph-custom-counters""#));
+    assert!(report.contains(r#"id="graph-custom-gauges""#));
+    assert!(report.contains(r#"id="graph-custom-timings""#));
+
+    common::cleanup_files(vec![REPORT_FILE]);
+}
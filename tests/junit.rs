@@ -0,0 +1,155 @@
+/// Validate that JUnit XML results are written for CI systems.
+use httpmock::{Method::GET, Mock, MockServer};
+use serial_test::serial;
+
+mod common;
+
+use goose::config::GooseConfiguration;
+use goose::prelude::*;
+
+// Paths used in load tests performed during these tests.
+const INDEX_PATH: &str = "/";
+const ERROR_PATH: &str = "/error";
+
+// Indexes to the above paths.
+const INDEX_KEY: usize = 0;
+const ERROR_KEY: usize = 1;
+
+// Files used in load tests performed during these tests.
+const JUNIT_FILE: &str = "junit-test.xml";
+const JUNIT_FILE_DEFAULT: &str = "junit-test-default.xml";
+
+// Test transaction.
+pub async fn get_index(user: &mut GooseUser) -> TransactionResult {
+    let _goose = user.get(INDEX_PATH).await?;
+    Ok(())
+}
+
+// Test transaction.
+pub async fn get_error(user: &mut GooseUser) -> TransactionResult {
+    let _goose = user.get(ERROR_PATH).await?;
+    Ok(())
+}
+
+// All tests in this file run against common endpoints.
+fn setup_mock_server_endpoints(server: &MockServer) -> Vec<Mock<'_>> {
+    vec![
+        // First set up INDEX_PATH, store in vector at INDEX_KEY.
+        server.mock(|when, then| {
+            when.method(GET).path(INDEX_PATH);
+            then.status(200);
+        }),
+        // Next set up ERROR_PATH, store in vector at ERROR_KEY.
+        server.mock(|when, then| {
+            when.method(GET).path(ERROR_PATH);
+            then.status(500);
+        }),
+    ]
+}
+
+// Build appropriate configuration for these tests.
+fn common_build_configuration(server: &MockServer, custom: Vec<&str>) -> GooseConfiguration {
+    let mut configuration = vec!["--users", "2", "--hatch-rate", "2", "--run-time", "2"];
+    configuration.extend_from_slice(&custom);
+    common::build_configuration(server, configuration)
+}
+
+// Build a load test that loads both the index and an error page.
+fn build_load_test(configuration: GooseConfiguration) -> GooseAttack {
+    common::build_load_test(
+        configuration,
+        vec![scenario!("LoadTest")
+            .register_transaction(transaction!(get_index))
+            .register_transaction(transaction!(get_error))],
+        None,
+        None,
+    )
+}
+
+#[tokio::test]
+#[serial]
+/// Requests and transactions are test cases that fail if errors are recorded.
+async fn test_junit_file() {
+    let server = MockServer::start();
+    let mock_endpoints = setup_mock_server_endpoints(&server);
+
+    let _goose_metrics = common::run_load_test(
+        build_load_test(common_build_configuration(
+            &server,
+            vec!["--junit-file", JUNIT_FILE],
+        )),
+        None,
+    )
+    .await;
+    assert!(mock_endpoints[INDEX_KEY].hits() > 0);
+    assert!(mock_endpoints[ERROR_KEY].hits() > 0);
+
+    let junit = std::fs::read_to_string(JUNIT_FILE).expect("failed to read junit file");
+    assert!(junit.starts_with("<?xml"));
+    assert!(junit.contains(r#"<testsuites name="Goose" tests="4" failures="1""#));
+    assert!(junit.contains(r#"<testcase classname="requests" name="GET /" "#));
+    assert!(junit.contains(r#"<testcase classname="requests" name="GET /error" "#));
+    assert!(junit.contains(r#"<testcase classname="LoadTest" name="1" "#));
+    assert!(junit.contains(r#"<testcase classname="LoadTest" name="2" "#));
+    assert!(junit.contains("500 Internal Server Error"));
+    // No threshold test cases are included if none were configured.
+    assert!(!junit.contains(r#"<testsuite name="thresholds""#));
+
+    common::cleanup_files(vec![JUNIT_FILE]);
+}
+
+#[tokio::test]
+#[serial]
+/// Thresholds fail the test cases they apply to, and the JUnit file is still written when
+/// the load test fails thresholds.
+async fn test_junit_file_thresholds() {
+    let server = MockServer::start();
+    let mock_endpoints = setup_mock_server_endpoints(&server);
+
+    let result = build_load_test(common_build_configuration(
+        &server,
+        vec![
+            "--junit-file",
+            JUNIT_FILE,
+            "--thresholds",
+            "GET /:max<0;p95<10000",
+        ],
+    ))
+    .execute()
+    .await;
+    assert!(mock_endpoints[INDEX_KEY].hits() > 0);
+    assert!(matches!(result, Err(GooseError::ThresholdsFailed { .. })));
+
+    let junit = std::fs::read_to_string(JUNIT_FILE).expect("failed to read junit file");
+    assert!(junit.contains(r#"<testsuites name="Goose" tests="5" failures="2""#));
+    assert!(junit.contains("threshold GET /:max&lt;0 not met"));
+    assert!(junit.contains(r#"<testsuite name="thresholds" tests="1" failures="0""#));
+    assert!(junit.contains(r#"<testcase classname="thresholds" name="p95&lt;10000" "#));
+
+    common::cleanup_files(vec![JUNIT_FILE]);
+}
+
+#[tokio::test]
+#[serial]
+/// The JUnit file can be configured with a default.
+async fn test_junit_file_default() {
+    let server = MockServer::start();
+    let _mock_endpoints = setup_mock_server_endpoints(&server);
+
+    let _goose_metrics = common::build_load_test(
+        common_build_configuration(&server, vec![]),
+        vec![scenario!("LoadTest").register_transaction(transaction!(get_index))],
+        None,
+        None,
+    )
+    .set_default(GooseDefault::JunitFile, JUNIT_FILE_DEFAULT)
+    .unwrap()
+    .execute()
+    .await
+    .unwrap();
+
+    let junit = std::fs::read_to_string(JUNIT_FILE_DEFAULT).expect("failed to read junit file");
+    assert!(junit.contains(r#"<testsuites name="Goose" tests="2" failures="0""#));
+
+    common::cleanup_files(vec![JUNIT_FILE_DEFAULT]);
+}
@@ -0,0 +1,172 @@
+/// Validate that Locust-compatible CSV files are written.
+use httpmock::{Method::GET, Mock, MockServer};
+use serial_test::serial;
+
+mod common;
+
+use goose::config::GooseConfiguration;
+use goose::prelude::*;
+
+// Paths used in load tests performed during these tests.
+const INDEX_PATH: &str = "/";
+const ERROR_PATH: &str = "/error";
+
+// Indexes to the above paths.
+const INDEX_KEY: usize = 0;
+const ERROR_KEY: usize = 1;
+
+// Files used in load tests performed during these tests.
+const CSV_PREFIX: &str = "locust-test";
+const CSV_PREFIX_DEFAULT: &str = "locust-test-default";
+
+// Test transaction.
+pub async fn get_index(user: &mut GooseUser) -> TransactionResult {
+    let _goose = user.get(INDEX_PATH).await?;
+    Ok(())
+}
+
+// Test transaction, fails if the request fails.
+pub async fn get_error(user: &mut GooseUser) -> TransactionResult {
+    let goose = user.get(ERROR_PATH).await?;
+    if !goose.request.success {
+        return Err(Box::new(TransactionError::RequestFailed {
+            raw_request: goose.request,
+        }));
+    }
+    Ok(())
+}
+
+// All tests in this file run against common endpoints.
+fn setup_mock_server_endpoints(server: &MockServer) -> Vec<Mock<'_>> {
+    vec![
+        // First set up INDEX_PATH, store in vector at INDEX_KEY.
+        server.mock(|when, then| {
+            when.method(GET).path(INDEX_PATH);
+            then.status(200);
+        }),
+        // Next set up ERROR_PATH, store in vector at ERROR_KEY.
+        server.mock(|when, then| {
+            when.method(GET).path(ERROR_PATH);
+            then.status(500);
+        }),
+    ]
+}
+
+// Build appropriate configuration for these tests.
+fn common_build_configuration(server: &MockServer, custom: Vec<&str>) -> GooseConfiguration {
+    let mut configuration = vec!["--users", "2", "--hatch-rate", "2", "--run-time", "3"];
+    configuration.extend_from_slice(&custom);
+    common::build_configuration(server, configuration)
+}
+
+// Build a load test that loads both the index and an error page.
+fn build_load_test(configuration: GooseConfiguration) -> GooseAttack {
+    common::build_load_test(
+        configuration,
+        vec![scenario!("LoadTest")
+            .register_transaction(transaction!(get_index))
+            .register_transaction(transaction!(get_error))],
+        None,
+        None,
+    )
+}
+
+// Read a CSV file written with the given prefix, returning its lines split into fields.
+fn read_csv(prefix: &str, suffix: &str) -> Vec<Vec<String>> {
+    let csv = std::fs::read_to_string(format!("{}_{}.csv", prefix, suffix))
+        .expect("failed to read csv file");
+    csv.lines()
+        .map(|line| line.split(',').map(|field| field.to_string()).collect())
+        .collect()
+}
+
+// Remove the CSV files written with the given prefix.
+fn cleanup_csv_files(prefix: &str) {
+    let files: Vec<String> = ["stats", "stats_history", "failures", "exceptions"]
+        .iter()
+        .map(|suffix| format!("{}_{}.csv", prefix, suffix))
+        .collect();
+    common::cleanup_files(files.iter().map(|file| file.as_str()).collect());
+}
+
+#[tokio::test]
+#[serial]
+/// Final statistics, history, failures and exceptions are written in Locust's format.
+async fn test_locust_csv() {
+    let server = MockServer::start();
+    let mock_endpoints = setup_mock_server_endpoints(&server);
+
+    let goose_metrics = common::run_load_test(
+        build_load_test(common_build_configuration(
+            &server,
+            vec!["--csv", CSV_PREFIX],
+        )),
+        None,
+    )
+    .await;
+    assert!(mock_endpoints[INDEX_KEY].hits() > 0);
+    assert!(mock_endpoints[ERROR_KEY].hits() > 0);
+
+    // Each request is followed by all requests aggregated together.
+    let stats = read_csv(CSV_PREFIX, "stats");
+    assert!(stats.len() == 4);
+    assert!(stats[0][..4] == ["Type", "Name", "Request Count", "Failure Count"]);
+    assert!(stats[0].len() == 22);
+    assert!(stats[1][..2] == ["GET", "/"]);
+    assert!(stats[1][3] == "0");
+    assert!(stats[2][..2] == ["GET", "/error"]);
+    assert!(stats[2][2] == stats[2][3]);
+    assert!(stats[3][..2] == ["", "Aggregated"]);
+    let index = goose_metrics.requests.get("GET /").unwrap();
+    assert!(stats[1][2] == index.success_count.to_string());
+
+    // Rows were appended to the history while the load test was running.
+    let history = read_csv(CSV_PREFIX, "stats_history");
+    assert!(history[0][..4] == ["Timestamp", "User Count", "Type", "Name"]);
+    assert!(history[0].len() == 24);
+    let aggregated: Vec<&Vec<String>> = history
+        .iter()
+        .filter(|row| row[3] == "Aggregated")
+        .collect();
+    assert!(aggregated.len() > 1);
+    assert!(aggregated.iter().all(|row| row.len() == 24));
+    // The final row includes the final totals.
+    assert!(aggregated.last().unwrap()[17] == stats[3][2]);
+
+    let failures = read_csv(CSV_PREFIX, "failures");
+    assert!(failures[0] == ["Method", "Name", "Error", "Occurrences"]);
+    assert!(failures.len() == 2);
+    assert!(failures[1][..2] == ["GET", "/error"]);
+    assert!(failures[1][2].contains("500 Internal Server Error"));
+
+    // The transaction loading the error page failed.
+    let exceptions = read_csv(CSV_PREFIX, "exceptions");
+    assert!(exceptions[0] == ["Count", "Message", "Traceback", "Nodes"]);
+    assert!(exceptions.len() == 2);
+    let get_error = &goose_metrics.transactions[0][1];
+    assert!(exceptions[1][0] == get_error.fail_count.to_string());
+    assert!(exceptions[1][1] == "Transaction LoadTest 0.1 failed");
+    assert!(exceptions[1][3] == "local");
+
+    cleanup_csv_files(CSV_PREFIX);
+}
+
+#[tokio::test]
+#[serial]
+/// The CSV prefix can be configured with a default.
+async fn test_locust_csv_default() {
+    let server = MockServer::start();
+    let _mock_endpoints = setup_mock_server_endpoints(&server);
+
+    let _goose_metrics = build_load_test(common_build_configuration(&server, vec![]))
+        .set_default(GooseDefault::Csv, CSV_PREFIX_DEFAULT)
+        .unwrap()
+        .execute()
+        .await
+        .unwrap();
+
+    assert!(read_csv(CSV_PREFIX_DEFAULT, "stats").len() == 4);
+    assert!(read_csv(CSV_PREFIX_DEFAULT, "stats_history").len() > 1);
+
+    cleanup_csv_files(CSV_PREFIX_DEFAULT);
+}
@@ -4,6 +4,7 @@ use std::fmt;
 
 mod common;
 
+use goose::logger::GooseLogFormat;
 use goose::prelude::*;
 
 // Paths used in load tests performed during these tests.
@@ -536,6 +537,120 @@ async fn test_requests_logs_pretty_gaggle() {
     run_gaggle_test(TestType::Requests, "pretty").await;
 }
 
+#[tokio::test]
+// Enable jtl-formatted requests log.
+async fn test_requests_logs_jtl() {
+    run_standalone_test(TestType::Requests, "jtl").await;
+}
+
+#[tokio::test]
+#[serial]
+// The jtl-formatted requests log has JMeter's columns.
+async fn test_requests_logs_jtl_columns() {
+    let request_log = "columns-request-log.jtl";
+
+    let server = MockServer::start();
+    let mock_endpoints = setup_mock_server_endpoints(&server);
+
+    let configuration = common::build_configuration(
+        &server,
+        vec![
+            "--request-log",
+            request_log,
+            "--request-format",
+            "jtl",
+            "--users",
+            "2",
+            "--hatch-rate",
+            "4",
+            "--run-time",
+            "2",
+        ],
+    );
+    let started = std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .unwrap()
+        .as_millis();
+    let _goose_metrics = common::run_load_test(
+        common::build_load_test(configuration, vec![get_transactions()], None, None),
+        None,
+    )
+    .await;
+    assert!(mock_endpoints[ERROR_KEY].hits() > 0);
+
+    let jtl = std::fs::read_to_string(request_log).expect("failed to read request log");
+    let mut lines = jtl.lines();
+    assert_eq!(
+        lines.next(),
+        Some(
+            "timeStamp,elapsed,label,responseCode,responseMessage,threadName,dataType,success,\
+            failureMessage,bytes,sentBytes,grpThreads,allThreads,URL,Latency,IdleTime,Connect"
+        )
+    );
+    let rows: Vec<Vec<&str>> = lines.map(|line| line.split(',').collect()).collect();
+    assert!(rows.iter().all(|row| row.len() == 17));
+    // Each request started during the load test.
+    let now = std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .unwrap()
+        .as_millis();
+    assert!(rows.iter().all(|row| {
+        let timestamp: u128 = row[0].parse().unwrap();
+        timestamp >= started && timestamp <= now
+    }));
+    let error = rows.iter().find(|row| row[2] == ERROR_PATH).unwrap();
+    assert_eq!(error[3], "503");
+    assert_eq!(error[4], "Service Unavailable");
+    assert_eq!(error[6], "text");
+    assert_eq!(error[7], "false");
+    assert_eq!(error[8], "503 Service Unavailable: /error");
+    assert!(error[13].ends_with(ERROR_PATH));
+    let index = rows.iter().find(|row| row[2] == INDEX_PATH).unwrap();
+    assert_eq!(index[3], "200");
+    assert_eq!(index[7], "true");
+    assert!(index[5].starts_with("Goose Users 1-"));
+    // Running users are counted, at most the 2 users started by this load test.
+    assert!(rows.iter().all(|row| row[11] == row[12]));
+    assert!(rows
+        .iter()
+        .all(|row| row[12].parse::<usize>().unwrap() <= 2));
+    assert!(rows.iter().any(|row| row[12] == "2"));
+
+    common::cleanup_files(vec![request_log]);
+}
+
+#[tokio::test]
+// The jtl format is only supported by the request log.
+async fn test_debug_logs_jtl_invalid() {
+    let server = MockServer::start();
+    let configuration = common::build_configuration(
+        &server,
+        vec![
+            "--debug-log",
+            "invalid-debug-log.jtl",
+            "--debug-format",
+            "jtl",
+            "--users",
+            "1",
+            "--run-time",
+            "1",
+        ],
+    );
+    let goose_attack = common::build_load_test(configuration, vec![get_transactions()], None, None);
+    assert!(goose_attack.execute().await.is_err());
+}
+
+#[test]
+// The jtl format can't be the default format of logs other than the request log.
+fn test_default_logs_jtl_invalid() {
+    let server = MockServer::start();
+    let configuration = common::build_configuration(&server, vec![]);
+    let goose_attack = GooseAttack::initialize_with_config(configuration).unwrap();
+    assert!(goose_attack
+        .set_default(GooseDefault::TransactionFormat, GooseLogFormat::Jtl)
+        .is_err());
+}
+
 /* Transaction logs */
 
 #[tokio::test]
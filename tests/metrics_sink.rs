@@ -0,0 +1,168 @@
+/// Validate that metrics are pushed to StatsD and InfluxDB.
+use httpmock::{
+    Method::{GET, POST},
+    Mock, MockServer,
+};
+use serial_test::serial;
+use std::time::Duration;
+use tokio::io::AsyncReadExt;
+use tokio::net::{TcpListener, UdpSocket};
+
+mod common;
+
+use goose::config::GooseConfiguration;
+use goose::prelude::*;
+
+// Paths used in load tests performed during these tests.
+const INDEX_PATH: &str = "/";
+const WRITE_PATH: &str = "/write";
+
+// Indexes to the above paths.
+const INDEX_KEY: usize = 0;
+const WRITE_KEY: usize = 1;
+
+// Test transaction.
+pub async fn get_index(user: &mut GooseUser) -> TransactionResult {
+    let _goose = user.get(INDEX_PATH).await?;
+    Ok(())
+}
+
+// All tests in this file run against common endpoints.
+fn setup_mock_server_endpoints(server: &MockServer) -> Vec<Mock<'_>> {
+    vec![
+        // First set up INDEX_PATH, store in vector at INDEX_KEY.
+        server.mock(|when, then| {
+            when.method(GET).path(INDEX_PATH);
+            then.status(200);
+        }),
+        // Next set up WRITE_PATH, an InfluxDB write endpoint, store in vector at WRITE_KEY.
+        server.mock(|when, then| {
+            when.method(POST)
+                .path(WRITE_PATH)
+                .query_param("db", "goose")
+                .body_contains("goose_requests,method=GET,name=/ count=");
+            then.status(204);
+        }),
+    ]
+}
+
+// Build appropriate configuration for these tests.
+fn common_build_configuration(server: &MockServer, metrics_sink: &str) -> GooseConfiguration {
+    common::build_configuration(
+        server,
+        vec![
+            "--users",
+            "2",
+            "--hatch-rate",
+            "2",
+            "--run-time",
+            "2",
+            "--metrics-sink",
+            metrics_sink,
+            "--metrics-sink-interval",
+            "1",
+        ],
+    )
+}
+
+// Build a load test that repeatedly loads the index.
+fn build_load_test(configuration: GooseConfiguration) -> GooseAttack {
+    common::build_load_test(
+        configuration,
+        vec![scenario!("LoadTest").register_transaction(transaction!(get_index))],
+        None,
+        None,
+    )
+}
+
+#[tokio::test]
+#[serial]
+/// Push metrics to a local StatsD listener over UDP.
+async fn test_metrics_sink_statsd() {
+    let server = MockServer::start();
+    let mock_endpoints = setup_mock_server_endpoints(&server);
+
+    let statsd = UdpSocket::bind("127.0.0.1:0").await.unwrap();
+    let metrics_sink = format!("statsd://{}", statsd.local_addr().unwrap());
+
+    let _goose_metrics = common::run_load_test(
+        build_load_test(common_build_configuration(&server, &metrics_sink)),
+        None,
+    )
+    .await;
+    assert!(mock_endpoints[INDEX_KEY].hits() > 0);
+
+    // Collect all packets received while the load test was running.
+    let mut received = String::new();
+    let mut buf = vec![0; 65_536];
+    while let Ok(Ok(length)) =
+        tokio::time::timeout(Duration::from_millis(200), statsd.recv(&mut buf)).await
+    {
+        received.push_str(&String::from_utf8_lossy(&buf[..length]));
+        received.push('\n');
+    }
+
+    assert!(received.contains("goose.users:2|g"));
+    assert!(received.contains("goose.requests.get.root.count:"));
+    assert!(received.contains("goose.transactions.loadtest.0.count:"));
+    assert!(received.contains("goose.scenarios.loadtest.count:"));
+    // Each line is a valid StatsD metric.
+    for line in received.lines().filter(|line| !line.is_empty()) {
+        assert!(line.ends_with("|c") || line.ends_with("|g"), "{}", line);
+    }
+    // All requests made are counted, as the counts pushed are added together.
+    let counted: usize = received
+        .lines()
+        .filter_map(|line| line.strip_prefix("goose.requests.get.root.count:"))
+        .map(|count| count.trim_end_matches("|c").parse::<usize>().unwrap())
+        .sum();
+    assert!(counted == mock_endpoints[INDEX_KEY].hits());
+}
+
+#[tokio::test]
+#[serial]
+/// Push metrics to a local InfluxDB line protocol listener over TCP.
+async fn test_metrics_sink_influx_tcp() {
+    let server = MockServer::start();
+    let mock_endpoints = setup_mock_server_endpoints(&server);
+
+    let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+    let metrics_sink = format!("influx://{}", listener.local_addr().unwrap());
+    // Read everything sent until the metrics sink disconnects.
+    let influx = tokio::spawn(async move {
+        let (mut stream, _) = listener.accept().await.unwrap();
+        let mut received = String::new();
+        stream.read_to_string(&mut received).await.unwrap();
+        received
+    });
+
+    let _goose_metrics = common::run_load_test(
+        build_load_test(common_build_configuration(&server, &metrics_sink)),
+        None,
+    )
+    .await;
+    assert!(mock_endpoints[INDEX_KEY].hits() > 0);
+
+    let received = influx.await.unwrap();
+    assert!(received.contains("goose_users value=2i "));
+    assert!(received.contains("goose_requests,method=GET,name=/ count="));
+    assert!(received.contains("goose_transactions,scenario=LoadTest,transaction=0 count="));
+    assert!(received.contains("goose_scenarios,scenario=LoadTest count="));
+}
+
+#[tokio::test]
+#[serial]
+/// Post metrics to a local InfluxDB HTTP write endpoint.
+async fn test_metrics_sink_influx_http() {
+    let server = MockServer::start();
+    let mock_endpoints = setup_mock_server_endpoints(&server);
+
+    let metrics_sink = server.url(format!("{}?db=goose", WRITE_PATH));
+    let _goose_metrics = common::run_load_test(
+        build_load_test(common_build_configuration(&server, &metrics_sink)),
+        None,
+    )
+    .await;
+    assert!(mock_endpoints[INDEX_KEY].hits() > 0);
+    assert!(mock_endpoints[WRITE_KEY].hits() > 0);
+}
@@ -0,0 +1,126 @@
+/// Validate that metrics and spans are exported to an OpenTelemetry collector.
+use httpmock::{
+    Method::{GET, POST},
+    Mock, MockServer,
+};
+use serial_test::serial;
+
+mod common;
+
+use goose::config::GooseConfiguration;
+use goose::prelude::*;
+
+// Paths used in load tests performed during these tests.
+const INDEX_PATH: &str = "/";
+const METRICS_PATH: &str = "/v1/metrics";
+const TRACES_PATH: &str = "/v1/traces";
+
+// Indexes to the above paths.
+const INDEX_KEY: usize = 0;
+const METRICS_KEY: usize = 1;
+const TRACES_KEY: usize = 2;
+
+// Test transaction.
+pub async fn get_index(user: &mut GooseUser) -> TransactionResult {
+    let _goose = user.get(INDEX_PATH).await?;
+    Ok(())
+}
+
+// All tests in this file run against common endpoints.
+fn setup_mock_server_endpoints(server: &MockServer) -> Vec<Mock<'_>> {
+    vec![
+        // First set up INDEX_PATH, store in vector at INDEX_KEY.
+        server.mock(|when, then| {
+            when.method(GET).path(INDEX_PATH);
+            then.status(200);
+        }),
+        // Next set up METRICS_PATH, where the collector receives metrics, store in vector
+        // at METRICS_KEY.
+        server.mock(|when, then| {
+            when.method(POST)
+                .path(METRICS_PATH)
+                .header("content-type", "application/json")
+                .body_contains(r#""name":"goose.requests""#)
+                .body_contains(r#"{"key":"goose.request.name","value":{"stringValue":"/"}}"#)
+                .body_contains(r#""name":"goose.request.duration""#);
+            then.status(200);
+        }),
+        // Next set up TRACES_PATH, where the collector receives spans, store in vector at
+        // TRACES_KEY.
+        server.mock(|when, then| {
+            when.method(POST)
+                .path(TRACES_PATH)
+                .header("content-type", "application/json")
+                .body_contains(r#""name":"GET /""#)
+                .body_contains(
+                    r#"{"key":"goose.scenario.name","value":{"stringValue":"LoadTest"}}"#,
+                )
+                .body_contains(r#"{"key":"goose.transaction.index","value":{"intValue":"0"}}"#)
+                // Requests are children of the transaction that made them.
+                .body_contains(r#""parentSpanId":"#);
+            then.status(200);
+        }),
+    ]
+}
+
+// Build appropriate configuration for these tests.
+fn common_build_configuration(server: &MockServer, custom: Vec<&str>) -> GooseConfiguration {
+    let mut configuration = vec!["--users", "2", "--hatch-rate", "2", "--run-time", "2"];
+    configuration.extend_from_slice(&custom);
+    common::build_configuration(server, configuration)
+}
+
+// Build a load test that repeatedly loads the index.
+fn build_load_test(configuration: GooseConfiguration) -> GooseAttack {
+    common::build_load_test(
+        configuration,
+        vec![scenario!("LoadTest").register_transaction(transaction!(get_index))],
+        None,
+        None,
+    )
+}
+
+#[tokio::test]
+#[serial]
+/// Export metrics to a local collector.
+async fn test_otlp_metrics() {
+    let server = MockServer::start();
+    let mock_endpoints = setup_mock_server_endpoints(&server);
+
+    let endpoint = server.url("");
+    let _goose_metrics = common::run_load_test(
+        build_load_test(common_build_configuration(
+            &server,
+            vec!["--otlp-endpoint", &endpoint],
+        )),
+        None,
+    )
+    .await;
+    assert!(mock_endpoints[INDEX_KEY].hits() > 0);
+
+    // Metrics are exported before they're reset and when the load test finishes.
+    assert!(mock_endpoints[METRICS_KEY].hits() >= 2);
+    // Spans aren't exported unless enabled.
+    assert!(mock_endpoints[TRACES_KEY].hits() == 0);
+}
+
+#[tokio::test]
+#[serial]
+/// Export metrics and a span for each request and transaction to a local collector.
+async fn test_otlp_traces() {
+    let server = MockServer::start();
+    let mock_endpoints = setup_mock_server_endpoints(&server);
+
+    let endpoint = server.url("");
+    let _goose_metrics = common::run_load_test(
+        build_load_test(common_build_configuration(
+            &server,
+            vec!["--otlp-endpoint", &endpoint, "--otlp-traces"],
+        )),
+        None,
+    )
+    .await;
+    assert!(mock_endpoints[INDEX_KEY].hits() > 0);
+    assert!(mock_endpoints[METRICS_KEY].hits() >= 2);
+    assert!(mock_endpoints[TRACES_KEY].hits() > 0);
+}
@@ -0,0 +1,117 @@
+/// Validate that the configured response time percentiles are displayed and reported.
+use httpmock::{Method::GET, Mock, MockServer};
+use serial_test::serial;
+
+mod common;
+
+use goose::config::GooseConfiguration;
+use goose::prelude::*;
+
+// Paths used in load tests performed during these tests.
+const INDEX_PATH: &str = "/";
+const ABOUT_PATH: &str = "/about.html";
+
+// Indexes to the above paths.
+const INDEX_KEY: usize = 0;
+const ABOUT_KEY: usize = 1;
+
+// Files used in load tests performed during these tests.
+const REPORT_FILE: &str = "test-percentiles-report.html";
+
+// Test transaction.
+pub async fn get_index(user: &mut GooseUser) -> TransactionResult {
+    let _goose = user.get(INDEX_PATH).await?;
+    Ok(())
+}
+
+// Test transaction.
+pub async fn get_about(user: &mut GooseUser) -> TransactionResult {
+    let _goose = user.get(ABOUT_PATH).await?;
+    Ok(())
+}
+
+// All tests in this file run against common endpoints.
+fn setup_mock_server_endpoints(server: &MockServer) -> Vec<Mock<'_>> {
+    vec![
+        // First set up INDEX_PATH, store in vector at INDEX_KEY.
+        server.mock(|when, then| {
+            when.method(GET).path(INDEX_PATH);
+            then.status(200);
+        }),
+        // Next set up ABOUT_PATH, store in vector at ABOUT_KEY.
+        server.mock(|when, then| {
+            when.method(GET).path(ABOUT_PATH);
+            then.status(200);
+        }),
+    ]
+}
+
+// Build appropriate configuration for these tests.
+fn common_build_configuration(server: &MockServer, custom: Vec<&str>) -> GooseConfiguration {
+    let mut configuration = vec!["--users", "2", "--hatch-rate", "2"];
+    configuration.extend_from_slice(&custom);
+    common::build_configuration(server, configuration)
+}
+
+// Build a load test that loads both the index and the about page.
+fn build_load_test(configuration: GooseConfiguration) -> GooseAttack {
+    common::build_load_test(
+        configuration,
+        vec![scenario!("LoadTest")
+            .register_transaction(transaction!(get_index))
+            .register_transaction(transaction!(get_about))],
+        None,
+        None,
+    )
+}
+
+#[tokio::test]
+#[serial]
+/// Display, serialize and report the configured percentiles.
+async fn test_percentiles() {
+    // Be sure there's no report file from a previous test.
+    common::cleanup_files(vec![REPORT_FILE]);
+
+    let server = MockServer::start();
+    let mock_endpoints = setup_mock_server_endpoints(&server);
+    let goose_metrics = common::run_load_test(
+        build_load_test(common_build_configuration(
+            &server,
+            vec!["--percentiles", "50, 99.9", "--report-file", REPORT_FILE],
+        )),
+        None,
+    )
+    .await;
+    assert!(mock_endpoints[INDEX_KEY].hits() > 0);
+    assert!(mock_endpoints[ABOUT_KEY].hits() > 0);
+
+    // The configured percentiles are displayed.
+    assert!(goose_metrics.percentiles.percentiles == vec![50.0, 99.9]);
+    let metrics = goose_metrics.to_string();
+    assert!(metrics.contains("50%"));
+    assert!(metrics.contains("99.9%"));
+    assert!(!metrics.contains("75%"));
+
+    // The configured percentiles of each request, and of all requests, are serialized.
+    let json: serde_json::Value =
+        serde_json::from_str(&serde_json::to_string(&goose_metrics).unwrap()).unwrap();
+    let response_time_percentiles = json["response_time_percentiles"].as_object().unwrap();
+    assert!(response_time_percentiles.len() == 3);
+    for request in ["GET /", "GET /about.html", "Aggregated"] {
+        let percentiles = response_time_percentiles[request].as_array().unwrap();
+        assert!(percentiles.len() == 2);
+        assert!(percentiles[1]["percentile"].as_f64().unwrap() > 99.8);
+    }
+
+    // The configured percentiles are included in the html report.
+    let report = std::fs::read_to_string(REPORT_FILE).expect("failed to read report file");
+    assert!(report.contains("<th>99.9%ile (ms)</th>"));
+    assert!(!report.contains("<th>60%ile (ms)</th>"));
+
+    // The p50, p95 and p99 response times are graphed over time in the html report.
+    for graph in ["p50", "p95", "p99"] {
+        assert!(report.contains(&format!(r#"id="graph-{}-response-time""#, graph)));
+    }
+
+    common::cleanup_files(vec![REPORT_FILE]);
+}
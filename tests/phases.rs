@@ -0,0 +1,156 @@
+/// Validate that metrics are collected for each test plan step.
+use httpmock::{Method::GET, Mock, MockServer};
+use serial_test::serial;
+use std::time::Duration;
+
+mod common;
+
+use goose::config::GooseConfiguration;
+use goose::prelude::*;
+
+// Paths used in load tests performed during these tests.
+const INDEX_PATH: &str = "/";
+const ABOUT_PATH: &str = "/about.html";
+
+// Indexes to the above paths.
+const INDEX_KEY: usize = 0;
+const ABOUT_KEY: usize = 1;
+
+// Files used in load tests performed during these tests.
+const REPORT_FILE: &str = "test-phases-report.html";
+
+// Test transaction.
+pub async fn get_index(user: &mut GooseUser) -> TransactionResult {
+    let _goose = user.get(INDEX_PATH).await?;
+    Ok(())
+}
+
+// Test transaction.
+pub async fn get_about(user: &mut GooseUser) -> TransactionResult {
+    let _goose = user.get(ABOUT_PATH).await?;
+    Ok(())
+}
+
+// All tests in this file run against common endpoints.
+fn setup_mock_server_endpoints(server: &MockServer) -> Vec<Mock<'_>> {
+    vec![
+        // First set up INDEX_PATH, store in vector at INDEX_KEY.
+        server.mock(|when, then| {
+            when.method(GET).path(INDEX_PATH);
+            then.status(200);
+        }),
+        // Next set up ABOUT_PATH, store in vector at ABOUT_KEY.
+        server.mock(|when, then| {
+            when.method(GET).path(ABOUT_PATH);
+            then.status(200);
+        }),
+    ]
+}
+
+// Build appropriate configuration for these tests.
+fn common_build_configuration(server: &MockServer, custom: Vec<&str>) -> GooseConfiguration {
+    let mut configuration = vec!["--test-plan", "2,1s;2,2s;0,1s"];
+    configuration.extend_from_slice(&custom);
+    let mut configuration = common::build_configuration(server, configuration);
+
+    // The common::build_configuration() function sets a few default options which can't be
+    // set with --test-plan.
+    configuration.run_time = "".to_string();
+    configuration.hatch_rate = None;
+    configuration.users = None;
+
+    configuration
+}
+
+// Build a load test that loads both the index and the about page.
+fn build_load_test(configuration: GooseConfiguration) -> GooseAttack {
+    common::build_load_test(
+        configuration,
+        vec![scenario!("LoadTest")
+            .register_transaction(transaction!(get_index))
+            .register_transaction(transaction!(get_about))
+            .set_wait_time(Duration::from_millis(100), Duration::from_millis(100))
+            .unwrap()],
+        None,
+        None,
+    )
+}
+
+#[tokio::test]
+#[serial]
+/// Collect, display and report metrics for each test plan step.
+async fn test_phase_metrics() {
+    // Be sure there's no report file from a previous test.
+    common::cleanup_files(vec![REPORT_FILE]);
+
+    let server = MockServer::start();
+    let mock_endpoints = setup_mock_server_endpoints(&server);
+    let goose_metrics = common::run_load_test(
+        build_load_test(common_build_configuration(
+            &server,
+            vec!["--phase-metrics", "--report-file", REPORT_FILE],
+        )),
+        None,
+    )
+    .await;
+    assert!(mock_endpoints[INDEX_KEY].hits() > 0);
+    assert!(mock_endpoints[ABOUT_KEY].hits() > 0);
+
+    // Requests were made while increasing and maintaining users.
+    assert!(goose_metrics.phases.len() >= 2);
+    assert!(format!("{:?}", goose_metrics.phases[0].action) == "Increasing");
+    assert!(format!("{:?}", goose_metrics.phases[1].action) == "Maintaining");
+    for phase in &goose_metrics.phases[0..2] {
+        assert!(phase.requests.contains_key("GET /"));
+        assert!(phase.transactions[0].len() == 2);
+        assert!(phase.scenarios.len() == 1);
+    }
+
+    // Metrics aren't reset when running a test plan, so all requests are included in
+    // exactly one phase.
+    let count = |requests: &goose::metrics::GooseRequestMetrics| {
+        requests
+            .values()
+            .map(|request| request.success_count + request.fail_count)
+            .sum::<usize>()
+    };
+    let phase_count: usize = goose_metrics
+        .phases
+        .iter()
+        .map(|phase| count(&phase.requests))
+        .sum();
+    assert!(phase_count == count(&goose_metrics.requests));
+    assert!(phase_count == mock_endpoints[INDEX_KEY].hits() + mock_endpoints[ABOUT_KEY].hits());
+
+    // Per phase metrics are displayed.
+    let metrics = goose_metrics.to_string();
+    assert!(metrics.contains("PER PHASE METRICS"));
+    assert!(metrics.contains("1: Increasing"));
+    assert!(metrics.contains("2: Maintaining"));
+
+    // Per phase metrics are included in the html report.
+    let report = std::fs::read_to_string(REPORT_FILE).expect("failed to read report file");
+    assert!(report.contains("<h2>Per Phase Metrics</h2>"));
+    assert!(report.contains("<td>2: Maintaining</td>"));
+
+    common::cleanup_files(vec![REPORT_FILE]);
+}
+
+#[tokio::test]
+#[serial]
+/// Per phase metrics are only collected and displayed if enabled.
+async fn test_phase_metrics_disabled() {
+    let server = MockServer::start();
+    let _mock_endpoints = setup_mock_server_endpoints(&server);
+    let goose_metrics = common::run_load_test(
+        build_load_test(common_build_configuration(&server, vec![])),
+        None,
+    )
+    .await;
+
+    assert!(goose_metrics.phases.is_empty());
+    assert!(!goose_metrics.to_string().contains("PER PHASE METRICS"));
+    // Empty phases aren't serialized.
+    let json = serde_json::to_string(&goose_metrics).expect("failed to serialize metrics");
+    assert!(!json.contains("\"phases\""));
+}
@@ -0,0 +1,116 @@
+/// Validate that live metrics are served to Prometheus.
+use httpmock::{Method::GET, Mock, MockServer};
+use serial_test::serial;
+use std::time::Duration;
+
+mod common;
+
+use goose::config::GooseConfiguration;
+use goose::prelude::*;
+
+// Paths used in load tests performed during these tests.
+const INDEX_PATH: &str = "/";
+
+// Indexes to the above paths.
+const INDEX_KEY: usize = 0;
+
+// The port the Prometheus endpoint listens on during these tests.
+const PROMETHEUS_PORT: &str = "5118";
+
+// Test transaction.
+pub async fn get_index(user: &mut GooseUser) -> TransactionResult {
+    let _goose = user.get(INDEX_PATH).await?;
+    Ok(())
+}
+
+// All tests in this file run against common endpoints.
+fn setup_mock_server_endpoints(server: &MockServer) -> Vec<Mock<'_>> {
+    vec![
+        // First set up INDEX_PATH, store in vector at INDEX_KEY.
+        server.mock(|when, then| {
+            when.method(GET).path(INDEX_PATH);
+            then.status(200);
+        }),
+    ]
+}
+
+// Build appropriate configuration for these tests.
+fn common_build_configuration(server: &MockServer, custom: Vec<&str>) -> GooseConfiguration {
+    let mut configuration = vec![
+        "--users",
+        "2",
+        "--hatch-rate",
+        "2",
+        "--run-time",
+        "2",
+        "--no-reset-metrics",
+    ];
+    configuration.extend_from_slice(&custom);
+    common::build_configuration(server, configuration)
+}
+
+// Build a load test that repeatedly loads the index.
+fn build_load_test(configuration: GooseConfiguration) -> GooseAttack {
+    common::build_load_test(
+        configuration,
+        vec![scenario!("LoadTest").register_transaction(transaction!(get_index))],
+        None,
+        None,
+    )
+}
+
+#[tokio::test]
+#[serial]
+/// Scrape the live metrics of a running load test.
+async fn test_prometheus() {
+    let server = MockServer::start();
+    let mock_endpoints = setup_mock_server_endpoints(&server);
+
+    // Scrape the Prometheus endpoint while the load test is running.
+    let scrape = tokio::spawn(async {
+        tokio::time::sleep(Duration::from_millis(1500)).await;
+        let url = format!("http://127.0.0.1:{}", PROMETHEUS_PORT);
+        let client = reqwest::Client::new();
+        let metrics = client
+            .get(format!("{}/metrics", url))
+            .send()
+            .await
+            .expect("failed to scrape metrics");
+        let not_found = client
+            .get(format!("{}/other", url))
+            .send()
+            .await
+            .expect("failed to request other path");
+        (metrics, not_found)
+    });
+
+    let _goose_metrics = common::run_load_test(
+        build_load_test(common_build_configuration(
+            &server,
+            vec!["--prometheus-port", PROMETHEUS_PORT],
+        )),
+        None,
+    )
+    .await;
+    assert!(mock_endpoints[INDEX_KEY].hits() > 0);
+
+    let (metrics, not_found) = scrape.await.unwrap();
+
+    // Metrics are served in the Prometheus text format.
+    assert_eq!(metrics.status(), 200);
+    assert!(metrics.headers()["content-type"]
+        .to_str()
+        .unwrap()
+        .starts_with("text/plain; version=0.0.4"));
+    let text = metrics.text().await.unwrap();
+    assert!(text.contains("goose_users 2\n"));
+    assert!(text.contains("goose_attack_phase{phase=\"maintain\"} 1\n"));
+    assert!(text.contains("goose_requests_total{method=\"GET\",name=\"/\"} "));
+    assert!(text.contains("# TYPE goose_response_time_seconds histogram"));
+    assert!(
+        text.contains("goose_response_time_seconds_bucket{method=\"GET\",name=\"/\",le=\"+Inf\"} ")
+    );
+
+    // Only /metrics is served.
+    assert_eq!(not_found.status(), 404);
+}
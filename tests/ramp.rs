@@ -0,0 +1,75 @@
+/// Validate that test plan steps follow their configured ramp shape.
+use httpmock::{Method::GET, Mock, MockServer};
+use serial_test::serial;
+
+mod common;
+
+use goose::prelude::*;
+
+// Paths used in load tests performed during these tests.
+const INDEX_PATH: &str = "/";
+
+// Indexes to the above paths.
+const INDEX_KEY: usize = 0;
+
+// Test transaction.
+pub async fn get_index(user: &mut GooseUser) -> TransactionResult {
+    let _goose = user.get(INDEX_PATH).await?;
+    Ok(())
+}
+
+// All tests in this file run against common endpoints.
+fn setup_mock_server_endpoints(server: &MockServer) -> Vec<Mock<'_>> {
+    vec![
+        // Set up INDEX_PATH, store in vector at INDEX_KEY.
+        server.mock(|when, then| {
+            when.method(GET).path(INDEX_PATH);
+            then.status(200);
+        }),
+    ]
+}
+
+#[tokio::test]
+#[serial]
+/// Oscillate users in a sine wave, launching and stopping users throughout the step.
+async fn test_ramp_sine() {
+    // Start the mock server.
+    let server = MockServer::start();
+
+    // Setup the endpoints needed for this test on the mock server.
+    let mock_endpoints = setup_mock_server_endpoints(&server);
+
+    // Launch 4 users, then oscillate 3 users above and below for two 2 second waves.
+    let mut configuration =
+        common::build_configuration(&server, vec!["--test-plan", "4,1s;4,4s,sine(3,2s);0,0"]);
+
+    // Do not set --run-time, --hatch-rate or --users with --test-plan.
+    configuration.run_time = "".to_string();
+    configuration.hatch_rate = None;
+    configuration.users = None;
+
+    // Run the Goose Attack.
+    let goose_metrics = common::run_load_test(
+        common::build_load_test(
+            configuration,
+            vec![scenario!("LoadTest").register_transaction(transaction!(get_index))],
+            None,
+            None,
+        ),
+        None,
+    )
+    .await;
+
+    // Confirm that the load test ran.
+    assert!(mock_endpoints[INDEX_KEY].hits() > 0);
+
+    // Users were launched above the step's 4 users at the peaks of the wave.
+    assert!(goose_metrics.maximum_users > 4);
+    assert!(goose_metrics.maximum_users <= 7);
+
+    // Users stopped in the troughs of the wave were replaced by new users.
+    assert!(goose_metrics.total_users > goose_metrics.maximum_users);
+
+    // The ramp is recorded in the test plan history.
+    assert_eq!(goose_metrics.history[1].ramp.to_string(), "sine(3,2s)");
+}
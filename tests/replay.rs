@@ -0,0 +1,330 @@
+/// Validate that metrics and reports can be rebuilt from a request log.
+use httpmock::{Method::GET, Mock, MockServer};
+use serial_test::serial;
+
+mod common;
+
+use goose::config::GooseConfiguration;
+use goose::metrics::GooseMetrics;
+use goose::prelude::*;
+use goose::replay::{GooseComparison, GooseReport};
+
+// Paths used in load tests performed during these tests.
+const INDEX_PATH: &str = "/";
+const ERROR_PATH: &str = "/error";
+
+// Indexes to the above paths.
+const INDEX_KEY: usize = 0;
+const ERROR_KEY: usize = 1;
+
+// Files used in load tests performed during these tests.
+const REQUEST_LOG: &str = "replay-requests.log";
+const BASELINE_LOG: &str = "replay-baseline.log";
+const BASELINE_FILE: &str = "replay-baseline.json";
+const COMPARISON_REPORT: &str = "replay-comparison.html";
+const HTML_REPORT: &str = "replay-report.html";
+const JSON_REPORT: &str = "replay-report.json";
+
+// Test transaction.
+pub async fn get_index(user: &mut GooseUser) -> TransactionResult {
+    let _goose = user.get(INDEX_PATH).await?;
+    Ok(())
+}
+
+// Test transaction.
+pub async fn get_error(user: &mut GooseUser) -> TransactionResult {
+    let _goose = user.get(ERROR_PATH).await?;
+    Ok(())
+}
+
+// All tests in this file run against common endpoints.
+fn setup_mock_server_endpoints(server: &MockServer) -> Vec<Mock<'_>> {
+    vec![
+        // First set up INDEX_PATH, store in vector at INDEX_KEY.
+        server.mock(|when, then| {
+            when.method(GET).path(INDEX_PATH);
+            then.status(200);
+        }),
+        // Next set up ERROR_PATH, store in vector at ERROR_KEY.
+        server.mock(|when, then| {
+            when.method(GET).path(ERROR_PATH);
+            then.status(500);
+        }),
+    ]
+}
+
+// Run a load test that loads both the index and an error page, writing a request log in
+// the specified format.
+async fn run_load_test(server: &MockServer, format: &str) -> GooseMetrics {
+    run_custom_load_test(
+        server,
+        vec!["--request-log", REQUEST_LOG, "--request-format", format],
+    )
+    .await
+}
+
+// Run a load test that loads both the index and an error page, with custom options.
+async fn run_custom_load_test(server: &MockServer, custom: Vec<&str>) -> GooseMetrics {
+    let mut configuration = vec![
+        "--users",
+        "2",
+        "--hatch-rate",
+        "4",
+        "--run-time",
+        "2",
+        // All requests are logged, so don't reset the metrics after all users start.
+        "--no-reset-metrics",
+    ];
+    configuration.extend_from_slice(&custom);
+    let configuration: GooseConfiguration = common::build_configuration(server, configuration);
+    common::run_load_test(
+        common::build_load_test(
+            configuration,
+            vec![scenario!("LoadTest")
+                .register_transaction(transaction!(get_index))
+                .register_transaction(transaction!(get_error))],
+            None,
+            None,
+        ),
+        None,
+    )
+    .await
+}
+
+// Validate that the rebuilt metrics match the metrics collected during the load test.
+fn validate_metrics(goose_metrics: &GooseMetrics, rebuilt: &GooseMetrics) {
+    assert!(rebuilt.requests.len() == 2);
+    for (key, request) in &goose_metrics.requests {
+        let rebuilt_request = rebuilt.requests.get(key).expect("missing request");
+        assert!(rebuilt_request.success_count == request.success_count);
+        assert!(rebuilt_request.fail_count == request.fail_count);
+        assert!(rebuilt_request.raw_data.counter == request.raw_data.counter);
+        assert!(rebuilt_request.raw_data.total_time == request.raw_data.total_time);
+        assert!(rebuilt_request.status_code_counts == request.status_code_counts);
+    }
+    assert!(rebuilt.maximum_users == 2);
+    assert!(rebuilt.hosts.len() == 1);
+    assert!(rebuilt
+        .errors
+        .values()
+        .all(|error| error.error.contains("500 Internal Server Error")));
+    // The load test started when the earliest request was made, less its elapsed time.
+    let started = goose_metrics.history.first().unwrap().timestamp;
+    let rebuilt_started = rebuilt.history.first().unwrap().timestamp;
+    assert!((rebuilt_started - started).num_milliseconds().abs() < 1_000);
+}
+
+// Rebuild the metrics from a request log written in the specified format.
+async fn run_replay_test(format: &str) {
+    let server = MockServer::start();
+    let mock_endpoints = setup_mock_server_endpoints(&server);
+
+    let goose_metrics = run_load_test(&server, format).await;
+    assert!(mock_endpoints[INDEX_KEY].hits() > 0);
+    assert!(mock_endpoints[ERROR_KEY].hits() > 0);
+
+    let rebuilt = GooseReport::from_request_log(REQUEST_LOG)
+        .await
+        .unwrap()
+        .metrics()
+        .await
+        .unwrap();
+    validate_metrics(&goose_metrics, &rebuilt);
+
+    common::cleanup_files(vec![REQUEST_LOG]);
+}
+
+#[tokio::test]
+#[serial]
+/// Rebuild metrics from a json request log.
+async fn test_replay_json() {
+    run_replay_test("json").await;
+}
+
+#[tokio::test]
+#[serial]
+/// Rebuild metrics from a csv request log.
+async fn test_replay_csv() {
+    run_replay_test("csv").await;
+}
+
+#[tokio::test]
+#[serial]
+/// Rebuild metrics from a raw request log.
+async fn test_replay_raw() {
+    run_replay_test("raw").await;
+}
+
+#[tokio::test]
+#[serial]
+/// Rebuild metrics from a pretty request log.
+async fn test_replay_pretty() {
+    run_replay_test("pretty").await;
+}
+
+#[tokio::test]
+#[serial]
+/// Rebuild reports from a request log filtered by request name and time.
+async fn test_replay_filtered_reports() {
+    let server = MockServer::start();
+    let _mock_endpoints = setup_mock_server_endpoints(&server);
+
+    let goose_metrics = run_load_test(&server, "json").await;
+
+    // Only include the error page.
+    let rebuilt = GooseReport::from_request_log(REQUEST_LOG)
+        .await
+        .unwrap()
+        .set_name(ERROR_PATH)
+        .write(&[HTML_REPORT.to_string(), JSON_REPORT.to_string()])
+        .await
+        .unwrap();
+    assert!(rebuilt.requests.len() == 1);
+    let error_request = rebuilt.requests.get("GET /error").unwrap();
+    assert!(error_request.success_count == 0);
+    assert!(error_request.fail_count == goose_metrics.requests["GET /error"].fail_count);
+
+    let html = std::fs::read_to_string(HTML_REPORT).expect("failed to read html report");
+    assert!(html.contains("<title>Goose Attack Report</title>"));
+    assert!(html.contains("<td>/error</td>"));
+    assert!(!html.contains("<td>/</td>"));
+
+    let json = std::fs::read_to_string(JSON_REPORT).expect("failed to read json report");
+    let report: serde_json::Value = serde_json::from_str(&json).expect("invalid json report");
+    assert!(report["users"] == 2);
+    assert!(report["steps"][0]["action"] == "Maintaining");
+    assert!(report["transactions"].is_null());
+    assert!(report["scenarios"].is_null());
+    assert!(report["raw_requests"].as_array().unwrap().len() == 2);
+
+    // Only include the second second of the load test.
+    let rebuilt = GooseReport::from_request_log(REQUEST_LOG)
+        .await
+        .unwrap()
+        .set_from(1)
+        .metrics()
+        .await
+        .unwrap();
+    let index_request = rebuilt.requests.get("GET /").unwrap();
+    let error_request = rebuilt.requests.get("GET /error").unwrap();
+    assert!(index_request.success_count > 0);
+    assert!(index_request.success_count < goose_metrics.requests["GET /"].success_count);
+    assert!(error_request.fail_count < goose_metrics.requests["GET /error"].fail_count);
+
+    // No requests were made after the load test finished.
+    assert!(GooseReport::from_request_log(REQUEST_LOG)
+        .await
+        .unwrap()
+        .set_from(60)
+        .metrics()
+        .await
+        .is_err());
+
+    common::cleanup_files(vec![REQUEST_LOG, HTML_REPORT, JSON_REPORT]);
+}
+
+#[tokio::test]
+#[serial]
+/// Compare two load tests rebuilt from request logs.
+async fn test_compare_request_logs() {
+    let server = MockServer::start();
+    let _mock_endpoints = setup_mock_server_endpoints(&server);
+
+    let _baseline_metrics = run_custom_load_test(
+        &server,
+        vec!["--request-log", BASELINE_LOG, "--request-format", "csv"],
+    )
+    .await;
+    let goose_metrics = run_load_test(&server, "json").await;
+
+    let goose_comparison = GooseComparison::load(BASELINE_LOG, REQUEST_LOG, COMPARISON_REPORT)
+        .await
+        .unwrap();
+    assert!(goose_comparison.metrics_files().is_empty());
+    let metrics = goose_comparison.write().await.unwrap();
+    assert!(metrics.requests.len() == goose_metrics.requests.len());
+    // Each metric, including the 6 default percentiles, is compared for both requests, and
+    // then for all requests.
+    assert!(metrics.baseline.len() == 27);
+    let error_rate = metrics
+        .baseline
+        .iter()
+        .find(|delta| delta.request.is_none() && delta.metric.to_string() == "error-rate")
+        .unwrap();
+    assert!(error_rate.baseline.is_some());
+    assert!(error_rate.current.is_some());
+
+    let html = std::fs::read_to_string(COMPARISON_REPORT).expect("failed to read report");
+    assert!(html.contains("<title>Goose Comparison Report</title>"));
+    assert!(html.contains(&format!("<td>{}</td>", BASELINE_LOG)));
+    assert!(html.contains(&format!("<td>{}</td>", REQUEST_LOG)));
+    assert!(html.contains("<h2>Baseline Comparison</h2>"));
+    assert!(html.contains("<td>GET /error</td>"));
+    // Both load tests are overlaid on each graph.
+    assert!(html.contains(r#"id="graph-compare-rps""#));
+    assert!(html.contains(r#"id="graph-compare-avg-response-time""#));
+    assert!(html.contains(r#"id="graph-compare-eps""#));
+    assert!(html.contains(r#"id="graph-compare-active-users""#));
+    assert!(html.contains(r#"data: ["Baseline","Current"]"#));
+
+    // Only compare the error page.
+    let metrics = GooseComparison::load_with(
+        BASELINE_LOG,
+        REQUEST_LOG,
+        COMPARISON_REPORT,
+        |goose_report| goose_report.set_name(ERROR_PATH),
+    )
+    .await
+    .unwrap()
+    .metrics();
+    assert!(metrics.requests.len() == 1);
+    assert!(metrics.baseline.len() == 18);
+
+    common::cleanup_files(vec![BASELINE_LOG, REQUEST_LOG, COMPARISON_REPORT]);
+}
+
+#[tokio::test]
+#[serial]
+/// Compare a load test rebuilt from a request log to a metrics file.
+async fn test_compare_metrics_file() {
+    let server = MockServer::start();
+    let _mock_endpoints = setup_mock_server_endpoints(&server);
+
+    let baseline_metrics =
+        run_custom_load_test(&server, vec!["--baseline-file", BASELINE_FILE]).await;
+    let _goose_metrics = run_load_test(&server, "raw").await;
+
+    let goose_comparison = GooseComparison::load(BASELINE_FILE, REQUEST_LOG, COMPARISON_REPORT)
+        .await
+        .unwrap()
+        .set_tolerance(1_000);
+    // The metrics file is reported, as it has no per second data to graph.
+    assert!(goose_comparison.metrics_files() == vec![BASELINE_FILE]);
+    let metrics = goose_comparison.write().await.unwrap();
+    // Nothing got 1,000% worse.
+    assert!(metrics.baseline.iter().all(|delta| !delta.regression));
+    let requests_per_second = metrics
+        .baseline
+        .iter()
+        .find(|delta| {
+            delta.request.as_deref() == Some("GET /") && delta.metric.to_string() == "rps"
+        })
+        .unwrap();
+    let baseline_requests = &baseline_metrics.requests["GET /"];
+    assert!(
+        requests_per_second.baseline
+            == Some(
+                (baseline_requests.success_count + baseline_requests.fail_count) as f32
+                    / baseline_metrics.duration as f32
+            )
+    );
+
+    // Metrics files don't include per second data to graph.
+    let html = std::fs::read_to_string(COMPARISON_REPORT).expect("failed to read report");
+    assert!(html.contains(&format!("<td>{}</td>", BASELINE_FILE)));
+    assert!(html.contains("<h2>Baseline Comparison</h2>"));
+    assert!(!html.contains("graph-compare-rps"));
+    assert!(html.contains("Graphs are only available when comparing request logs."));
+
+    common::cleanup_files(vec![BASELINE_FILE, REQUEST_LOG, COMPARISON_REPORT]);
+}
@@ -0,0 +1,201 @@
+/// Validate that reports are written in the format selected by the report file extension.
+use httpmock::{Method::GET, Mock, MockServer};
+use serial_test::serial;
+
+mod common;
+
+use goose::config::GooseConfiguration;
+use goose::prelude::*;
+
+// Paths used in load tests performed during these tests.
+const INDEX_PATH: &str = "/";
+const ERROR_PATH: &str = "/error";
+
+// Indexes to the above paths.
+const INDEX_KEY: usize = 0;
+const ERROR_KEY: usize = 1;
+
+// Files used in load tests performed during these tests.
+const HTML_REPORT: &str = "report-test.html";
+const MARKDOWN_REPORT: &str = "report-test.md";
+const JSON_REPORT: &str = "report-test.json";
+
+// Test transaction.
+pub async fn get_index(user: &mut GooseUser) -> TransactionResult {
+    let _goose = user.get(INDEX_PATH).await?;
+    Ok(())
+}
+
+// Test transaction.
+pub async fn get_error(user: &mut GooseUser) -> TransactionResult {
+    let _goose = user.get(ERROR_PATH).await?;
+    Ok(())
+}
+
+// All tests in this file run against common endpoints.
+fn setup_mock_server_endpoints(server: &MockServer) -> Vec<Mock<'_>> {
+    vec![
+        // First set up INDEX_PATH, store in vector at INDEX_KEY.
+        server.mock(|when, then| {
+            when.method(GET).path(INDEX_PATH);
+            then.status(200);
+        }),
+        // Next set up ERROR_PATH, store in vector at ERROR_KEY.
+        server.mock(|when, then| {
+            when.method(GET).path(ERROR_PATH);
+            then.status(500);
+        }),
+    ]
+}
+
+// Build appropriate configuration for these tests.
+fn common_build_configuration(server: &MockServer, custom: Vec<&str>) -> GooseConfiguration {
+    let mut configuration = vec!["--users", "2", "--hatch-rate", "2", "--run-time", "2"];
+    configuration.extend_from_slice(&custom);
+    common::build_configuration(server, configuration)
+}
+
+// Build a load test that loads both the index and an error page.
+fn build_load_test(configuration: GooseConfiguration) -> GooseAttack {
+    common::build_load_test(
+        configuration,
+        vec![scenario!("LoadTest")
+            .register_transaction(transaction!(get_index))
+            .register_transaction(transaction!(get_error))],
+        None,
+        None,
+    )
+}
+
+#[tokio::test]
+#[serial]
+/// Write an html, a Markdown and a json report in a single load test.
+async fn test_report_formats() {
+    let server = MockServer::start();
+    let mock_endpoints = setup_mock_server_endpoints(&server);
+
+    let _goose_metrics = common::run_load_test(
+        build_load_test(common_build_configuration(
+            &server,
+            vec![
+                "--report-file",
+                HTML_REPORT,
+                "--report-file",
+                MARKDOWN_REPORT,
+                "--report-file",
+                JSON_REPORT,
+            ],
+        )),
+        None,
+    )
+    .await;
+    assert!(mock_endpoints[INDEX_KEY].hits() > 0);
+    assert!(mock_endpoints[ERROR_KEY].hits() > 0);
+
+    let html = std::fs::read_to_string(HTML_REPORT).expect("failed to read html report");
+    assert!(html.contains("<title>Goose Attack Report</title>"));
+    assert!(html.contains("<td>/error</td>"));
+
+    let markdown = std::fs::read_to_string(MARKDOWN_REPORT).expect("failed to read md report");
+    assert!(markdown.starts_with("# Goose Attack Report"));
+    assert!(markdown.contains("## Plan Overview"));
+    assert!(markdown.contains("| Method | Name | # Requests | # Fails |"));
+    assert!(markdown.contains("| GET | /error |"));
+    assert!(markdown.contains("[200] |"));
+    assert!(markdown.contains("[500] |"));
+    assert!(markdown.contains("| **LoadTest** |"));
+    assert!(markdown.contains("## Scenario Metrics"));
+    assert!(markdown.contains("## Errors"));
+    assert!(!markdown.contains("<"));
+
+    let json = std::fs::read_to_string(JSON_REPORT).expect("failed to read json report");
+    let report: serde_json::Value = serde_json::from_str(&json).expect("invalid json report");
+    assert!(report["users"] == 2);
+    assert!(report["steps"][0]["action"] == "Increasing");
+    assert!(report["steps"][0]["started"].is_string());
+    // Requests are sorted, and followed by the aggregate.
+    let requests = report["raw_requests"].as_array().unwrap();
+    assert!(requests.len() == 3);
+    assert!(requests[0]["name"] == "/");
+    assert!(requests[1]["name"] == "/error");
+    assert!(requests[1]["number_of_requests"] == requests[1]["number_of_failures"]);
+    assert!(requests[2]["name"] == "Aggregated");
+    assert!(
+        report["raw_responses"][0]["percentiles"]
+            .as_array()
+            .unwrap()
+            .len()
+            == 8
+    );
+    assert!(report["co_requests"].is_null());
+    assert!(report["status_codes"].as_array().unwrap().len() == 3);
+    assert!(report["transactions"].as_array().unwrap().len() == 4);
+    assert!(report["scenarios"][0]["name"] == "LoadTest");
+    assert!(report["errors"][0]["error"]
+        .as_str()
+        .unwrap()
+        .contains("500 Internal Server Error"));
+
+    common::cleanup_files(vec![HTML_REPORT, MARKDOWN_REPORT, JSON_REPORT]);
+}
+
+#[tokio::test]
+#[serial]
+/// Disabled tables are left out of the Markdown and json reports.
+async fn test_report_formats_disabled_tables() {
+    let server = MockServer::start();
+    let _mock_endpoints = setup_mock_server_endpoints(&server);
+
+    let _goose_metrics = common::run_load_test(
+        build_load_test(common_build_configuration(
+            &server,
+            vec![
+                "--no-status-codes",
+                "--no-transaction-metrics",
+                "--report-file",
+                MARKDOWN_REPORT,
+                "--report-file",
+                JSON_REPORT,
+            ],
+        )),
+        None,
+    )
+    .await;
+
+    let markdown = std::fs::read_to_string(MARKDOWN_REPORT).expect("failed to read md report");
+    assert!(!markdown.contains("## Status Code Metrics"));
+    assert!(!markdown.contains("## Transaction Metrics"));
+    assert!(markdown.contains("## Scenario Metrics"));
+
+    let json = std::fs::read_to_string(JSON_REPORT).expect("failed to read json report");
+    let report: serde_json::Value = serde_json::from_str(&json).expect("invalid json report");
+    assert!(report["status_codes"].is_null());
+    assert!(report["transactions"].is_null());
+    assert!(report["scenarios"].is_array());
+
+    common::cleanup_files(vec![MARKDOWN_REPORT, JSON_REPORT]);
+}
+
+#[tokio::test]
+#[serial]
+/// Multiple report files can be configured with defaults.
+async fn test_report_formats_default() {
+    let server = MockServer::start();
+    let _mock_endpoints = setup_mock_server_endpoints(&server);
+
+    let _goose_metrics = build_load_test(common_build_configuration(&server, vec![]))
+        .set_default(GooseDefault::ReportFile, MARKDOWN_REPORT)
+        .unwrap()
+        .set_default(GooseDefault::ReportFile, JSON_REPORT)
+        .unwrap()
+        .execute()
+        .await
+        .unwrap();
+
+    let markdown = std::fs::read_to_string(MARKDOWN_REPORT).expect("failed to read md report");
+    assert!(markdown.starts_with("# Goose Attack Report"));
+    let json = std::fs::read_to_string(JSON_REPORT).expect("failed to read json report");
+    assert!(serde_json::from_str::<serde_json::Value>(&json).is_ok());
+
+    common::cleanup_files(vec![MARKDOWN_REPORT, JSON_REPORT]);
+}
@@ -0,0 +1,168 @@
+/// Validate that Scenarios can run their own test plan, or a fixed number of users.
+use httpmock::{Method::GET, Mock, MockServer};
+use serial_test::serial;
+use std::time::Duration;
+
+mod common;
+
+use goose::config::GooseConfiguration;
+use goose::prelude::*;
+
+// Paths used in load tests performed during these tests.
+const INDEX_PATH: &str = "/";
+const ADMIN_PATH: &str = "/admin";
+
+// Indexes to the above paths.
+const INDEX_KEY: usize = 0;
+const ADMIN_KEY: usize = 1;
+
+// Load test configuration.
+const USERS: usize = 3;
+const ADMIN_USERS: usize = 2;
+
+// Test transaction.
+pub async fn get_index(user: &mut GooseUser) -> TransactionResult {
+    let _goose = user.get(INDEX_PATH).await?;
+    Ok(())
+}
+
+// Test transaction.
+pub async fn get_admin(user: &mut GooseUser) -> TransactionResult {
+    let _goose = user.get(ADMIN_PATH).await?;
+    Ok(())
+}
+
+// All tests in this file run against common endpoints.
+fn setup_mock_server_endpoints(server: &MockServer) -> Vec<Mock<'_>> {
+    vec![
+        // First set up INDEX_PATH, store in vector at INDEX_KEY.
+        server.mock(|when, then| {
+            when.method(GET).path(INDEX_PATH);
+            then.status(200);
+        }),
+        // Next set up ADMIN_PATH, store in vector at ADMIN_KEY.
+        server.mock(|when, then| {
+            when.method(GET).path(ADMIN_PATH);
+            then.status(200);
+        }),
+    ]
+}
+
+// Build appropriate configuration for these tests.
+fn common_build_configuration(server: &MockServer) -> GooseConfiguration {
+    common::build_configuration(
+        server,
+        vec![
+            "--users",
+            &USERS.to_string(),
+            "--hatch-rate",
+            &USERS.to_string(),
+            "--run-time",
+            "2",
+        ],
+    )
+}
+
+#[tokio::test]
+#[serial]
+/// Run a Scenario with a fixed number of users alongside a weighted Scenario.
+async fn test_scenario_fixed_users() {
+    // Start the mock server.
+    let server = MockServer::start();
+
+    // Setup the endpoints needed for this test on the mock server.
+    let mock_endpoints = setup_mock_server_endpoints(&server);
+
+    // Run the Goose Attack.
+    let goose_metrics = common::run_load_test(
+        common::build_load_test(
+            common_build_configuration(&server),
+            vec![
+                scenario!("Anonymous")
+                    .register_transaction(transaction!(get_index))
+                    .set_wait_time(Duration::from_millis(100), Duration::from_millis(100))
+                    .unwrap(),
+                scenario!("Admin")
+                    .register_transaction(transaction!(get_admin))
+                    .set_wait_time(Duration::from_millis(100), Duration::from_millis(100))
+                    .unwrap()
+                    .set_users(ADMIN_USERS)
+                    .unwrap(),
+            ],
+            None,
+            None,
+        ),
+        None,
+    )
+    .await;
+
+    // Both scenarios ran.
+    assert!(mock_endpoints[INDEX_KEY].hits() > 0);
+    assert!(mock_endpoints[ADMIN_KEY].hits() > 0);
+
+    // The Admin users were launched in addition to the users of the global test plan.
+    assert!(goose_metrics.total_users == USERS + ADMIN_USERS);
+    assert!(goose_metrics.maximum_users == USERS + ADMIN_USERS);
+
+    // The test plan history includes the Admin users.
+    assert!(goose_metrics
+        .history
+        .iter()
+        .any(|step| step.users == USERS + ADMIN_USERS));
+}
+
+#[tokio::test]
+#[serial]
+/// Run only Scenarios with their own test plan.
+async fn test_scenario_test_plan() {
+    // Start the mock server.
+    let server = MockServer::start();
+
+    // Setup the endpoints needed for this test on the mock server.
+    let mock_endpoints = setup_mock_server_endpoints(&server);
+
+    // Run the Goose Attack.
+    let goose_metrics = common::run_load_test(
+        common::build_load_test(
+            common_build_configuration(&server),
+            vec![
+                scenario!("Anonymous")
+                    .register_transaction(transaction!(get_index))
+                    .set_wait_time(Duration::from_millis(100), Duration::from_millis(100))
+                    .unwrap()
+                    // Launch 4 users in 1 second, then stop them all after 1 more second.
+                    .set_test_plan("4,1s;4,1s;0,0")
+                    .unwrap(),
+                scenario!("Admin")
+                    .register_transaction(transaction!(get_admin))
+                    .set_wait_time(Duration::from_millis(100), Duration::from_millis(100))
+                    .unwrap()
+                    .set_users(1)
+                    .unwrap(),
+            ],
+            None,
+            None,
+        ),
+        None,
+    )
+    .await;
+
+    // Both scenarios ran.
+    assert!(mock_endpoints[INDEX_KEY].hits() > 0);
+    assert!(mock_endpoints[ADMIN_KEY].hits() > 0);
+
+    // No users were launched by the global test plan, as all scenarios have their own.
+    assert!(goose_metrics.total_users == 5);
+    assert!(goose_metrics.maximum_users == 5);
+
+    // The load test still ran for as long as the global test plan.
+    assert!(goose_metrics.duration >= 2);
+}
+
+#[test]
+/// Invalid Scenario test plans and user counts are rejected.
+fn test_scenario_test_plan_invalid() {
+    assert!(scenario!("Anonymous").set_test_plan("foo").is_err());
+    assert!(scenario!("Anonymous").set_test_plan("10,1m;bar").is_err());
+    assert!(scenario!("Admin").set_users(0).is_err());
+}
@@ -0,0 +1,251 @@
+/// Validate that pass/fail thresholds are evaluated at the end of a load test, and that
+/// abort-on-fail rules stop a running load test.
+use httpmock::{Method::GET, Mock, MockServer};
+use serial_test::serial;
+use std::time::Duration;
+
+mod common;
+
+use goose::config::GooseConfiguration;
+use goose::prelude::*;
+
+// Paths used in load tests performed during these tests.
+const INDEX_PATH: &str = "/";
+const ERROR_PATH: &str = "/error";
+const STOP_PATH: &str = "/stop";
+
+// Indexes to the above paths.
+const INDEX_KEY: usize = 0;
+const ERROR_KEY: usize = 1;
+const STOP_KEY: usize = 2;
+
+// Files used in load tests performed during these tests.
+const ABORT_REPORT: &str = "abort-report.html";
+
+// Test transaction.
+pub async fn get_index(user: &mut GooseUser) -> TransactionResult {
+    let _goose = user.get(INDEX_PATH).await?;
+    Ok(())
+}
+
+// Test transaction.
+pub async fn get_error(user: &mut GooseUser) -> TransactionResult {
+    let _goose = user.get(ERROR_PATH).await?;
+    Ok(())
+}
+
+// Test transaction.
+pub async fn get_stop(user: &mut GooseUser) -> TransactionResult {
+    let _goose = user.get(STOP_PATH).await?;
+    Ok(())
+}
+
+// All tests in this file run against common endpoints.
+fn setup_mock_server_endpoints(server: &MockServer) -> Vec<Mock<'_>> {
+    vec![
+        // First set up INDEX_PATH, store in vector at INDEX_KEY.
+        server.mock(|when, then| {
+            when.method(GET).path(INDEX_PATH);
+            then.status(200);
+        }),
+        // Next set up ERROR_PATH, store in vector at ERROR_KEY.
+        server.mock(|when, then| {
+            when.method(GET).path(ERROR_PATH);
+            then.status(500);
+        }),
+        // Next set up STOP_PATH, store in vector at STOP_KEY.
+        server.mock(|when, then| {
+            when.method(GET).path(STOP_PATH);
+            then.status(200);
+        }),
+    ]
+}
+
+// Build appropriate configuration for these tests.
+fn common_build_configuration(server: &MockServer, thresholds: &str) -> GooseConfiguration {
+    common::build_configuration(
+        server,
+        vec![
+            "--users",
+            "2",
+            "--hatch-rate",
+            "2",
+            "--thresholds",
+            thresholds,
+        ],
+    )
+}
+
+// Build a load test that loads both the index and an error page.
+fn build_load_test(configuration: GooseConfiguration) -> GooseAttack {
+    common::build_load_test(
+        configuration,
+        vec![scenario!("LoadTest")
+            .register_transaction(transaction!(get_index))
+            .register_transaction(transaction!(get_error))],
+        None,
+        None,
+    )
+}
+
+#[tokio::test]
+#[serial]
+/// Met thresholds are recorded in the metrics, and the load test succeeds.
+async fn test_thresholds_passed() {
+    // Start the mock server.
+    let server = MockServer::start();
+
+    // Setup the endpoints needed for this test on the mock server.
+    let mock_endpoints = setup_mock_server_endpoints(&server);
+
+    // Run the Goose Attack.
+    let goose_metrics = common::run_load_test(
+        build_load_test(common_build_configuration(
+            &server,
+            "p95<5000;max<10000;GET /:error-rate<1;/error:error-rate>99;rps>1",
+        )),
+        None,
+    )
+    .await;
+
+    assert!(mock_endpoints[INDEX_KEY].hits() > 0);
+    assert!(mock_endpoints[ERROR_KEY].hits() > 0);
+
+    // All thresholds were evaluated, and all passed.
+    assert!(goose_metrics.thresholds.len() == 5);
+    assert!(goose_metrics.failed_thresholds().is_empty());
+    assert!(goose_metrics
+        .thresholds
+        .iter()
+        .all(|result| result.value.is_some()));
+}
+
+#[tokio::test]
+#[serial]
+/// Unmet thresholds fail the load test with a distinct error.
+async fn test_thresholds_failed() {
+    // Start the mock server.
+    let server = MockServer::start();
+
+    // Setup the endpoints needed for this test on the mock server.
+    let mock_endpoints = setup_mock_server_endpoints(&server);
+
+    // Run the Goose Attack.
+    let result = build_load_test(common_build_configuration(
+        &server,
+        "p95<5000;error-rate<1;GET /missing:p95<500",
+    ))
+    .execute()
+    .await;
+
+    assert!(mock_endpoints[INDEX_KEY].hits() > 0);
+    assert!(mock_endpoints[ERROR_KEY].hits() > 0);
+
+    match result {
+        Err(GooseError::ThresholdsFailed { metrics, detail }) => {
+            // The metrics are still available when thresholds fail.
+            assert!(metrics.requests.len() == 2);
+            assert!(metrics.thresholds.len() == 3);
+
+            // Half the requests fail, and no requests were made to the missing page.
+            let failed = metrics.failed_thresholds();
+            assert!(failed.len() == 2);
+            assert!(failed[0].threshold.to_string() == "error-rate<1");
+            assert!(failed[0].value.unwrap() > 40.0);
+            assert!(failed[1].threshold.to_string() == "GET /missing:p95<500");
+            assert!(failed[1].value.is_none());
+            assert!(detail.contains("error-rate<1"));
+        }
+        _ => panic!("load test didn't fail thresholds"),
+    }
+}
+
+#[tokio::test]
+#[serial]
+/// Thresholds can be configured with a default.
+async fn test_thresholds_default() {
+    // Start the mock server.
+    let server = MockServer::start();
+
+    // Setup the endpoints needed for this test on the mock server.
+    let _mock_endpoints = setup_mock_server_endpoints(&server);
+
+    // Run the Goose Attack.
+    let result = build_load_test(common::build_configuration(
+        &server,
+        vec!["--users", "2", "--hatch-rate", "2"],
+    ))
+    .set_default(GooseDefault::Thresholds, "/error:error-rate<50")
+    .unwrap()
+    .execute()
+    .await;
+
+    assert!(matches!(result, Err(GooseError::ThresholdsFailed { .. })));
+}
+
+#[tokio::test]
+#[serial]
+/// A load test is gracefully aborted when an abort-on-fail rule is breached.
+async fn test_abort_on_fail() {
+    // Start the mock server.
+    let server = MockServer::start();
+
+    // Setup the endpoints needed for this test on the mock server.
+    let mock_endpoints = setup_mock_server_endpoints(&server);
+
+    // Run a load test that only loads the error page, for much longer than it should run.
+    let started = std::time::Instant::now();
+    let result = common::build_load_test(
+        common::build_configuration(
+            &server,
+            vec![
+                "--users",
+                "2",
+                "--hatch-rate",
+                "2",
+                "--run-time",
+                "30",
+                "--abort-on-fail",
+                "error-rate<50,2s",
+                "--report-file",
+                ABORT_REPORT,
+            ],
+        ),
+        vec![scenario!("LoadTest")
+            .register_transaction(transaction!(get_error))
+            .register_transaction(transaction!(get_stop).set_on_stop())
+            .set_wait_time(Duration::from_millis(100), Duration::from_millis(100))
+            .unwrap()],
+        None,
+        Some(&transaction!(get_stop)),
+    )
+    .execute()
+    .await;
+
+    // The load test was aborted long before the configured run time.
+    assert!(started.elapsed().as_secs() < 15);
+    assert!(mock_endpoints[INDEX_KEY].hits() == 0);
+    assert!(mock_endpoints[ERROR_KEY].hits() > 0);
+
+    // Both users ran their on_stop transaction, and test_stop also ran.
+    assert!(mock_endpoints[STOP_KEY].hits() == 3);
+
+    match result {
+        Err(GooseError::Aborted { reason, metrics }) => {
+            // The reason the load test was aborted is recorded in the history.
+            let aborted = metrics
+                .history
+                .iter()
+                .find_map(|step| step.reason.as_ref())
+                .expect("abort reason not recorded");
+            assert!(aborted == "error-rate<50 breached for 2s");
+            assert!(reason == *aborted);
+        }
+        _ => panic!("load test wasn't aborted"),
+    }
+
+    // The reason is escaped in the html report.
+    let html = std::fs::read_to_string(ABORT_REPORT).expect("failed to read html report");
+    assert!(html.contains("<td colspan=\"5\">error-rate&lt;50 breached for 2s</td>"));
+    common::cleanup_files(vec![ABORT_REPORT]);
+}
@@ -0,0 +1,107 @@
+/// Validate that the time spent in each phase of a request is tracked.
+use httpmock::{Method::GET, Mock, MockServer};
+use serial_test::serial;
+
+mod common;
+
+use goose::config::GooseConfiguration;
+use goose::prelude::*;
+
+// Paths used in load tests performed during these tests.
+const INDEX_PATH: &str = "/";
+
+// Indexes to the above paths.
+const INDEX_KEY: usize = 0;
+
+// Files used in load tests performed during these tests.
+const REPORT_FILE: &str = "test-timing-breakdown-report.html";
+
+// Test transaction.
+pub async fn get_index(user: &mut GooseUser) -> TransactionResult {
+    let goose = user.get(INDEX_PATH).await?;
+    // Connection information is available from the response.
+    assert!(goose.response.unwrap().remote_addr().is_some());
+    Ok(())
+}
+
+// All tests in this file run against common endpoints.
+fn setup_mock_server_endpoints(server: &MockServer) -> Vec<Mock<'_>> {
+    vec![
+        // First set up INDEX_PATH, store in vector at INDEX_KEY.
+        server.mock(|when, then| {
+            when.method(GET).path(INDEX_PATH);
+            then.status(200)
+                .body("<html><body>Hello, world!</body></html>");
+        }),
+    ]
+}
+
+// Build appropriate configuration for these tests.
+fn common_build_configuration(server: &MockServer, custom: Vec<&str>) -> GooseConfiguration {
+    let mut configuration = vec!["--users", "2", "--hatch-rate", "2", "--no-reset-metrics"];
+    configuration.extend_from_slice(&custom);
+    common::build_configuration(server, configuration)
+}
+
+// Build a load test that repeatedly loads the index.
+fn build_load_test(configuration: GooseConfiguration) -> GooseAttack {
+    common::build_load_test(
+        configuration,
+        vec![scenario!("LoadTest").register_transaction(transaction!(get_index))],
+        None,
+        None,
+    )
+}
+
+#[tokio::test]
+#[serial]
+/// Track, display, serialize and report where the time of each request was spent.
+async fn test_timing_breakdown() {
+    // Be sure there's no report file from a previous test.
+    common::cleanup_files(vec![REPORT_FILE]);
+
+    let server = MockServer::start();
+    let mock_endpoints = setup_mock_server_endpoints(&server);
+    // Connect by host name so it has to be resolved.
+    let host = format!("http://localhost:{}", server.port());
+    let goose_metrics = common::run_load_test(
+        build_load_test(common_build_configuration(
+            &server,
+            vec!["--host", &host, "--report-file", REPORT_FILE],
+        )),
+        None,
+    )
+    .await;
+    let index_hits = mock_endpoints[INDEX_KEY].hits();
+    assert!(index_hits > 2);
+
+    // Each user opened a connection, which was then reused for its following requests.
+    let index = goose_metrics.requests.get("GET /").unwrap();
+    assert!(index.new_connections >= 2);
+    assert!(index.reused_connections > 0);
+    assert!(index.new_connections + index.reused_connections == index_hits);
+
+    // The timing breakdown is displayed.
+    let metrics = goose_metrics.to_string();
+    assert!(metrics.contains("PER REQUEST TIMING BREAKDOWN"));
+
+    // The timing breakdown of each request, and of all requests, is serialized.
+    let json: serde_json::Value =
+        serde_json::from_str(&serde_json::to_string(&goose_metrics).unwrap()).unwrap();
+    let timing_breakdown = json["timing_breakdown"].as_object().unwrap();
+    assert!(timing_breakdown.len() == 2);
+    assert!(
+        timing_breakdown["Aggregated"]["connection_reuse"]
+            .as_f64()
+            .unwrap()
+            > 0.0
+    );
+    assert!(timing_breakdown["Aggregated"]["connect_time"].is_f64());
+    assert!(timing_breakdown["Aggregated"]["time_to_first_byte"].is_f64());
+
+    // The timing breakdown is included in the html report.
+    let report = std::fs::read_to_string(REPORT_FILE).expect("failed to read report file");
+    assert!(report.contains("<h2>Request Timing Breakdown</h2>"));
+
+    common::cleanup_files(vec![REPORT_FILE]);
+}